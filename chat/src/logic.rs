@@ -20,7 +20,7 @@ use message::SignedMessage;
 use persistence::{get_or_create_schnorr_secret_key, get_or_create_secret_key};
 use pod2::pod::entry::Entry;
 use pod2::pod::POD;
-use pod2::signature::schnorr::SchnorrSecretKey;
+use pod2::signature::ecschnorr::EcSchnorrSecretKey;
 use pods::{get_string_field_elem, PodStore};
 use std::sync::Mutex;
 use std::time::Duration;
@@ -38,7 +38,7 @@ type IrohNode = iroh::node::Node<iroh::blobs::store::fs::Store>;
 pub struct Logic {
     iroh: Arc<tokio::sync::RwLock<Option<IrohNode>>>,
     secret_key: SecretKey,
-    schnorr_secret_key: SchnorrSecretKey,
+    schnorr_secret_key: EcSchnorrSecretKey,
     doc0: Arc<tokio::sync::RwLock<Option<Doc>>>,
     messages: RwLock<Vec<(PublicKey, Message)>>,
     identities: Mutex<Identities>,
@@ -181,7 +181,7 @@ impl Logic {
 
         self.add_message(self.secret_key.public(), &message);
 
-        let schnorr_secret_key = self.schnorr_secret_key;
+        let schnorr_secret_key = self.schnorr_secret_key.clone();
         let pod_store = self.pod_store.clone();
         let input = input.to_string();
         let pod_watch = self.pod_watch.clone();
@@ -277,7 +277,7 @@ impl Logic {
 
 fn store_message_pod(
     pod_store: Arc<Mutex<PodStore>>,
-    schnorr_secret_key: SchnorrSecretKey,
+    schnorr_secret_key: EcSchnorrSecretKey,
     pod_watch: watch::Sender<()>,
     input: &str,
 ) -> anyhow::Result<()> {
@@ -285,9 +285,8 @@ fn store_message_pod(
     let pod = POD::execute_schnorr_gadget::<NS, VL>(
         &[Entry::new_from_scalar("message", field_elem)],
         &schnorr_secret_key,
-    )
-    .unwrap();
-    pod_store.lock().unwrap().add_pod(&pod).unwrap();
+    )?;
+    pod_store.lock().unwrap().add_pod(&pod)?;
     pod_watch.send(()).unwrap();
     Ok(())
 }
@@ -1,5 +1,5 @@
 use iroh::net::key::SecretKey;
-use pod2::signature::schnorr::SchnorrSecretKey;
+use pod2::signature::ecschnorr::{EcSchnorrSecretKey, SECRET_KEY_BYTES};
 use std::{fs, path::PathBuf};
 use tracing::info;
 
@@ -30,24 +30,21 @@ impl KeyOperations for SecretKey {
     }
 }
 
-impl KeyOperations for SchnorrSecretKey {
-    const FILENAME: &'static str = "user_schnorr_key.bin";
-    const EXPECTED_BYTES: usize = 8;
+impl KeyOperations for EcSchnorrSecretKey {
+    const FILENAME: &'static str = "user_ecschnorr_key.bin";
+    const EXPECTED_BYTES: usize = SECRET_KEY_BYTES;
 
     fn generate_new() -> Self {
-        let random = rand::random::<u64>() % ((1 << 16) + 1);
-        Self { sk: random }
+        Self::random(&mut rand::thread_rng())
     }
 
     fn from_bytes(bytes: &[u8]) -> Option<Self> {
-        let bytes: [u8; 8] = bytes.try_into().ok()?;
-        Some(Self {
-            sk: u64::from_le_bytes(bytes),
-        })
+        let bytes: [u8; SECRET_KEY_BYTES] = bytes.try_into().ok()?;
+        Some(Self::from_bytes(&bytes))
     }
 
     fn to_bytes(&self) -> Vec<u8> {
-        self.sk.to_le_bytes().to_vec()
+        self.to_bytes()
     }
 }
 
@@ -94,6 +91,6 @@ pub fn get_or_create_secret_key() -> SecretKey {
     get_or_create_key()
 }
 
-pub fn get_or_create_schnorr_secret_key() -> SchnorrSecretKey {
+pub fn get_or_create_schnorr_secret_key() -> EcSchnorrSecretKey {
     get_or_create_key()
 }
@@ -1,10 +1,10 @@
 use anyhow::Result;
 use chrono::Utc;
 use plonky2::field::{goldilocks_field::GoldilocksField, types::Field};
-use pod2::pod::POD;
+use pod2::pod::{PodId, POD};
 use rusqlite::{params, Connection, OpenFlags};
 use std::path::PathBuf;
-use tracing::info;
+use tracing::{info, warn};
 
 use super::persistence::get_exe_parent_dir;
 
@@ -24,25 +24,47 @@ impl PodStore {
             "CREATE TABLE IF NOT EXISTS pods (
                             id INTEGER PRIMARY KEY AUTOINCREMENT,
                             pod BLOB NOT NULL,
-                            timestamp INTEGER NOT NULL
+                            timestamp INTEGER NOT NULL,
+                            pod_id TEXT
                         )",
             [],
         )
         .unwrap();
 
+        // Stores created before PODs had a canonical ID lack the column.
+        let has_pod_id: bool = conn
+            .query_row(
+                "SELECT COUNT(*) > 0 FROM pragma_table_info('pods') WHERE name = 'pod_id'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        if !has_pod_id {
+            conn.execute("ALTER TABLE pods ADD COLUMN pod_id TEXT", [])
+                .unwrap();
+        }
+        conn.execute(
+            "CREATE UNIQUE INDEX IF NOT EXISTS pods_pod_id ON pods (pod_id)",
+            [],
+        )
+        .unwrap();
+        backfill_pod_ids(&conn).unwrap();
+
         Self { conn }
     }
 
-    pub fn add_pod(&self, pod: &POD) -> Result<()> {
+    /// Stores the POD under its canonical ID, unless a POD with the same ID is already stored.
+    pub fn add_pod(&self, pod: &POD) -> Result<PodId> {
         let timestamp = Utc::now();
-        let serialized_pod = postcard::to_stdvec(pod)?;
+        let serialized_pod = pod.to_bytes()?;
+        let pod_id = pod.id();
 
         self.conn.execute(
-            "INSERT INTO pods (pod, timestamp) VALUES (?1, ?2)",
-            params![serialized_pod, timestamp.timestamp(),],
+            "INSERT OR IGNORE INTO pods (pod, timestamp, pod_id) VALUES (?1, ?2, ?3)",
+            params![serialized_pod, timestamp.timestamp(), pod_id.to_string()],
         )?;
-        info!("added pod");
-        Ok(())
+        info!("added pod {}", pod_id);
+        Ok(pod_id)
     }
 
     pub fn get_num_pods(&self) -> Result<u64> {
@@ -63,6 +85,43 @@ pub fn get_string_field_elem(text: &str) -> GoldilocksField {
     GoldilocksField::from_noncanonical_u128(n)
 }
 
+/// Gives the rows stored before PODs had a canonical ID their ID, re-encoding the PODs in the
+/// current wire format. Rows holding a POD that is already stored are dropped, and rows that
+/// can't be decoded are kept without an ID.
+fn backfill_pod_ids(conn: &Connection) -> Result<()> {
+    let rows = conn
+        .prepare("SELECT id, pod FROM pods WHERE pod_id IS NULL")?
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (row_id, bytes) in rows {
+        // Rows written before the versioned wire format hold the bare postcard encoding.
+        let decoded = POD::from_bytes(&bytes)
+            .ok()
+            .or_else(|| postcard::from_bytes::<POD>(&bytes).ok());
+        let Some(pod) = decoded else {
+            warn!(
+                "cannot decode pod in row {}, leaving it without an ID",
+                row_id
+            );
+            continue;
+        };
+        let pod_id = pod.id();
+        let updated = conn.execute(
+            "UPDATE OR IGNORE pods SET pod = ?1, pod_id = ?2 WHERE id = ?3",
+            params![pod.to_bytes()?, pod_id.to_string(), row_id],
+        )?;
+        if updated == 0 {
+            conn.execute("DELETE FROM pods WHERE id = ?1", params![row_id])?;
+            info!("dropped duplicate of pod {}", pod_id);
+        } else {
+            info!("backfilled pod {}", pod_id);
+        }
+    }
+    Ok(())
+}
+
 fn get_default_db_path() -> PathBuf {
     get_exe_parent_dir().join("podstore.db")
 }
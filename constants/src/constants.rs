@@ -1,3 +1,4 @@
+// Default circuit shape, registered in pod2 as `CircuitShape::DEFAULT`.
 pub const NS: usize = 10; // Max number of statements in a POD
 pub const VL: usize = 10; // Max length of vectors
 pub const L: usize = 0; // Max number of POD1-Introducer (1 level recursion) verifications
@@ -20,20 +20,22 @@ use anyhow::{anyhow, Result};
 use async_recursion::async_recursion;
 use plonky2::{
     field::{goldilocks_field::GoldilocksField, types::PrimeField64},
-    plonk::config::GenericHashOut,
+    hash::poseidon::PoseidonHash,
+    plonk::config::{GenericHashOut, Hasher},
 };
 
 use pod2::{
     pod::{
         entry::Entry,
         gadget::{plonky_pod::ProverParams, GadgetID},
+        merkle::{MerkleProof, MerkleTree},
         origin::Origin,
-        payload::HashablePayload,
+        predicate::{CustomPredicate, StatementTemplate},
         statement::{AnchoredKey, StatementRef},
-        value::ScalarOrVec,
-        GPGInput, Op, OpCmd, Statement, POD,
+        value::{HashableEntryValue, ScalarOrVec},
+        GPGInput, Op, OpCmd, Oracle, Statement, POD,
     },
-    signature::schnorr::SchnorrSecretKey,
+    signature::ecschnorr::EcSchnorrSecretKey,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -198,7 +200,7 @@ impl SharedStore for InMemoryStore {
     }
 
     fn store_pod(&self, pod: POD) -> String {
-        let id = PodBuilder::pod_id(&pod);
+        let id = pod.id().to_string();
         self.pods.lock().unwrap().insert(id.clone(), pod);
         id
     }
@@ -212,9 +214,11 @@ pub struct Env {
     pub current_query: Option<Arc<Mutex<PodQueryBuilder>>>,
     shared: Arc<dyn SharedStore>,
     bindings: Arc<Mutex<HashMap<String, Value>>>,
-    sk: Option<SchnorrSecretKey>,
+    sk: Option<EcSchnorrSecretKey>,
     script_id: Option<ScriptId>,
     prover_params: Option<Arc<Mutex<ProverParams<L, M, N, NS, VL>>>>,
+    /// Merkle trees built with `merkle-root`, by root
+    merkle_trees: Arc<Mutex<HashMap<GoldilocksField, MerkleTree>>>,
 }
 
 #[derive(Clone, Debug)]
@@ -236,6 +240,8 @@ pub enum Value {
     Operation(Box<Operation>),
     Assert(Box<Assert>),
     List(Vec<Value>),
+    #[serde(skip)]
+    Predicate(Arc<PexPredicate>),
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -244,6 +250,10 @@ pub enum AssertType {
     Lt,
     Eq,
     Neq,
+    Gte,
+    Lte,
+    InRange,
+    Member,
 }
 
 impl AssertType {
@@ -253,18 +263,33 @@ impl AssertType {
             "<" => Ok(AssertType::Lt),
             "=" => Ok(AssertType::Eq),
             "!=" => Ok(AssertType::Neq),
+            ">=" => Ok(AssertType::Gte),
+            "<=" => Ok(AssertType::Lte),
+            "in-range" => Ok(AssertType::InRange),
+            "member" => Ok(AssertType::Member),
             _ => Err(anyhow!("Unknown operation type: {}", s)),
         }
     }
-}
 
-impl From<(AssertType, Value, Value)> for Assert {
-    fn from((assert_type, op1, op2): (AssertType, Value, Value)) -> Self {
-        match assert_type {
-            AssertType::Gt => Assert::Gt(op1, op2),
-            AssertType::Lt => Assert::Lt(op1, op2),
-            AssertType::Eq => Assert::Eq(op1, op2),
-            AssertType::Neq => Assert::Neq(op1, op2),
+    /// Number of operands of the assert.
+    fn arity(&self) -> usize {
+        match self {
+            AssertType::InRange => 3,
+            _ => 2,
+        }
+    }
+
+    /// Code of the statement the assert is proven by.
+    fn statement_code(&self) -> GoldilocksField {
+        match self {
+            AssertType::Gt => Statement::GT,
+            AssertType::Lt => Statement::LT,
+            AssertType::Eq => Statement::EQUAL,
+            AssertType::Neq => Statement::NOT_EQUAL,
+            AssertType::Gte => Statement::GTE,
+            AssertType::Lte => Statement::LTE,
+            AssertType::InRange => Statement::IN_RANGE,
+            AssertType::Member => Statement::CONTAINED_IN_ROOT,
         }
     }
 }
@@ -275,9 +300,60 @@ pub enum Assert {
     Lt(Value, Value),
     Eq(Value, Value),
     Neq(Value, Value),
+    Gte(Value, Value),
+    Lte(Value, Value),
+    /// Value, lower bound and upper bound, the bounds being inclusive
+    InRange(Value, Value, Value),
+    /// Value and root of a Merkle tree built with `merkle-root`
+    Member(Value, Value),
 }
 
 impl Assert {
+    fn new(assert_type: AssertType, operands: &[Value]) -> Result<Self> {
+        match (assert_type, operands) {
+            (AssertType::Gt, [op1, op2]) => Ok(Assert::Gt(op1.clone(), op2.clone())),
+            (AssertType::Lt, [op1, op2]) => Ok(Assert::Lt(op1.clone(), op2.clone())),
+            (AssertType::Eq, [op1, op2]) => Ok(Assert::Eq(op1.clone(), op2.clone())),
+            (AssertType::Neq, [op1, op2]) => Ok(Assert::Neq(op1.clone(), op2.clone())),
+            (AssertType::Gte, [op1, op2]) => Ok(Assert::Gte(op1.clone(), op2.clone())),
+            (AssertType::Lte, [op1, op2]) => Ok(Assert::Lte(op1.clone(), op2.clone())),
+            (AssertType::InRange, [op1, op2, op3]) => {
+                Ok(Assert::InRange(op1.clone(), op2.clone(), op3.clone()))
+            }
+            (AssertType::Member, [op1, op2]) => Ok(Assert::Member(op1.clone(), op2.clone())),
+            _ => Err(anyhow!(
+                "Assert requires exactly {} operands",
+                assert_type.arity()
+            )),
+        }
+    }
+
+    fn assert_type(&self) -> AssertType {
+        match self {
+            Assert::Gt(_, _) => AssertType::Gt,
+            Assert::Lt(_, _) => AssertType::Lt,
+            Assert::Eq(_, _) => AssertType::Eq,
+            Assert::Neq(_, _) => AssertType::Neq,
+            Assert::Gte(_, _) => AssertType::Gte,
+            Assert::Lte(_, _) => AssertType::Lte,
+            Assert::InRange(_, _, _) => AssertType::InRange,
+            Assert::Member(_, _) => AssertType::Member,
+        }
+    }
+
+    fn operands(&self) -> Vec<&Value> {
+        match self {
+            Assert::Gt(a, b)
+            | Assert::Lt(a, b)
+            | Assert::Eq(a, b)
+            | Assert::Neq(a, b)
+            | Assert::Gte(a, b)
+            | Assert::Lte(a, b)
+            | Assert::Member(a, b) => vec![a, b],
+            Assert::InRange(a, b, c) => vec![a, b, c],
+        }
+    }
+
     fn extract_value(value: &Value, env: Option<&Env>) -> Result<GoldilocksField> {
         match value {
             Value::Scalar(s) => Ok(*s),
@@ -286,52 +362,37 @@ impl Assert {
         }
     }
 
-    fn evaluate_values(&self, env: Option<&Env>) -> Result<(GoldilocksField, GoldilocksField)> {
-        match self {
-            Assert::Gt(a, b) | Assert::Lt(a, b) | Assert::Eq(a, b) | Assert::Neq(a, b) => {
-                let value1 = Self::extract_value(a, env)?;
-                let value2 = Self::extract_value(b, env)?;
-                Ok((value1, value2))
-            }
-        }
+    fn evaluate_values(&self, env: Option<&Env>) -> Result<Vec<GoldilocksField>> {
+        self.operands()
+            .into_iter()
+            .map(|value| Self::extract_value(value, env))
+            .collect()
     }
 
-    fn apply_assert(&self, value1: GoldilocksField, value2: GoldilocksField) -> GoldilocksField {
-        match self {
-            Assert::Gt(_, _) => {
-                if value1.to_canonical_u64() > value2.to_canonical_u64() {
-                    GoldilocksField(1)
-                } else {
-                    GoldilocksField(0)
-                }
-            }
-            Assert::Lt(_, _) => {
-                if value1.to_canonical_u64() < value2.to_canonical_u64() {
-                    GoldilocksField(1)
-                } else {
-                    GoldilocksField(0)
-                }
-            }
-            Assert::Eq(_, _) => {
-                if value1.to_canonical_u64() == value2.to_canonical_u64() {
-                    GoldilocksField(1)
-                } else {
-                    GoldilocksField(0)
-                }
-            }
-            Assert::Neq(_, _) => {
-                if value1.to_canonical_u64() != value2.to_canonical_u64() {
-                    GoldilocksField(1)
-                } else {
-                    GoldilocksField(0)
-                }
-            }
+    fn apply_assert(&self, values: &[GoldilocksField], env: &Env) -> GoldilocksField {
+        if let (Assert::Member(_, _), [value, root]) = (self, values) {
+            return GoldilocksField(env.merkle_proof(*value, *root).is_ok() as u64);
         }
+        let values = values
+            .iter()
+            .map(|value| value.to_canonical_u64())
+            .collect::<Vec<_>>();
+        let holds = match (self, values.as_slice()) {
+            (Assert::Gt(_, _), [value1, value2]) => value1 > value2,
+            (Assert::Lt(_, _), [value1, value2]) => value1 < value2,
+            (Assert::Eq(_, _), [value1, value2]) => value1 == value2,
+            (Assert::Neq(_, _), [value1, value2]) => value1 != value2,
+            (Assert::Gte(_, _), [value1, value2]) => value1 >= value2,
+            (Assert::Lte(_, _), [value1, value2]) => value1 <= value2,
+            (Assert::InRange(_, _, _), [value, lower, upper]) => lower <= value && value <= upper,
+            _ => false,
+        };
+        GoldilocksField(holds as u64)
     }
 
-    fn eval(&self) -> Result<GoldilocksField> {
-        let (value1, value2) = self.evaluate_values(None)?;
-        Ok(self.apply_assert(value1, value2))
+    fn eval(&self, env: &Env) -> Result<GoldilocksField> {
+        let values = self.evaluate_values(None)?;
+        Ok(self.apply_assert(&values, env))
     }
 
     fn predicate_from_op(assert_type: AssertType) -> String {
@@ -340,22 +401,84 @@ impl Assert {
             AssertType::Lt => "LT".to_string(),
             AssertType::Eq => "EQUAL".to_string(),
             AssertType::Neq => "NOTEQUAL".to_string(),
+            AssertType::Gte => "GTE".to_string(),
+            AssertType::Lte => "LTE".to_string(),
+            AssertType::InRange => "INRANGE".to_string(),
+            AssertType::Member => "CONTAINEDINROOT".to_string(),
         }
     }
-    fn into_pod_op(assert_type: AssertType, op1: SRef, op2: SRef) -> Op<StatementRef> {
-        match assert_type {
-            AssertType::Gt => Op::GtFromEntries(op1.into(), op2.into()),
-            AssertType::Lt => Op::LtFromEntries(op1.into(), op2.into()),
-            AssertType::Eq => Op::EqualityFromEntries(op1.into(), op2.into()),
-            AssertType::Neq => Op::NonequalityFromEntries(op1.into(), op2.into()),
+    fn into_pod_op(
+        assert_type: AssertType,
+        operands: Vec<SRef>,
+        merkle_proof: Option<MerkleProof>,
+    ) -> Result<Op<StatementRef>> {
+        let operands: Vec<StatementRef> = operands.into_iter().map(|op| op.into()).collect();
+        match (assert_type, operands.as_slice()) {
+            (AssertType::Gt, [op1, op2]) => Ok(Op::GtFromEntries(op1.clone(), op2.clone())),
+            (AssertType::Lt, [op1, op2]) => Ok(Op::LtFromEntries(op1.clone(), op2.clone())),
+            (AssertType::Eq, [op1, op2]) => Ok(Op::EqualityFromEntries(op1.clone(), op2.clone())),
+            (AssertType::Neq, [op1, op2]) => {
+                Ok(Op::NonequalityFromEntries(op1.clone(), op2.clone()))
+            }
+            (AssertType::Gte, [op1, op2]) => Ok(Op::GteFromEntries(op1.clone(), op2.clone())),
+            (AssertType::Lte, [op1, op2]) => Ok(Op::LteFromEntries(op1.clone(), op2.clone())),
+            (AssertType::InRange, [op1, op2, op3]) => Ok(Op::InRangeFromEntries(
+                op1.clone(),
+                op2.clone(),
+                op3.clone(),
+            )),
+            (AssertType::Member, [op1, op2]) => Ok(Op::ContainedInRootFromEntries(
+                op1.clone(),
+                op2.clone(),
+                merkle_proof.ok_or(anyhow!("Membership requires a Merkle proof"))?,
+            )),
+            _ => Err(anyhow!(
+                "Assert requires exactly {} operands",
+                assert_type.arity()
+            )),
+        }
+    }
+}
+/// Custom predicate defined with `defpredicate`, e.g.
+/// `[defpredicate over-18 [age] [> age 18]]`, whose clauses are asserts and other custom
+/// predicates over its parameters and scalars.
+#[derive(Clone, Debug)]
+pub struct PexPredicate {
+    pub definition: CustomPredicate,
+    clauses: Vec<PredicateClause>,
+}
+
+#[derive(Clone, Debug)]
+enum PredicateClause {
+    Assert(AssertType, Vec<PredicateArg>),
+    Custom(Arc<PexPredicate>, Vec<PredicateArg>),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum PredicateArg {
+    Param(usize),
+    Scalar(GoldilocksField),
+}
+
+impl PredicateArg {
+    fn value(&self, args: &[Value]) -> Value {
+        match self {
+            PredicateArg::Param(i) => args[*i].clone(),
+            PredicateArg::Scalar(s) => Value::Scalar(*s),
         }
     }
 }
+
 #[derive(Clone, Copy, Debug)]
 pub enum OpType {
     Add,
     Multiply,
     Max,
+    Hash,
+    Subtract,
+    Min,
+    Divide,
+    Modulo,
 }
 
 impl OpType {
@@ -364,9 +487,21 @@ impl OpType {
             "+" => Ok(OpType::Add),
             "*" => Ok(OpType::Multiply),
             "max" => Ok(OpType::Max),
+            "hash" => Ok(OpType::Hash),
+            "-" => Ok(OpType::Subtract),
+            "min" => Ok(OpType::Min),
+            "div" => Ok(OpType::Divide),
+            "mod" => Ok(OpType::Modulo),
             _ => Err(anyhow!("Unknown operation type: {}", s)),
         }
     }
+
+    fn is_commutative(&self) -> bool {
+        !matches!(
+            self,
+            OpType::Hash | OpType::Subtract | OpType::Divide | OpType::Modulo
+        )
+    }
 }
 
 impl From<(OpType, Value, Value)> for Operation {
@@ -375,6 +510,11 @@ impl From<(OpType, Value, Value)> for Operation {
             OpType::Add => Operation::Sum(op1, op2),
             OpType::Multiply => Operation::Product(op1, op2),
             OpType::Max => Operation::Max(op1, op2),
+            OpType::Hash => Operation::Hash(op1, op2),
+            OpType::Subtract => Operation::Difference(op1, op2),
+            OpType::Min => Operation::Min(op1, op2),
+            OpType::Divide => Operation::Quotient(op1, op2),
+            OpType::Modulo => Operation::Remainder(op1, op2),
         }
     }
 }
@@ -384,6 +524,15 @@ pub enum Operation {
     Sum(Value, Value),
     Product(Value, Value),
     Max(Value, Value),
+    /// Poseidon hash of the two values
+    Hash(Value, Value),
+    /// Difference of the two values, the second being at most the first
+    Difference(Value, Value),
+    Min(Value, Value),
+    /// Quotient of the division of two u32 values
+    Quotient(Value, Value),
+    /// Remainder of the division of two u32 values
+    Remainder(Value, Value),
 }
 
 impl Operation {
@@ -397,7 +546,14 @@ impl Operation {
 
     fn evaluate_values(&self, env: Option<&Env>) -> Result<(GoldilocksField, GoldilocksField)> {
         match self {
-            Operation::Sum(a, b) | Operation::Product(a, b) | Operation::Max(a, b) => {
+            Operation::Sum(a, b)
+            | Operation::Product(a, b)
+            | Operation::Max(a, b)
+            | Operation::Hash(a, b)
+            | Operation::Difference(a, b)
+            | Operation::Min(a, b)
+            | Operation::Quotient(a, b)
+            | Operation::Remainder(a, b) => {
                 let value1 = Self::extract_value(a, env)?;
                 let value2 = Self::extract_value(b, env)?;
                 Ok((value1, value2))
@@ -405,15 +561,32 @@ impl Operation {
         }
     }
 
-    fn apply_operation(&self, value1: GoldilocksField, value2: GoldilocksField) -> GoldilocksField {
+    fn apply_operation(
+        &self,
+        value1: GoldilocksField,
+        value2: GoldilocksField,
+    ) -> Result<GoldilocksField> {
+        let (x1, x2) = (value1.to_canonical_u64(), value2.to_canonical_u64());
         match self {
-            Operation::Sum(_, _) => value1 + value2,
-            Operation::Product(_, _) => value1 * value2,
-            Operation::Max(_, _) => {
-                if value1.to_canonical_u64() > value2.to_canonical_u64() {
-                    value1
+            Operation::Sum(_, _) => Ok(value1 + value2),
+            Operation::Product(_, _) => Ok(value1 * value2),
+            Operation::Max(_, _) => Ok(if x1 > x2 { value1 } else { value2 }),
+            Operation::Hash(_, _) => Ok(Self::hash_values(value1, value2).hash_or_value()),
+            Operation::Difference(_, _) => x1.checked_sub(x2).map(GoldilocksField).ok_or(anyhow!(
+                "Cannot subtract {} from {}",
+                x2,
+                x1
+            )),
+            Operation::Min(_, _) => Ok(if x1 < x2 { value1 } else { value2 }),
+            Operation::Quotient(_, _) | Operation::Remainder(_, _) => {
+                if x1 > u32::MAX as u64 || x2 > u32::MAX as u64 {
+                    Err(anyhow!("Cannot divide {} by {}: not u32 values", x1, x2))
+                } else if x2 == 0 {
+                    Err(anyhow!("Cannot divide {} by zero", x1))
+                } else if matches!(self, Operation::Quotient(_, _)) {
+                    Ok(GoldilocksField(x1 / x2))
                 } else {
-                    value2
+                    Ok(GoldilocksField(x1 % x2))
                 }
             }
         }
@@ -421,18 +594,34 @@ impl Operation {
 
     fn eval(&self) -> Result<GoldilocksField> {
         let (value1, value2) = self.evaluate_values(None)?;
-        Ok(self.apply_operation(value1, value2))
+        self.apply_operation(value1, value2)
     }
 
-    fn eval_with_env(&self, env: &Env) -> Result<GoldilocksField> {
+    /// Evaluates the operation to the value of its result entry, which for hashes is the full
+    /// Poseidon hash of the operands.
+    fn eval_with_env(&self, env: &Env) -> Result<ScalarOrVec> {
         let (value1, value2) = self.evaluate_values(Some(env))?;
-        Ok(self.apply_operation(value1, value2))
+        match self {
+            Operation::Hash(_, _) => Ok(Self::hash_values(value1, value2)),
+            _ => self
+                .apply_operation(value1, value2)
+                .map(ScalarOrVec::Scalar),
+        }
+    }
+
+    fn hash_values(value1: GoldilocksField, value2: GoldilocksField) -> ScalarOrVec {
+        ScalarOrVec::from_hash(PoseidonHash::hash_no_pad(&[value1, value2]))
     }
     fn into_pod_op(op_type: OpType, result_ref: SRef, op1: SRef, op2: SRef) -> Op<StatementRef> {
         match op_type {
             OpType::Add => Op::SumOf(result_ref.into(), op1.into(), op2.into()),
             OpType::Multiply => Op::ProductOf(result_ref.into(), op1.into(), op2.into()),
             OpType::Max => Op::MaxOf(result_ref.into(), op1.into(), op2.into()),
+            OpType::Hash => Op::HashOf(result_ref.into(), vec![op1.into(), op2.into()]),
+            OpType::Subtract => Op::DifferenceOf(result_ref.into(), op1.into(), op2.into()),
+            OpType::Min => Op::MinOf(result_ref.into(), op1.into(), op2.into()),
+            OpType::Divide => Op::QuotientOf(result_ref.into(), op1.into(), op2.into()),
+            OpType::Modulo => Op::RemainderOf(result_ref.into(), op1.into(), op2.into()),
         }
     }
 }
@@ -460,7 +649,7 @@ enum QueryConstraint {
     },
     Assert {
         assert_type: AssertType,
-        operands: (Box<OperandConstraint>, Box<OperandConstraint>),
+        operands: Vec<OperandConstraint>,
     },
 }
 
@@ -486,33 +675,29 @@ impl PodQueryBuilder {
         }
     }
     fn add_assert(&mut self, assert: &Assert) -> Result<()> {
-        let (op1, op2) = match assert {
-            Assert::Eq(v1, v2) | Assert::Neq(v1, v2) | Assert::Gt(v1, v2) | Assert::Lt(v1, v2) => {
-                (v1, v2)
-            }
-        };
-        let op1_constraint = self.add_value(op1)?;
-        let op2_constraint = self.add_value(op2)?;
-
-        let assert_type = match assert {
-            Assert::Eq(_, _) => AssertType::Eq,
-            Assert::Neq(_, _) => AssertType::Neq,
-            Assert::Gt(_, _) => AssertType::Gt,
-            Assert::Lt(_, _) => AssertType::Lt,
-        };
+        let operands = assert
+            .operands()
+            .into_iter()
+            .map(|op| self.add_value(op))
+            .collect::<Result<Vec<_>>>()?;
 
         self.constraints.push(QueryConstraint::Assert {
-            assert_type,
-            operands: (Box::new(op1_constraint), Box::new(op2_constraint)),
+            assert_type: assert.assert_type(),
+            operands,
         });
         Ok(())
     }
 
     fn add_operation(&mut self, op: &Operation) -> Result<OperandConstraint> {
         let (op1, op2) = match op {
-            Operation::Sum(v1, v2) | Operation::Product(v1, v2) | Operation::Max(v1, v2) => {
-                (v1, v2)
-            }
+            Operation::Sum(v1, v2)
+            | Operation::Product(v1, v2)
+            | Operation::Max(v1, v2)
+            | Operation::Hash(v1, v2)
+            | Operation::Difference(v1, v2)
+            | Operation::Min(v1, v2)
+            | Operation::Quotient(v1, v2)
+            | Operation::Remainder(v1, v2) => (v1, v2),
         };
 
         let op1_constraint = self.add_value(op1)?;
@@ -522,6 +707,11 @@ impl PodQueryBuilder {
             Operation::Sum(_, _) => OpType::Add,
             Operation::Product(_, _) => OpType::Multiply,
             Operation::Max(_, _) => OpType::Max,
+            Operation::Hash(_, _) => OpType::Hash,
+            Operation::Difference(_, _) => OpType::Subtract,
+            Operation::Min(_, _) => OpType::Min,
+            Operation::Quotient(_, _) => OpType::Divide,
+            Operation::Remainder(_, _) => OpType::Modulo,
         };
 
         Ok(OperandConstraint::Operation(Box::new(
@@ -582,13 +772,8 @@ impl PodBuilder {
             next_statement_id: 0,
         }
     }
-    pub fn pod_id(pod: &POD) -> String {
-        let pod_hash = pod.payload.hash_payload().to_vec()[0];
-        let name = format!("{}{}", POD_PREFIX, pod_hash);
-        name
-    }
     pub fn register_input_pod(&mut self, pod: &POD) -> String {
-        let name = PodBuilder::pod_id(pod);
+        let name = pod.id().to_string();
         if let Some(_) = self.input_pods.get(&name) {
             name.clone()
         } else {
@@ -619,14 +804,21 @@ impl PodBuilder {
     }
 
     fn get_or_create_constant_ref(&mut self, value: GoldilocksField) -> SRef {
-        let key = value.to_string();
+        self.get_or_create_keyed_constant_ref(value.to_string(), ScalarOrVec::Scalar(value))
+    }
+
+    /// Constant holding a u64 as a signed integer, see `ScalarOrVec::from(u64)`.
+    fn get_or_create_int_constant_ref(&mut self, value: u64) -> SRef {
+        self.get_or_create_keyed_constant_ref(format!("int_{}", value), ScalarOrVec::from(value))
+    }
 
+    fn get_or_create_keyed_constant_ref(&mut self, key: String, value: ScalarOrVec) -> SRef {
         // Only match against other constants (statements starting with "constant_")
         if let Some((statement_id, _)) =
             self.pending_operations.iter().find(|(statement_id, op)| {
                 statement_id.starts_with(STATEMENT_PREFIX_CONSTANT)
                     && if let Op::NewEntry(entry) = &op.0 {
-                        entry.key == key && entry.value == ScalarOrVec::Scalar(value)
+                        entry.key == key && entry.value == value
                     } else {
                         false
                     }
@@ -640,13 +832,43 @@ impl PodBuilder {
         self.add_operation(
             Op::NewEntry(Entry {
                 key: key.clone(),
-                value: ScalarOrVec::Scalar(value),
+                value,
             }),
             statement_name.clone(),
         );
         SRef::self_ref(format!("{}:{}", PREDICATE_VALUEOF, statement_name))
     }
 
+    /// Names the statements of new entries after their keys, as PODs require of their own
+    /// VALUEOF statements, and points the operations referencing them to the new names.
+    fn name_entries_after_keys(&mut self) -> Result<()> {
+        let new_names = self
+            .pending_operations
+            .iter()
+            .filter_map(|(_, OpCmd(op, name))| match op {
+                Op::NewEntry(entry) if entry.key != *name => Some((
+                    format!("{}:{}", PREDICATE_VALUEOF, name),
+                    format!("{}:{}", PREDICATE_VALUEOF, entry.key),
+                )),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+        for (_, OpCmd(op, name)) in self.pending_operations.iter_mut() {
+            *op = op.map_operands(&|statement_ref: &StatementRef| {
+                Ok(match new_names.get(&statement_ref.1) {
+                    Some(new_name) if statement_ref.0 == SELF_ORIGIN_NAME => {
+                        StatementRef(statement_ref.0.clone(), new_name.clone())
+                    }
+                    _ => statement_ref.clone(),
+                })
+            })?;
+            if let Op::NewEntry(entry) = op {
+                *name = entry.key.clone();
+            }
+        }
+        Ok(())
+    }
+
     pub fn finalize(&mut self, env: &Env) -> Result<POD> {
         let could_be_schnorr = self.input_pods.is_empty()
             && self
@@ -669,16 +891,20 @@ impl PodBuilder {
                 })
                 .collect::<Vec<_>>();
 
-            Ok(POD::execute_schnorr_gadget::<NS, VL>(&entries, &env.sk.unwrap()).unwrap())
+            let sk = env
+                .sk
+                .as_ref()
+                .ok_or(anyhow!("A secret key is needed to sign PODs"))?;
+            Ok(POD::execute_schnorr_gadget::<NS, VL>(&entries, sk)?)
         } else {
+            self.name_entries_after_keys()?;
             let mut origin_renaming_map = HashMap::new();
             let mut used_origin_names = HashSet::new();
             let mut next_id = 1;
 
             for (pod_id, pod) in &self.input_pods {
-                // For _SELF origins, use the pod's payload hash
-                let pod_hash = format!("{}", pod.payload.hash_payload().to_vec()[0]);
-                if !used_origin_names.insert(pod_hash.clone()) {
+                // For _SELF origins, use the pod's ID
+                if !used_origin_names.insert(pod_id.clone()) {
                     while used_origin_names.contains(&format!("origin_{}", next_id)) {
                         next_id += 1;
                     }
@@ -692,7 +918,7 @@ impl PodBuilder {
                 } else {
                     origin_renaming_map.insert(
                         (pod_id.clone(), SELF_ORIGIN_NAME.to_string()),
-                        format!("{}{}", POD_PREFIX, pod_hash),
+                        pod_id.clone(),
                     );
                 }
 
@@ -744,9 +970,22 @@ impl PodBuilder {
                 .collect::<Vec<OpCmd>>()[..];
             if let Some(prover_params) = &env.prover_params {
                 let mut params = prover_params.lock().unwrap();
-                POD::execute_plonky_gadget::<L, M, N, NS, VL>(&mut params, &gpg_input, pending_ops)
+                Ok(POD::execute_plonky_gadget::<L, M, N, NS, VL>(
+                    &mut params,
+                    &gpg_input,
+                    pending_ops,
+                )?)
             } else {
-                POD::execute_oracle_gadget(&gpg_input, pending_ops)
+                // without prover params, the user acts as the oracle vouching for the POD
+                let sk = env
+                    .sk
+                    .clone()
+                    .ok_or(anyhow!("A secret key is needed to sign oracle PODs"))?;
+                Ok(POD::execute_oracle_gadget(
+                    &Oracle::new(sk),
+                    &gpg_input,
+                    pending_ops,
+                )?)
             }
         }
     }
@@ -757,7 +996,7 @@ impl Env {
         user: User,
         shared: Arc<dyn SharedStore>,
         pod_store: Arc<Mutex<MyPods>>,
-        sk: Option<SchnorrSecretKey>,
+        sk: Option<EcSchnorrSecretKey>,
         script_id: Option<ScriptId>,
         prover_params: Option<Arc<Mutex<ProverParams<L, M, N, NS, VL>>>>,
     ) -> Self {
@@ -771,6 +1010,7 @@ impl Env {
             sk,
             script_id,
             prover_params,
+            merkle_trees: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
@@ -786,6 +1026,7 @@ impl Env {
             sk: self.sk.clone(),
             script_id: self.script_id.clone(),
             prover_params: self.prover_params.clone(),
+            merkle_trees: self.merkle_trees.clone(),
         }
     }
 
@@ -806,6 +1047,23 @@ impl Env {
     pub fn set_binding(&self, name: String, value: Value) {
         self.bindings.lock().unwrap().insert(name, value);
     }
+
+    fn add_merkle_tree(&self, tree: MerkleTree) -> GoldilocksField {
+        let root = tree.root();
+        self.merkle_trees.lock().unwrap().insert(root, tree);
+        root
+    }
+
+    /// Proof that `value` is a member of the Merkle tree with the given root.
+    fn merkle_proof(&self, value: GoldilocksField, root: GoldilocksField) -> Result<MerkleProof> {
+        self.merkle_trees
+            .lock()
+            .unwrap()
+            .get(&root)
+            .ok_or(anyhow!("No Merkle tree with root {}", root))?
+            .prove(value)
+            .ok_or(anyhow!("{} is not a member of the Merkle tree", value))
+    }
 }
 
 type Id = u64;
@@ -973,6 +1231,10 @@ impl Expr {
                                     }
                                     self.eval_create_pod(&exprs[1..], env).await
                                 }
+                                "neg" => self.eval_negation(&exprs[1..], env).await,
+                                "defpredicate" => {
+                                    self.eval_define_predicate(&exprs[1..], env).await
+                                }
                                 "pod?" => {
                                     if exprs.len() < 2 {
                                         return Err(anyhow!("pod? requires at least one argument"));
@@ -1059,6 +1321,25 @@ impl Expr {
                                         _ => Err(anyhow!("cdr requires a list argument")),
                                     }
                                 }
+                                "merkle-root" => {
+                                    let mut values = Vec::new();
+                                    for expr in &exprs[1..] {
+                                        match expr.eval(env.clone()).await? {
+                                            Value::List(list) => values.extend(list),
+                                            value => values.push(value),
+                                        }
+                                    }
+                                    let leaves = values
+                                        .into_iter()
+                                        .map(|value| match value {
+                                            Value::Scalar(s) => Ok(s),
+                                            _ => Err(anyhow!("merkle-root requires scalars")),
+                                        })
+                                        .collect::<Result<Vec<_>>>()?;
+                                    Ok(Value::Scalar(
+                                        env.add_merkle_tree(MerkleTree::new(&leaves)?),
+                                    ))
+                                }
                                 "cons" => {
                                     if exprs.len() != 3 {
                                         return Err(anyhow!("cons requires exactly two arguments"));
@@ -1074,7 +1355,12 @@ impl Expr {
                                         }
                                     }
                                 }
-                                op => Err(anyhow!("Unknown operation: {}", op)),
+                                op => match env.get_binding(op) {
+                                    Some(Value::Predicate(predicate)) => {
+                                        self.eval_predicate(&predicate, &exprs[1..], env).await
+                                    }
+                                    _ => Err(anyhow!("Unknown operation: {}", op)),
+                                },
                             }
                         }
                     }
@@ -1139,15 +1425,8 @@ impl Expr {
         // Then process key-value pairs until we hit an assertion or end
         let mut j = i;
         while j < body.len() {
-            match &body[j] {
-                Expr::List(_, exprs) => {
-                    if let Some(Expr::Atom(_, op)) = exprs.first() {
-                        if matches!(op.as_str(), "<" | ">" | "=" | "!=") {
-                            break;
-                        }
-                    }
-                }
-                _ => {}
+            if body[j].is_assertion(&pod_env) {
+                break;
             }
             j += 1;
         }
@@ -1294,12 +1573,8 @@ impl Expr {
             }
         }
         for assertion in &body[j..] {
-            if let Expr::List(_, exprs) = assertion {
-                if let Some(Expr::Atom(_, op)) = exprs.first() {
-                    if matches!(op.as_str(), ">" | "=" | "!=") {
-                        assertion.eval(pod_env.clone()).await?;
-                    }
-                }
+            if assertion.is_assertion(&pod_env) {
+                assertion.eval(pod_env.clone()).await?;
             }
         }
         let pod = builder.lock().unwrap().finalize(&env)?;
@@ -1333,14 +1608,12 @@ impl Expr {
                         }
 
                         // Handle assertions
-                        if matches!(op.as_str(), ">" | "=" | "!=" | "<") {
-                            if exprs.len() != 3 {
-                                return Err(anyhow!("Assert requires exactly two operands"));
+                        if let Ok(assert_type) = AssertType::from_str(op) {
+                            let mut operands = Vec::new();
+                            for expr in &exprs[1..] {
+                                operands.push(expr.eval(query_env.clone()).await?);
                             }
-                            let assert_type = AssertType::from_str(op)?;
-                            let op1 = exprs[1].eval(query_env.clone()).await?;
-                            let op2 = exprs[2].eval(query_env.clone()).await?;
-                            let assert = (assert_type, op1, op2).into();
+                            let assert = Assert::new(assert_type, &operands)?;
                             query_builder.lock().unwrap().add_assert(&assert)?;
                             continue;
                         }
@@ -1405,7 +1678,7 @@ impl Expr {
         if let Some(ref _query) = env.current_query {
             match (&op1, &op2) {
                 (Value::Scalar(s1), Value::Scalar(s2)) => {
-                    Ok(Value::Scalar(operation.apply_operation(*s1, *s2)))
+                    Ok(Value::Scalar(operation.apply_operation(*s1, *s2)?))
                 }
                 _ => Ok(Value::Operation(Box::new(operation))),
             }
@@ -1440,7 +1713,7 @@ impl Expr {
                     builder.add_operation(
                         Op::NewEntry(Entry {
                             key: result_key.clone(),
-                            value: ScalarOrVec::Scalar(result_value),
+                            value: result_value.clone(),
                         }),
                         new_entry_statement_id,
                     );
@@ -1451,7 +1724,7 @@ impl Expr {
 
                     Ok(Value::SRef(result_sref))
                 }
-                _ => Ok(Value::Scalar(result_value)),
+                _ => Ok(Value::Scalar(result_value.hash_or_value())),
             }
         } else {
             // Direct evaluation
@@ -1461,67 +1734,362 @@ impl Expr {
             Ok(Value::Scalar(operation.eval()?))
         }
     }
+    /// Negates a signed integer entry, or a u64 constant entered as one, into a new entry of
+    /// the POD being created. Other values have no negation, see `ScalarOrVec::negation`.
+    async fn eval_negation(&self, operands: &[Expr], env: Env) -> Result<Value> {
+        if operands.len() != 1 {
+            return Err(anyhow!("neg requires exactly one operand"));
+        }
+        let Some(ref builder) = env.current_builder else {
+            return Err(anyhow!("neg is only supported when creating PODs"));
+        };
+        let operand = operands[0].eval(env.clone()).await?;
+        let value = match &operand {
+            Value::Scalar(s) => ScalarOrVec::from(s.to_canonical_u64()),
+            Value::SRef(r) => get_entry_value_from_sref(r, &env)?,
+            _ => return Err(anyhow!("Invalid operand type")),
+        };
+        let result_value = value
+            .negation()
+            .ok_or(anyhow!("Cannot negate {:?}: not a signed integer", value))?;
+        let mut builder = builder.lock().unwrap();
+        let operand_sref = match operand {
+            Value::SRef(r) => r,
+            Value::Scalar(s) => builder.get_or_create_int_constant_ref(s.to_canonical_u64()),
+            _ => return Err(anyhow!("Invalid operand type")),
+        };
+        let result_key = builder.next_result_key_id();
+        let new_entry_statement_id = builder.next_statement_id();
+        let result_sref =
+            SRef::self_ref(format!("{}:{}", PREDICATE_VALUEOF, new_entry_statement_id));
+        builder.add_operation(
+            Op::NewEntry(Entry {
+                key: result_key,
+                value: result_value,
+            }),
+            new_entry_statement_id,
+        );
+        let op_statement_id = builder.next_statement_id();
+        builder.add_operation(
+            Op::NegationOf(result_sref.clone().into(), operand_sref.into()),
+            op_statement_id,
+        );
+        Ok(Value::SRef(result_sref))
+    }
+
     async fn eval_assert(
         &self,
         assert_type: AssertType,
         operands: &[Expr],
         env: Env,
     ) -> Result<Value> {
-        if operands.len() != 2 {
-            return Err(anyhow!("Asserts require exactly two operands"));
+        if operands.len() != assert_type.arity() {
+            return Err(anyhow!(
+                "Asserts require exactly {} operands",
+                assert_type.arity()
+            ));
         }
-        let op1 = operands[0].eval(env.clone()).await?;
-        let op2 = operands[1].eval(env.clone()).await?;
+        let mut values = Vec::new();
+        for operand in operands {
+            values.push(operand.eval(env.clone()).await?);
+        }
+        apply_assert_values(assert_type, values, &env)
+    }
 
-        let assert: Assert = (assert_type, op1.clone(), op2.clone()).into();
-        if let Some(ref _query) = env.current_query {
-            match (&op1, &op2) {
-                (Value::Scalar(s1), Value::Scalar(s2)) => {
-                    Ok(Value::Scalar(assert.apply_assert(*s1, *s2)))
+    async fn eval_define_predicate(&self, args: &[Expr], env: Env) -> Result<Value> {
+        let (name, params, body) = match args {
+            [Expr::Atom(_, name), Expr::List(_, params), body @ ..] if !body.is_empty() => {
+                (name, params, body)
+            }
+            _ => {
+                return Err(anyhow!(
+                    "defpredicate requires a name, a list of parameters and a body"
+                ))
+            }
+        };
+        let params = params
+            .iter()
+            .map(|param| match param {
+                Expr::Atom(_, param) => Ok(param.clone()),
+                _ => Err(anyhow!("Predicate parameters must be atoms")),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut templates = Vec::new();
+        let mut clauses = Vec::new();
+        let mut next_var = params.len();
+        for clause in body {
+            let (head, operands) = match clause {
+                Expr::List(_, exprs) => match exprs.split_first() {
+                    Some((Expr::Atom(_, head), operands)) => (head, operands),
+                    _ => return Err(anyhow!("Predicate clauses must start with a predicate")),
+                },
+                _ => return Err(anyhow!("Predicate clauses must be lists")),
+            };
+            let mut clause_args = Vec::new();
+            for operand in operands {
+                let param = match operand {
+                    Expr::Atom(_, atom) => params.iter().position(|param| param == atom),
+                    _ => None,
+                };
+                clause_args.push(match param {
+                    Some(i) => PredicateArg::Param(i),
+                    None => match operand.eval(env.clone()).await? {
+                        Value::Scalar(s) => PredicateArg::Scalar(s),
+                        _ => {
+                            return Err(anyhow!(
+                                "Predicate clauses only take parameters and scalars"
+                            ))
+                        }
+                    },
+                });
+            }
+            let (template_code, template_value, clause) =
+                if let Ok(assert_type) = AssertType::from_str(head) {
+                    if clause_args.len() != assert_type.arity() {
+                        return Err(anyhow!(
+                            "Asserts require exactly {} operands",
+                            assert_type.arity()
+                        ));
+                    }
+                    let clause = PredicateClause::Assert(assert_type, clause_args.clone());
+                    (assert_type.statement_code(), None, clause)
+                } else if let Some(Value::Predicate(predicate)) = env.get_binding(head) {
+                    if clause_args.len() != predicate.definition.arity() {
+                        return Err(anyhow!(
+                            "Predicate {} requires exactly {} operands",
+                            head,
+                            predicate.definition.arity()
+                        ));
+                    }
+                    let id = predicate.definition.id();
+                    let clause = PredicateClause::Custom(predicate, clause_args.clone());
+                    (Statement::CUSTOM, Some(id), clause)
+                } else {
+                    return Err(anyhow!("Unknown predicate: {}", head));
+                };
+
+            // Scalars are bound to fresh variables, whose values are fixed by the `VALUEOF`
+            // statements following the clause.
+            let mut vars = Vec::new();
+            let mut constants = Vec::new();
+            for arg in &clause_args {
+                match arg {
+                    PredicateArg::Param(i) => vars.push(*i),
+                    PredicateArg::Scalar(s) => {
+                        vars.push(next_var);
+                        constants.push(
+                            StatementTemplate::new(Statement::VALUE_OF, &[next_var]).with_value(*s),
+                        );
+                        next_var += 1;
+                    }
                 }
-                _ => Ok(Value::Assert(Box::new(assert))),
             }
-        } else if let Some(ref builder) = env.current_builder {
-            let mut builder = builder.lock().unwrap();
+            let template = StatementTemplate::new(template_code, &vars);
+            templates.push(match template_value {
+                Some(value) => template.with_value(value),
+                None => template,
+            });
+            templates.extend(constants);
+            clauses.push(clause);
+        }
 
-            // Create refs for any values that need tracking
-            match (&op1, &op2) {
-                (Value::SRef(_), _) | (_, Value::SRef(_)) => {
-                    // Convert operands to SRefs if they're scalars
-                    let op1_sref = match op1 {
-                        Value::Scalar(s) => builder.get_or_create_constant_ref(s),
-                        Value::SRef(r) => r,
-                        _ => return Err(anyhow!("Invalid operand type")),
-                    };
+        let predicate = Value::Predicate(Arc::new(PexPredicate {
+            definition: CustomPredicate::new(name.clone(), params.len(), templates)?,
+            clauses,
+        }));
+        env.set_binding(name.clone(), predicate.clone());
+        Ok(predicate)
+    }
 
-                    let op2_sref = match op2 {
-                        Value::Scalar(s) => builder.get_or_create_constant_ref(s),
-                        Value::SRef(r) => r,
-                        _ => return Err(anyhow!("Invalid operand type")),
-                    };
+    async fn eval_predicate(
+        &self,
+        predicate: &PexPredicate,
+        operands: &[Expr],
+        env: Env,
+    ) -> Result<Value> {
+        if operands.len() != predicate.definition.arity() {
+            return Err(anyhow!(
+                "Predicate {} requires exactly {} operands",
+                predicate.definition.name(),
+                predicate.definition.arity()
+            ));
+        }
+        let mut values = Vec::new();
+        for operand in operands {
+            values.push(operand.eval(env.clone()).await?);
+        }
+        apply_predicate(predicate, values, &env)
+    }
 
-                    // We need to create a new entry for the result
-                    let pod_op = Assert::into_pod_op(assert_type, op1_sref, op2_sref);
-                    let op_statement_id = builder.next_statement_id();
-                    builder.add_operation(pod_op, op_statement_id.clone());
-                    let assert_sref = SRef::self_ref(format!(
-                        "{}:{}",
-                        Assert::predicate_from_op(assert_type),
-                        op_statement_id.clone()
-                    ));
-
-                    Ok(Value::SRef(assert_sref))
+    /// Whether the expression is an assert or the application of a custom predicate.
+    fn is_assertion(&self, env: &Env) -> bool {
+        match self {
+            Expr::List(_, exprs) => match exprs.first() {
+                Some(Expr::Atom(_, op)) => {
+                    AssertType::from_str(op).is_ok()
+                        || matches!(env.get_binding(op), Some(Value::Predicate(_)))
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+/// Applies an assert to its evaluated operands. In a POD being created, the assert is added to
+/// it if it involves statements, and otherwise it is evaluated on scalars.
+fn apply_assert_values(assert_type: AssertType, values: Vec<Value>, env: &Env) -> Result<Value> {
+    let assert = Assert::new(assert_type, &values)?;
+    if let Some(ref _query) = env.current_query {
+        let scalars = values
+            .iter()
+            .map(|value| match value {
+                Value::Scalar(s) => Some(*s),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        match scalars {
+            Some(scalars) => Ok(Value::Scalar(assert.apply_assert(&scalars, env))),
+            None => Ok(Value::Assert(Box::new(assert))),
+        }
+    } else if let Some(ref builder) = env.current_builder {
+        // Membership is proven with a path to the root, found before locking the builder as
+        // the values of the refs are read from it
+        let merkle_proof = match assert {
+            Assert::Member(_, _) if values.iter().any(|value| matches!(value, Value::SRef(_))) => {
+                match assert.evaluate_values(Some(env))?.as_slice() {
+                    [value, root] => Some(env.merkle_proof(*value, *root)?),
+                    _ => None,
                 }
-                _ => Ok(Value::Scalar(assert.eval()?)),
             }
+            _ => None,
+        };
+        let mut builder = builder.lock().unwrap();
+
+        // Create refs for any values that need tracking
+        if values.iter().any(|value| matches!(value, Value::SRef(_))) {
+            // Convert operands to SRefs if they're scalars
+            let srefs = values
+                .into_iter()
+                .map(|value| match value {
+                    Value::Scalar(s) => Ok(builder.get_or_create_constant_ref(s)),
+                    Value::SRef(r) => Ok(r),
+                    _ => Err(anyhow!("Invalid operand type")),
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            // We need to create a new entry for the result
+            let pod_op = Assert::into_pod_op(assert_type, srefs, merkle_proof)?;
+            let op_statement_id = builder.next_statement_id();
+            builder.add_operation(pod_op, op_statement_id.clone());
+            let assert_sref = SRef::self_ref(format!(
+                "{}:{}",
+                Assert::predicate_from_op(assert_type),
+                op_statement_id.clone()
+            ));
+
+            Ok(Value::SRef(assert_sref))
         } else {
-            // Direct evaluation
-            Ok(Value::Scalar(assert.eval()?))
+            Ok(Value::Scalar(assert.eval(env)?))
         }
+    } else {
+        // Direct evaluation
+        Ok(Value::Scalar(assert.eval(env)?))
     }
 }
 
+/// Applies a custom predicate to its evaluated arguments. In a POD being created, the
+/// statements of its clauses are added to it together with the custom statement derived from
+/// them, and otherwise it is evaluated on scalars.
+fn apply_predicate(predicate: &PexPredicate, args: Vec<Value>, env: &Env) -> Result<Value> {
+    if env.current_query.is_some() {
+        return Err(anyhow!(
+            "Custom predicate {} can not be queried",
+            predicate.definition.name()
+        ));
+    }
+    let Some(builder) = env.current_builder.as_ref() else {
+        let holds = predicate.clauses.iter().try_fold(true, |holds, clause| {
+            let value = match clause {
+                PredicateClause::Assert(assert_type, clause_args) => {
+                    let values = clause_args.iter().map(|arg| arg.value(&args)).collect();
+                    apply_assert_values(*assert_type, values, env)?
+                }
+                PredicateClause::Custom(predicate, clause_args) => {
+                    let values = clause_args.iter().map(|arg| arg.value(&args)).collect();
+                    apply_predicate(predicate, values, env)?
+                }
+            };
+            Ok::<_, anyhow::Error>(holds && matches!(value, Value::Scalar(GoldilocksField(1))))
+        })?;
+        return Ok(Value::Scalar(GoldilocksField(holds as u64)));
+    };
+
+    // Scalars are entries of the POD, so that the clauses are proven by statements.
+    let constant_ref = |s: GoldilocksField| builder.lock().unwrap().get_or_create_constant_ref(s);
+    let args = args
+        .into_iter()
+        .map(|arg| match arg {
+            Value::Scalar(s) => Ok(Value::SRef(constant_ref(s))),
+            Value::SRef(r) => Ok(Value::SRef(r)),
+            _ => Err(anyhow!("Invalid operand type")),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut operands: Vec<StatementRef> = Vec::new();
+    for clause in &predicate.clauses {
+        let clause_args = match clause {
+            PredicateClause::Assert(_, clause_args) | PredicateClause::Custom(_, clause_args) => {
+                clause_args
+            }
+        };
+        let values = clause_args
+            .iter()
+            .map(|arg| match arg {
+                PredicateArg::Scalar(s) => Value::SRef(constant_ref(*s)),
+                arg => arg.value(&args),
+            })
+            .collect();
+        let statement = match clause {
+            PredicateClause::Assert(assert_type, _) => {
+                apply_assert_values(*assert_type, values, env)?
+            }
+            PredicateClause::Custom(predicate, _) => apply_predicate(predicate, values, env)?,
+        };
+        match statement {
+            Value::SRef(r) => operands.push(r.into()),
+            _ => return Err(anyhow!("Predicate clause did not yield a statement")),
+        }
+        // `VALUEOF` statements of the scalars, in the order of the definition.
+        operands.extend(clause_args.iter().filter_map(|arg| match arg {
+            PredicateArg::Scalar(s) => Some(constant_ref(*s).into()),
+            PredicateArg::Param(_) => None,
+        }));
+    }
+
+    let mut builder = builder.lock().unwrap();
+    let statement_id = builder.next_statement_id();
+    builder.add_operation(
+        Op::CustomFromStatements(operands, predicate.definition.clone()),
+        statement_id.clone(),
+    );
+    Ok(Value::SRef(SRef::self_ref(format!(
+        "{}:{}",
+        Statement::code_to_predicate(Statement::CUSTOM),
+        statement_id
+    ))))
+}
+
 fn get_value_from_sref(sref: &SRef, env: &Env) -> Result<GoldilocksField> {
+    match get_entry_value_from_sref(sref, env)? {
+        ScalarOrVec::Scalar(value) => Ok(value),
+        value if sref.0.eq(&ORef::S) => Ok(value.hash_or_value()),
+        _ => Err(anyhow!("Value not found or not scalar")),
+    }
+}
+
+/// Full value of the entry a ref points to, in the POD being created or in an input POD.
+fn get_entry_value_from_sref(sref: &SRef, env: &Env) -> Result<ScalarOrVec> {
     if let Some(ref builder) = env.current_builder {
         let builder = builder.lock().unwrap();
         if sref.0.eq(&ORef::S) {
@@ -1531,9 +2099,7 @@ fn get_value_from_sref(sref: &SRef, env: &Env) -> Result<GoldilocksField> {
                 })
             {
                 if let Op::NewEntry(entry) = &op_cmd.0 {
-                    if let ScalarOrVec::Scalar(value) = entry.value {
-                        return Ok(value);
-                    }
+                    return Ok(entry.value.clone());
                 }
             }
             return Err(anyhow!("Value not found in current pod operations"));
@@ -1541,12 +2107,10 @@ fn get_value_from_sref(sref: &SRef, env: &Env) -> Result<GoldilocksField> {
         // Look up value in input pods using origin mapping
         let key: String = sref.0.clone().into();
         if let Some(pod) = builder.input_pods.get(&key) {
-            if let Some(Statement::ValueOf(_, ScalarOrVec::Scalar(value))) =
-                pod.payload.statements_map.get(&sref.1)
-            {
-                Ok(*value)
+            if let Some(Statement::ValueOf(_, value)) = pod.payload.statements_map.get(&sref.1) {
+                Ok(value.clone())
             } else {
-                Err(anyhow!("Value not found or not scalar"))
+                Err(anyhow!("Value not found"))
             }
         } else {
             Err(anyhow!("Pod not found for ref"))
@@ -1561,7 +2125,7 @@ fn find_matching_pod(query: PodQueryBuilder, env: Env) -> Result<Value> {
     let constraints = query.build_constraints();
     let store = env.pod_store.lock().unwrap();
     for pod in store.pods.iter() {
-        let pod_id = PodBuilder::pod_id(pod);
+        let pod_id = pod.id().to_string();
 
         if let Some(ref builder) = env.current_builder {
             if builder.lock().unwrap().input_pods.contains_key(&pod_id) {
@@ -1670,8 +2234,7 @@ fn matches_constraints(pod: &POD, constraints: &[QueryConstraint]) -> Option<Vec
 
                 // Add the operation statement itself
                 if let Some((stmt_id, _)) = matching_ops.first() {
-                    matched_statements
-                        .push(SRef(ORef::P(PodBuilder::pod_id(pod)), stmt_id.clone()));
+                    matched_statements.push(SRef(ORef::P(pod.id().to_string()), stmt_id.clone()));
                 }
             }
             QueryConstraint::Assert {
@@ -1701,8 +2264,7 @@ fn matches_constraints(pod: &POD, constraints: &[QueryConstraint]) -> Option<Vec
 
                 // Add the assert statement itself
                 if let Some(stmt_id) = matching_asserts.first() {
-                    matched_statements
-                        .push(SRef(ORef::P(PodBuilder::pod_id(pod)), stmt_id.clone()));
+                    matched_statements.push(SRef(ORef::P(pod.id().to_string()), stmt_id.clone()));
                 }
             }
         }
@@ -1713,32 +2275,30 @@ fn matches_constraints(pod: &POD, constraints: &[QueryConstraint]) -> Option<Vec
 fn matches_assert_constraint(
     pod: &POD,
     assert_type: AssertType,
-    operands: &(Box<OperandConstraint>, Box<OperandConstraint>),
+    operands: &[OperandConstraint],
     statement: &Statement,
     matched_statements: &mut Vec<SRef>,
 ) -> Option<bool> {
-    let (op1, op2) = match (assert_type, statement) {
+    let statement_operands = match (assert_type, statement) {
         (AssertType::Gt, Statement::Gt(l, r))
         | (AssertType::Lt, Statement::Lt(l, r))
         | (AssertType::Eq, Statement::Equal(l, r))
-        | (AssertType::Neq, Statement::NotEqual(l, r)) => (l, r),
+        | (AssertType::Neq, Statement::NotEqual(l, r))
+        | (AssertType::Gte, Statement::Gte(l, r))
+        | (AssertType::Lte, Statement::Lte(l, r)) => vec![l, r],
+        (AssertType::InRange, Statement::InRange(x, lo, hi)) => vec![x, lo, hi],
+        (AssertType::Member, Statement::ContainedInRoot(x, root)) => vec![x, root],
         _ => return None,
     };
 
-    let (op1_constraint, op2_constraint) = operands;
-
-    if let Some(left_res) = matches_operand_constraint(pod, op1_constraint, op1, matched_statements)
-    {
-        if let Some(right_res) =
-            matches_operand_constraint(pod, op2_constraint, op2, matched_statements)
-        {
-            if op1 == &left_res && op2 == &right_res {
-                return Some(true);
-            }
+    for (constraint, op) in operands.iter().zip(statement_operands) {
+        let res = matches_operand_constraint(pod, constraint, op, matched_statements)?;
+        if op != &res {
+            return None;
         }
     }
 
-    None
+    Some(true)
 }
 
 fn matches_operation_constraint(
@@ -1750,7 +2310,15 @@ fn matches_operation_constraint(
     let (result, left, right) = match (op_constraint.op_type, statement) {
         (OpType::Add, Statement::SumOf(res, l, r))
         | (OpType::Multiply, Statement::ProductOf(res, l, r))
-        | (OpType::Max, Statement::MaxOf(res, l, r)) => (res, l, r),
+        | (OpType::Max, Statement::MaxOf(res, l, r))
+        | (OpType::Subtract, Statement::DifferenceOf(res, l, r))
+        | (OpType::Min, Statement::MinOf(res, l, r))
+        | (OpType::Divide, Statement::QuotientRemainderOf(res, l, r, false))
+        | (OpType::Modulo, Statement::QuotientRemainderOf(res, l, r, true)) => (res, l, r),
+        (OpType::Hash, Statement::HashOf(res, inputs)) => match inputs.as_slice() {
+            [l, r] => (res, l, r),
+            _ => return None,
+        },
         _ => return None,
     };
 
@@ -1765,6 +2333,9 @@ fn matches_operation_constraint(
     }
 
     // Try reverse order for commutative operations
+    if !op_constraint.op_type.is_commutative() {
+        return None;
+    }
     if let Some(left_res) = matches_operand_constraint(pod, op2, left, matched_statements) {
         if let Some(right_res) = matches_operand_constraint(pod, op1, right, matched_statements) {
             if left == &left_res && right == &right_res {
@@ -1794,7 +2365,7 @@ fn matches_operand_constraint(
             pod.payload.statements_list.iter().find_map(|(id, stmt)| {
                 if let Statement::ValueOf(ak, val) = stmt {
                     if ak == operand && val == value {
-                        matched_statements.push(SRef(ORef::P(PodBuilder::pod_id(pod)), id.clone()));
+                        matched_statements.push(SRef(ORef::P(pod.id().to_string()), id.clone()));
                         Some(ak.clone())
                     } else {
                         None
@@ -1809,13 +2380,17 @@ fn matches_operand_constraint(
                 match stmt {
                     Statement::SumOf(res, _, _)
                     | Statement::ProductOf(res, _, _)
-                    | Statement::MaxOf(res, _, _) => {
+                    | Statement::MaxOf(res, _, _)
+                    | Statement::HashOf(res, _)
+                    | Statement::DifferenceOf(res, _, _)
+                    | Statement::MinOf(res, _, _)
+                    | Statement::QuotientRemainderOf(res, _, _, _) => {
                         if operand == res {
                             if let Some(matched_res) =
                                 matches_operation_constraint(pod, op, stmt, matched_statements)
                             {
                                 matched_statements
-                                    .push(SRef(ORef::P(PodBuilder::pod_id(pod)), id.clone()));
+                                    .push(SRef(ORef::P(pod.id().to_string()), id.clone()));
                                 return Some(matched_res);
                             }
                         }
@@ -1852,7 +2427,7 @@ mod tests {
             "test_user".to_string(),
             shared,
             pod_store.clone(),
-            Some(SchnorrSecretKey { sk: 42 }),
+            Some(EcSchnorrSecretKey::from(42)),
             None,
             None,
         );
@@ -2497,6 +3072,142 @@ mod tests {
             Err(anyhow!("Failed to create test pod"))
         }
     }
+    #[tokio::test]
+    async fn test_hash_operation_query() -> Result<()> {
+        let (env, pod_store) = setup_env().await;
+
+        let pod = eval(
+            "[createpod test_pod
+                secret 42
+                salt 7
+                commitment [hash secret salt]]",
+            env.clone(),
+        )
+        .await?;
+        match pod {
+            Value::PodRef(pod) => {
+                assert_eq!(
+                    get_self_entry_value(&pod, "commitment").unwrap(),
+                    ScalarOrVec::from_hash(PoseidonHash::hash_no_pad(&[
+                        GoldilocksField(42),
+                        GoldilocksField(7)
+                    ]))
+                );
+                assert!(pod
+                    .payload
+                    .statements_map
+                    .values()
+                    .any(|statement| matches!(statement, Statement::HashOf(_, _))));
+                pod_store.lock().unwrap().add_pod(pod);
+            }
+            _ => return Err(anyhow!("Expected PodRef")),
+        }
+
+        let result = eval("[pod? [commitment [hash secret salt]]]", env.clone()).await?;
+        assert!(matches!(result, Value::SRef(_)));
+
+        // the order of the inputs matters
+        let result = eval("[pod? [commitment [hash salt secret]]]", env.clone()).await;
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_difference_min_and_division_operations() -> Result<()> {
+        let (env, pod_store) = setup_env().await;
+
+        let pod = eval(
+            "[createpod budget
+                total 100
+                spent 37
+                cap 50
+                left [- total spent]
+                allowance [min left cap]
+                weeks [div left 7]
+                extra [mod left 7]]",
+            env.clone(),
+        )
+        .await?;
+        match pod {
+            Value::PodRef(pod) => {
+                for (key, value) in [("left", 63), ("allowance", 50), ("weeks", 9), ("extra", 0)] {
+                    assert_eq!(
+                        get_self_entry_value(&pod, key).unwrap(),
+                        ScalarOrVec::Scalar(GoldilocksField(value))
+                    );
+                }
+                assert!(pod
+                    .payload
+                    .statements_map
+                    .values()
+                    .any(|statement| matches!(
+                        statement,
+                        Statement::QuotientRemainderOf(_, _, _, true)
+                    )));
+                pod_store.lock().unwrap().add_pod(pod);
+            }
+            _ => return Err(anyhow!("Expected PodRef")),
+        }
+
+        let result = eval("[pod? [left [- total spent]]]", env.clone()).await?;
+        assert!(matches!(result, Value::SRef(_)));
+        let result = eval("[pod? [weeks [div left 7]]]", env.clone()).await?;
+        assert!(matches!(result, Value::SRef(_)));
+        // the order of the inputs matters
+        let result = eval("[pod? [left [- spent total]]]", env.clone()).await;
+        assert!(result.is_err());
+
+        // u64 subtraction cannot underflow, and division by zero fails
+        assert!(eval("[createpod bad x 1 y [- x 2]]", env.clone())
+            .await
+            .is_err());
+        assert!(eval("[createpod bad x 1 y [div x 0]]", env.clone())
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_negation_operation() -> Result<()> {
+        let (env, _) = setup_env().await;
+
+        let pod = eval(
+            "[createpod balance
+                debt [neg 5]
+                credit [neg debt]]",
+            env.clone(),
+        )
+        .await?;
+        match pod {
+            Value::PodRef(pod) => {
+                assert_eq!(
+                    get_self_entry_value(&pod, "debt").unwrap(),
+                    ScalarOrVec::from(-5i64)
+                );
+                assert_eq!(
+                    get_self_entry_value(&pod, "credit").unwrap(),
+                    ScalarOrVec::from(5u64)
+                );
+                assert_eq!(
+                    pod.payload
+                        .statements_map
+                        .values()
+                        .filter(|statement| matches!(statement, Statement::NegationOf(_, _)))
+                        .count(),
+                    2
+                );
+            }
+            _ => return Err(anyhow!("Expected PodRef")),
+        }
+
+        // entries of field elements are not signed integers
+        assert!(eval("[createpod bad x 5 y [neg x]]", env.clone())
+            .await
+            .is_err());
+        assert!(eval("[neg 5]", env.clone()).await.is_err());
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_pod_operations_with_refs() -> Result<()> {
         let (env, _) = setup_env().await;
@@ -2643,6 +3354,102 @@ mod tests {
         assert!(result.is_err());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_pod_with_comparison_assertions() -> Result<()> {
+        let (env, pod_store) = setup_env().await;
+
+        let source_pod = eval(
+            "[createpod source
+                x 10
+                y [+ x 5]
+                [>= y x]
+                [<= x y]
+                [in-range y x 20]]",
+            env.clone(),
+        )
+        .await?;
+        match source_pod {
+            Value::PodRef(source_pod) => pod_store.lock().unwrap().add_pod(source_pod),
+            _ => return Err(anyhow!("Expected PodRef")),
+        }
+
+        // y = 15 is out of [x, 12]
+        let result = eval(
+            "[createpod test
+                x 10
+                y [+ x 5]
+                [in-range y x 12]]",
+            env.clone(),
+        )
+        .await;
+        assert!(result.is_err());
+
+        // in-range takes a value and two bounds
+        let result = eval("[createpod test x 10 [in-range x 5]]", env.clone()).await;
+        assert!(result.is_err());
+
+        let result = eval(
+            "[pod?
+                [y [+ x 5]]
+                [in-range y x 20]]",
+            env.clone(),
+        )
+        .await?;
+        match result {
+            Value::SRef(_) => Ok(()),
+            _ => Err(anyhow!("Expected SRef")),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pod_with_membership_assertion() -> Result<()> {
+        let (env, pod_store) = setup_env().await;
+
+        let source_pod = eval(
+            "[createpod source
+                allowlist [merkle-root 3 [list 5 7] 11]
+                x [+ 2 5]
+                [member x allowlist]]",
+            env.clone(),
+        )
+        .await?;
+        match source_pod {
+            Value::PodRef(source_pod) => {
+                assert!(source_pod
+                    .payload
+                    .statements_map
+                    .values()
+                    .any(|statement| matches!(statement, Statement::ContainedInRoot(_, _))));
+                pod_store.lock().unwrap().add_pod(source_pod)
+            }
+            _ => return Err(anyhow!("Expected PodRef")),
+        }
+
+        // 8 is not in the allowlist
+        let result = eval(
+            "[createpod test
+                allowlist [merkle-root 3 5 7 11]
+                x [+ 3 5]
+                [member x allowlist]]",
+            env.clone(),
+        )
+        .await;
+        assert!(result.is_err());
+
+        let result = eval(
+            "[pod?
+                [x]
+                [member x allowlist]]",
+            env.clone(),
+        )
+        .await?;
+        match result {
+            Value::SRef(_) => Ok(()),
+            _ => Err(anyhow!("Expected SRef")),
+        }
+    }
+
     #[tokio::test]
     async fn test_pod_query_with_operation_and_assert() -> Result<()> {
         let (env, pod_store) = setup_env().await;
@@ -2716,7 +3523,7 @@ mod tests {
             "alice".to_string(),
             shared.clone(),
             Arc::new(Mutex::new(MyPods::default())),
-            Some(SchnorrSecretKey { sk: 42 }),
+            Some(EcSchnorrSecretKey::from(42)),
             None,
             None,
         );
@@ -2726,7 +3533,7 @@ mod tests {
             "bob".to_string(),
             shared.clone(),
             Arc::new(Mutex::new(MyPods::default())),
-            Some(SchnorrSecretKey { sk: 43 }),
+            Some(EcSchnorrSecretKey::from(43)),
             None,
             None,
         );
@@ -2750,7 +3557,7 @@ mod tests {
             "alice".to_string(),
             shared.clone(),
             alice_pod_store.clone(),
-            Some(SchnorrSecretKey { sk: 42 }),
+            Some(EcSchnorrSecretKey::from(42)),
             None,
             None,
         );
@@ -2759,7 +3566,7 @@ mod tests {
             "bob".to_string(),
             shared.clone(),
             bob_pod_store.clone(),
-            Some(SchnorrSecretKey { sk: 43 }),
+            Some(EcSchnorrSecretKey::from(43)),
             None,
             None,
         );
@@ -2807,7 +3614,7 @@ mod tests {
             "alice".to_string(),
             shared.clone(),
             Arc::new(Mutex::new(MyPods::default())),
-            Some(SchnorrSecretKey { sk: 42 }),
+            Some(EcSchnorrSecretKey::from(42)),
             None,
             None,
         );
@@ -2833,7 +3640,7 @@ mod tests {
             "alice".to_string(),
             shared.clone(),
             alice_pod_store.clone(),
-            Some(SchnorrSecretKey { sk: 42 }),
+            Some(EcSchnorrSecretKey::from(42)),
             None,
             None,
         );
@@ -2842,7 +3649,7 @@ mod tests {
             "bob".to_string(),
             shared.clone(),
             bob_pod_store.clone(),
-            Some(SchnorrSecretKey { sk: 43 }),
+            Some(EcSchnorrSecretKey::from(43)),
             None,
             None,
         );
@@ -3022,4 +3829,54 @@ mod tests {
             _ => Err(anyhow!("Expected List, got something else")),
         }
     }
+
+    #[tokio::test]
+    async fn test_custom_predicates() -> Result<()> {
+        let (env, _) = setup_env().await;
+
+        eval("[defpredicate over-18 [age] [> age 18]]", env.clone()).await?;
+        eval("[define trusted 7]", env.clone()).await?;
+        let is_adult = eval(
+            "[defpredicate is-adult [age issuer] [over-18 age] [= issuer trusted]]",
+            env.clone(),
+        )
+        .await?;
+        let is_adult_id = match is_adult {
+            Value::Predicate(predicate) => predicate.definition.id(),
+            _ => return Err(anyhow!("Expected Predicate, got something else")),
+        };
+
+        // Predicates are evaluated on scalars outside of PODs.
+        let result = eval("[is-adult 30 7]", env.clone()).await?;
+        assert!(matches!(result, Value::Scalar(GoldilocksField(1))));
+        let result = eval("[is-adult 30 8]", env.clone()).await?;
+        assert!(matches!(result, Value::Scalar(GoldilocksField(0))));
+
+        let result = eval(
+            "[createpod adult age 30 issuer 7 [is-adult age issuer]]",
+            env.clone(),
+        )
+        .await?;
+        match result {
+            Value::PodRef(pod) => {
+                assert!(pod.payload.statements_map.values().any(|statement| {
+                    matches!(statement, Statement::Custom(id, args)
+                        if *id == is_adult_id && args.len() == 2)
+                }));
+            }
+            _ => return Err(anyhow!("Expected PodRef, got something else")),
+        }
+
+        // The clauses must hold for the POD to be created.
+        let result = eval(
+            "[createpod minor age 17 issuer 7 [is-adult age issuer]]",
+            env.clone(),
+        )
+        .await;
+        assert!(result.is_err());
+        assert!(eval("[defpredicate broken [x] [> y 1]]", env.clone())
+            .await
+            .is_err());
+        Ok(())
+    }
 }
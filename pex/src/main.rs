@@ -14,8 +14,7 @@ use pex::{
 use pex::{Env, MyPods, Value};
 use pod2::{
     pod::gadget::PlonkyButNotPlonkyGadget,
-    recursion::traits::IntroducerCircuitTrait,
-    signature::schnorr::{SchnorrSecretKey, SchnorrSigner},
+    signature::ecschnorr::{EcSchnorrSecretKey, EcSchnorrSigner},
 };
 use reedline::{
     default_emacs_keybindings, ColumnarMenu, DefaultPrompt, DefaultPromptSegment, Emacs, KeyCode,
@@ -23,8 +22,6 @@ use reedline::{
 };
 use std::time::Instant;
 
-use rand::Rng;
-
 fn create_spinner(msg: &str) -> ProgressBar {
     let pb = ProgressBar::new_spinner();
     pb.set_style(
@@ -38,7 +35,7 @@ fn create_spinner(msg: &str) -> ProgressBar {
     pb
 }
 
-fn get_username_from_key(sk: &SchnorrSecretKey) -> String {
+fn get_username_from_key(sk: &EcSchnorrSecretKey) -> String {
     let cosmic_prefixes = [
         "stellar",
         "nova",
@@ -86,8 +83,9 @@ fn get_username_from_key(sk: &SchnorrSecretKey) -> String {
     ];
 
     // Use the secret key to deterministically choose prefix and suffix
-    let prefix_index = sk.sk % cosmic_prefixes.len() as u64;
-    let suffix_index = (sk.sk >> 8) % cosmic_suffixes.len() as u64;
+    let sk_low = sk.sk.iter_u64_digits().next().unwrap_or(0);
+    let prefix_index = sk_low % cosmic_prefixes.len() as u64;
+    let suffix_index = (sk_low >> 8) % cosmic_suffixes.len() as u64;
 
     format!(
         "{}_{}",
@@ -97,9 +95,8 @@ fn get_username_from_key(sk: &SchnorrSecretKey) -> String {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let random_sk = rand::thread_rng().gen::<u64>() % 10000;
-    let schnorr_key = SchnorrSecretKey { sk: random_sk };
-    let signer = SchnorrSigner::new();
+    let schnorr_key = EcSchnorrSecretKey::random(&mut rand::thread_rng());
+    let signer = EcSchnorrSigner::new();
     let public_key = signer.keygen(&schnorr_key);
     let username = get_username_from_key(&schnorr_key);
     let secret_key = iroh::net::key::SecretKey::generate();
@@ -132,16 +129,15 @@ async fn main() -> Result<()> {
     let pod_store = Arc::new(Mutex::new(MyPods::default()));
     let spinner = create_spinner("Generating prover parameters...");
 
-    let pod1_circuit_data =
-        pod2::recursion::traits_examples::ExampleIntroducer::circuit_data().unwrap();
-    let pod1_verifier_data = pod1_circuit_data.verifier_data();
-    let circuit_data =
-        PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::circuit_data(pod1_verifier_data).unwrap();
-    let prover_params = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::build_prover_params(
-        pod1_circuit_data,
-        circuit_data,
+    // the params are cached next to the executable, and only rebuilt when the circuit changes
+    let prover_params_path = std::env::current_exe()?.with_file_name(format!(
+        "pex_prover_params_{}_{}_{}_{}_{}.bin",
+        L, M, N, NS, VL
+    ));
+    let prover_params = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::load_or_build_prover_params(
+        &prover_params_path,
     )
-    .unwrap();
+    .map_err(|e| eyre!("Failed to load the prover parameters: {}", e))?;
     spinner.finish_and_clear();
     println!("⚙️ Prover parameters generated");
     let env = Env::new(
@@ -164,6 +160,15 @@ async fn main() -> Result<()> {
         "+".into(),
         "*".into(),
         "max".into(),
+        "hash".into(),
+        "-".into(),
+        "min".into(),
+        "div".into(),
+        "mod".into(),
+        "neg".into(),
+        "in-range".into(),
+        "member".into(),
+        "merkle-root".into(),
         "exit".into(),
         "list-pods".into(),
     ];
@@ -199,7 +204,7 @@ async fn main() -> Result<()> {
     println!("Welcome, {}!", username.cyan().bold());
     println!(
         "Public key: {}",
-        format!("{:?}", public_key.pk).yellow().bold()
+        format!("{:?}", public_key.hash()).yellow().bold()
     );
     println!("Type 'exit' to quit");
     println!("Commands:");
@@ -2,5 +2,4 @@ pub const PREDICATE_VALUEOF: &str = "VALUEOF";
 pub const STATEMENT_PREFIX_CONSTANT: &str = "constant_";
 pub const STATEMENT_PREFIX_RESULT: &str = "result_";
 pub const STATEMENT_PREFIX_OTHER: &str = "statement_";
-pub const POD_PREFIX: &str = "pod_";
 pub const SELF_ORIGIN_NAME: &str = "_SELF";
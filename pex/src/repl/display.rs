@@ -2,9 +2,13 @@ use std::collections::HashMap;
 
 use colored::Colorize;
 use plonky2::field::{goldilocks_field::GoldilocksField, types::Field};
-use pod2::pod::{statement::AnchoredKey, Statement, POD};
+use pod2::pod::{
+    provenance::{ProvenanceGraph, ROOT},
+    statement::AnchoredKey,
+    Statement, POD,
+};
 
-use crate::{MyPods, PodBuilder};
+use crate::MyPods;
 
 pub fn get_pod_info(pod: &POD) -> HashMap<String, Vec<String>> {
     let mut origin_statements: HashMap<String, Vec<String>> = HashMap::new();
@@ -14,11 +18,24 @@ pub fn get_pod_info(pod: &POD) -> HashMap<String, Vec<String>> {
             Statement::SumOf(_, op1, op2)
             | Statement::ProductOf(_, op1, op2)
             | Statement::MaxOf(_, op1, op2)
+            | Statement::DifferenceOf(_, op1, op2)
+            | Statement::MinOf(_, op1, op2)
+            | Statement::QuotientRemainderOf(_, op1, op2, _)
             | Statement::Equal(op1, op2) => vec![op1, op2]
                 .into_iter()
                 .filter(|r| !r.0.is_self())
                 .map(|r| (statement_id.clone(), r))
                 .collect::<Vec<_>>(),
+            Statement::HashOf(_, ops) => ops
+                .iter()
+                .filter(|r| !r.0.is_self())
+                .map(|r| (statement_id.clone(), r))
+                .collect::<Vec<_>>(),
+            Statement::NegationOf(_, op) => vec![op]
+                .into_iter()
+                .filter(|r| !r.0.is_self())
+                .map(|r| (statement_id.clone(), r))
+                .collect::<Vec<_>>(),
             Statement::ValueOf(key, _) => {
                 if !key.0.is_self() {
                     vec![(statement_id.clone(), key)]
@@ -111,10 +128,58 @@ impl<'a> StatementFormatter<'a> {
                 format_ref(result).bright_green()
             ),
 
+            Statement::HashOf(result, ops) => format!(
+                "{} hash({}) → {}",
+                self.prefix(),
+                ops.iter()
+                    .map(|op| format_ref(op).yellow().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                format_ref(result).bright_green()
+            ),
+
+            Statement::DifferenceOf(result, op1, op2) => self.ternary_op(result, op1, "-", op2),
+
+            Statement::MinOf(result, op1, op2) => format!(
+                "{} min({}, {}) → {}",
+                self.prefix(),
+                format_ref(op1).yellow(),
+                format_ref(op2).yellow(),
+                format_ref(result).bright_green()
+            ),
+
+            Statement::QuotientRemainderOf(result, op1, op2, is_remainder) => {
+                self.ternary_op(result, op1, if *is_remainder { "mod" } else { "div" }, op2)
+            }
+
+            Statement::NegationOf(result, op) => format!(
+                "{} -{} → {}",
+                self.prefix(),
+                format_ref(op).yellow(),
+                format_ref(result).bright_green()
+            ),
+
             Statement::Equal(op1, op2) => self.binary_op(op1, "=", op2),
             Statement::Gt(op1, op2) => self.binary_op(op1, ">", op2),
             Statement::Lt(op1, op2) => self.binary_op(op1, "<", op2),
             Statement::NotEqual(op1, op2) => self.binary_op(op1, "!=", op2),
+            Statement::Gte(op1, op2) => self.binary_op(op1, ">=", op2),
+            Statement::Lte(op1, op2) => self.binary_op(op1, "<=", op2),
+
+            Statement::InRange(value, lower, upper) => format!(
+                "{} {} ∈ [{}, {}]",
+                self.prefix(),
+                format_ref(value).yellow(),
+                format_ref(lower).yellow(),
+                format_ref(upper).yellow()
+            ),
+
+            Statement::ContainedInRoot(value, root) => format!(
+                "{} {} ∈ {}",
+                self.prefix(),
+                format_ref(value).yellow(),
+                format_ref(root).yellow()
+            ),
 
             Statement::ValueOf(key, value) => format!(
                 "{} {} = {}",
@@ -123,6 +188,16 @@ impl<'a> StatementFormatter<'a> {
                 format!("{:?}", value).bright_blue()
             ),
 
+            Statement::Custom(id, args) => format!(
+                "{} {}({})",
+                self.prefix(),
+                format!("custom {}", id).bright_cyan(),
+                args.iter()
+                    .map(|arg| format_ref(arg).yellow().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+
             _ => format!("{} Other Operation", self.prefix()),
         }
     }
@@ -151,7 +226,7 @@ pub fn print_pod_details(pod: &POD, pod_store: &MyPods) {
             let matching_pods: Vec<_> = pod_store
                 .pods
                 .iter()
-                .filter(|p| PodBuilder::pod_id(p) == *origin)
+                .filter(|p| p.id().to_string() == *origin)
                 .collect();
 
             for matched_pod in matching_pods {
@@ -179,7 +254,7 @@ pub fn print_pod_details(pod: &POD, pod_store: &MyPods) {
             let gadget_id = pod_store
                 .pods
                 .iter()
-                .find(|p| PodBuilder::pod_id(p) == *origin)
+                .find(|p| p.id().to_string() == *origin)
                 .map(|p| p.proof_type.to_string())
                 .unwrap_or_else(|| "unknown".to_string());
 
@@ -193,7 +268,7 @@ pub fn print_pod_details(pod: &POD, pod_store: &MyPods) {
             let referenced_values: Vec<String> = pod_store
                 .pods
                 .iter()
-                .filter(|p| PodBuilder::pod_id(p) == *origin)
+                .filter(|p| p.id().to_string() == *origin)
                 .flat_map(|p| {
                     statements
                         .iter()
@@ -233,6 +308,8 @@ pub fn print_pod_details(pod: &POD, pod_store: &MyPods) {
         }
     }
 
+    print_provenance(pod, pod_store);
+
     print_section_header("POD Statements", Some(&pod.proof_type.to_string()));
     for (statement_id, statement) in pod
         .payload
@@ -244,3 +321,34 @@ pub fn print_pod_details(pod: &POD, pod_store: &MyPods) {
     }
     println!();
 }
+
+/// Prints the operations and sources each statement of the POD is derived from, tracing origins
+/// back through the PODs in the store.
+pub fn print_provenance(pod: &POD, pod_store: &MyPods) {
+    let known_pods: HashMap<String, &POD> = pod_store
+        .pods
+        .iter()
+        .map(|p| (p.id().to_string(), p))
+        .collect();
+    let graph = ProvenanceGraph::new(pod, &known_pods);
+    let root_prefix = format!("{}/", ROOT);
+    let derivations: Vec<_> = graph
+        .edges
+        .iter()
+        .filter(|e| e.label != "IN" && e.to.starts_with(&root_prefix))
+        .collect();
+    if derivations.is_empty() {
+        return;
+    }
+
+    print_section_header("Provenance", None);
+    for edge in derivations {
+        println!(
+            "  {} {} {} {}",
+            "└─".bright_black(),
+            edge.from.yellow(),
+            format!("─{}→", edge.label).bright_black(),
+            edge.to.trim_start_matches(&root_prefix).blue()
+        );
+    }
+}
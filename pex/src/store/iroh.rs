@@ -30,7 +30,8 @@ const DOC_TICKET: &str = "docaaacb6cej4lpglwuuya5tecmiflfmnkeprhubm6nk7lhdhj4vwn
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 enum PodOrValue {
-    Pod(String, POD),
+    /// POD in the pod2 binary wire format
+    Pod(String, Vec<u8>),
     Value(String, Value),
 }
 
@@ -93,9 +94,11 @@ impl IrohStore {
                         if let Ok(content) = iroh.blobs().read_to_bytes(hash).await {
                             if let Ok(pod_or_value) = postcard::from_bytes::<PodOrValue>(&content) {
                                 match pod_or_value {
-                                    PodOrValue::Pod(_, pod) => {
-                                        let id = crate::PodBuilder::pod_id(&pod);
-                                        pods.lock().unwrap().insert(id, pod);
+                                    PodOrValue::Pod(_, bytes) => {
+                                        if let Ok(pod) = POD::from_bytes(&bytes) {
+                                            let id = pod.id().to_string();
+                                            pods.lock().unwrap().insert(id, pod);
+                                        }
                                     }
                                     PodOrValue::Value(key, value) => {
                                         let parts: Vec<&str> = key.split(':').collect();
@@ -182,20 +185,22 @@ impl SharedStore for IrohStore {
     }
 
     fn store_pod(&self, pod: POD) -> String {
-        let id = crate::PodBuilder::pod_id(&pod);
+        let id = pod.id().to_string();
         self.pods.lock().unwrap().insert(id.clone(), pod.clone());
 
         // Sync to iroh network
         let doc = self.doc.clone();
         let iroh = self.iroh.clone();
         let key = format!("pod:{}", id);
-        let pod = PodOrValue::Pod(key.clone(), pod.clone());
+        let pod = pod
+            .to_bytes()
+            .map(|bytes| PodOrValue::Pod(key.clone(), bytes));
 
         tokio::spawn(async move {
             if let (Some(doc), Some(iroh)) = (doc.read().await.as_ref(), iroh.read().await.as_ref())
             {
                 let author = iroh.authors().default().await?;
-                let serialized_value: Vec<u8> = postcard::to_stdvec(&pod)?;
+                let serialized_value: Vec<u8> = postcard::to_stdvec(&pod?)?;
                 doc.set_bytes(author, key, serialized_value).await?;
                 Ok::<(), anyhow::Error>(())
             } else {
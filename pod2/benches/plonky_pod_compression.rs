@@ -0,0 +1,78 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use plonky2::field::goldilocks_field::GoldilocksField;
+use pod2::pod::{
+    entry::Entry,
+    gadget::PlonkyButNotPlonkyGadget,
+    operation::{OpList, Operation as Op, OperationCmd as OpCmd},
+    statement::StatementRef,
+    POD,
+};
+use pod2::signature::ecschnorr::EcSchnorrSecretKey;
+use std::collections::HashMap;
+use std::time::Instant;
+
+const L: usize = 0;
+const M: usize = 2;
+const N: usize = 1;
+const NS: usize = 3;
+const VL: usize = 0;
+
+fn plonky_pod() -> POD {
+    let schnorr_pod = POD::execute_schnorr_gadget::<NS, VL>(
+        &[
+            Entry::new_from_scalar("s1", GoldilocksField(55)),
+            Entry::new_from_scalar("s2", GoldilocksField(56)),
+        ],
+        &EcSchnorrSecretKey::from(27),
+    )
+    .unwrap();
+    let prover_params =
+        PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::cached_prover_params().unwrap();
+    let mut prover_params = prover_params.lock().unwrap();
+    PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::execute(
+        &mut prover_params,
+        &[("Test POD".to_string(), schnorr_pod)],
+        OpList(vec![OpCmd::new(
+            Op::CopyStatement(StatementRef::new("Test POD", "VALUEOF:s2")),
+            "op1",
+        )]),
+        HashMap::new(),
+    )
+    .unwrap()
+}
+
+fn benchmark_compression(c: &mut Criterion) {
+    let pod = plonky_pod();
+
+    // the first compression also builds the compression circuit
+    let start = Instant::now();
+    let compressed_pod = pod.compress::<L, M, N, NS, VL>().unwrap();
+    println!(
+        "compression circuit built and first POD compressed in {:?}",
+        start.elapsed()
+    );
+    println!(
+        "serialized proof size: {} bytes plonky, {} bytes compressed",
+        serde_json::to_vec(&pod.proof).unwrap().len(),
+        serde_json::to_vec(&compressed_pod.proof).unwrap().len()
+    );
+    // build the verifiers before timing them
+    assert!(pod.verify::<L, M, N, NS, VL>().unwrap());
+    assert!(compressed_pod.verify::<L, M, N, NS, VL>().unwrap());
+
+    let mut group = c.benchmark_group("plonky pod compression");
+    group.sample_size(10);
+    group.bench_function("compress plonky pod", |b| {
+        b.iter(|| black_box(&pod).compress::<L, M, N, NS, VL>())
+    });
+    group.bench_function("verify plonky pod", |b| {
+        b.iter(|| black_box(&pod).verify::<L, M, N, NS, VL>())
+    });
+    group.bench_function("verify compressed pod", |b| {
+        b.iter(|| black_box(&compressed_pod).verify::<L, M, N, NS, VL>())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_compression);
+criterion_main!(benches);
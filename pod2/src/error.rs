@@ -0,0 +1,160 @@
+//! Error type of the public API of the crate.
+use parcnet_pod::pod::PodCreationError;
+use std::fmt;
+
+use crate::pod::gadget::DryRunError;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Signature or proof which does not hold for the POD.
+    VerificationFailed(String),
+    /// More input PODs, statements or operations than the circuits take.
+    CapacityExceeded { what: String, max: usize },
+    /// Statement or POD referenced but not found.
+    MissingStatement(String),
+    /// Origin of an input POD which can not be renamed or remapped.
+    OriginRemapError(String),
+    /// POD or operation which can not be taken as input.
+    InvalidInput(String),
+    /// Custom predicate which is ill-defined, or statements which do not match its definition.
+    InvalidPredicate(String),
+    /// Failure to build a circuit or to generate a proof.
+    ProofGeneration(String),
+    /// Failure to compute or check a POD1.
+    PodCreation(PodCreationError),
+    /// Any other failure of the internals of the crate.
+    Other(anyhow::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::VerificationFailed(reason) => write!(f, "Verification failed: {}", reason),
+            Self::CapacityExceeded { what, max } => {
+                write!(f, "Capacity exceeded: at most {} {}.", max, what)
+            }
+            Self::MissingStatement(reason) => write!(f, "Missing statement: {}", reason),
+            Self::OriginRemapError(reason) => write!(f, "Origin remapping failed: {}", reason),
+            Self::InvalidInput(reason) => write!(f, "Invalid input: {}", reason),
+            Self::InvalidPredicate(reason) => write!(f, "Invalid custom predicate: {}", reason),
+            Self::ProofGeneration(reason) => write!(f, "Proof generation failed: {}", reason),
+            Self::PodCreation(e) => write!(f, "POD1 error: {}", e),
+            Self::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::PodCreation(e) => Some(e),
+            Self::Other(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl Error {
+    /// Converts an error of the proving internals, which are not typed any further.
+    pub(crate) fn proving(e: anyhow::Error) -> Self {
+        match Self::from(e) {
+            Self::Other(e) => Self::ProofGeneration(e.to_string()),
+            e => e,
+        }
+    }
+
+    /// Converts an error of the verification internals, which are not typed any further.
+    pub(crate) fn verification(e: anyhow::Error) -> Self {
+        match Self::from(e) {
+            Self::Other(e) => Self::VerificationFailed(e.to_string()),
+            e => e,
+        }
+    }
+}
+
+/// Typed errors returned through the anyhow-based internals are recovered.
+impl From<anyhow::Error> for Error {
+    fn from(e: anyhow::Error) -> Self {
+        let e = match e.downcast::<Error>() {
+            Ok(e) => return e,
+            Err(e) => e,
+        };
+        let e = match e.downcast::<DryRunError>() {
+            Ok(e) => return e.into(),
+            Err(e) => e,
+        };
+        match e.downcast::<PodCreationError>() {
+            Ok(e) => e.into(),
+            Err(e) => Self::Other(e),
+        }
+    }
+}
+
+impl From<DryRunError> for Error {
+    fn from(e: DryRunError) -> Self {
+        match e {
+            DryRunError::TooManyInputs { gadget_id, max, .. } => Self::CapacityExceeded {
+                what: format!("{} input PODs", gadget_id),
+                max,
+            },
+            DryRunError::TooManyOperations { max, .. } => Self::CapacityExceeded {
+                what: "operations".to_string(),
+                max,
+            },
+            DryRunError::MissingOrigin { .. }
+            | DryRunError::OriginRemapping(_)
+            | DryRunError::OriginIdOutOfRange { .. } => Self::OriginRemapError(e.to_string()),
+            DryRunError::UnknownPod { .. } | DryRunError::UnknownStatement { .. } => {
+                Self::MissingStatement(e.to_string())
+            }
+            DryRunError::UnsupportedInput { .. }
+            | DryRunError::ShapeMismatch { .. }
+            | DryRunError::StatementCount { .. }
+            | DryRunError::InvalidOperation { .. } => Self::InvalidInput(e.to_string()),
+        }
+    }
+}
+
+impl From<PodCreationError> for Error {
+    fn from(e: PodCreationError) -> Self {
+        Self::PodCreation(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+
+    use super::Error;
+    use crate::pod::{entry::Entry, gadget::DryRunError, gadget::GadgetID, POD};
+    use crate::signature::ecschnorr::EcSchnorrSecretKey;
+
+    #[test]
+    fn test_error_conversions() {
+        // typed errors are recovered from anyhow errors
+        let e = Error::from(anyhow::Error::from(DryRunError::TooManyInputs {
+            gadget_id: GadgetID::ECSCHNORR,
+            count: 3,
+            max: 2,
+        }));
+        assert!(matches!(e, Error::CapacityExceeded { max: 2, .. }));
+        let e = Error::proving(Error::MissingStatement("VALUEOF:a".to_string()).into());
+        assert!(matches!(e, Error::MissingStatement(_)));
+        assert!(matches!(
+            Error::proving(anyhow!("out of memory")),
+            Error::ProofGeneration(_)
+        ));
+        assert!(matches!(Error::from(anyhow!("other")), Error::Other(_)));
+
+        let entries = ["a", "b", "c"]
+            .map(|key| Entry::new_from_scalar(key, GoldilocksField(1)))
+            .to_vec();
+        assert!(matches!(
+            POD::execute_schnorr_gadget::<3, 0>(&entries, &EcSchnorrSecretKey::from(25)),
+            Err(Error::CapacityExceeded { max: 3, .. })
+        ));
+    }
+}
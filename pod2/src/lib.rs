@@ -15,14 +15,17 @@ pub type C = PoseidonGoldilocksConfig;
 pub const D: usize = 2;
 pub type PlonkyProof = Proof<F, PoseidonGoldilocksConfig, D>;
 
-// For the purposes of inequality checks, we assume values are of type
-// u32.
+// For the purposes of `MaxOf` checks, we assume values are of type
+// u32. Comparison statements handle full 64-bit values.
 pub const NUM_BITS: usize = 32;
 
+pub mod error;
 pub mod plonky2_u32;
 pub mod pod;
 pub mod recursion;
+pub mod serialization;
 pub mod signature;
 
 // expose the main structs & traits at the high level
+pub use error::{Error, Result};
 pub use pod::{PODProof, POD};
@@ -2,19 +2,20 @@ use anyhow::Result;
 use plonky2::{
     field::goldilocks_field::GoldilocksField,
     iop::{
-        target::Target,
+        target::{BoolTarget, Target},
         witness::{PartialWitness, WitnessWrite},
     },
     plonk::circuit_builder::CircuitBuilder,
 };
 
-use crate::pod::{entry::Entry, util::hash_string_to_field, value::HashableEntryValue};
+use crate::pod::entry::Entry;
 use crate::{D, F};
 
 #[derive(Clone, Copy, Debug)]
 pub struct EntryTarget {
     pub key: Target,
     pub value: Target,
+    pub is_vector: BoolTarget,
 }
 
 impl EntryTarget {
@@ -22,6 +23,7 @@ impl EntryTarget {
         Self {
             key: builder.add_virtual_target(),
             value: builder.add_virtual_target(),
+            is_vector: builder.add_virtual_bool_target_safe(),
         }
     }
     pub fn set_witness(
@@ -30,11 +32,8 @@ impl EntryTarget {
         entry: &Entry,
     ) -> Result<()> {
         pw.set_target_arr(
-            &[self.key, self.value],
-            &[
-                hash_string_to_field(&entry.key),
-                entry.value.hash_or_value(),
-            ],
+            &[self.key, self.value, self.is_vector.target],
+            &entry.to_fields(),
         )
     }
 }
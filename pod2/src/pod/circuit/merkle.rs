@@ -0,0 +1,64 @@
+use anyhow::Result;
+use plonky2::{
+    field::goldilocks_field::GoldilocksField,
+    hash::poseidon::PoseidonHash,
+    iop::{
+        target::{BoolTarget, Target},
+        witness::{PartialWitness, WitnessWrite},
+    },
+    plonk::circuit_builder::CircuitBuilder,
+};
+use std::iter::zip;
+
+use crate::pod::merkle::{MERKLE_DEPTH, SORTED_LEAF_TAG, SORTED_NODE_TAG};
+use crate::{D, F};
+
+/// Path from a leaf to the root of a Merkle tree, as in `MerkleProof`.
+#[derive(Clone, Copy, Debug)]
+pub struct MerkleProofTarget {
+    pub index: Target,
+    pub siblings: [Target; MERKLE_DEPTH],
+}
+
+impl MerkleProofTarget {
+    pub fn new_virtual(builder: &mut CircuitBuilder<F, D>) -> Self {
+        Self {
+            index: builder.add_virtual_target(),
+            siblings: builder.add_virtual_target_arr(),
+        }
+    }
+
+    /// Sets the witness from the field representation of a `MerkleProof`.
+    pub fn set_witness(
+        &self,
+        pw: &mut PartialWitness<GoldilocksField>,
+        proof_as_fields: &[GoldilocksField],
+    ) -> Result<()> {
+        pw.set_target(self.index, proof_as_fields[0])?;
+        pw.set_target_arr(&self.siblings, &proof_as_fields[1..])
+    }
+
+    /// Root of the tree having `value` as leaf along this path, in the sorted domain if
+    /// `is_sorted` holds and in the plain one otherwise. The index is range checked.
+    pub fn root(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        is_sorted: BoolTarget,
+        value: Target,
+    ) -> Target {
+        let zero = builder.zero();
+        let [leaf_tag, node_tag] =
+            [SORTED_LEAF_TAG, SORTED_NODE_TAG].map(|tag| builder.mul_const(tag, is_sorted.target));
+        let index_bits = builder.split_le(self.index, MERKLE_DEPTH);
+        let leaf = builder
+            .hash_n_to_hash_no_pad::<PoseidonHash>(vec![value, zero, leaf_tag])
+            .elements[0];
+        zip(index_bits, self.siblings).fold(leaf, |node, (is_right, sibling)| {
+            let left = builder.select(is_right, sibling, node);
+            let right = builder.select(is_right, node, sibling);
+            builder
+                .hash_n_to_hash_no_pad::<PoseidonHash>(vec![left, right, node_tag])
+                .elements[0]
+        })
+    }
+}
@@ -1,4 +1,5 @@
 pub mod entry;
+pub mod merkle;
 pub mod operation;
 pub mod origin;
 pub mod pod;
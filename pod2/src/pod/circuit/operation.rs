@@ -3,7 +3,7 @@ use plonky2::{
     field::goldilocks_field::GoldilocksField,
     hash::poseidon::PoseidonHash,
     iop::{
-        target::Target,
+        target::{BoolTarget, Target},
         witness::{PartialWitness, WitnessWrite},
     },
     plonk::circuit_builder::CircuitBuilder,
@@ -15,18 +15,23 @@ use crate::{
     pod::{
         circuit::util::statement_matrix_ref,
         gadget::GadgetID,
+        merkle::MERKLE_DEPTH,
         operation::{OpList, Operation as Op},
+        owner::{NULLIFIER_DOMAIN, OWNER_KEY_DOMAIN},
+        payload::PODPayload,
+        predicate::{CUSTOM_PREDICATE_LEN, MAX_CUSTOM_STATEMENTS, MAX_CUSTOM_VARS},
         statement::{StatementOrRef, StatementRef},
         GPGInput, OpCmd, Statement,
     },
-    D, F, NUM_BITS, POD,
+    D, F, POD,
 };
 
 use super::{
     entry::EntryTarget,
+    merkle::MerkleProofTarget,
     origin::OriginTarget,
     statement::{StatementRefTarget, StatementTarget},
-    util::{and, assert_less_if, member},
+    util::{and, comparison_limbs, holds_hash, limbs_less_or_equal, member, split_u64},
 };
 
 #[derive(Clone, Copy, Debug)]
@@ -37,6 +42,21 @@ pub struct OperationTarget<const VL: usize> {
     pub operand3: StatementRefTarget,
     pub entry: EntryTarget,
     pub contains_proof: [Target; VL],
+    /// Vector value of the second operand of a comparison, whose first
+    /// operand's is in `contains_proof`
+    pub operand2_vector: [Target; VL],
+    /// Path from the value to the root of `ContainedInRootFromEntries`,
+    /// or from the lower adjacent leaf of `NotContainedInRootFromEntries`
+    pub merkle_proof: MerkleProofTarget,
+    /// Leaves of a sorted Merkle tree which the value of
+    /// `NotContainedInRootFromEntries` lies between
+    pub adjacent_leaves: [Target; 2],
+    /// Path from the upper adjacent leaf to the root
+    pub upper_merkle_proof: MerkleProofTarget,
+    /// Secret of the owner key of `NullifierFromOwnerKey`
+    pub owner_secret: [Target; 4],
+    /// Definition of the custom predicate of `CustomFromStatements`
+    pub custom_predicate: [Target; CUSTOM_PREDICATE_LEN],
 }
 
 impl<const VL: usize> OperationTarget<VL> {
@@ -48,6 +68,12 @@ impl<const VL: usize> OperationTarget<VL> {
             operand3: StatementRefTarget::new_virtual(builder),
             entry: EntryTarget::new_virtual(builder),
             contains_proof: builder.add_virtual_target_arr(),
+            operand2_vector: builder.add_virtual_target_arr(),
+            merkle_proof: MerkleProofTarget::new_virtual(builder),
+            adjacent_leaves: builder.add_virtual_target_arr(),
+            upper_merkle_proof: MerkleProofTarget::new_virtual(builder),
+            owner_secret: builder.add_virtual_target_arr(),
+            custom_predicate: builder.add_virtual_target_arr(),
         }
     }
     // TODO: Perestroika!
@@ -73,10 +99,39 @@ impl<const VL: usize> OperationTarget<VL> {
             &[operation_as_fields[5], operation_as_fields[6]],
         )?;
         pw.set_target_arr(
-            &[self.entry.key, self.entry.value],
-            &[operation_as_fields[7], operation_as_fields[8]],
+            &[
+                self.entry.key,
+                self.entry.value,
+                self.entry.is_vector.target,
+            ],
+            &operation_as_fields[7..10],
+        )?;
+        pw.set_target_arr(&self.contains_proof, &operation_as_fields[10..10 + VL])?;
+        pw.set_target_arr(
+            &self.operand2_vector,
+            &operation_as_fields[10 + VL..10 + 2 * VL],
+        )?;
+        let merkle_proof_end = 11 + 2 * VL + MERKLE_DEPTH;
+        self.merkle_proof
+            .set_witness(pw, &operation_as_fields[10 + 2 * VL..merkle_proof_end])?;
+        pw.set_target_arr(
+            &self.adjacent_leaves,
+            &operation_as_fields[merkle_proof_end..merkle_proof_end + 2],
+        )?;
+        let upper_merkle_proof_end = merkle_proof_end + 3 + MERKLE_DEPTH;
+        self.upper_merkle_proof.set_witness(
+            pw,
+            &operation_as_fields[merkle_proof_end + 2..upper_merkle_proof_end],
+        )?;
+        pw.set_target_arr(
+            &self.owner_secret,
+            &operation_as_fields[upper_merkle_proof_end..upper_merkle_proof_end + 4],
+        )?;
+        pw.set_target_arr(
+            &self.custom_predicate,
+            &operation_as_fields
+                [upper_merkle_proof_end + 4..upper_merkle_proof_end + 4 + CUSTOM_PREDICATE_LEN],
         )?;
-        pw.set_target_arr(&self.contains_proof, &operation_as_fields[9..])?;
         Ok(())
     }
 
@@ -113,6 +168,25 @@ impl<const VL: usize> OperationTarget<VL> {
         )?;
         let entry_target = self.entry;
 
+        // Owner key and nullifier of the secret, the scope being in statement 2.
+        let owner_key_domain = builder.constant(OWNER_KEY_DOMAIN);
+        let nullifier_domain = builder.constant(NULLIFIER_DOMAIN);
+        let [owner_key, nullifier] = [
+            [&[owner_key_domain][..], &self.owner_secret].concat(),
+            [
+                &[nullifier_domain][..],
+                &self.owner_secret,
+                &[statement2_target.value],
+            ]
+            .concat(),
+        ]
+        .map(|inputs| builder.hash_n_to_hash_no_pad::<PoseidonHash>(inputs));
+
+        let (custom_statement, custom_statement_is_valid) = self.custom_statement(
+            builder,
+            [statement1_target, statement2_target, statement3_target],
+        );
+
         // StatementTarget output of the ith opcode.
         let op_out = [
             StatementTarget::none(builder),                                 // None
@@ -131,7 +205,7 @@ impl<const VL: usize> OperationTarget<VL> {
                 key3: builder.zero(),
                 value: builder.zero(),
             }, // TransitiveEqualityFromStatements
-            StatementTarget::not_equal(builder, statement1_target, statement2_target), // GtToNonequality. TODO.
+            StatementTarget::not_equal_of_args(builder, statement1_target),     // GtToNonequality
             StatementTarget::contains(builder, statement1_target, statement2_target), // TODO: ContainsFromEntries.
             StatementTarget::rename_contained_by(builder, statement1_target, statement2_target), // TODO: RenameContainedBy
             StatementTarget::sum_of(
@@ -152,8 +226,53 @@ impl<const VL: usize> OperationTarget<VL> {
                 statement2_target,
                 statement3_target,
             ), // TODO: MaxOf
-            StatementTarget::lt(builder, statement1_target, statement2_target), // TODO: Lt
-            StatementTarget::not_equal(builder, statement1_target, statement2_target), // LtToNonequality. TODO.
+            StatementTarget::lt(builder, statement1_target, statement2_target), // LtFromEntries
+            StatementTarget::not_equal_of_args(builder, statement1_target),     // LtToNonequality
+            StatementTarget::gte(builder, statement1_target, statement2_target), // GteFromEntries
+            StatementTarget::lte(builder, statement1_target, statement2_target), // LteFromEntries
+            StatementTarget::in_range(
+                builder,
+                statement1_target,
+                statement2_target,
+                statement3_target,
+            ), // InRangeFromEntries
+            StatementTarget::contained_in_root(builder, statement1_target, statement2_target), // ContainedInRootFromEntries
+            StatementTarget::not_contained_in_root(builder, statement1_target, statement2_target), // NotContainedInRootFromEntries
+            StatementTarget::hash_of(
+                builder,
+                statement1_target,
+                &[statement2_target, statement3_target],
+            ), // HashOf
+            StatementTarget::nullifier(builder, statement1_target, statement2_target, nullifier), // NullifierFromOwnerKey
+            StatementTarget::difference_of(
+                builder,
+                statement1_target,
+                statement2_target,
+                statement3_target,
+            ), // DifferenceOf
+            StatementTarget::min_of(
+                builder,
+                statement1_target,
+                statement2_target,
+                statement3_target,
+            ), // MinOf
+            StatementTarget::quotient_remainder_of(
+                builder,
+                statement1_target,
+                statement2_target,
+                statement3_target,
+                false,
+            ), // QuotientOf
+            StatementTarget::quotient_remainder_of(
+                builder,
+                statement1_target,
+                statement2_target,
+                statement3_target,
+                true,
+            ), // RemainderOf
+            custom_statement, // CustomFromStatements
+            StatementTarget::negation_of(builder, statement1_target, statement2_target), // NegationOf
+            StatementTarget::hash_of(builder, statement1_target, &[statement2_target]), // HashOfSingle
         ];
 
         // Type indicators
@@ -161,19 +280,268 @@ impl<const VL: usize> OperationTarget<VL> {
             .iter()
             .map(|s_target| s_target.has_code(builder, Statement::VALUE_OF))
             .collect::<Vec<_>>();
+        let [statement_is_scalar, statement_is_vector] = {
+            let [s1_types, s2_types, s3_types] =
+                [statement1_target, statement2_target, statement3_target]
+                    .map(|s_target| s_target.value_types(builder));
+            [0, 1].map(|i| [s1_types[i], s2_types[i], s3_types[i]])
+        };
 
-        let statements_1_and_2_equal =
-            builder.is_equal(statement1_target.value, statement2_target.value);
+        // Values are equal if they have the same type, held in place of key3.
+        let statements_1_and_2_equal = {
+            let values_equal = builder.is_equal(statement1_target.value, statement2_target.value);
+            let types_equal = builder.is_equal(statement1_target.key3, statement2_target.key3);
+            builder.and(values_equal, types_equal)
+        };
 
-        // Gt check. This is a constraint for now (if applicable).
-        let gt_opcode_target = builder.constant(Op::<Statement>::GT_FROM_ENTRIES);
-        let op_is_gt = builder.is_equal(self.op, gt_opcode_target);
-        assert_less_if::<NUM_BITS>(
+        // Comparisons, in the order of `ScalarOrVec::numeric_cmp`. Vector
+        // values are opened in `contains_proof` and `operand2_vector`, and
+        // values of different types are not comparable.
+        let op_is_comparison = [
+            Op::<Statement>::GT_FROM_ENTRIES,
+            Op::<Statement>::LT_FROM_ENTRIES,
+            Op::<Statement>::GTE_FROM_ENTRIES,
+            Op::<Statement>::LTE_FROM_ENTRIES,
+        ]
+        .into_iter()
+        .fold(builder._false(), |acc, opcode| {
+            let opcode_target = builder.constant(opcode);
+            let op_is_opcode = builder.is_equal(self.op, opcode_target);
+            builder.or(acc, op_is_opcode)
+        });
+        let operand_vectors_are_valid = [
+            (statement1_target.value, self.contains_proof),
+            (statement2_target.value, self.operand2_vector),
+        ]
+        .map(|(value, vector)| {
+            let root = builder
+                .hash_n_to_hash_no_pad::<PoseidonHash>(vector.to_vec())
+                .elements[0];
+            builder.is_equal(root, value)
+        });
+        let compare_vectors = and(
             builder,
-            op_is_gt,
-            statement2_target.value,
+            &[
+                op_is_comparison,
+                statement_is_vector[0],
+                statement_is_vector[1],
+            ],
+        );
+        let operands_are_comparable = {
+            let scalars = builder.and(statement_is_scalar[0], statement_is_scalar[1]);
+            let vectors = and(
+                builder,
+                &[
+                    statement_is_vector[0],
+                    statement_is_vector[1],
+                    operand_vectors_are_valid[0],
+                    operand_vectors_are_valid[1],
+                ],
+            );
+            builder.or(scalars, vectors)
+        };
+        // All limbs of vectors are compared, scalars being padded to as many.
+        let num_limbs = VL.max(2);
+        let compare_scalars = builder._false();
+        let s1_limbs = comparison_limbs(
+            builder,
+            compare_vectors,
             statement1_target.value,
+            &self.contains_proof,
+            num_limbs,
+        );
+        let s2_limbs = comparison_limbs(
+            builder,
+            compare_vectors,
+            statement2_target.value,
+            &self.operand2_vector,
+            num_limbs,
         );
+        // Statement 3 is only compared as a scalar.
+        let s3_limbs = comparison_limbs(
+            builder,
+            compare_scalars,
+            statement3_target.value,
+            &[],
+            num_limbs,
+        );
+        let s1_le_s2 = limbs_less_or_equal(builder, &s1_limbs, &s2_limbs);
+        let s2_le_s1 = limbs_less_or_equal(builder, &s2_limbs, &s1_limbs);
+        let s1_le_s3 = limbs_less_or_equal(builder, &s1_limbs, &s3_limbs);
+        let s3_le_s2 = limbs_less_or_equal(builder, &s3_limbs, &s2_limbs);
+        let s3_le_s1 = limbs_less_or_equal(builder, &s3_limbs, &s1_limbs);
+
+        // Division of statement 2 by statement 3, statement 1 being the
+        // quotient or the remainder, from which the other one is derived.
+        // All four are u32s, so that `s2 = q * s3 + r` has no overflow.
+        let division_is_valid = {
+            let op_is_remainder = {
+                let opcode_target = builder.constant(Op::<Statement>::REMAINDER_OF);
+                builder.is_equal(self.op, opcode_target)
+            };
+            let zero = builder.zero();
+            let one = builder.one();
+            let divisor_is_zero = builder.is_equal(statement3_target.value, zero);
+            let divisor = builder.select(divisor_is_zero, one, statement3_target.value);
+            let quotient_times_divisor =
+                builder.sub(statement2_target.value, statement1_target.value);
+            let quotient_if_remainder = builder.div(quotient_times_divisor, divisor);
+            let product = builder.mul(statement1_target.value, statement3_target.value);
+            let remainder_if_quotient = builder.sub(statement2_target.value, product);
+            let quotient = builder.select(
+                op_is_remainder,
+                quotient_if_remainder,
+                statement1_target.value,
+            );
+            let remainder = builder.select(
+                op_is_remainder,
+                statement1_target.value,
+                remainder_if_quotient,
+            );
+            let [dividend_limbs, divisor_limbs, quotient_limbs, remainder_limbs] = [
+                statement2_target.value,
+                statement3_target.value,
+                quotient,
+                remainder,
+            ]
+            .map(|x| split_u64(builder, x));
+            let divisor_le_remainder =
+                limbs_less_or_equal(builder, &divisor_limbs, &remainder_limbs);
+            let remainder_lt_divisor = builder.not(divisor_le_remainder);
+            let limbs_are_u32 = [
+                dividend_limbs,
+                divisor_limbs,
+                quotient_limbs,
+                remainder_limbs,
+            ]
+            .map(|[_, hi]| builder.is_equal(hi, zero));
+            and(
+                builder,
+                &[
+                    statement_is_scalar[0],
+                    statement_is_scalar[1],
+                    statement_is_scalar[2],
+                    remainder_lt_divisor,
+                    limbs_are_u32[0],
+                    limbs_are_u32[1],
+                    limbs_are_u32[2],
+                    limbs_are_u32[3],
+                ],
+            )
+        };
+
+        // Negation of signed integers, ie. of vectors of u32 limbs whose
+        // first limb extends the sign, as in `ScalarOrVec::negation`. They
+        // sum to 0 modulo 2^(32 * VL) if each limb sum, with the carry from
+        // the less significant limbs, is 0 or 2^32.
+        let negation_is_valid = {
+            let op_is_negation = {
+                let opcode_target = builder.constant(Op::<Statement>::NEGATION_OF);
+                builder.is_equal(self.op, opcode_target)
+            };
+            let negate_vectors = and(
+                builder,
+                &[
+                    op_is_negation,
+                    statement_is_vector[0],
+                    statement_is_vector[1],
+                ],
+            );
+            let zero = builder.zero();
+            let [s1_limbs, s2_limbs] = [self.contains_proof, self.operand2_vector].map(|vector| {
+                vector.map(|limb| {
+                    let limb = builder.select(negate_vectors, limb, zero);
+                    builder.range_check(limb, 32);
+                    limb
+                })
+            });
+            let sign_extension = builder.constant(GoldilocksField(u32::MAX as u64));
+            let [s1_top_is_valid, s2_top_is_valid] = [s1_limbs[0], s2_limbs[0]].map(|top| {
+                let top_is_zero = builder.is_equal(top, zero);
+                let top_is_sign_extension = builder.is_equal(top, sign_extension);
+                builder.or(top_is_zero, top_is_sign_extension)
+            });
+            let limb_modulus = builder.constant(GoldilocksField(1 << 32));
+            let mut carry = zero;
+            let mut sum_is_zero = builder._true();
+            for (s1_limb, s2_limb) in s1_limbs.into_iter().zip(s2_limbs).rev() {
+                let limb_sum = builder.add_many([s1_limb, s2_limb, carry]);
+                let limb_sum_is_zero = builder.is_equal(limb_sum, zero);
+                let limb_sum_wraps = builder.is_equal(limb_sum, limb_modulus);
+                let limb_sum_is_valid = builder.or(limb_sum_is_zero, limb_sum_wraps);
+                sum_is_zero = builder.and(sum_is_zero, limb_sum_is_valid);
+                carry = limb_sum_wraps.target;
+            }
+            and(
+                builder,
+                &[
+                    negate_vectors,
+                    operand_vectors_are_valid[0],
+                    operand_vectors_are_valid[1],
+                    s1_top_is_valid,
+                    s2_top_is_valid,
+                    sum_is_zero,
+                ],
+            )
+        };
+
+        // Hashes of the values of the first 1 or 2 input statements, whose
+        // elements the result vector, opened in `contains_proof`, must hold
+        // one by one, padded with the first as by `Entry::pad_if_vec`.
+        let [hash_of_single_is_valid, hash_of_is_valid] = [1, 2].map(|num_inputs| {
+            if VL < 4 {
+                return builder._false();
+            }
+            let hash = builder.hash_n_to_hash_no_pad::<PoseidonHash>(
+                [statement2_target.value, statement3_target.value][..num_inputs].to_vec(),
+            );
+            let limbs_are_valid = self
+                .contains_proof
+                .iter()
+                .enumerate()
+                .map(|(i, limb)| {
+                    let element = *hash.elements.get(i).unwrap_or(&hash.elements[0]);
+                    builder.is_equal(*limb, element)
+                })
+                .collect::<Vec<_>>();
+            and(
+                builder,
+                &[
+                    &[statement_is_vector[0], operand_vectors_are_valid[0]][..],
+                    &statement_is_valueof[1..=num_inputs],
+                    &limbs_are_valid[..],
+                ]
+                .concat(),
+            )
+        });
+
+        // Merkle paths to the root in statement 2. Non-membership ops prove
+        // the adjacent leaves of a sorted tree, the lower one in place of the
+        // value.
+        let op_is_non_membership = {
+            let opcode_target =
+                builder.constant(Op::<Statement>::NOT_CONTAINED_IN_ROOT_FROM_ENTRIES);
+            builder.is_equal(self.op, opcode_target)
+        };
+        let [lower_leaf, upper_leaf] = self.adjacent_leaves;
+        let merkle_leaf = builder.select(op_is_non_membership, lower_leaf, statement1_target.value);
+        let merkle_root = self
+            .merkle_proof
+            .root(builder, op_is_non_membership, merkle_leaf);
+        let merkle_root_is_valid = builder.is_equal(merkle_root, statement2_target.value);
+        let upper_merkle_root =
+            self.upper_merkle_proof
+                .root(builder, op_is_non_membership, upper_leaf);
+        let upper_merkle_root_is_valid =
+            builder.is_equal(upper_merkle_root, statement2_target.value);
+        let leaves_are_adjacent = {
+            let next_index = builder.add_const(self.merkle_proof.index, GoldilocksField(1));
+            builder.is_equal(next_index, self.upper_merkle_proof.index)
+        };
+        let [lower_limbs, upper_limbs] = [lower_leaf, upper_leaf]
+            .map(|leaf| comparison_limbs(builder, compare_scalars, leaf, &[], num_limbs));
+        let s1_le_lower = limbs_less_or_equal(builder, &s1_limbs, &lower_limbs);
+        let upper_le_s1 = limbs_less_or_equal(builder, &upper_limbs, &s1_limbs);
 
         // Check whether statement 1 is (a == b) and statement 2 is (b == c)
         let statements_are_equalities = {
@@ -191,13 +559,17 @@ impl<const VL: usize> OperationTarget<VL> {
             builder.and(origins_match, keys_match)
         };
 
-        // Do a membership check for `ContainsFromEntries`
-        // TODO: Type check args.
-        let scalar_is_member = member(builder, statement2_target.value, &self.contains_proof);
-        let proof_root = builder
-            .hash_n_to_hash_no_pad::<PoseidonHash>(self.contains_proof.to_vec())
-            .elements[0];
-        let root_is_valid = builder.is_equal(proof_root, statement1_target.value);
+        // Do a membership check for `ContainsFromEntries`, vector elements
+        // being looked up by the hash their statement value holds
+        let element_is_member = member(builder, statement2_target.value, &self.contains_proof);
+        let root_is_valid = and(
+            builder,
+            &[
+                statement_is_vector[0],
+                statement_is_valueof[1],
+                operand_vectors_are_valid[0],
+            ],
+        );
 
         let op_is_valid = [
             builder._true(), // None - no checks needed.
@@ -212,10 +584,13 @@ impl<const VL: usize> OperationTarget<VL> {
                 ],
             ), // EqualityFromEntries - equality check
             builder.not(statements_1_and_2_equal), // NonequalityFromEntries - non-equality check
-            and(builder, &[statement_is_valueof[0], statement_is_valueof[1]]), // GtFromEntries - Type-check input statements. TODO: Replace assertion above.
+            {
+                let s2_lt_s1 = builder.not(s1_le_s2);
+                builder.and(operands_are_comparable, s2_lt_s1)
+            }, // GtFromEntries
             builder.and(statements_are_equalities, statements_allow_transitivity), // TransitiveEqualityFromStatements
             statement1_target.has_code(builder, Statement::GT), // GtToNonequality
-            builder.and(scalar_is_member, root_is_valid),       // TODO: ContainsFromEntries
+            builder.and(element_is_member, root_is_valid),      // TODO: ContainsFromEntries
             {
                 let conditions = &[
                     // Types
@@ -233,9 +608,9 @@ impl<const VL: usize> OperationTarget<VL> {
             {
                 let conditions = &[
                     // Types
-                    statement_is_valueof[0],
-                    statement_is_valueof[1],
-                    statement_is_valueof[2],
+                    statement_is_scalar[0],
+                    statement_is_scalar[1],
+                    statement_is_scalar[2],
                     // s1 = s2 + s3
                     {
                         let rhs = builder.add(statement2_target.value, statement3_target.value);
@@ -247,9 +622,9 @@ impl<const VL: usize> OperationTarget<VL> {
             {
                 let conditions = &[
                     // Types
-                    statement_is_valueof[0],
-                    statement_is_valueof[1],
-                    statement_is_valueof[2],
+                    statement_is_scalar[0],
+                    statement_is_scalar[1],
+                    statement_is_scalar[2],
                     // s1 = s2 * s3
                     {
                         let rhs = builder.mul(statement2_target.value, statement3_target.value);
@@ -259,34 +634,111 @@ impl<const VL: usize> OperationTarget<VL> {
                 and(builder, conditions)
             }, // ProductOf
             {
-                let conditions = &[
-                    // Types
-                    statement_is_valueof[0],
-                    statement_is_valueof[1],
-                    statement_is_valueof[2],
-                    // s1 = max(s2, s3) <=> s1 >= s2, s3 and (s1 = s2 or s1 = s3)
-                    {
-                        // TODO: Replace assertions.
-                        let maxof_opcode_target = builder.constant(Op::<Statement>::MAX_OF);
-                        let one_target = builder.one();
-                        let op_is_maxof = builder.is_equal(self.op, maxof_opcode_target);
-                        let s1 = statement1_target.value;
-                        let s1_plus_one = builder.add(statement1_target.value, one_target);
-                        let s2 = statement2_target.value;
-                        let s3 = statement3_target.value;
-                        assert_less_if::<NUM_BITS>(builder, op_is_maxof, s2, s1_plus_one);
-                        assert_less_if::<NUM_BITS>(builder, op_is_maxof, s3, s1_plus_one);
-
-                        let s1_eq_s2 = builder.is_equal(s1, s2);
-                        let s1_eq_s3 = builder.is_equal(s1, s3);
-
-                        builder.or(s1_eq_s2, s1_eq_s3)
-                    },
-                ];
-                and(builder, conditions)
+                // s1 = max(s2, s3) <=> s1 >= s2, s3 and (s1 = s2 or s1 = s3)
+                let s1_eq_s2 = builder.is_equal(statement1_target.value, statement2_target.value);
+                let s1_eq_s3 = builder.is_equal(statement1_target.value, statement3_target.value);
+                let s1_is_input = builder.or(s1_eq_s2, s1_eq_s3);
+                and(
+                    builder,
+                    &[
+                        statement_is_scalar[0],
+                        statement_is_scalar[1],
+                        statement_is_scalar[2],
+                        s1_is_input,
+                        s2_le_s1,
+                        s3_le_s1,
+                    ],
+                )
             }, // MaxOf
-            builder._true(),                                    // TODO: Lt
-            builder._true(),                                    // TODO: LtToNonequality
+            {
+                let s1_lt_s2 = builder.not(s2_le_s1);
+                builder.and(operands_are_comparable, s1_lt_s2)
+            }, // LtFromEntries
+            statement1_target.has_code(builder, Statement::LT), // LtToNonequality
+            builder.and(operands_are_comparable, s2_le_s1),     // GteFromEntries
+            builder.and(operands_are_comparable, s1_le_s2),     // LteFromEntries
+            and(
+                builder,
+                &[
+                    statement_is_scalar[0],
+                    statement_is_scalar[1],
+                    statement_is_scalar[2],
+                    s2_le_s1,
+                    s1_le_s3,
+                ],
+            ), // InRangeFromEntries
+            and(
+                builder,
+                &[
+                    statement_is_valueof[0],
+                    statement_is_scalar[1],
+                    merkle_root_is_valid,
+                ],
+            ), // ContainedInRootFromEntries
+            {
+                let lower_lt_s1 = builder.not(s1_le_lower);
+                let s1_lt_upper = builder.not(upper_le_s1);
+                and(
+                    builder,
+                    &[
+                        statement_is_valueof[0],
+                        statement_is_scalar[1],
+                        merkle_root_is_valid,
+                        upper_merkle_root_is_valid,
+                        leaves_are_adjacent,
+                        lower_lt_s1,
+                        s1_lt_upper,
+                    ],
+                )
+            }, // NotContainedInRootFromEntries
+            hash_of_is_valid,                                   // HashOf
+            {
+                let owner_key_is_valid =
+                    holds_hash::<VL>(builder, statement1_target.value, owner_key);
+                and(
+                    builder,
+                    &[
+                        statement_is_vector[0],
+                        statement_is_valueof[1],
+                        owner_key_is_valid,
+                    ],
+                )
+            }, // NullifierFromOwnerKey
+            {
+                let s1_plus_s3 = builder.add(statement1_target.value, statement3_target.value);
+                let s2_eq_s1_plus_s3 = builder.is_equal(statement2_target.value, s1_plus_s3);
+                and(
+                    builder,
+                    &[
+                        statement_is_scalar[0],
+                        statement_is_scalar[1],
+                        statement_is_scalar[2],
+                        s2_eq_s1_plus_s3,
+                        s3_le_s2,
+                    ],
+                )
+            }, // DifferenceOf
+            {
+                let s1_eq_s2 = builder.is_equal(statement1_target.value, statement2_target.value);
+                let s1_eq_s3 = builder.is_equal(statement1_target.value, statement3_target.value);
+                let s1_is_input = builder.or(s1_eq_s2, s1_eq_s3);
+                and(
+                    builder,
+                    &[
+                        statement_is_scalar[0],
+                        statement_is_scalar[1],
+                        statement_is_scalar[2],
+                        s1_is_input,
+                        s1_le_s2,
+                        s1_le_s3,
+                    ],
+                )
+            }, // MinOf
+            division_is_valid,                                  // QuotientOf
+            division_is_valid,                                  // RemainderOf
+            custom_statement_is_valid,                          // CustomFromStatements
+            negation_is_valid,                                  // NegationOf
+            hash_of_single_is_valid,                            // HashOfSingle
         ]
         .iter()
         .enumerate()
@@ -316,6 +768,106 @@ impl<const VL: usize> OperationTarget<VL> {
         builder.assert_one(output_statement_is_valid.target);
         Ok(output_statement_target)
     }
+
+    /// Custom statement derived from the operands, and whether they match
+    /// the templates of the custom predicate. Each variable is bound to the
+    /// first anchored key bound to it, as in `CustomPredicate::eval`.
+    fn custom_statement(
+        &self,
+        builder: &mut CircuitBuilder<F, D>,
+        operands: [StatementTarget; MAX_CUSTOM_STATEMENTS],
+    ) -> (StatementTarget, BoolTarget) {
+        let id = builder
+            .hash_n_to_hash_no_pad::<PoseidonHash>(self.custom_predicate.to_vec())
+            .elements[0];
+        let arity = self.custom_predicate[1];
+        let zero = builder.zero();
+
+        // Variables as [origin_id, gadget_id, key].
+        let mut vars = [[zero; 3]; MAX_CUSTOM_VARS];
+        let mut var_is_bound = [builder._false(); MAX_CUSTOM_VARS];
+        // Slots hold 0 or a variable index plus 1.
+        let slot_values = (0..=MAX_CUSTOM_VARS)
+            .map(|i| builder.constant(GoldilocksField(i as u64)))
+            .collect::<Vec<_>>();
+        let mut templates_are_valid = builder._true();
+        for (template, statement) in zip(self.custom_predicate[2..].chunks(6), operands) {
+            // Templates are [code, has_value, value, var1 + 1, var2 + 1, var3 + 1],
+            // unused ones being 0s.
+            let template_is_unused = builder.is_equal(template[0], zero);
+            let template_is_used = builder.not(template_is_unused);
+            let code_matches = builder.is_equal(statement.predicate, template[0]);
+            let value_is_free = builder.is_equal(template[1], zero);
+            let value_matches = builder.is_equal(statement.value, template[2]);
+            let value_is_valid = builder.or(value_is_free, value_matches);
+
+            let keys = [
+                (statement.origin1, statement.key1),
+                (statement.origin2, statement.key2),
+                (statement.origin3, statement.key3),
+            ]
+            .map(|(origin, key)| [origin.origin_id, origin.gadget_id, key]);
+            let mut keys_are_valid = builder._true();
+            for (slot_var, key) in zip(&template[3..], keys) {
+                let slot_is_valid = member(builder, *slot_var, &slot_values);
+                keys_are_valid = builder.and(keys_are_valid, slot_is_valid);
+                for (i, (var, is_bound)) in zip(&mut vars, &mut var_is_bound).enumerate() {
+                    let var_index = builder.constant(GoldilocksField(i as u64 + 1));
+                    let slot_is_var = builder.is_equal(*slot_var, var_index);
+                    let slot_is_var = builder.and(template_is_used, slot_is_var);
+                    let key_matches = {
+                        let fields_match = zip(*var, key)
+                            .map(|(x, y)| builder.is_equal(x, y))
+                            .collect::<Vec<_>>();
+                        and(builder, &fields_match)
+                    };
+                    // Keys bound to a bound variable must match it.
+                    let key_mismatches = builder.not(key_matches);
+                    let is_mismatch = and(builder, &[slot_is_var, *is_bound, key_mismatches]);
+                    let is_match = builder.not(is_mismatch);
+                    keys_are_valid = builder.and(keys_are_valid, is_match);
+
+                    let var_is_free = builder.not(*is_bound);
+                    let binds_var = builder.and(slot_is_var, var_is_free);
+                    for (x, y) in var.iter_mut().zip(key) {
+                        *x = builder.select(binds_var, y, *x);
+                    }
+                    *is_bound = builder.or(*is_bound, slot_is_var);
+                }
+            }
+
+            let template_matches = and(builder, &[code_matches, value_is_valid, keys_are_valid]);
+            let template_is_valid = builder.or(template_is_unused, template_matches);
+            templates_are_valid = builder.and(templates_are_valid, template_is_valid);
+        }
+
+        // The first `arity` variables are the arguments, the other slots being 0s.
+        let arities = (0..=3)
+            .map(|a| builder.constant(GoldilocksField(a)))
+            .collect::<Vec<_>>();
+        let arity_is_valid = member(builder, arity, &arities);
+        let [arg1, arg2, arg3] = array::from_fn(|i| {
+            let is_arg = arities[i + 1..].iter().fold(builder._false(), |acc, a| {
+                let arity_is_a = builder.is_equal(arity, *a);
+                builder.or(acc, arity_is_a)
+            });
+            vars[i].map(|x| builder.mul(is_arg.target, x))
+        });
+        let custom_statement = StatementTarget {
+            predicate: builder.constant(Statement::CUSTOM),
+            origin1: OriginTarget::from_targets(&arg1[..2]),
+            key1: arg1[2],
+            origin2: OriginTarget::from_targets(&arg2[..2]),
+            key2: arg2[2],
+            origin3: OriginTarget::from_targets(&arg3[..2]),
+            key3: arg3[2],
+            value: id,
+        };
+        (
+            custom_statement,
+            builder.and(templates_are_valid, arity_is_valid),
+        )
+    }
 }
 
 pub struct OpListTarget<const NS: usize, const VL: usize>(pub [OperationTarget<VL>; NS]);
@@ -335,20 +887,20 @@ impl<const NS: usize, const VL: usize> OpListTarget<NS, VL> {
     ) -> Result<()> {
         // TODO: Abstract this away.
         // Determine output POD statements for the purposes of later reference
-        let output_pod = POD::execute_oracle_gadget(gpg_input, &op_list.0)?;
-        // println!("{:?}", output_pod.payload.statements_list);
-        let input_and_output_pod_list = [
-            gpg_input.pods_list.clone(),
-            vec![("_SELF".to_string(), output_pod)],
-        ]
-        .concat();
+        let output_payload = POD::execute_ops(gpg_input, &op_list.0)?;
+        let input_and_output_pod_list: Vec<(String, PODPayload)> = gpg_input
+            .pods_list
+            .iter()
+            .map(|(pod_name, pod)| (pod_name.clone(), pod.payload.clone()))
+            .chain(std::iter::once(("_SELF".to_string(), output_payload)))
+            .collect();
 
         // Set operation targets
         let ref_index_map = StatementRef::index_map(&input_and_output_pod_list);
         let statement_table: <StatementRef as StatementOrRef>::StatementTable =
             input_and_output_pod_list
                 .iter()
-                .map(|(pod_name, pod)| (pod_name.clone(), pod.payload.statements_map.clone()))
+                .map(|(pod_name, payload)| (pod_name.clone(), payload.statements_map.clone()))
                 .collect();
 
         zip(&self.0, op_list.sort(&input_and_output_pod_list).0).try_for_each(
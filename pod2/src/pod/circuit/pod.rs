@@ -10,7 +10,10 @@ use plonky2::{
 };
 use std::iter::zip;
 
-use super::{statement::StatementTarget, util::vector_ref};
+use super::{
+    statement::StatementTarget,
+    util::{holds_hash, vector_ref},
+};
 use crate::{
     pod::{util::hash_string_to_field, PODProof, Statement, POD, SIGNER_PK_KEY},
     signature::schnorr_prover::{
@@ -26,6 +29,8 @@ pub struct SchnorrPODTarget {
     /// Index of statement containing value of signer's public
     /// key. Checked in circuit.
     pub pk_index: Target,
+    /// Signer's public key, whose hash the signer's public key statement holds.
+    pub pk: SchnorrPublicKeyTarget,
     pub proof: SchnorrSignatureTarget,
 }
 
@@ -37,6 +42,7 @@ impl SchnorrPODTarget {
                 .map(|_| StatementTarget::new_virtual(builder))
                 .collect(),
             pk_index,
+            pk: SchnorrPublicKeyTarget::new_virtual(builder),
             proof: SchnorrSignatureTarget::new_virtual(builder),
         }
     }
@@ -70,10 +76,23 @@ impl SchnorrPODTarget {
                 .as_ref(),
             self.pk_index,
         )?;
+        // Type of the value, held in place of key3.
+        let type_target = vector_ref(
+            builder,
+            self.payload
+                .iter()
+                .map(|s| s.key3)
+                .collect::<Vec<_>>()
+                .as_ref(),
+            self.pk_index,
+        )?;
         // Check public key entry key.
         let expected_pk_entry_key = builder.constant(hash_string_to_field(SIGNER_PK_KEY));
         builder.connect(key_target, expected_pk_entry_key);
 
+        // Check that the value is a vector, ie. the full hash of the public key.
+        builder.assert_one(type_target);
+
         // Check origin ID, which should be 1 for self.
         builder.assert_one(origin_id_target);
 
@@ -102,29 +121,26 @@ impl SchnorrPODTarget {
     }
 
     /// Verifies the signature over the hash_target, and returns a boolean indicating whether
-    /// verification of the POD signature was successful.
-    pub fn compute_targets_and_verify(
+    /// verification of the POD signature was successful. The signer's public key entry may
+    /// hold the hash padded to VL, as in `POD::execute_schnorr_gadget`.
+    pub fn compute_targets_and_verify<const VL: usize>(
         &self,
         builder: &mut CircuitBuilder<F, D>,
         hash_target: &HashOutTarget,
-    ) -> Result<(SchnorrPublicKeyTarget, BoolTarget)> {
+    ) -> Result<BoolTarget> {
         // build the msg of the sig, from the given hash
         let msg_target = MessageTarget {
             msg: hash_target.elements.to_vec(),
         };
-        // Extract signer's key.
-        let pk_target = SchnorrPublicKeyTarget {
-            pk: self.signer_pk_target(builder)?,
-        };
+        // Extract signer's key and check it against the public key.
+        let signer_target = self.signer_pk_target(builder)?;
+        let pk_hash_target = self.pk.hash(builder);
+        let signer_check = holds_hash::<VL>(builder, signer_target, pk_hash_target);
         // Check signature.
         let sb = SchnorrBuilder;
-        let verification_target = sb.verify_sig::<PoseidonGoldilocksConfig>(
-            builder,
-            &self.proof,
-            &msg_target,
-            &pk_target,
-        );
-        Ok((pk_target, verification_target))
+        let verification_target =
+            sb.verify_sig::<PoseidonGoldilocksConfig>(builder, &self.proof, &msg_target, &self.pk);
+        Ok(builder.and(signer_check, verification_target))
     }
     pub fn set_witness(&self, pw: &mut PartialWitness<GoldilocksField>, pod: &POD) -> Result<()> {
         // Assign payload witness.
@@ -145,9 +161,13 @@ impl SchnorrPODTarget {
                 pod
             ))? as u64;
         pw.set_target(self.pk_index, GoldilocksField::from_canonical_u64(pk_index))?;
-        // Assign POD signature witness.
-        match pod.proof {
-            PODProof::Schnorr(sig) => self.proof.set_witness(pw, &sig),
+        // Assign POD signature and public key witnesses.
+        match &pod.proof {
+            PODProof::EcSchnorr(pk, sig) => {
+                self.pk.set_witness(pw, pk)?;
+                self.proof.set_witness(pw, sig)?;
+                Ok(())
+            }
             _ => Err(anyhow!("The following POD is not a Schnorr POD: {:?}", pod)),
         }
     }
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use plonky2::{
     field::{goldilocks_field::GoldilocksField, types::Field},
+    hash::hash_types::HashOutTarget,
     iop::{
         target::{BoolTarget, Target},
         witness::{PartialWitness, WitnessWrite},
@@ -128,11 +129,13 @@ impl StatementTarget {
             value: builder.zero(),
         }
     }
+    /// `VALUEOF` statement, whose value type is held in place of `key3`.
     pub fn value_of(
         builder: &mut CircuitBuilder<F, D>,
         origin: OriginTarget,
         key: Target,
         value: Target,
+        is_vector: BoolTarget,
     ) -> Self {
         Self {
             predicate: builder.constant(Statement::VALUE_OF),
@@ -141,7 +144,7 @@ impl StatementTarget {
             origin2: OriginTarget::none(builder),
             key2: builder.zero(),
             origin3: OriginTarget::none(builder),
-            key3: builder.zero(),
+            key3: is_vector.target,
             value,
         }
     }
@@ -178,6 +181,23 @@ impl StatementTarget {
         }
     }
 
+    /// Nonequality of the two anchored keys of a comparison statement.
+    pub fn not_equal_of_args(
+        builder: &mut CircuitBuilder<F, D>,
+        statement_target: StatementTarget,
+    ) -> Self {
+        Self {
+            predicate: builder.constant(Statement::NOT_EQUAL),
+            origin1: statement_target.origin1,
+            key1: statement_target.key1,
+            origin2: statement_target.origin2,
+            key2: statement_target.key2,
+            origin3: OriginTarget::none(builder),
+            key3: builder.zero(),
+            value: builder.zero(),
+        }
+    }
+
     pub fn gt(
         builder: &mut CircuitBuilder<F, D>,
         statement1_target: StatementTarget,
@@ -283,6 +303,101 @@ impl StatementTarget {
         }
     }
 
+    /// `HASHOF` statement of the result in statement 1 and the given inputs, whose unused
+    /// key slots are 0s as in `Statement::to_fields`.
+    pub fn hash_of(
+        builder: &mut CircuitBuilder<F, D>,
+        statement1_target: StatementTarget,
+        input_targets: &[StatementTarget],
+    ) -> Self {
+        let (origin3, key3) = match input_targets.get(1) {
+            Some(statement3_target) => (statement3_target.origin1, statement3_target.key1),
+            None => (OriginTarget::none(builder), builder.zero()),
+        };
+        Self {
+            predicate: builder.constant(Statement::HASH_OF),
+            origin1: statement1_target.origin1,
+            key1: statement1_target.key1,
+            origin2: input_targets[0].origin1,
+            key2: input_targets[0].key1,
+            origin3,
+            key3,
+            value: builder.zero(),
+        }
+    }
+
+    pub fn difference_of(
+        builder: &mut CircuitBuilder<F, D>,
+        statement1_target: StatementTarget,
+        statement2_target: StatementTarget,
+        statement3_target: StatementTarget,
+    ) -> Self {
+        Self {
+            predicate: builder.constant(Statement::DIFFERENCE_OF),
+            origin1: statement1_target.origin1,
+            key1: statement1_target.key1,
+            origin2: statement2_target.origin1,
+            key2: statement2_target.key1,
+            origin3: statement3_target.origin1,
+            key3: statement3_target.key1,
+            value: builder.zero(),
+        }
+    }
+
+    pub fn min_of(
+        builder: &mut CircuitBuilder<F, D>,
+        statement1_target: StatementTarget,
+        statement2_target: StatementTarget,
+        statement3_target: StatementTarget,
+    ) -> Self {
+        Self {
+            predicate: builder.constant(Statement::MIN_OF),
+            origin1: statement1_target.origin1,
+            key1: statement1_target.key1,
+            origin2: statement2_target.origin1,
+            key2: statement2_target.key1,
+            origin3: statement3_target.origin1,
+            key3: statement3_target.key1,
+            value: builder.zero(),
+        }
+    }
+
+    pub fn quotient_remainder_of(
+        builder: &mut CircuitBuilder<F, D>,
+        statement1_target: StatementTarget,
+        statement2_target: StatementTarget,
+        statement3_target: StatementTarget,
+        is_remainder: bool,
+    ) -> Self {
+        Self {
+            predicate: builder.constant(Statement::QUOTIENT_REMAINDER_OF),
+            origin1: statement1_target.origin1,
+            key1: statement1_target.key1,
+            origin2: statement2_target.origin1,
+            key2: statement2_target.key1,
+            origin3: statement3_target.origin1,
+            key3: statement3_target.key1,
+            value: builder.constant_bool(is_remainder).target,
+        }
+    }
+
+    pub fn negation_of(
+        builder: &mut CircuitBuilder<F, D>,
+        statement1_target: StatementTarget,
+        statement2_target: StatementTarget,
+    ) -> Self {
+        Self {
+            predicate: builder.constant(Statement::NEGATION_OF),
+            origin1: statement1_target.origin1,
+            key1: statement1_target.key1,
+            origin2: statement2_target.origin1,
+            key2: statement2_target.key1,
+            origin3: OriginTarget::none(builder),
+            key3: builder.zero(),
+            value: builder.zero(),
+        }
+    }
+
     pub fn lt(
         builder: &mut CircuitBuilder<F, D>,
         statement1_target: StatementTarget,
@@ -300,13 +415,125 @@ impl StatementTarget {
         }
     }
 
+    pub fn gte(
+        builder: &mut CircuitBuilder<F, D>,
+        statement1_target: StatementTarget,
+        statement2_target: StatementTarget,
+    ) -> Self {
+        Self {
+            predicate: builder.constant(Statement::GTE),
+            origin1: statement1_target.origin1,
+            key1: statement1_target.key1,
+            origin2: statement2_target.origin1,
+            key2: statement2_target.key1,
+            origin3: OriginTarget::none(builder),
+            key3: builder.zero(),
+            value: builder.zero(),
+        }
+    }
+
+    pub fn lte(
+        builder: &mut CircuitBuilder<F, D>,
+        statement1_target: StatementTarget,
+        statement2_target: StatementTarget,
+    ) -> Self {
+        Self {
+            predicate: builder.constant(Statement::LTE),
+            origin1: statement1_target.origin1,
+            key1: statement1_target.key1,
+            origin2: statement2_target.origin1,
+            key2: statement2_target.key1,
+            origin3: OriginTarget::none(builder),
+            key3: builder.zero(),
+            value: builder.zero(),
+        }
+    }
+
+    pub fn in_range(
+        builder: &mut CircuitBuilder<F, D>,
+        statement1_target: StatementTarget,
+        statement2_target: StatementTarget,
+        statement3_target: StatementTarget,
+    ) -> Self {
+        Self {
+            predicate: builder.constant(Statement::IN_RANGE),
+            origin1: statement1_target.origin1,
+            key1: statement1_target.key1,
+            origin2: statement2_target.origin1,
+            key2: statement2_target.key1,
+            origin3: statement3_target.origin1,
+            key3: statement3_target.key1,
+            value: builder.zero(),
+        }
+    }
+
+    pub fn contained_in_root(
+        builder: &mut CircuitBuilder<F, D>,
+        statement1_target: StatementTarget,
+        statement2_target: StatementTarget,
+    ) -> Self {
+        Self {
+            predicate: builder.constant(Statement::CONTAINED_IN_ROOT),
+            origin1: statement1_target.origin1,
+            key1: statement1_target.key1,
+            origin2: statement2_target.origin1,
+            key2: statement2_target.key1,
+            origin3: OriginTarget::none(builder),
+            key3: builder.zero(),
+            value: builder.zero(),
+        }
+    }
+
+    pub fn not_contained_in_root(
+        builder: &mut CircuitBuilder<F, D>,
+        statement1_target: StatementTarget,
+        statement2_target: StatementTarget,
+    ) -> Self {
+        Self {
+            predicate: builder.constant(Statement::NOT_CONTAINED_IN_ROOT),
+            origin1: statement1_target.origin1,
+            key1: statement1_target.key1,
+            origin2: statement2_target.origin1,
+            key2: statement2_target.key1,
+            origin3: OriginTarget::none(builder),
+            key3: builder.zero(),
+            value: builder.zero(),
+        }
+    }
+
+    /// The nullifier is held in place of anchored key 3 and value, as in `Statement::to_fields`.
+    pub fn nullifier(
+        builder: &mut CircuitBuilder<F, D>,
+        statement1_target: StatementTarget,
+        statement2_target: StatementTarget,
+        nullifier: HashOutTarget,
+    ) -> Self {
+        let [n0, n1, n2, n3] = nullifier.elements;
+        Self {
+            predicate: builder.constant(Statement::NULLIFIER),
+            origin1: statement1_target.origin1,
+            key1: statement1_target.key1,
+            origin2: statement2_target.origin1,
+            key2: statement2_target.key1,
+            origin3: OriginTarget::from_targets(&[n0, n1]),
+            key3: n2,
+            value: n3,
+        }
+    }
+
     pub fn from_entry(
         builder: &mut CircuitBuilder<F, D>,
         entry_target: &EntryTarget,
         this_gadget_id: GadgetID,
     ) -> Self {
         let origin = OriginTarget::auto(builder, this_gadget_id);
-        Self::value_of(builder, origin, entry_target.key, entry_target.value)
+        Self::value_of(
+            builder,
+            origin,
+            entry_target.key,
+            entry_target.value,
+            entry_target.is_vector,
+        )
     }
 
     pub fn constant(builder: &mut CircuitBuilder<F, D>, statement: &Statement) -> Self {
@@ -328,6 +555,17 @@ impl StatementTarget {
         builder.is_equal(self.predicate, code_target)
     }
 
+    /// Whether the statement is a `VALUEOF` statement of a scalar, and
+    /// whether it is one of a vector.
+    pub fn value_types(&self, builder: &mut CircuitBuilder<F, D>) -> [BoolTarget; 2] {
+        let is_value_of = self.has_code(builder, Statement::VALUE_OF);
+        [GoldilocksField::ZERO, GoldilocksField::ONE].map(|value_type| {
+            let value_type = builder.constant(value_type);
+            let has_type = builder.is_equal(self.key3, value_type);
+            builder.and(is_value_of, has_type)
+        })
+    }
+
     pub fn connect(&self, builder: &mut CircuitBuilder<F, D>, statement_target: &Self) {
         std::iter::zip(Self::to_targets(self), Self::to_targets(statement_target))
             .for_each(|(s1, s2)| builder.connect(s1, s2));
@@ -339,13 +577,26 @@ impl StatementTarget {
         origin_id_map: &[Vec<Target>],
         pod_index: Target,
     ) -> Result<Self> {
+        // The third origin of `NULLIFIER` statements is part of the nullifier, which is kept
+        // as is, the map being looked up at origin 0 instead.
+        let nullifier_code = builder.constant(Statement::NULLIFIER);
+        let is_nullifier = builder.is_equal(self.predicate, nullifier_code);
+        let zero = builder.zero();
+        let origin3 = OriginTarget {
+            origin_id: builder.select(is_nullifier, zero, self.origin3.origin_id),
+            gadget_id: self.origin3.gadget_id,
+        }
+        .remap(builder, origin_id_map, pod_index)?;
         Ok(Self {
             predicate: self.predicate,
             origin1: self.origin1.remap(builder, origin_id_map, pod_index)?,
             key1: self.key1,
             origin2: self.origin2.remap(builder, origin_id_map, pod_index)?,
             key2: self.key2,
-            origin3: self.origin3.remap(builder, origin_id_map, pod_index)?,
+            origin3: OriginTarget {
+                origin_id: builder.select(is_nullifier, self.origin3.origin_id, origin3.origin_id),
+                gadget_id: self.origin3.gadget_id,
+            },
             key3: self.key3,
             value: self.value,
         })
@@ -1,12 +1,14 @@
 use anyhow::{anyhow, Result};
 use plonky2::{
     field::{goldilocks_field::GoldilocksField, types::Field},
+    hash::{hash_types::HashOutTarget, poseidon::PoseidonHash},
     iop::target::{BoolTarget, Target},
     plonk::circuit_builder::CircuitBuilder,
     // util::log2_ceil,
 };
 
 use super::statement::StatementTarget;
+use crate::plonky2_u32::gadgets::multiple_comparison::list_le_circuit;
 use crate::{D, F};
 
 const NUM_BITS: usize = 32;
@@ -142,6 +144,63 @@ pub fn assert_less_if<const NUM_BITS: usize>(
     assert_less::<NUM_BITS>(builder, lhs, rhs)
 }
 
+/// Splits a field element into its LE u32 limbs. The decomposition is
+/// checked to be the canonical one.
+pub fn split_u64(builder: &mut CircuitBuilder<F, D>, x: Target) -> [Target; 2] {
+    let (lo, hi) = builder.split_low_high(x, 32, 64);
+    // x < p = 2^64 - 2^32 + 1, so hi = 2^32 - 1 implies lo = 0.
+    let max_hi = builder.constant(GoldilocksField(u32::MAX as u64));
+    let hi_is_max = builder.is_equal(hi, max_hi);
+    let lo_if_hi_is_max = builder.mul(hi_is_max.target, lo);
+    builder.assert_zero(lo_if_hi_is_max);
+    [lo, hi]
+}
+
+/// LE u32 limbs by which a value is compared, as in
+/// `ScalarOrVec::numeric_cmp`, padded with 0s to `num_limbs`: if
+/// `is_vector`, those of `vector` from its last limb, with the sign bit
+/// of its first limb flipped so that two's complement integers compare
+/// as u32 limbs, and otherwise those of `value`.
+pub fn comparison_limbs(
+    builder: &mut CircuitBuilder<F, D>,
+    is_vector: BoolTarget,
+    value: Target,
+    vector: &[Target],
+    num_limbs: usize,
+) -> Vec<Target> {
+    let zero = builder.zero();
+    let value_limbs = split_u64(builder, value);
+    let mut limbs = (0..num_limbs)
+        .map(|i| {
+            let value_limb = *value_limbs.get(i).unwrap_or(&zero);
+            let vector_limb = match vector.len().checked_sub(i + 1) {
+                Some(j) => vector[j],
+                None => zero,
+            };
+            builder.select(is_vector, vector_limb, value_limb)
+        })
+        .collect::<Vec<_>>();
+    if let Some(top) = vector.len().checked_sub(1).filter(|top| *top < num_limbs) {
+        // Flip the sign bit, ie. add 2^31 and subtract 2^32 if it is set.
+        let sign = builder.split_le(limbs[top], 32)[31];
+        let top_plus_offset = builder.add_const(limbs[top], GoldilocksField(1 << 31));
+        let flipped_top =
+            builder.mul_const_add(-GoldilocksField(1 << 32), sign.target, top_plus_offset);
+        limbs[top] = builder.select(is_vector, flipped_top, limbs[top]);
+    }
+    limbs
+}
+
+/// Less than or equal check for values given as LE u32 limbs, which
+/// are range checked here.
+pub fn limbs_less_or_equal(
+    builder: &mut CircuitBuilder<F, D>,
+    x: &[Target],
+    y: &[Target],
+) -> BoolTarget {
+    list_le_circuit(builder, x.to_vec(), y.to_vec(), 32)
+}
+
 pub fn member(builder: &mut CircuitBuilder<F, D>, x: Target, v: &[Target]) -> BoolTarget {
     v.iter().fold(builder._false(), |acc, y| {
         let eq_x_y = builder.is_equal(x, *y);
@@ -153,3 +212,28 @@ pub fn and(builder: &mut CircuitBuilder<F, D>, v: &[BoolTarget]) -> BoolTarget {
     v.iter()
         .fold(builder._true(), |acc, ind| builder.and(acc, *ind))
 }
+
+/// Whether `value` is the value of a `VALUEOF` statement holding the hash, ie. the hash of its
+/// elements as such, as in new entries, or padded to `VL` as by `Entry::pad_if_vec`, as in
+/// signed PODs.
+pub fn holds_hash<const VL: usize>(
+    builder: &mut CircuitBuilder<F, D>,
+    value: Target,
+    hash: HashOutTarget,
+) -> BoolTarget {
+    let unpadded_hash = builder
+        .hash_n_to_hash_no_pad::<PoseidonHash>(hash.elements.to_vec())
+        .elements[0];
+    let holds_unpadded = builder.is_equal(value, unpadded_hash);
+    if VL <= hash.elements.len() {
+        return holds_unpadded;
+    }
+    let padded = (0..VL)
+        .map(|i| *hash.elements.get(i).unwrap_or(&hash.elements[0]))
+        .collect();
+    let padded_hash = builder
+        .hash_n_to_hash_no_pad::<PoseidonHash>(padded)
+        .elements[0];
+    let holds_padded = builder.is_equal(value, padded_hash);
+    builder.or(holds_unpadded, holds_padded)
+}
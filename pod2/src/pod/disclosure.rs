@@ -0,0 +1,336 @@
+//! Selective disclosure: derives from a POD a Plonky POD which reveals some of its entries and
+//! proves predicates about others (eg. `age > 18`) without revealing them.
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    entry::Entry,
+    gadget::plonky_pod::ProverParams,
+    operation::{Operation as Op, OperationCmd as OpCmd},
+    statement::StatementRef,
+    value::ScalarOrVec,
+    CircuitShape, GPGInput, POD,
+};
+use crate::{Error, Result};
+
+/// Name given to the POD a disclosure is derived from, and thus to the origin of the statements
+/// it discloses, unless one of its own origins already has this name.
+pub const DISCLOSURE_SOURCE: &str = "source";
+
+/// Predicate about an entry of the source POD, given by its key, and constants. The constants
+/// are revealed as entries of the derived POD named `<key>-<predicate>-<bound>`, and the
+/// predicate statement is named after the key, eg. `GT:age`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DisclosurePredicate {
+    Equal(String, ScalarOrVec),
+    NotEqual(String, ScalarOrVec),
+    Gt(String, ScalarOrVec),
+    Lt(String, ScalarOrVec),
+    Gte(String, ScalarOrVec),
+    Lte(String, ScalarOrVec),
+    /// Value between a lower and an upper bound, inclusive
+    InRange(String, ScalarOrVec, ScalarOrVec),
+}
+
+impl DisclosurePredicate {
+    fn key(&self) -> &str {
+        match self {
+            Self::Equal(key, _)
+            | Self::NotEqual(key, _)
+            | Self::Gt(key, _)
+            | Self::Lt(key, _)
+            | Self::Gte(key, _)
+            | Self::Lte(key, _)
+            | Self::InRange(key, _, _) => key,
+        }
+    }
+
+    /// Operations proving the predicate about the entry of the source POD.
+    fn ops(&self, source: &str) -> Vec<OpCmd> {
+        let key = self.key();
+        let entry = StatementRef::new(source, format!("VALUEOF:{}", key));
+        let bound = |name: &str, value: &ScalarOrVec| {
+            let bound_key = format!("{}-{}", key, name);
+            (
+                OpCmd::new(
+                    Op::NewEntry(Entry {
+                        key: bound_key.clone(),
+                        value: value.clone(),
+                    }),
+                    bound_key.clone(),
+                ),
+                StatementRef::new("_SELF", format!("VALUEOF:{}", bound_key)),
+            )
+        };
+        let (bounds, op) = match self {
+            Self::InRange(_, lower, upper) => {
+                let (lower_op, lower) = bound("inrange-lower", lower);
+                let (upper_op, upper) = bound("inrange-upper", upper);
+                (
+                    vec![lower_op, upper_op],
+                    Op::InRangeFromEntries(entry, lower, upper),
+                )
+            }
+            Self::Equal(_, value) => {
+                let (bound_op, bound) = bound("equal-bound", value);
+                (vec![bound_op], Op::EqualityFromEntries(entry, bound))
+            }
+            Self::NotEqual(_, value) => {
+                let (bound_op, bound) = bound("notequal-bound", value);
+                (vec![bound_op], Op::NonequalityFromEntries(entry, bound))
+            }
+            Self::Gt(_, value) => {
+                let (bound_op, bound) = bound("gt-bound", value);
+                (vec![bound_op], Op::GtFromEntries(entry, bound))
+            }
+            Self::Lt(_, value) => {
+                let (bound_op, bound) = bound("lt-bound", value);
+                (vec![bound_op], Op::LtFromEntries(entry, bound))
+            }
+            Self::Gte(_, value) => {
+                let (bound_op, bound) = bound("gte-bound", value);
+                (vec![bound_op], Op::GteFromEntries(entry, bound))
+            }
+            Self::Lte(_, value) => {
+                let (bound_op, bound) = bound("lte-bound", value);
+                (vec![bound_op], Op::LteFromEntries(entry, bound))
+            }
+        };
+        [bounds, vec![OpCmd::new(op, key)]].concat()
+    }
+}
+
+impl POD {
+    /// Derives from `pod` a Plonky POD revealing the entries with the given keys, eg.
+    /// `SIGNER_PK_KEY` to disclose the signer, and proving the predicates.
+    pub fn derive_disclosure<
+        const L: usize,
+        const M: usize,
+        const N: usize,
+        const NS: usize,
+        const VL: usize,
+    >(
+        prover_params: &mut ProverParams<L, M, N, NS, VL>,
+        pod: &POD,
+        keys_to_reveal: &[&str],
+        predicates: &[DisclosurePredicate],
+    ) -> Result<Self>
+    where
+        [(); L + M + N]:,
+        [(); L + N]:,
+    {
+        let (input, cmds) = Self::disclosure(pod, keys_to_reveal, predicates)?;
+        Self::execute_plonky_gadget(prover_params, &input, &cmds)
+    }
+
+    /// Same as `derive_disclosure`, with the circuit shape chosen at runtime among the
+    /// registered ones.
+    pub fn derive_disclosure_with_shape(
+        shape: CircuitShape,
+        pod: &POD,
+        keys_to_reveal: &[&str],
+        predicates: &[DisclosurePredicate],
+    ) -> Result<Self> {
+        let (input, cmds) = Self::disclosure(pod, keys_to_reveal, predicates)?;
+        Self::execute_plonky_gadget_with_shape(shape, &input, &cmds)
+    }
+
+    /// GPG input and operations of a disclosure. The origins of the statements of `pod` keep
+    /// their names.
+    fn disclosure(
+        pod: &POD,
+        keys_to_reveal: &[&str],
+        predicates: &[DisclosurePredicate],
+    ) -> Result<(GPGInput, Vec<OpCmd>)> {
+        let origin_names = pod
+            .payload
+            .statements_list
+            .iter()
+            .flat_map(|(_, statement)| statement.anchored_keys())
+            .map(|anchored_key| anchored_key.0.origin_name)
+            .filter(|origin_name| origin_name != "_SELF")
+            .collect::<HashSet<_>>();
+        let source = (1..)
+            .map(|i| match i {
+                1 => DISCLOSURE_SOURCE.to_string(),
+                _ => format!("{}{}", DISCLOSURE_SOURCE, i),
+            })
+            .find(|name| !origin_names.contains(name))
+            .ok_or(Error::InvalidInput(
+                "No name left for the source POD".to_string(),
+            ))?;
+
+        let keys = keys_to_reveal
+            .iter()
+            .copied()
+            .chain(predicates.iter().map(|predicate| predicate.key()));
+        for key in keys {
+            if !pod
+                .payload
+                .statements_map
+                .contains_key(&format!("VALUEOF:{}", key))
+            {
+                return Err(Error::MissingStatement(format!(
+                    "The POD has no entry with key {}",
+                    key
+                )));
+            }
+        }
+
+        let cmds = keys_to_reveal
+            .iter()
+            .map(|key| {
+                OpCmd::new(
+                    Op::CopyStatement(StatementRef::new(&source, format!("VALUEOF:{}", key))),
+                    *key,
+                )
+            })
+            .chain(
+                predicates
+                    .iter()
+                    .flat_map(|predicate| predicate.ops(&source)),
+            )
+            .collect::<Vec<_>>();
+
+        // Output statements are named by predicate and output name, so two operations with the
+        // same name would overwrite each other's statement. Here both copies and new entries
+        // output VALUEOF statements.
+        let mut names = HashSet::new();
+        for OpCmd(op, name) in cmds.iter() {
+            let code = match op {
+                Op::CopyStatement(_) => Op::<StatementRef>::NEW_ENTRY,
+                _ => op.code(),
+            };
+            if !names.insert((code, name)) {
+                return Err(Error::InvalidInput(format!(
+                    "Statement {} is disclosed twice",
+                    name
+                )));
+            }
+        }
+
+        let origin_renaming_map = origin_names
+            .into_iter()
+            .map(|origin_name| ((source.clone(), origin_name.clone()), origin_name))
+            .collect();
+        let input = GPGInput::new(HashMap::from([(source, pod.clone())]), origin_renaming_map);
+        Ok((input, cmds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+
+    use super::*;
+    use crate::pod::gadget::PlonkyButNotPlonkyGadget;
+    use crate::pod::{Statement, SIGNER_PK_KEY};
+    use crate::recursion::{traits_examples::ExampleIntroducer, IntroducerCircuitTrait};
+    use crate::signature::ecschnorr::EcSchnorrSecretKey;
+    use anyhow::Result;
+
+    const L: usize = 0;
+    const M: usize = 2;
+    const N: usize = 1;
+    const NS: usize = 3;
+    const VL: usize = 2;
+
+    fn prover_params() -> Result<ProverParams<L, M, N, NS, VL>> {
+        let pod1_circuit_data = ExampleIntroducer::circuit_data()?;
+        let circuit_data = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::circuit_data(
+            pod1_circuit_data.verifier_data(),
+        )?;
+        PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::build_prover_params(
+            pod1_circuit_data,
+            circuit_data,
+        )
+    }
+
+    #[test]
+    fn test_disclosure() -> Result<()> {
+        let mut prover_params = prover_params()?;
+        let schnorr_pod = POD::execute_schnorr_gadget::<NS, VL>(
+            &[
+                Entry::new_from_scalar("age", GoldilocksField(25)),
+                Entry::new_from_scalar("zipcode", GoldilocksField(75011)),
+            ],
+            &EcSchnorrSecretKey::from(25),
+        )?;
+
+        // Schnorr-backed input: reveals the signer, and that age > 18
+        let pod = POD::derive_disclosure(
+            &mut prover_params,
+            &schnorr_pod,
+            &[SIGNER_PK_KEY],
+            &[DisclosurePredicate::Gt(
+                "age".to_string(),
+                ScalarOrVec::Scalar(GoldilocksField(18)),
+            )],
+        )?;
+        assert!(pod.verify::<L, M, N, NS, VL>()?);
+        let statements = &pod.payload.statements_map;
+        assert_eq!(
+            statements.get("VALUEOF:_signer"),
+            schnorr_pod.payload.statements_map.get("VALUEOF:_signer")
+        );
+        assert!(matches!(
+            statements.get("VALUEOF:age-gt-bound"),
+            Some(Statement::ValueOf(
+                _,
+                ScalarOrVec::Scalar(GoldilocksField(18))
+            ))
+        ));
+        assert!(matches!(
+            statements.get("GT:age"),
+            Some(Statement::Gt(_, _))
+        ));
+        // the other entries are not revealed
+        assert!(!statements
+            .values()
+            .any(|statement| statement.value().ok()
+                == Some(ScalarOrVec::Scalar(GoldilocksField(25)))));
+        assert!(!statements.contains_key("VALUEOF:zipcode"));
+
+        // Plonky-backed input: proves that the bound is in [10, 20]. Its origin is renamed, as the
+        // input already has statements from an origin named "source".
+        let pod2 = POD::derive_disclosure(
+            &mut prover_params,
+            &pod,
+            &[],
+            &[DisclosurePredicate::InRange(
+                "age-gt-bound".to_string(),
+                ScalarOrVec::Scalar(GoldilocksField(10)),
+                ScalarOrVec::Scalar(GoldilocksField(20)),
+            )],
+        )?;
+        assert!(pod2.verify::<L, M, N, NS, VL>()?);
+        assert!(matches!(
+            pod2.payload.statements_map.get("INRANGE:age-gt-bound"),
+            Some(Statement::InRange(_, _, _))
+        ));
+        assert!(pod2
+            .payload
+            .statements_map
+            .get("INRANGE:age-gt-bound")
+            .unwrap()
+            .anchored_keys()
+            .iter()
+            .any(|anchored_key| anchored_key.0.origin_name == "source2"));
+
+        // false predicates, unknown keys and duplicates are rejected
+        assert!(POD::derive_disclosure(
+            &mut prover_params,
+            &schnorr_pod,
+            &[SIGNER_PK_KEY],
+            &[DisclosurePredicate::Gt(
+                "age".to_string(),
+                ScalarOrVec::Scalar(GoldilocksField(30)),
+            )],
+        )
+        .is_err());
+        assert!(POD::derive_disclosure(&mut prover_params, &schnorr_pod, &["name"], &[]).is_err());
+        assert!(
+            POD::derive_disclosure(&mut prover_params, &schnorr_pod, &["age", "age"], &[]).is_err()
+        );
+        Ok(())
+    }
+}
@@ -60,8 +60,13 @@ impl Entry {
         }
     }
 
-    /// Representation as field vector of length 2.
+    /// Representation as field vector of length 3 of the form
+    /// [key_hash, value, value_type].
     pub fn to_fields(&self) -> Vec<GoldilocksField> {
-        vec![hash_string_to_field(&self.key), self.value.hash_or_value()]
+        vec![
+            hash_string_to_field(&self.key),
+            self.value.hash_or_value(),
+            self.value.type_field(),
+        ]
     }
 }
@@ -0,0 +1,277 @@
+//! Wrapping step for Plonky PODs before transport: the POD's proof is re-proved in a
+//! size-optimised plonky2 config. Compressed PODs can be verified, but can not be used as inputs
+//! of further PODs.
+use anyhow::{anyhow, Result};
+use plonky2::fri::reduction_strategies::FriReductionStrategy;
+use plonky2::fri::FriConfig;
+use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::{CircuitConfig, CircuitData, VerifierCircuitData};
+use plonky2::plonk::proof::{ProofWithPublicInputs, ProofWithPublicInputsTarget};
+use std::any::Any;
+use std::collections::HashMap;
+use std::iter::zip;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::pod::gadget::GadgetID;
+use crate::pod::shape::CircuitShape;
+use crate::pod::{PODProof, Verifier, POD};
+use crate::{PlonkyProof, C, D, F};
+
+/// Process-wide compression circuits, keyed by the circuit shape of the PODs they compress.
+static COMPRESSION_CIRCUITS: OnceLock<Mutex<HashMap<CircuitShape, Arc<dyn Any + Send + Sync>>>> =
+    OnceLock::new();
+
+/// CompressionCircuit verifies a proof of the PlonkyButNotPlonkyGadget circuit for the given
+/// parameters, and registers as public inputs the NS statements of the POD.
+pub struct CompressionCircuit<
+    const L: usize,
+    const M: usize,
+    const N: usize,
+    const NS: usize,
+    const VL: usize,
+> {
+    circuit_data: CircuitData<F, C, D>,
+    proof_targ: ProofWithPublicInputsTarget<D>,
+    plonky_verifier_data: VerifierCircuitData<F, C, D>,
+}
+
+impl<const L: usize, const M: usize, const N: usize, const NS: usize, const VL: usize>
+    CompressionCircuit<L, M, N, NS, VL>
+where
+    [(); L + M + N]:,
+    [(); L + N]:,
+{
+    /// Circuit config of the compressed proofs. A higher FRI rate allows for fewer query rounds,
+    /// which shrinks the proof at the cost of a slower prover.
+    pub fn config() -> CircuitConfig {
+        let config = CircuitConfig::standard_recursion_config();
+        CircuitConfig {
+            fri_config: FriConfig {
+                rate_bits: 7,
+                cap_height: 0,
+                proof_of_work_bits: 16,
+                reduction_strategy: FriReductionStrategy::MinSize(None),
+                num_query_rounds: 12,
+            },
+            ..config
+        }
+    }
+
+    /// Builds the circuit, given the verifier data of the PlonkyButNotPlonkyGadget circuit.
+    pub fn new(plonky_verifier_data: VerifierCircuitData<F, C, D>) -> Result<Self> {
+        let mut builder = CircuitBuilder::<F, D>::new(Self::config());
+        let proof_targ = builder.add_virtual_proof_with_pis(&plonky_verifier_data.common);
+        let verifier_data_targ =
+            builder.constant_verifier_data(&plonky_verifier_data.verifier_only);
+        builder.verify_proof::<C>(
+            &proof_targ,
+            &verifier_data_targ,
+            &plonky_verifier_data.common,
+        );
+
+        // The public inputs of the PlonkyButNotPlonkyGadget circuit are the statements followed
+        // by its own verifier data, which must be the one the proof is verified against.
+        let verifier_data_pubinp = [
+            verifier_data_targ.circuit_digest.elements.to_vec(),
+            verifier_data_targ
+                .constants_sigmas_cap
+                .0
+                .iter()
+                .flat_map(|h| h.elements)
+                .collect(),
+        ]
+        .concat();
+        let num_statement_fields = proof_targ
+            .public_inputs
+            .len()
+            .checked_sub(verifier_data_pubinp.len())
+            .ok_or(anyhow!(
+                "The PlonkyButNotPlonkyGadget circuit does not register its verifier data."
+            ))?;
+        zip(
+            &proof_targ.public_inputs[num_statement_fields..],
+            verifier_data_pubinp,
+        )
+        .for_each(|(pubinp, t)| builder.connect(*pubinp, t));
+        builder.register_public_inputs(&proof_targ.public_inputs[..num_statement_fields]);
+
+        Ok(Self {
+            circuit_data: builder.build::<C>(),
+            proof_targ,
+            plonky_verifier_data,
+        })
+    }
+
+    /// Returns the CompressionCircuit shared by the whole process for these parameters, so that
+    /// it is built at most once.
+    pub fn cached() -> Result<Arc<Self>> {
+        let shape = CircuitShape::of::<L, M, N, NS, VL>();
+        let mut circuits = COMPRESSION_CIRCUITS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(circuit) = circuits.get(&shape) {
+            return circuit
+                .clone()
+                .downcast::<Self>()
+                .map_err(|_| anyhow!("Compression circuits are keyed by their circuit shape."));
+        }
+        let plonky_verifier_data = Verifier::<L, M, N, NS, VL>::cached()
+            .plonky_verifier_data()?
+            .clone();
+        let circuit = Arc::new(Self::new(plonky_verifier_data)?);
+        circuits.insert(shape, circuit.clone());
+        Ok(circuit)
+    }
+
+    pub fn verifier_data(&self) -> VerifierCircuitData<F, C, D> {
+        self.circuit_data.verifier_data()
+    }
+
+    /// Re-proves the given Plonky POD in the compression config.
+    pub fn compress(&self, pod: &POD) -> Result<POD> {
+        let shape = CircuitShape::of::<L, M, N, NS, VL>();
+        let proof = match &pod.proof {
+            PODProof::Plonky(pod_shape, proof) if *pod_shape == shape => proof.clone(),
+            PODProof::Plonky(pod_shape, _) => {
+                return Err(anyhow!(
+                    "POD was produced with the circuit shape {}, not {}",
+                    pod_shape,
+                    shape
+                ))
+            }
+            _ => return Err(anyhow!("Expected PODProof's Plonky variant")),
+        };
+        if pod.payload.statements_list.len() != NS {
+            return Err(anyhow!("POD must contain exactly {} statements", NS));
+        }
+
+        let verifier_only = &self.plonky_verifier_data.verifier_only;
+        let public_inputs: Vec<F> = [
+            pod.payload
+                .statements_list
+                .iter()
+                .flat_map(|(_, s)| s.to_fields())
+                .collect(),
+            verifier_only.circuit_digest.elements.to_vec(),
+            verifier_only
+                .constants_sigmas_cap
+                .0
+                .iter()
+                .flat_map(|h| h.elements)
+                .collect(),
+        ]
+        .concat();
+        let mut pw = PartialWitness::new();
+        pw.set_proof_with_pis_target(
+            &self.proof_targ,
+            &ProofWithPublicInputs {
+                proof,
+                public_inputs,
+            },
+        )?;
+        let compressed_proof = self.circuit_data.prove(pw)?;
+
+        Ok(POD {
+            payload: pod.payload.clone(),
+            proof: PODProof::CompressedPlonky(shape, compressed_proof.proof),
+            proof_type: GadgetID::COMPRESSED_PLONKY,
+        })
+    }
+
+    /// Verifies the given compressed proof of a POD with the given statement fields.
+    pub fn verify_proof(
+        verifier_data: &VerifierCircuitData<F, C, D>,
+        proof: PlonkyProof,
+        public_inputs: Vec<F>,
+    ) -> Result<()> {
+        verifier_data.verify(ProofWithPublicInputs {
+            proof,
+            public_inputs,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use std::collections::HashMap;
+
+    use super::CompressionCircuit;
+    use crate::pod::{
+        entry::Entry,
+        gadget::{GadgetID, PlonkyButNotPlonkyGadget},
+        operation::{OpList, Operation as Op, OperationCmd as OpCmd},
+        payload::PODPayload,
+        statement::StatementRef,
+        Statement, POD,
+    };
+    use crate::signature::ecschnorr::EcSchnorrSecretKey;
+
+    #[test]
+    fn test_compress_plonky_pod() -> Result<()> {
+        const L: usize = 0;
+        const M: usize = 1;
+        const N: usize = 1;
+        const NS: usize = 3;
+        const VL: usize = 0;
+
+        let schnorr_pod = POD::execute_schnorr_gadget::<NS, VL>(
+            &[
+                Entry::new_from_scalar("s1", GoldilocksField(55)),
+                Entry::new_from_scalar("s2", GoldilocksField(56)),
+            ],
+            &EcSchnorrSecretKey::from(27),
+        )?;
+        let pods_list = vec![("Test POD 1".to_string(), schnorr_pod)];
+        let op_list = OpList(vec![OpCmd::new(
+            Op::CopyStatement(StatementRef::new("Test POD 1", "VALUEOF:s2")),
+            "op1",
+        )]);
+        let prover_params = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::cached_prover_params()?;
+        let plonky_pod = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::execute(
+            &mut prover_params.lock().unwrap(),
+            &pods_list,
+            op_list,
+            HashMap::new(),
+        )?;
+
+        let compressed_pod = plonky_pod.compress::<L, M, N, NS, VL>()?;
+        assert_eq!(compressed_pod.proof_type, GadgetID::COMPRESSED_PLONKY);
+        assert_eq!(compressed_pod.payload, plonky_pod.payload);
+        assert!(compressed_pod.verify::<L, M, N, NS, VL>()?);
+        assert!(
+            serde_json::to_vec(&compressed_pod.proof)?.len()
+                < serde_json::to_vec(&plonky_pod.proof)?.len()
+        );
+
+        // the compressed proof does not hold for other statements
+        let mut statements = compressed_pod.payload.statements_map.clone();
+        statements.insert(
+            "VALUEOF:op1".to_string(),
+            Statement::from_entry(
+                &Entry::new_from_scalar("op1", GoldilocksField(57)),
+                GadgetID::ECSCHNORR,
+            ),
+        );
+        let mut forged_pod = compressed_pod.clone();
+        forged_pod.payload = PODPayload::new(&statements);
+        assert!(forged_pod.payload.validate().is_ok());
+        assert!(forged_pod.verify::<L, M, N, NS, VL>().is_err());
+
+        // compressed PODs are not taken as inputs of further PODs
+        assert!(PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::execute(
+            &mut prover_params.lock().unwrap(),
+            &[("Compressed POD".to_string(), compressed_pod)],
+            OpList(vec![]),
+            HashMap::new(),
+        )
+        .is_err());
+
+        // only Plonky PODs can be compressed
+        assert!(pods_list[0].1.compress::<L, M, N, NS, VL>().is_err());
+        Ok(())
+    }
+}
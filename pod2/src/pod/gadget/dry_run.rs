@@ -0,0 +1,428 @@
+//! Native evaluation of the inputs of `PlonkyButNotPlonkyGadget::execute`, so that mistakes in
+//! the input PODs or in the OpList are reported before spending time on the proof.
+use plonky2::field::types::PrimeField64;
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::pod::gadget::{GadgetID, PlonkyButNotPlonkyGadget};
+use crate::pod::operation::{OpList, OperationCmd};
+use crate::pod::payload::{validate_statement_name, PODPayload};
+use crate::pod::shape::CircuitShape;
+use crate::pod::statement::{StatementOrRef, StatementRef};
+use crate::pod::{GPGInput, POD};
+use crate::Error;
+
+/// Reason for which the inputs of `PlonkyButNotPlonkyGadget::execute` can not be proven.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DryRunError {
+    /// Input POD of a type which is not verified in circuit.
+    UnsupportedInput { pod: String, gadget_id: GadgetID },
+    /// More input PODs of the given type than the circuit takes.
+    TooManyInputs {
+        gadget_id: GadgetID,
+        count: usize,
+        max: usize,
+    },
+    /// Input PlonkyPOD produced with another circuit shape.
+    ShapeMismatch {
+        pod: String,
+        shape: Option<CircuitShape>,
+        expected: CircuitShape,
+    },
+    /// Input POD which does not contain exactly NS statements.
+    StatementCount {
+        pod: String,
+        count: usize,
+        expected: usize,
+    },
+    /// More operations than output statements.
+    TooManyOperations { count: usize, max: usize },
+    /// Origin of an input POD which the origin renaming map does not rename.
+    MissingOrigin { pod: String, origin: String },
+    /// Failure to remap the origins of the input PODs.
+    OriginRemapping(String),
+    /// Origin of an input POD whose ID is beyond the ones remapped in circuit.
+    OriginIdOutOfRange {
+        pod: String,
+        origin: String,
+        origin_id: u64,
+        max: u64,
+    },
+    /// Operand of the operation at `op_index` referencing a POD which is not among the inputs.
+    UnknownPod {
+        op_index: usize,
+        statement_ref: StatementRef,
+    },
+    /// Operand of the operation at `op_index` referencing a missing statement.
+    UnknownStatement {
+        op_index: usize,
+        statement_ref: StatementRef,
+    },
+    /// Operation at `op_index` which does not hold, or can not be checked in circuit.
+    InvalidOperation {
+        op_index: usize,
+        out_name: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for DryRunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedInput {
+                pod,
+                gadget_id: GadgetID::SCHNORR16,
+            } => write!(
+                f,
+                "Input POD {} is a legacy SCHNORR16 POD, which can not be verified in circuit. Re-sign it as an ECSCHNORR POD.",
+                pod
+            ),
+            Self::UnsupportedInput { pod, gadget_id } => write!(
+                f,
+                "Input POD {} of type {} can not be verified in circuit.",
+                pod, gadget_id
+            ),
+            Self::TooManyInputs {
+                gadget_id,
+                count,
+                max,
+            } => write!(
+                f,
+                "Number of {} PODs ({}) exceeds allowed maximum ({}).",
+                gadget_id, count, max
+            ),
+            Self::ShapeMismatch {
+                pod,
+                shape,
+                expected,
+            } => write!(
+                f,
+                "PlonkyPOD {} was produced with the circuit shape {:?}, expected {}.",
+                pod, shape, expected
+            ),
+            Self::StatementCount {
+                pod,
+                count,
+                expected,
+            } => write!(
+                f,
+                "Input POD {} contains {} statements, expected {}.",
+                pod, count, expected
+            ),
+            Self::TooManyOperations { count, max } => write!(
+                f,
+                "The operation list contains {} operations, at most {} are allowed.",
+                count, max
+            ),
+            Self::MissingOrigin { pod, origin } => write!(
+                f,
+                "Origin {} of input POD {} is missing from the origin renaming map.",
+                origin, pod
+            ),
+            Self::OriginRemapping(reason) => write!(f, "Origin remapping failed: {}", reason),
+            Self::OriginIdOutOfRange {
+                pod,
+                origin,
+                origin_id,
+                max,
+            } => write!(
+                f,
+                "Origin {} of input POD {} has ID {}, which exceeds the maximum of {}.",
+                origin, pod, origin_id, max
+            ),
+            Self::UnknownPod {
+                op_index,
+                statement_ref,
+            } => write!(
+                f,
+                "Operation {} references POD {}, which is not among the input PODs.",
+                op_index, statement_ref.0
+            ),
+            Self::UnknownStatement {
+                op_index,
+                statement_ref,
+            } => write!(
+                f,
+                "Operation {} references statement {} of POD {}, which does not exist.",
+                op_index, statement_ref.1, statement_ref.0
+            ),
+            Self::InvalidOperation {
+                op_index,
+                out_name,
+                reason,
+            } => write!(
+                f,
+                "Operation {} ({}) failed: {}",
+                op_index, out_name, reason
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DryRunError {}
+
+impl<const L: usize, const M: usize, const N: usize, const NS: usize, const VL: usize>
+    PlonkyButNotPlonkyGadget<L, M, N, NS, VL>
+where
+    [(); L + M + N]:,
+    [(); L + N]:,
+{
+    /// Checks the inputs of `execute` against the circuit parameters and evaluates the OpList
+    /// natively, without generating any proof. Operations are indexed as in `op_list`.
+    pub fn dry_run(
+        input_pods: &[(String, POD)],
+        op_list: &OpList,
+        origin_renaming_map: &HashMap<(String, String), String>,
+    ) -> Result<(), DryRunError> {
+        let shape = CircuitShape::of::<L, M, N, NS, VL>();
+        for (name, pod) in input_pods {
+            match pod.proof_type {
+                GadgetID::INTRODUCER | GadgetID::ECSCHNORR => {}
+                GadgetID::PLONKY if pod.shape() == Some(shape) => {}
+                GadgetID::PLONKY => {
+                    return Err(DryRunError::ShapeMismatch {
+                        pod: name.clone(),
+                        shape: pod.shape(),
+                        expected: shape,
+                    })
+                }
+                gadget_id => {
+                    return Err(DryRunError::UnsupportedInput {
+                        pod: name.clone(),
+                        gadget_id,
+                    })
+                }
+            }
+            if pod.payload.statements_list.len() != NS {
+                return Err(DryRunError::StatementCount {
+                    pod: name.clone(),
+                    count: pod.payload.statements_list.len(),
+                    expected: NS,
+                });
+            }
+        }
+        for (gadget_id, max) in [
+            (GadgetID::INTRODUCER, L),
+            (GadgetID::ECSCHNORR, M),
+            (GadgetID::PLONKY, N),
+        ] {
+            let count = input_pods
+                .iter()
+                .filter(|(_, pod)| pod.proof_type == gadget_id)
+                .count();
+            if count > max {
+                return Err(DryRunError::TooManyInputs {
+                    gadget_id,
+                    count,
+                    max,
+                });
+            }
+        }
+        if op_list.0.len() > NS {
+            return Err(DryRunError::TooManyOperations {
+                count: op_list.0.len(),
+                max: NS,
+            });
+        }
+
+        // Origin IDs are remapped in circuit through a table of NS + 2 columns.
+        for (name, pod) in input_pods {
+            for (_, statement) in pod.payload.statements_map.iter() {
+                for anchkey in statement.anchored_keys() {
+                    let origin = anchkey.0;
+                    if origin.origin_name != "_SELF"
+                        && !origin_renaming_map
+                            .contains_key(&(name.clone(), origin.origin_name.clone()))
+                    {
+                        return Err(DryRunError::MissingOrigin {
+                            pod: name.clone(),
+                            origin: origin.origin_name,
+                        });
+                    }
+                    if origin.origin_id.to_canonical_u64() >= (NS + 2) as u64 {
+                        return Err(DryRunError::OriginIdOutOfRange {
+                            pod: name.clone(),
+                            origin: origin.origin_name,
+                            origin_id: origin.origin_id.to_canonical_u64(),
+                            max: (NS + 1) as u64,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Evaluate the operations in order, as `POD::execute_ops` does.
+        let gpg_input = GPGInput::new(
+            input_pods.iter().cloned().collect(),
+            origin_renaming_map.clone(),
+        );
+        let mut statements = gpg_input.remap_origin_ids_by_name().map_err(|e| match e {
+            Error::OriginRemapError(reason) => DryRunError::OriginRemapping(reason),
+            e => DryRunError::OriginRemapping(e.to_string()),
+        })?;
+        statements.insert("_SELF".to_string(), HashMap::new());
+        for (op_index, OperationCmd(op, out_name)) in op_list.0.iter().enumerate() {
+            for statement_ref in op.operands() {
+                let pod_statements =
+                    statements
+                        .get(&statement_ref.0)
+                        .ok_or(DryRunError::UnknownPod {
+                            op_index,
+                            statement_ref: statement_ref.clone(),
+                        })?;
+                if !pod_statements.contains_key(&statement_ref.1) {
+                    return Err(DryRunError::UnknownStatement {
+                        op_index,
+                        statement_ref: statement_ref.clone(),
+                    });
+                }
+            }
+            let invalid_operation = |reason: String| DryRunError::InvalidOperation {
+                op_index,
+                out_name: out_name.clone(),
+                reason,
+            };
+            let new_statement = op
+                .execute(GadgetID::ORACLE, &statements)
+                .map_err(|e| invalid_operation(e.to_string()))?;
+            let name = format!("{}:{}", new_statement.predicate(), out_name);
+            validate_statement_name(&name, &new_statement)
+                .map_err(|e| invalid_operation(e.to_string()))?;
+            statements
+                .get_mut("_SELF")
+                .unwrap()
+                .insert(name, new_statement);
+        }
+
+        // Check that the operations can be encoded for the circuit, eg. that their vectors are
+        // of length VL.
+        let input_and_output_pod_list: Vec<(String, PODPayload)> = input_pods
+            .iter()
+            .map(|(pod_name, pod)| (pod_name.clone(), pod.payload.clone()))
+            .chain(std::iter::once((
+                "_SELF".to_string(),
+                PODPayload::new(&statements["_SELF"]),
+            )))
+            .collect();
+        let ref_index_map = StatementRef::index_map(&input_and_output_pod_list);
+        let statement_table: <StatementRef as StatementOrRef>::StatementTable =
+            input_and_output_pod_list
+                .iter()
+                .map(|(pod_name, payload)| (pod_name.clone(), payload.statements_map.clone()))
+                .collect();
+        for (op_index, OperationCmd(op, out_name)) in op_list.0.iter().enumerate() {
+            op.to_fields::<VL>(&ref_index_map, &statement_table)
+                .map_err(|e| DryRunError::InvalidOperation {
+                    op_index,
+                    out_name: out_name.clone(),
+                    reason: e.to_string(),
+                })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use std::collections::HashMap;
+
+    use super::DryRunError;
+    use crate::pod::{
+        entry::Entry,
+        gadget::{GadgetID, PlonkyButNotPlonkyGadget},
+        operation::{OpList, Operation as Op, OperationCmd as OpCmd},
+        statement::StatementRef,
+        POD,
+    };
+    use crate::signature::ecschnorr::EcSchnorrSecretKey;
+
+    type Gadget = PlonkyButNotPlonkyGadget<0, 1, 1, 3, 0>;
+
+    #[test]
+    fn test_dry_run() -> anyhow::Result<()> {
+        let schnorr_pod = |i: u64, big: u64| {
+            POD::execute_schnorr_gadget::<3, 0>(
+                &[
+                    Entry::new_from_scalar("small", GoldilocksField(i)),
+                    Entry::new_from_scalar("big", GoldilocksField(big)),
+                ],
+                &EcSchnorrSecretKey::from(30 + i),
+            )
+        };
+        let pods_list = vec![("pod".to_string(), schnorr_pod(1, 1 << 32)?)];
+        let dry_run = |ops: Vec<OpCmd>| Gadget::dry_run(&pods_list, &OpList(ops), &HashMap::new());
+        let gt = OpCmd::new(
+            Op::GtFromEntries(
+                StatementRef::new("pod", "VALUEOF:big"),
+                StatementRef::new("pod", "VALUEOF:small"),
+            ),
+            "gt",
+        );
+
+        assert_eq!(dry_run(vec![gt.clone()]), Ok(()));
+        assert_eq!(
+            dry_run(vec![
+                gt.clone(),
+                OpCmd::new(
+                    Op::CopyStatement(StatementRef::new("other pod", "VALUEOF:small")),
+                    "copy",
+                ),
+            ]),
+            Err(DryRunError::UnknownPod {
+                op_index: 1,
+                statement_ref: StatementRef::new("other pod", "VALUEOF:small"),
+            })
+        );
+        assert_eq!(
+            dry_run(vec![OpCmd::new(
+                Op::CopyStatement(StatementRef::new("_SELF", "GT:lt")),
+                "copy",
+            )]),
+            Err(DryRunError::UnknownStatement {
+                op_index: 0,
+                statement_ref: StatementRef::new("_SELF", "GT:lt"),
+            })
+        );
+        assert!(matches!(
+            dry_run(vec![OpCmd::new(
+                Op::LtFromEntries(
+                    StatementRef::new("pod", "VALUEOF:big"),
+                    StatementRef::new("pod", "VALUEOF:small"),
+                ),
+                "lt",
+            )]),
+            Err(DryRunError::InvalidOperation { op_index: 0, .. })
+        ));
+        // MaxOf holds in circuit for 64-bit values, as natively
+        assert_eq!(
+            dry_run(vec![OpCmd::new(
+                Op::MaxOf(
+                    StatementRef::new("pod", "VALUEOF:big"),
+                    StatementRef::new("pod", "VALUEOF:big"),
+                    StatementRef::new("pod", "VALUEOF:small"),
+                ),
+                "max",
+            )]),
+            Ok(())
+        );
+        assert_eq!(
+            dry_run(vec![gt.clone(), gt.clone(), gt.clone(), gt]),
+            Err(DryRunError::TooManyOperations { count: 4, max: 3 })
+        );
+
+        let two_pods = vec![
+            ("pod".to_string(), schnorr_pod(1, 2)?),
+            ("other pod".to_string(), schnorr_pod(2, 3)?),
+        ];
+        assert_eq!(
+            Gadget::dry_run(&two_pods, &OpList(vec![]), &HashMap::new()),
+            Err(DryRunError::TooManyInputs {
+                gadget_id: GadgetID::ECSCHNORR,
+                count: 2,
+                max: 1,
+            })
+        );
+        Ok(())
+    }
+}
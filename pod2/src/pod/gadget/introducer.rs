@@ -1,49 +1,684 @@
 use anyhow::{anyhow, Result};
-use hashbrown::HashMap;
+use num::BigUint;
+use parcnet_pod::pod::{create_pod, Pod, PodValue};
+use parcnet_pod::pod_entries;
 use plonky2::field::types::Field;
 use plonky2::gates::noop::NoopGate;
+use plonky2::hash::poseidon::PoseidonHash;
 use plonky2::iop::target::{BoolTarget, Target};
 use plonky2::iop::witness::{PartialWitness, WitnessWrite};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
-use plonky2::plonk::circuit_data::{
-    CircuitConfig, CircuitData, ProverCircuitData, VerifierCircuitData, VerifierCircuitTarget,
-};
-use plonky2::plonk::proof::{ProofWithPublicInputs, ProofWithPublicInputsTarget};
-use plonky2::recursion::dummy_circuit::cyclic_base_proof;
-use std::array;
-use std::marker::PhantomData;
-use std::time::Instant;
+use plonky2::plonk::circuit_data::{CircuitConfig, CircuitData};
 
-use crate::pod::gadget::SchnorrPODGadget;
+use crate::plonky2_u32::gadgets::arithmetic_u32::U32Target;
+use crate::plonky2_u32::gadgets::range_check::range_check_u32_circuit;
+use crate::pod::circuit::{
+    origin::OriginTarget, statement::StatementTarget, util::statement_matrix_ref,
+};
+use crate::pod::gadget::GadgetID;
+use crate::pod::statement::Statement;
+use crate::pod::util::hash_string_to_field;
+use crate::pod::value::ScalarOrVec;
+use crate::pod::{POD, POD1_SIGNER_KEY};
+use crate::recursion::utils::assert_one_if_enabled;
 use crate::recursion::IntroducerCircuitTrait;
-use crate::{PlonkyProof, C, D, F};
+use crate::signature::biguint::{BigUintTarget, CircuitBuilderBiguint, WitnessBigUint};
+use crate::signature::eddsa::{
+    EddsaBuilder, EddsaPublicKeyTarget, EddsaSignatureTarget, MessageHashTarget,
+};
+use crate::signature::jubjubcurve::CircuitBuilderJubjubCurve;
+use crate::signature::jubjubfield::{JubjubFieldTarget, JubjubP};
+use crate::signature::poseidon_bn254::CircuitBuilderPoseidonBn254;
+use crate::signature::sha256::CircuitBuilderSha256;
+use crate::{Error, PlonkyProof, C, D, F};
+
+/// Number of u32 limbs of a BN254 field element.
+const FQ_LIMBS: usize = 8;
+
+/// Maximum length in bytes of the keys of POD1 entries, so that the SHA-256 hashes of the keys
+/// fit in one block.
+pub const MAX_POD1_KEY_LEN: usize = 55;
+
+/// How a POD1 value is represented in its pod2 statement (see `ScalarOrVec::from(PodValue)`)
+/// and in the POD1 content ID.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ValueKind {
+    // i64 as LE u32 limbs of the corresponding u64; also used for dates
+    Int,
+    // 0 or 1 as a scalar
+    Bool,
+    // BN254 field element as LE u32 limbs
+    Cryptographic,
+    // any other value, passed in as the LE u32 limbs of its BN254 hash
+    Hashed,
+}
+
+impl ValueKind {
+    fn of(value: &PodValue) -> Self {
+        match value {
+            PodValue::Int(_) | PodValue::Date(_) => Self::Int,
+            PodValue::Boolean(_) => Self::Bool,
+            PodValue::Cryptographic(_) => Self::Cryptographic,
+            _ => Self::Hashed,
+        }
+    }
+}
+
+/// Targets of one entry slot of the POD1. Slots are filled in order, so the `present` flags
+/// form a prefix.
+#[derive(Clone, Debug)]
+pub struct Pod1EntryTarget {
+    pub present: BoolTarget,
+    /// bytes of the key, followed by 0s
+    pub key_bytes: [Target; MAX_POD1_KEY_LEN],
+    pub key_len: Target,
+    /// key as used in the statements, ie. `hash_string_to_field(key)`, computed from the bytes
+    pub key: Target,
+    /// BN254 hash of the key, as used in the content ID, computed from the bytes
+    pub key_hash: BigUintTarget,
+    /// value as LE u32 limbs, as described in `ValueKind`
+    pub value: BigUintTarget,
+    pub is_int: BoolTarget,
+    pub is_bool: BoolTarget,
+    pub is_cryptographic: BoolTarget,
+    pub is_hashed: BoolTarget,
+}
+
+impl Pod1EntryTarget {
+    pub fn new_virtual(builder: &mut CircuitBuilder<F, D>) -> Self {
+        let key_bytes = builder.add_virtual_target_arr();
+        for byte in key_bytes {
+            builder.range_check(byte, 8);
+        }
+        let key_len = builder.add_virtual_target();
+        let (key, key_hash) = Self::key_hashes(builder, &key_bytes, key_len);
+        let value = builder.add_virtual_biguint_target(FQ_LIMBS);
+        range_check_u32_circuit(builder, value.limbs.clone());
+        Self {
+            present: builder.add_virtual_bool_target_safe(),
+            key_bytes,
+            key_len,
+            key,
+            key_hash,
+            value,
+            is_int: builder.add_virtual_bool_target_safe(),
+            is_bool: builder.add_virtual_bool_target_safe(),
+            is_cryptographic: builder.add_virtual_bool_target_safe(),
+            is_hashed: builder.add_virtual_bool_target_safe(),
+        }
+    }
+
+    /// Returns `hash_string_to_field(key)` and the BN254 hash of the key, ie. its SHA-256 digest
+    /// shifted right by 8 bits, both padding the bytes of the key as their reference
+    /// implementations do.
+    fn key_hashes(
+        builder: &mut CircuitBuilder<F, D>,
+        key_bytes: &[Target; MAX_POD1_KEY_LEN],
+        key_len: Target,
+    ) -> (Target, BigUintTarget) {
+        let zero = builder.zero();
+        let bytes: Vec<Target> = [key_bytes.to_vec(), vec![zero]].concat();
+        let padded_len = bytes.len();
+
+        // one-hot encoding of the length, which is thus at most MAX_POD1_KEY_LEN, and bytes past
+        // the length are 0
+        let len_is: Vec<BoolTarget> = (0..padded_len)
+            .map(|i| {
+                let i = builder.constant(F::from_canonical_usize(i));
+                builder.is_equal(key_len, i)
+            })
+            .collect();
+        let num_lens = builder.add_many(len_is.iter().map(|b| b.target));
+        builder.assert_one(num_lens);
+        let mut past_len = zero;
+        for (byte, len_is_i) in bytes.iter().zip(&len_is) {
+            past_len = builder.add(past_len, len_is_i.target);
+            let byte_past_len = builder.mul(*byte, past_len);
+            builder.assert_zero(byte_past_len);
+        }
+
+        // `hash_string_to_field`: 0x01 after the key and 0x80 at the end of its last u32 chunk,
+        // the last byte of the chunk of the length
+        let chunk_end_is: Vec<Target> = (0..padded_len)
+            .map(|i| match i % 4 {
+                3 => builder.add_many(len_is[i - 3..=i].iter().map(|b| b.target)),
+                _ => zero,
+            })
+            .collect();
+        let poseidon_bytes: Vec<Target> = (0..padded_len)
+            .map(|i| {
+                let byte = builder.add(bytes[i], len_is[i].target);
+                builder.mul_const_add(F::from_canonical_u64(0x80), chunk_end_is[i], byte)
+            })
+            .collect();
+        let chunks: Vec<Target> = poseidon_bytes
+            .chunks(4)
+            .map(|chunk| Self::be_word(builder, chunk))
+            .collect();
+        let key = (1..=chunks.len()).fold(zero, |key, num_chunks| {
+            let hash = builder
+                .hash_n_to_hash_no_pad::<PoseidonHash>(chunks[..num_chunks].to_vec())
+                .elements[0];
+            builder.mul_add(chunk_end_is[4 * num_chunks - 1], hash, key)
+        });
+
+        // SHA-256: 0x80 after the key, and the length in bits in the last word of the block
+        let sha_bytes: Vec<Target> = (0..padded_len)
+            .map(|i| builder.mul_const_add(F::from_canonical_u64(0x80), len_is[i].target, bytes[i]))
+            .collect();
+        let mut block = [zero; 16];
+        for (word, chunk) in block.iter_mut().zip(sha_bytes.chunks(4)) {
+            *word = Self::be_word(builder, chunk);
+        }
+        block[15] = builder.mul_const(F::from_canonical_u64(8), key_len);
+        let digest = builder.sha256_block(&block);
+
+        // the digest as a 256-bit big-endian integer, shifted right by 8 bits, in LE u32 limbs
+        let limbs = (0..FQ_LIMBS)
+            .map(|j| {
+                let lower = &digest[FQ_LIMBS - 1 - j][8..];
+                let upper = match j {
+                    7 => &[][..],
+                    _ => &digest[FQ_LIMBS - 2 - j][..8],
+                };
+                U32Target(builder.le_sum(lower.iter().chain(upper)))
+            })
+            .collect();
+        (key, BigUintTarget { limbs })
+    }
+
+    /// u32 of 4 big-endian bytes
+    fn be_word(builder: &mut CircuitBuilder<F, D>, bytes: &[Target]) -> Target {
+        bytes.iter().fold(builder.zero(), |acc, byte| {
+            builder.mul_const_add(F::from_canonical_u64(256), acc, *byte)
+        })
+    }
+
+    /// Sets the witness of the slot from the given entry, or as an empty slot if `None`.
+    pub fn set_witness(
+        &self,
+        pw: &mut PartialWitness<F>,
+        entry: Option<(&str, &PodValue)>,
+    ) -> Result<()> {
+        let (key, kind, value) = match entry {
+            Some((key, value)) => {
+                if key.len() > MAX_POD1_KEY_LEN {
+                    return Err(anyhow!(
+                        "POD1 key {} is longer than {} bytes.",
+                        key,
+                        MAX_POD1_KEY_LEN
+                    ));
+                }
+                let limbs = match ScalarOrVec::from(value.clone()) {
+                    ScalarOrVec::Scalar(x) => vec![x],
+                    // the low and high limbs of the i64's u64, from its statement limbs
+                    ScalarOrVec::Vector(v) if ValueKind::of(value) == ValueKind::Int => {
+                        vec![v[2], v[1]]
+                    }
+                    ScalarOrVec::Vector(v) => v,
+                };
+                (
+                    key.as_bytes(),
+                    ValueKind::of(value),
+                    BigUint::new(limbs.iter().map(|l| l.0 as u32).collect()),
+                )
+            }
+            None => (&[][..], ValueKind::Hashed, BigUint::from(0u32)),
+        };
 
-pub struct IntroducerCircuit {}
+        pw.set_bool_target(self.present, entry.is_some())?;
+        for (i, byte_target) in self.key_bytes.iter().enumerate() {
+            let byte = key.get(i).copied().unwrap_or(0);
+            pw.set_target(*byte_target, F::from_canonical_u8(byte))?;
+        }
+        pw.set_target(self.key_len, F::from_canonical_usize(key.len()))?;
+        pw.set_biguint_target(&self.value, &value);
+        pw.set_bool_target(self.is_int, kind == ValueKind::Int)?;
+        pw.set_bool_target(self.is_bool, kind == ValueKind::Bool)?;
+        pw.set_bool_target(self.is_cryptographic, kind == ValueKind::Cryptographic)?;
+        pw.set_bool_target(self.is_hashed, kind == ValueKind::Hashed)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct IntroducerTargets {
+    pub entries: Vec<Pod1EntryTarget>,
+    pub signature: EddsaSignatureTarget,
+    pub signer_pk: EddsaPublicKeyTarget,
+    /// for each output statement, its index in the list of statements computed in-circuit
+    pub statement_indices: Vec<Target>,
+}
 
 /// IntroducerCircuit defines the circuit whose plonky2 proof is verified in the RecursiveCircuit
 /// (1-level recursion). This is, the POD1-Introducer circuit.
-impl IntroducerCircuitTrait for IntroducerCircuit {
-    type Input = (); // TODO
-    type Targets = ();
+///
+/// It proves that the given POD1 (`parcnet_pod::Pod`) is signed, ie. that its content ID (lean
+/// Poseidon IMT over its entries) carries a valid EdDSA signature, and registers as public inputs
+/// the NS statements of the payload computed by `POD::pod1_payload`: one `VALUEOF` statement per
+/// entry, one for the signer's public key, and `NONE` statements as padding.
+///
+/// NS: number of statements, so the POD1 can have up to NS-1 entries
+/// VL: vector length, at least 8 so that BN254 field elements fit
+pub struct IntroducerCircuit<const NS: usize, const VL: usize> {}
+
+impl<const NS: usize, const VL: usize> IntroducerCircuit<NS, VL> {
+    /// Proves the given POD1 with the IntroducerCircuit
+    pub fn prove(circuit_data: &CircuitData<F, C, D>, pod: &Pod) -> Result<PlonkyProof> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::new(config);
+        let targets = Self::add_targets(&mut builder)?;
+        // pad min gates, as in `circuit_data`
+        while builder.num_gates() < 1 << 12 {
+            builder.add_gate(NoopGate, vec![]);
+        }
+
+        let mut pw = PartialWitness::new();
+        Self::set_targets(&mut pw, &targets, pod)?;
+
+        let proof = circuit_data.prove(pw)?;
+        Ok(proof.proof)
+    }
+
+    /// Hash of a vector value padded to length VL, as in `Entry::pad_if_vec`
+    fn vector_value(builder: &mut CircuitBuilder<F, D>, limbs: &[Target]) -> Target {
+        let padding = vec![limbs[0]; VL - limbs.len()];
+        builder
+            .hash_n_to_hash_no_pad::<PoseidonHash>([limbs.to_vec(), padding].concat())
+            .elements[0]
+    }
+
+    /// Returns the statement value of the entry and the BN254 hash of its value as used in the
+    /// content ID.
+    fn entry_values(
+        builder: &mut CircuitBuilder<F, D>,
+        entry: &Pod1EntryTarget,
+    ) -> (Target, BigUintTarget) {
+        // exactly one kind
+        let kinds = [
+            entry.is_int,
+            entry.is_bool,
+            entry.is_cryptographic,
+            entry.is_hashed,
+        ];
+        let num_kinds = builder.add_many(kinds.iter().map(|k| k.target));
+        builder.assert_one(num_kinds);
+
+        let limbs: Vec<Target> = entry.value.limbs.iter().map(|l| l.0).collect();
+
+        // pod2 statement value. An int is held as in `ScalarOrVec::from(i64)`: its sign
+        // extension limb followed by its high and low limbs.
+        let sign = builder.split_le(limbs[1], 32)[31];
+        let sign_limb = builder.mul_const(F::from_canonical_u32(u32::MAX), sign.target);
+        let int_value = Self::vector_value(builder, &[sign_limb, limbs[1], limbs[0]]);
+        let vec_value = Self::vector_value(builder, &limbs);
+        let value = builder.select(entry.is_int, int_value, vec_value);
+        let value = builder.select(entry.is_bool, limbs[0], value);
+
+        // BN254 field element. An int n is embedded as n mod p, ie. the u64 limbs plus
+        // p - 2^64 if the sign bit is set.
+        let offset = builder.constant_biguint(&(BigUint::jubjub_p() - (BigUint::from(1u32) << 64)));
+        let offset = builder.mul_biguint_by_bool(&offset, sign);
+        let int_fq = BigUintTarget {
+            limbs: entry.value.limbs[..2].to_vec(),
+        };
+        let int_fq = builder.add_biguint(&int_fq, &offset);
+        let bool_fq = BigUintTarget {
+            limbs: vec![entry.value.limbs[0]],
+        };
+        let fq = builder.multiplex_biguint(&entry.value, &int_fq, entry.is_int);
+        let fq = builder.multiplex_biguint(&fq, &bool_fq, entry.is_bool);
+
+        // values passed in by limbs must be canonical, otherwise two different statement values
+        // would have the same content ID
+        let max = builder.constant_biguint(&(BigUint::jubjub_p() - 1u32));
+        let canonical = builder.cmp_biguint(&entry.value, &max);
+        let by_limbs = builder.or(entry.is_cryptographic, entry.is_hashed);
+        assert_one_if_enabled(builder, canonical.target, &by_limbs);
+
+        let fq_hash = builder.poseidon_bn254(&[JubjubFieldTarget(fq)]);
+        let value_hash = builder.multiplex_biguint(&fq_hash.0, &entry.value, entry.is_hashed);
+
+        (value, value_hash)
+    }
+
+    /// Lean IMT (as in parcnet_pod) over the leaves flagged as present, which must form a
+    /// prefix. A node without sibling is promoted to the next level.
+    fn lean_imt_root(
+        builder: &mut CircuitBuilder<F, D>,
+        leaves: Vec<(BigUintTarget, BoolTarget)>,
+    ) -> BigUintTarget {
+        let mut level = leaves;
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [(a, a_present), (b, b_present)] => {
+                        let h = builder.poseidon_bn254(&[
+                            JubjubFieldTarget(a.clone()),
+                            JubjubFieldTarget(b.clone()),
+                        ]);
+                        (builder.multiplex_biguint(a, &h.0, *b_present), *a_present)
+                    }
+                    [(a, a_present)] => (a.clone(), *a_present),
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+        level[0].0.clone()
+    }
+
+    fn select_statement(
+        builder: &mut CircuitBuilder<F, D>,
+        b: BoolTarget,
+        x: &StatementTarget,
+        y: &StatementTarget,
+    ) -> StatementTarget {
+        StatementTarget::from_targets(
+            &std::iter::zip(x.to_targets(), y.to_targets())
+                .map(|(x, y)| builder.select(b, x, y))
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl<const NS: usize, const VL: usize> IntroducerCircuitTrait for IntroducerCircuit<NS, VL> {
+    type Input = Pod;
+    type Targets = IntroducerTargets;
 
     /// return dummy inputs that will satisfy the circuit. This is used to generate the
     /// dummy_proof.
-    fn dummy_inputs() -> Result<Self::Input> {
-        todo!();
+    fn dummy_inputs() -> crate::Result<Self::Input> {
+        Ok(create_pod(&[0u8; 32], pod_entries!["_dummy" => 0])?)
     }
 
     /// set up the circuit logic
-    fn add_targets(builder: &mut CircuitBuilder<F, D>) -> Result<Self::Targets> {
-        todo!();
+    fn add_targets(builder: &mut CircuitBuilder<F, D>) -> crate::Result<Self::Targets> {
+        if NS < 2 {
+            return Err(Error::ProofGeneration(format!(
+                "The IntroducerCircuit needs NS >= 2, got NS={}",
+                NS
+            )));
+        }
+        if VL < FQ_LIMBS {
+            return Err(Error::ProofGeneration(format!(
+                "The IntroducerCircuit needs VL >= {}, got VL={}",
+                FQ_LIMBS, VL
+            )));
+        }
+
+        // one statement is reserved for the signer's public key
+        let entries: Vec<Pod1EntryTarget> = (0..NS - 1)
+            .map(|_| Pod1EntryTarget::new_virtual(builder))
+            .collect();
+        let signature = EddsaSignatureTarget::new_virtual(builder);
+        let signer_pk = EddsaPublicKeyTarget::new_virtual(builder);
+
+        // a POD1 has at least one entry, and its entries are in the first slots
+        builder.assert_one(entries[0].present.target);
+        for pair in entries.windows(2) {
+            let prev_absent = builder.not(pair[0].present);
+            let gap = builder.and(pair[1].present, prev_absent);
+            builder.assert_zero(gap.target);
+        }
+
+        // entry statements and content ID leaves
+        let none_statement = StatementTarget::none(builder);
+        let mut statements: Vec<StatementTarget> = vec![];
+        let mut leaves: Vec<(BigUintTarget, BoolTarget)> = vec![];
+        for entry in entries.iter() {
+            let (value, value_hash) = Self::entry_values(builder, entry);
+            let origin = OriginTarget::auto(builder, GadgetID::INTRODUCER);
+            // only booleans are scalars
+            let is_vector = builder.not(entry.is_bool);
+            let statement = StatementTarget::value_of(builder, origin, entry.key, value, is_vector);
+            statements.push(Self::select_statement(
+                builder,
+                entry.present,
+                &statement,
+                &none_statement,
+            ));
+            leaves.push((entry.key_hash.clone(), entry.present));
+            leaves.push((value_hash, entry.present));
+        }
+
+        // the content ID is the signed message
+        let msg = MessageHashTarget {
+            m: Self::lean_imt_root(builder, leaves),
+        };
+        builder.constrain_eddsa::<C>(&signature, &msg, &signer_pk);
+
+        // signer statement, with the value of `PodValue::EdDSAPublicKey`
+        let signer_hash = builder.poseidon_bn254(&[signer_pk.a.x.clone(), signer_pk.a.y.clone()]);
+        let signer_limbs: Vec<Target> = signer_hash.0.limbs.iter().map(|l| l.0).collect();
+        let signer_value = Self::vector_value(builder, &signer_limbs);
+        let signer_key = builder.constant(hash_string_to_field(POD1_SIGNER_KEY));
+        let origin = OriginTarget::auto(builder, GadgetID::INTRODUCER);
+        let is_vector = builder._true();
+        let signer_statement =
+            StatementTarget::value_of(builder, origin, signer_key, signer_value, is_vector);
+        statements.insert(0, signer_statement);
+
+        // expose the statements in the order of the payload, ie. sorted by name
+        let zero = builder.zero();
+        let statement_indices: Vec<Target> = (0..NS)
+            .map(|_| {
+                let index = builder.add_virtual_target();
+                let statement = statement_matrix_ref(builder, &[statements.clone()], zero, index)?;
+                statement.register_as_public_input(builder);
+                Ok(index)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        // the indices are less than NS, so distinct indices expose each statement exactly once
+        for (i, index) in statement_indices.iter().enumerate() {
+            for other_index in &statement_indices[..i] {
+                let is_equal = builder.is_equal(*index, *other_index);
+                builder.assert_zero(is_equal.target);
+            }
+        }
+
+        Ok(IntroducerTargets {
+            entries,
+            signature,
+            signer_pk,
+            statement_indices,
+        })
     }
 
-    /// set the actual witness values for the current instance of the circuit. Returns a Vec<F>
-    /// containing the values that will be set as public inputs
+    /// set the actual witness values for the current instance of the circuit.
     fn set_targets(
         pw: &mut PartialWitness<F>,
         targets: &Self::Targets,
         input: &Self::Input,
-    ) -> Result<()> {
-        todo!();
+    ) -> crate::Result<()> {
+        Self::set_pod1_targets(pw, targets, input)?;
+        for (index_target, index) in targets
+            .statement_indices
+            .iter()
+            .zip(Self::statement_indices(input)?)
+        {
+            pw.set_target(*index_target, F::from_canonical_usize(index))?;
+        }
+        Ok(())
+    }
+}
+
+impl<const NS: usize, const VL: usize> IntroducerCircuit<NS, VL> {
+    /// Sets the witness of the entries, signature and signer of the POD1.
+    fn set_pod1_targets(
+        pw: &mut PartialWitness<F>,
+        targets: &IntroducerTargets,
+        input: &Pod,
+    ) -> crate::Result<()> {
+        let pod_entries = input.entries();
+        if pod_entries.len() > NS - 1 {
+            return Err(Error::CapacityExceeded {
+                what: "POD1 entries".to_string(),
+                max: NS - 1,
+            });
+        }
+
+        for (i, entry_target) in targets.entries.iter().enumerate() {
+            let entry = pod_entries
+                .get_index(i)
+                .map(|(key, value)| (key.as_str(), value));
+            entry_target.set_witness(pw, entry)?;
+        }
+        targets.signature.set_witness(pw, &input.signature())?;
+        targets
+            .signer_pk
+            .set_witness(pw, &input.signer_public_key())?;
+        Ok(())
+    }
+
+    /// Indices of the in-circuit statements exposed in the order of the payload. The NONE
+    /// padding statements are equal, but each of them is exposed once.
+    fn statement_indices(input: &Pod) -> Result<Vec<usize>> {
+        let pod_entries = input.entries();
+        // in-circuit statements: signer, entries in slot order, NONE padding
+        let payload = POD::pod1_payload::<NS, VL>(input)?;
+        let statement_fields = |key: &str| -> Result<Vec<F>> {
+            payload
+                .statements_map
+                .get(&format!("VALUEOF:{}", key))
+                .map(|s| s.to_fields())
+                .ok_or(anyhow!("Statement VALUEOF:{} missing from payload", key))
+        };
+        let circuit_statements: Vec<Vec<F>> = [
+            vec![statement_fields(POD1_SIGNER_KEY)?],
+            pod_entries
+                .keys()
+                .map(|key| statement_fields(key))
+                .collect::<Result<Vec<_>>>()?,
+            vec![Statement::None.to_fields(); NS - 1 - pod_entries.len()],
+        ]
+        .concat();
+
+        let mut exposed = vec![false; circuit_statements.len()];
+        payload
+            .statements_list
+            .iter()
+            .map(|(_, statement)| {
+                let statement_fields = statement.to_fields();
+                let index = (0..circuit_statements.len())
+                    .find(|i| !exposed[*i] && circuit_statements[*i] == statement_fields)
+                    .ok_or(anyhow!("Statement {:?} not found in circuit", statement))?;
+                exposed[index] = true;
+                Ok(index)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::{anyhow, Result};
+    use parcnet_pod::{
+        pod::{create_pod, PodValue},
+        pod_entries,
+    };
+    use plonky2::field::types::Field;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use std::iter::zip;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    use super::{IntroducerCircuit, Pod1EntryTarget, MAX_POD1_KEY_LEN};
+    use crate::pod::util::hash_string_to_field;
+    use crate::pod::POD;
+    use crate::recursion::IntroducerCircuitTrait;
+    use crate::signature::biguint::CircuitBuilderBiguint;
+    use crate::signature::jubjubfield::ark_to_biguint;
+    use crate::{C, F};
+
+    #[test]
+    fn test_pod1_key_hashes() -> Result<()> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::new(config);
+        let mut pw = PartialWitness::<F>::new();
+
+        // keys ending at each offset of a u32 chunk, and of the maximum length
+        let long_key = "k".repeat(MAX_POD1_KEY_LEN);
+        let keys = ["", "a", "ab", "abc", "abcd", "owner", &long_key];
+        for key in keys {
+            let entry = Pod1EntryTarget::new_virtual(&mut builder);
+            entry.set_witness(&mut pw, Some((key, &PodValue::Int(1))))?;
+            let expected_key = builder.constant(hash_string_to_field(key));
+            builder.connect(entry.key, expected_key);
+            let key_hash = PodValue::String(key.to_string())
+                .hash()
+                .map_err(|e| anyhow!("{:?}", e))?;
+            let expected_key_hash = builder.constant_biguint(&ark_to_biguint(&key_hash));
+            builder.connect_biguint(&entry.key_hash, &expected_key_hash);
+        }
+        let entry = Pod1EntryTarget::new_virtual(&mut builder);
+        let too_long_key = "k".repeat(MAX_POD1_KEY_LEN + 1);
+        assert!(entry
+            .set_witness(&mut pw, Some((&too_long_key, &PodValue::Int(1))))
+            .is_err());
+        entry.set_witness(&mut pw, None)?;
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    #[test]
+    fn test_introducer_circuit() -> Result<()> {
+        const NS: usize = 5;
+        const VL: usize = 8;
+
+        let pod = create_pod(
+            &[0u8; 32],
+            pod_entries![
+                "speed" => 5,
+                "jump" => -10,
+                "owner" => "gub"
+            ],
+        )?;
+
+        let circuit_data = IntroducerCircuit::<NS, VL>::circuit_data()?;
+        let introduced_pod = POD::introduce_pod1::<NS, VL>(pod, &circuit_data)?;
+
+        // the public inputs of the proof are the fields of the payload statements
+        assert!(introduced_pod.verify::<0, 0, 1, NS, VL>()?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_introducer_statement_indices() -> Result<()> {
+        const NS: usize = 5;
+        const VL: usize = 8;
+
+        let pod = create_pod(&[0u8; 32], pod_entries!["speed" => 5, "jump" => -10])?;
+        let mut builder = CircuitBuilder::new(CircuitConfig::standard_recursion_config());
+        let targets = IntroducerCircuit::<NS, VL>::add_targets(&mut builder)?;
+        let data = builder.build::<C>();
+        let prove = |indices: &[usize]| -> Result<()> {
+            let mut pw = PartialWitness::new();
+            IntroducerCircuit::<NS, VL>::set_pod1_targets(&mut pw, &targets, &pod)?;
+            for (index_target, index) in zip(&targets.statement_indices, indices) {
+                pw.set_target(*index_target, F::from_canonical_usize(*index))?;
+            }
+            let proof = catch_unwind(AssertUnwindSafe(|| data.prove(pw)))
+                .map_err(|_| anyhow!("The prover panicked"))??;
+            data.verify(proof)
+        };
+
+        // the payload has the signer, 2 entries and 2 NONE padding statements
+        let indices = IntroducerCircuit::<NS, VL>::statement_indices(&pod)?;
+        prove(&indices)?;
+        // a statement can't be exposed twice, eg. to hide another one
+        let mut duplicated = indices.clone();
+        duplicated[1] = duplicated[0];
+        assert!(prove(&duplicated).is_err());
+        // nor be dropped for an index out of range
+        let mut dropped = indices.clone();
+        dropped[1] = NS;
+        assert!(prove(&dropped).is_err());
+
+        Ok(())
     }
 }
@@ -1,11 +1,15 @@
 use core::fmt;
 use serde::{Deserialize, Serialize};
 
+pub mod compression;
+pub mod dry_run;
 pub mod introducer;
 pub mod opexecutor;
 pub mod plonky_pod;
 pub mod schnorr_pod;
 
+pub use compression::CompressionCircuit;
+pub use dry_run::DryRunError;
 pub use introducer::IntroducerCircuit;
 pub use opexecutor::OpExecutorGadget;
 pub use plonky_pod::PlonkyButNotPlonkyGadget;
@@ -15,9 +19,12 @@ pub use schnorr_pod::SchnorrPODGadget;
 pub enum GadgetID {
     #[default]
     NONE = 0,
-    SCHNORR16 = 1,
+    SCHNORR16 = 1, // legacy, insecure
     ORACLE = 2,
-    PLONKY = 3, // plonky2 recursive proof
+    PLONKY = 3,            // plonky2 recursive proof
+    INTRODUCER = 4,        // POD1-Introducer proof
+    ECSCHNORR = 5,         // Schnorr signature over ecgfp5
+    COMPRESSED_PLONKY = 6, // plonky2 proof re-proved in a size-optimised config
 }
 
 impl fmt::Display for GadgetID {
@@ -27,6 +34,9 @@ impl fmt::Display for GadgetID {
             GadgetID::SCHNORR16 => write!(f, "SCHNORR16"),
             GadgetID::ORACLE => write!(f, "ORACLE"),
             GadgetID::PLONKY => write!(f, "PLONKY"),
+            GadgetID::INTRODUCER => write!(f, "INTRODUCER"),
+            GadgetID::ECSCHNORR => write!(f, "ECSCHNORR"),
+            GadgetID::COMPRESSED_PLONKY => write!(f, "COMPRESSED_PLONKY"),
         }
     }
 }
@@ -58,7 +58,7 @@ impl<const NP: usize, const NS: usize, const VL: usize> OpsExecutorTrait
         [StatementTarget; NS], // registered as public input
     );
 
-    fn add_targets(builder: &mut CircuitBuilder<F, D>) -> Result<Self::Targets> {
+    fn add_targets(builder: &mut CircuitBuilder<F, D>) -> crate::Result<Self::Targets> {
         let statement_list_vec_target: [[StatementTarget; NS]; NP] = array::from_fn(|_| {
             array::from_fn::<StatementTarget, NS, _>(|_| StatementTarget::new_virtual(builder))
         });
@@ -132,7 +132,7 @@ impl<const NP: usize, const NS: usize, const VL: usize> OpsExecutorTrait
         targets: &Self::Targets,
         input: &Self::Input,
         output: &Self::Output,
-    ) -> Result<Vec<F>> {
+    ) -> crate::Result<Vec<F>> {
         // Set POD targets.
         // TODO: Connect these to the POD targets that go into the inner and recursion circuits instead!
         zip(&targets.0, &input.0.pods_list).try_for_each(|(s_targets, (_, pod))| {
@@ -167,19 +167,24 @@ mod tests {
     use itertools::Itertools;
     use plonky2::{
         field::goldilocks_field::GoldilocksField,
+        hash::poseidon::PoseidonHash,
         iop::witness::PartialWitness,
-        plonk::{circuit_builder::CircuitBuilder, circuit_data::CircuitConfig},
+        plonk::{circuit_builder::CircuitBuilder, circuit_data::CircuitConfig, config::Hasher},
     };
 
     use crate::{
         pod::{
             entry::Entry,
+            merkle::{MerkleTree, SortedMerkleTree},
             operation::{OpList, Operation as Op, OperationCmd as OpCmd},
-            statement::StatementRef,
-            GPGInput, POD,
+            owner::OwnerSecretKey,
+            predicate::{CustomPredicate, StatementTemplate},
+            statement::{Statement, StatementRef},
+            value::ScalarOrVec,
+            GPGInput, Oracle, POD,
         },
         recursion::OpsExecutorTrait,
-        signature::schnorr::SchnorrSecretKey,
+        signature::ecschnorr::EcSchnorrSecretKey,
         C,
     };
 
@@ -189,7 +194,7 @@ mod tests {
         // Input Schnorr PODs. For now, they must all have the same number
         // of statements.
         const NS: usize = 3;
-        const VL: usize = 3;
+        const VL: usize = 4;
 
         let schnorr_pod1_name = "Test POD 1".to_string();
         let schnorr_pod1 = POD::execute_schnorr_gadget::<NS, VL>(
@@ -197,7 +202,7 @@ mod tests {
                 Entry::new_from_scalar("s1", GoldilocksField(55)),
                 Entry::new_from_scalar("s2", GoldilocksField(56)),
             ],
-            &SchnorrSecretKey { sk: 27 },
+            &EcSchnorrSecretKey::from(27),
         )?;
         let schnorr_pod2_name = "Test POD 2".to_string();
         let schnorr_pod2 = POD::execute_schnorr_gadget::<NS, VL>(
@@ -205,7 +210,7 @@ mod tests {
                 Entry::new_from_scalar("s3", GoldilocksField(57)),
                 Entry::new_from_scalar("s4", GoldilocksField(55)),
             ],
-            &SchnorrSecretKey { sk: 29 },
+            &EcSchnorrSecretKey::from(29),
         )?;
 
         let schnorr_pod3_name = "Test POD 3".to_string();
@@ -214,7 +219,7 @@ mod tests {
                 Entry::new_from_scalar("s0", GoldilocksField(57)),
                 Entry::new_from_scalar("s-1", GoldilocksField(55)),
             ],
-            &SchnorrSecretKey { sk: 24 },
+            &EcSchnorrSecretKey::from(24),
         )?;
 
         let schnorr_pod4_name = "Test POD 4".to_string();
@@ -226,7 +231,7 @@ mod tests {
                 ),
                 Entry::new_from_scalar("what", GoldilocksField(5)),
             ],
-            &SchnorrSecretKey { sk: 20 },
+            &EcSchnorrSecretKey::from(20),
         )?;
 
         let schnorr_pod5_name = "Test POD 5".to_string();
@@ -235,7 +240,7 @@ mod tests {
                 Entry::new_from_scalar("who", GoldilocksField(111)),
                 Entry::new_from_scalar("what", GoldilocksField(55 * 57)),
             ],
-            &SchnorrSecretKey { sk: 20 },
+            &EcSchnorrSecretKey::from(20),
         )?;
 
         let schnorr_pod6_name = "Test POD 6".to_string();
@@ -247,11 +252,85 @@ mod tests {
                 ),
                 Entry::new_from_scalar("why", GoldilocksField(0)),
             ],
-            &SchnorrSecretKey { sk: 20 },
+            &EcSchnorrSecretKey::from(20),
+        )?;
+
+        // Values beyond 32 bits: i64 vectors, eg. a negative value and a
+        // timestamp in ms, and scalars.
+        let schnorr_pod7_name = "Test POD 7".to_string();
+        let schnorr_pod7 = POD::execute_schnorr_gadget::<NS, VL>(
+            &[
+                Entry {
+                    key: "t1".to_string(),
+                    value: ScalarOrVec::from(-5i64),
+                },
+                Entry {
+                    key: "t2".to_string(),
+                    value: ScalarOrVec::from(1_700_000_000_000i64),
+                },
+            ],
+            &EcSchnorrSecretKey::from(21),
+        )?;
+
+        let schnorr_pod8_name = "Test POD 8".to_string();
+        let schnorr_pod8 = POD::execute_schnorr_gadget::<NS, VL>(
+            &[
+                Entry::new_from_scalar("b1", GoldilocksField(1 << 40)),
+                Entry::new_from_scalar("b2", GoldilocksField((1 << 40) + (1 << 32))),
+            ],
+            &EcSchnorrSecretKey::from(22),
+        )?;
+
+        // Root of a set of 1025 members, among which b1 = 1024 << 30, and
+        // of a sorted set which b2 is not a member of.
+        let allowlist = MerkleTree::new(
+            &(0..=1024)
+                .map(|i| GoldilocksField(i << 30))
+                .collect::<Vec<_>>(),
+        )?;
+        let revoked = SortedMerkleTree::new(
+            &(1..1000)
+                .map(|i| GoldilocksField(i << 33))
+                .collect::<Vec<_>>(),
+        )?;
+        let schnorr_pod9_name = "Test POD 9".to_string();
+        let schnorr_pod9 = POD::execute_schnorr_gadget::<NS, VL>(
+            &[
+                Entry::new_from_scalar("allowlist", allowlist.root()),
+                Entry::new_from_scalar("revoked", revoked.root()),
+            ],
+            &EcSchnorrSecretKey::from(23),
+        )?;
+
+        // Commitment to s1 and s2, and owner key.
+        let owner_secret = OwnerSecretKey::new([1, 2, 3, 777].map(GoldilocksField));
+        let schnorr_pod10_name = "Test POD 10".to_string();
+        let schnorr_pod10 = POD::execute_schnorr_gadget::<NS, VL>(
+            &[
+                Entry::new_from_vec(
+                    "commitment",
+                    PoseidonHash::hash_no_pad(&[GoldilocksField(55), GoldilocksField(56)])
+                        .elements
+                        .to_vec(),
+                ),
+                Entry::new_from_vec("owner", owner_secret.public_key().elements.to_vec()),
+            ],
+            &EcSchnorrSecretKey::from(26),
+        )?;
+
+        // s3 - s1 and s2 mod s1.
+        let schnorr_pod11_name = "Test POD 11".to_string();
+        let schnorr_pod11 = POD::execute_schnorr_gadget::<NS, VL>(
+            &[
+                Entry::new_from_scalar("difference", GoldilocksField(2)),
+                Entry::new_from_scalar("remainder", GoldilocksField(1)),
+            ],
+            &EcSchnorrSecretKey::from(25),
         )?;
 
         let oracle_pod_name = "Oracle POD".to_string();
         let oracle_pod = POD::execute_oracle_gadget(
+            &Oracle::random(&mut rand::thread_rng()),
             &GPGInput::new(
                 [
                     (schnorr_pod4_name.clone(), schnorr_pod4.clone()),
@@ -288,10 +367,15 @@ mod tests {
             (schnorr_pod4_name.clone(), schnorr_pod4),
             (schnorr_pod5_name.clone(), schnorr_pod5),
             (schnorr_pod6_name.clone(), schnorr_pod6),
+            (schnorr_pod7_name.clone(), schnorr_pod7),
+            (schnorr_pod8_name.clone(), schnorr_pod8),
+            (schnorr_pod9_name.clone(), schnorr_pod9),
+            (schnorr_pod10_name.clone(), schnorr_pod10),
+            (schnorr_pod11_name.clone(), schnorr_pod11),
             (oracle_pod_name.clone(), oracle_pod),
         ];
 
-        const NP: usize = 7;
+        const NP: usize = 12;
         if pods_list.len() != NP {
             return Err(anyhow!(
                 "Number of PODs in list must be equal to NP ({})!",
@@ -318,7 +402,7 @@ mod tests {
             ),
             // VALUEOF:nono
             OpCmd::new(
-                Op::NewEntry(Entry::new_from_scalar("what", GoldilocksField(23))),
+                Op::NewEntry(Entry::new_from_scalar("nono", GoldilocksField(23))),
                 "nono",
             ),
             // EQUAL:op2
@@ -377,6 +461,141 @@ mod tests {
                 ),
                 "cadadr",
             ),
+            OpCmd::new(
+                Op::GteFromEntries(
+                    StatementRef::new(&schnorr_pod2_name, "VALUEOF:s4"),
+                    StatementRef::new(&schnorr_pod1_name, "VALUEOF:s1"),
+                ),
+                "gte",
+            ),
+            OpCmd::new(
+                Op::LteFromEntries(
+                    StatementRef::new(&schnorr_pod1_name, "VALUEOF:s1"),
+                    StatementRef::new(&schnorr_pod2_name, "VALUEOF:s3"),
+                ),
+                "lte",
+            ),
+            OpCmd::new(
+                Op::InRangeFromEntries(
+                    StatementRef::new(&schnorr_pod1_name, "VALUEOF:s2"),
+                    StatementRef::new(&schnorr_pod1_name, "VALUEOF:s1"),
+                    StatementRef::new(&schnorr_pod2_name, "VALUEOF:s3"),
+                ),
+                "inrange",
+            ),
+            OpCmd::new(
+                Op::GtFromEntries(
+                    StatementRef::new(&schnorr_pod7_name, "VALUEOF:t2"),
+                    StatementRef::new(&schnorr_pod7_name, "VALUEOF:t1"),
+                ),
+                "gt64",
+            ),
+            OpCmd::new(
+                Op::LtFromEntries(
+                    StatementRef::new(&schnorr_pod7_name, "VALUEOF:t1"),
+                    StatementRef::new(&schnorr_pod7_name, "VALUEOF:t2"),
+                ),
+                "lt64",
+            ),
+            OpCmd::new(
+                Op::GteFromEntries(
+                    StatementRef::new(&schnorr_pod7_name, "VALUEOF:t1"),
+                    StatementRef::new(&schnorr_pod7_name, "VALUEOF:t1"),
+                ),
+                "gte64",
+            ),
+            OpCmd::new(
+                Op::GtFromEntries(
+                    StatementRef::new(&schnorr_pod8_name, "VALUEOF:b2"),
+                    StatementRef::new(&schnorr_pod8_name, "VALUEOF:b1"),
+                ),
+                "gtbig",
+            ),
+            OpCmd::new(
+                Op::LtFromEntries(
+                    StatementRef::new(&schnorr_pod8_name, "VALUEOF:b1"),
+                    StatementRef::new(&schnorr_pod8_name, "VALUEOF:b2"),
+                ),
+                "ltbig",
+            ),
+            OpCmd::new(
+                Op::InRangeFromEntries(
+                    StatementRef::new(&schnorr_pod8_name, "VALUEOF:b1"),
+                    StatementRef::new(&schnorr_pod8_name, "VALUEOF:b1"),
+                    StatementRef::new(&schnorr_pod8_name, "VALUEOF:b2"),
+                ),
+                "inrangebig",
+            ),
+            OpCmd::new(
+                Op::ContainedInRootFromEntries(
+                    StatementRef::new(&schnorr_pod8_name, "VALUEOF:b1"),
+                    StatementRef::new(&schnorr_pod9_name, "VALUEOF:allowlist"),
+                    allowlist
+                        .prove(GoldilocksField(1 << 40))
+                        .ok_or(anyhow!("b1 is not in the allowlist"))?,
+                ),
+                "member",
+            ),
+            OpCmd::new(
+                Op::NotContainedInRootFromEntries(
+                    StatementRef::new(&schnorr_pod8_name, "VALUEOF:b2"),
+                    StatementRef::new(&schnorr_pod9_name, "VALUEOF:revoked"),
+                    revoked
+                        .prove_non_membership(GoldilocksField((1 << 40) + (1 << 32)))
+                        .ok_or(anyhow!("b2 is revoked"))?,
+                ),
+                "notrevoked",
+            ),
+            OpCmd::new(
+                Op::HashOf(
+                    StatementRef::new(&schnorr_pod10_name, "VALUEOF:commitment"),
+                    vec![
+                        StatementRef::new(&schnorr_pod1_name, "VALUEOF:s1"),
+                        StatementRef::new(&schnorr_pod1_name, "VALUEOF:s2"),
+                    ],
+                ),
+                "opening",
+            ),
+            OpCmd::new(
+                Op::NullifierFromOwnerKey(
+                    StatementRef::new(&schnorr_pod10_name, "VALUEOF:owner"),
+                    StatementRef::new(&schnorr_pod1_name, "VALUEOF:s1"),
+                    owner_secret,
+                ),
+                "ticket",
+            ),
+            OpCmd::new(
+                Op::DifferenceOf(
+                    StatementRef::new(&schnorr_pod11_name, "VALUEOF:difference"),
+                    StatementRef::new(&schnorr_pod2_name, "VALUEOF:s3"),
+                    StatementRef::new(&schnorr_pod1_name, "VALUEOF:s1"),
+                ),
+                "diff",
+            ),
+            OpCmd::new(
+                Op::MinOf(
+                    StatementRef::new(&schnorr_pod2_name, "VALUEOF:s4"),
+                    StatementRef::new(&schnorr_pod1_name, "VALUEOF:s1"),
+                    StatementRef::new(&schnorr_pod2_name, "VALUEOF:s3"),
+                ),
+                "min",
+            ),
+            OpCmd::new(
+                Op::QuotientOf(
+                    StatementRef::new(&schnorr_pod2_name, "VALUEOF:s3"),
+                    StatementRef::new(&schnorr_pod5_name, "VALUEOF:what"),
+                    StatementRef::new(&schnorr_pod1_name, "VALUEOF:s1"),
+                ),
+                "div",
+            ),
+            OpCmd::new(
+                Op::RemainderOf(
+                    StatementRef::new(&schnorr_pod11_name, "VALUEOF:remainder"),
+                    StatementRef::new(&schnorr_pod1_name, "VALUEOF:s2"),
+                    StatementRef::new(&schnorr_pod1_name, "VALUEOF:s1"),
+                ),
+                "mod",
+            ),
         ];
         let op_lists = ops
             .iter()
@@ -409,7 +628,7 @@ mod tests {
             let config = CircuitConfig::standard_recursion_config();
             let mut builder = CircuitBuilder::<F, D>::new(config);
             let mut pw: PartialWitness<F> = PartialWitness::new();
-            let oracle_pod = POD::execute_oracle_gadget(&gpg_input, &op_list.0)?;
+            let output_payload = POD::execute_ops(&gpg_input, &op_list.0)?;
 
             // circuit test
             let targets = OpExecutorGadget::<NP, NS, VL>::add_targets(&mut builder)?;
@@ -417,7 +636,7 @@ mod tests {
                 &mut pw,
                 &targets,
                 &(gpg_input.clone(), op_list),
-                &oracle_pod.payload.statements_list,
+                &output_payload.statements_list,
             )?;
 
             let data = builder.build::<C>();
@@ -425,4 +644,611 @@ mod tests {
             data.verify(proof.clone())
         })
     }
+
+    #[test]
+    fn test_op_executor_gadget_custom_predicates() -> Result<()> {
+        const NP: usize = 2;
+        const NS: usize = 5;
+        const VL: usize = 2;
+
+        let person_pod_name = "Person".to_string();
+        let person_pod = POD::execute_schnorr_gadget::<NS, VL>(
+            &[
+                Entry::new_from_scalar("age", GoldilocksField(30)),
+                Entry::new_from_scalar("issuer", GoldilocksField(7)),
+            ],
+            &EcSchnorrSecretKey::from(27),
+        )?;
+        let registry_pod_name = "Registry".to_string();
+        let registry_pod = POD::execute_schnorr_gadget::<NS, VL>(
+            &[
+                Entry::new_from_scalar("adult_age", GoldilocksField(18)),
+                Entry::new_from_scalar("trusted", GoldilocksField(7)),
+            ],
+            &EcSchnorrSecretKey::from(29),
+        )?;
+
+        // Over18(age) := Gt(age, x) and ValueOf(x = 18)
+        let over_18 = CustomPredicate::new(
+            "Over18",
+            1,
+            vec![
+                StatementTemplate::new(Statement::GT, &[0, 1]),
+                StatementTemplate::new(Statement::VALUE_OF, &[1]).with_value(GoldilocksField(18)),
+            ],
+        )?;
+        // IsAdult(age, issuer) := Over18(age) and Equal(issuer, trusted)
+        let is_adult = CustomPredicate::new(
+            "IsAdult",
+            2,
+            vec![
+                StatementTemplate::new(Statement::CUSTOM, &[0]).with_value(over_18.id()),
+                StatementTemplate::new(Statement::EQUAL, &[1, 2]),
+            ],
+        )?;
+
+        let op_list = OpList(vec![
+            OpCmd::new(
+                Op::GtFromEntries(
+                    StatementRef::new(&person_pod_name, "VALUEOF:age"),
+                    StatementRef::new(&registry_pod_name, "VALUEOF:adult_age"),
+                ),
+                "age",
+            ),
+            OpCmd::new(
+                Op::EqualityFromEntries(
+                    StatementRef::new(&person_pod_name, "VALUEOF:issuer"),
+                    StatementRef::new(&registry_pod_name, "VALUEOF:trusted"),
+                ),
+                "issuer",
+            ),
+            OpCmd::new(
+                Op::CustomFromStatements(
+                    vec![
+                        StatementRef::new("_SELF", "GT:age"),
+                        StatementRef::new(&registry_pod_name, "VALUEOF:adult_age"),
+                    ],
+                    over_18.clone(),
+                ),
+                "over_18",
+            ),
+            OpCmd::new(
+                Op::CustomFromStatements(
+                    vec![
+                        StatementRef::new("_SELF", "CUSTOM:over_18"),
+                        StatementRef::new("_SELF", "EQUAL:issuer"),
+                    ],
+                    is_adult.clone(),
+                ),
+                "is_adult",
+            ),
+        ])
+        .pad::<NS>()?;
+
+        let gpg_input = GPGInput::new(
+            [
+                (person_pod_name.clone(), person_pod),
+                (registry_pod_name.clone(), registry_pod),
+            ]
+            .into_iter()
+            .collect(),
+            HashMap::new(),
+        );
+        let output_payload = POD::execute_ops(&gpg_input, &op_list.0)?;
+        let args = match output_payload.statements_map.get("CUSTOM:is_adult") {
+            Some(Statement::Custom(id, args)) if *id == is_adult.id() => args,
+            s => return Err(anyhow!("Expected an IsAdult statement, got {:?}", s)),
+        };
+        assert_eq!(
+            args.iter().map(|arg| arg.1.as_str()).collect::<Vec<_>>(),
+            ["age", "issuer"]
+        );
+
+        // A statement of another predicate does not match the nested template.
+        let over_21 = CustomPredicate::new(
+            "Over21",
+            1,
+            vec![
+                StatementTemplate::new(Statement::GT, &[0, 1]),
+                StatementTemplate::new(Statement::VALUE_OF, &[1]).with_value(GoldilocksField(21)),
+            ],
+        )?;
+        let is_adult_21 = CustomPredicate::new(
+            "IsAdult",
+            2,
+            vec![
+                StatementTemplate::new(Statement::CUSTOM, &[0]).with_value(over_21.id()),
+                StatementTemplate::new(Statement::EQUAL, &[1, 2]),
+            ],
+        )?;
+        let mut invalid_op_list = op_list.clone();
+        invalid_op_list.0[3].0 = Op::CustomFromStatements(
+            vec![
+                StatementRef::new("_SELF", "CUSTOM:over_18"),
+                StatementRef::new("_SELF", "EQUAL:issuer"),
+            ],
+            is_adult_21,
+        );
+        assert!(POD::execute_ops(&gpg_input, &invalid_op_list.0).is_err());
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let mut pw: PartialWitness<F> = PartialWitness::new();
+        let targets = OpExecutorGadget::<NP, NS, VL>::add_targets(&mut builder)?;
+        OpExecutorGadget::<NP, NS, VL>::set_targets(
+            &mut pw,
+            &targets,
+            &(gpg_input, op_list),
+            &output_payload.statements_list,
+        )?;
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+
+    /// Randomized comparison of the native evaluation of operations with their evaluation in
+    /// circuit, over input PODs with foreign origins, origin renaming maps and operations which
+    /// may take the statements output by the previous ones. Failing cases are shrunk by proptest.
+    mod native_equivalence {
+        use anyhow::ensure;
+        use plonky2::field::types::Field;
+        use plonky2::iop::witness::WitnessWrite;
+        use plonky2::plonk::circuit_data::CircuitData;
+        use proptest::{
+            array::uniform3,
+            collection::vec,
+            option, prop_oneof,
+            sample::select,
+            strategy::Strategy,
+            test_runner::{Config, TestCaseError, TestRunner},
+        };
+        use std::iter::zip;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        use super::*;
+        use crate::pod::{
+            gadget::GadgetID,
+            payload::PODPayload,
+            statement::{AnchoredKey, StatementOrRef},
+            Origin, Statement,
+        };
+
+        const NP: usize = 2;
+        const NS: usize = 4;
+        /// Owner keys are vectors of 4 elements.
+        const VL: usize = 4;
+        /// Foreign origins an input POD may have, with IDs 2, 3, ...
+        const NUM_ORIGINS: usize = 2;
+        const NEW_ORIGIN_NAMES: [&str; 3] = ["alpha", "beta", "gamma"];
+        const OWNER_SECRET: u64 = 7;
+        const NUM_OP_KINDS: usize = 30;
+
+        /// Merkle tree of some of the scalars taken by entries, whose root entries also take.
+        fn members() -> MerkleTree {
+            MerkleTree::new(&[0, 1, 1 << 32].map(GoldilocksField)).expect("Few members")
+        }
+
+        /// Sorted Merkle tree of some of the scalars taken by entries, whose root entries also
+        /// take.
+        fn revoked() -> SortedMerkleTree {
+            SortedMerkleTree::new(&[2, 4, 1 << 40].map(GoldilocksField)).expect("Few members")
+        }
+
+        /// IsThree(x) := ValueOf(x = 3)
+        fn is_three() -> CustomPredicate {
+            CustomPredicate::new(
+                "IsThree",
+                1,
+                vec![StatementTemplate::new(Statement::VALUE_OF, &[0])
+                    .with_value(GoldilocksField(3))],
+            )
+            .expect("Well-defined predicate")
+        }
+
+        /// GtThree(x) := Gt(x, y) and ValueOf(y = 3)
+        fn gt_three() -> CustomPredicate {
+            CustomPredicate::new(
+                "GtThree",
+                1,
+                vec![
+                    StatementTemplate::new(Statement::GT, &[0, 1]),
+                    StatementTemplate::new(Statement::VALUE_OF, &[1])
+                        .with_value(GoldilocksField(3)),
+                ],
+            )
+            .expect("Well-defined predicate")
+        }
+
+        type Targets = <OpExecutorGadget<NP, NS, VL> as OpsExecutorTrait>::Targets;
+
+        #[derive(Clone, Copy, Debug)]
+        enum Operand {
+            /// Entry `k{statement}` of the input POD `pod{pod}`
+            Input(usize, usize),
+            /// Output of a previous operation
+            Output(usize),
+        }
+
+        #[derive(Clone, Debug)]
+        struct OpSpec {
+            kind: usize,
+            operands: [Operand; 3],
+            value: ScalarOrVec,
+            secret: u64,
+        }
+
+        impl OpSpec {
+            /// Operation referencing the given names of the outputs of the previous operations.
+            /// Merkle proofs are for the value of the first operand if it has one, and otherwise
+            /// for another value, which the operation must then fail on.
+            fn op(
+                &self,
+                outputs: &[String],
+                statements: &<StatementRef as StatementOrRef>::StatementTable,
+            ) -> Op<StatementRef> {
+                let input = |operand: Operand| match operand {
+                    Operand::Input(pod, statement) => {
+                        StatementRef::new(format!("pod{}", pod), format!("VALUEOF:k{}", statement))
+                    }
+                    Operand::Output(i) => StatementRef::new("pod0", format!("VALUEOF:k{}", i % NS)),
+                };
+                let any = |operand: Operand| match operand {
+                    Operand::Output(i) if !outputs.is_empty() => {
+                        StatementRef::new("_SELF", &outputs[i % outputs.len()])
+                    }
+                    operand => input(operand),
+                };
+                let [a, b, c] = self.operands.map(any);
+                let x = match a.deref_cloned(statements) {
+                    Ok(Statement::ValueOf(_, ScalarOrVec::Scalar(x))) => x,
+                    _ => GoldilocksField(3),
+                };
+                match self.kind {
+                    0 => Op::None,
+                    1 => Op::NewEntry(Entry {
+                        key: "new".to_string(),
+                        value: self.value.clone(),
+                    }),
+                    // copies are sorted by the predicate of the copied statement, which must
+                    // then be an input one
+                    2 => Op::CopyStatement(input(self.operands[0])),
+                    3 => Op::EqualityFromEntries(a, b),
+                    4 => Op::NonequalityFromEntries(a, b),
+                    5 => Op::GtFromEntries(a, b),
+                    6 => Op::LtFromEntries(a, b),
+                    7 => Op::TransitiveEqualityFromStatements(a, b),
+                    8 => Op::GtToNonequality(a),
+                    9 => Op::LtToNonequality(a),
+                    10 => Op::ContainsFromEntries(a, b),
+                    11 => Op::RenameContainedBy(a, b),
+                    12 => Op::SumOf(a, b, c),
+                    13 => Op::ProductOf(a, b, c),
+                    14 => Op::MaxOf(a, b, c),
+                    15 => Op::GteFromEntries(a, b),
+                    16 => Op::LteFromEntries(a, b),
+                    17 => Op::InRangeFromEntries(a, b, c),
+                    18 => Op::HashOf(a, vec![b, c]),
+                    19 => Op::NullifierFromOwnerKey(
+                        a,
+                        b,
+                        OwnerSecretKey::new([GoldilocksField(self.secret); 4]),
+                    ),
+                    20 => Op::DifferenceOf(a, b, c),
+                    21 => Op::MinOf(a, b, c),
+                    22 => Op::QuotientOf(a, b, c),
+                    23 => Op::RemainderOf(a, b, c),
+                    24 => Op::ContainedInRootFromEntries(
+                        a,
+                        b,
+                        members()
+                            .prove(x)
+                            .or(members().prove(GoldilocksField(0)))
+                            .expect("0 is a member"),
+                    ),
+                    25 => Op::NotContainedInRootFromEntries(
+                        a,
+                        b,
+                        revoked()
+                            .prove_non_membership(x)
+                            .or(revoked().prove_non_membership(GoldilocksField(3)))
+                            .expect("3 is not revoked"),
+                    ),
+                    26 => Op::CustomFromStatements(vec![a], is_three()),
+                    27 => Op::CustomFromStatements(vec![a, b], gt_three()),
+                    28 => Op::NegationOf(a, b),
+                    _ => Op::HashOf(a, vec![b]),
+                }
+            }
+        }
+
+        #[derive(Clone, Debug)]
+        struct Case {
+            /// Value and foreign origin, if not `_SELF`, of the entries `k0`, `k1`, ... of each
+            /// input POD
+            pods: Vec<Vec<(ScalarOrVec, Option<usize>)>>,
+            /// New name of each foreign origin of each input POD
+            renaming: Vec<Vec<&'static str>>,
+            ops: Vec<OpSpec>,
+        }
+
+        impl Case {
+            fn gpg_input(&self, template: &POD) -> GPGInput {
+                let mut origin_renaming_map = HashMap::new();
+                let pods = self.pods.iter().enumerate().map(|(i, entries)| {
+                    let pod_name = format!("pod{}", i);
+                    let statements = entries
+                        .iter()
+                        .enumerate()
+                        .map(|(j, (value, origin))| {
+                            let origin = match origin {
+                                Some(o) => {
+                                    origin_renaming_map.insert(
+                                        (pod_name.clone(), format!("o{}", o)),
+                                        self.renaming[i][*o].to_string(),
+                                    );
+                                    Origin::new(
+                                        GoldilocksField(*o as u64 + 2),
+                                        format!("o{}", o),
+                                        GadgetID::ECSCHNORR,
+                                    )
+                                }
+                                None => Origin::auto("_SELF".to_string(), GadgetID::ECSCHNORR),
+                            };
+                            (
+                                format!("VALUEOF:k{}", j),
+                                Statement::ValueOf(
+                                    AnchoredKey(origin, format!("k{}", j)),
+                                    value.clone(),
+                                ),
+                            )
+                        })
+                        .collect::<HashMap<_, _>>();
+                    let pod = POD {
+                        payload: PODPayload::new(&statements),
+                        ..template.clone()
+                    };
+                    (pod_name, pod)
+                });
+                let pods = pods.collect::<HashMap<_, _>>();
+                GPGInput::new(pods, origin_renaming_map)
+            }
+
+            /// Evaluates the operations natively up to the first one which fails, returning
+            /// the ones which hold and the one which fails.
+            fn eval(&self, gpg_input: &GPGInput) -> Result<(Vec<OpCmd>, Option<OpCmd>)> {
+                let mut statements = gpg_input.remap_origin_ids_by_name()?;
+                statements.insert("_SELF".to_string(), HashMap::new());
+                let mut outputs: Vec<String> = Vec::new();
+                let mut cmds = Vec::new();
+                for (i, spec) in self.ops.iter().enumerate() {
+                    let op = spec.op(&outputs, &statements);
+                    let out_name = format!("op{}", i);
+                    let Ok(statement) = op.execute(GadgetID::ORACLE, &statements) else {
+                        return Ok((cmds, Some(OpCmd::new(op, out_name))));
+                    };
+                    let output = format!("{}:{}", statement.predicate(), out_name);
+                    statements
+                        .get_mut("_SELF")
+                        .ok_or(anyhow!("Missing output statements"))?
+                        .insert(output.clone(), statement);
+                    outputs.push(output);
+                    cmds.push(OpCmd::new(op, out_name));
+                }
+                Ok((cmds, None))
+            }
+        }
+
+        fn value() -> impl Strategy<Value = ScalarOrVec> {
+            let scalars = [
+                GoldilocksField(0),
+                GoldilocksField(1),
+                GoldilocksField(2),
+                GoldilocksField(3),
+                GoldilocksField(4),
+                GoldilocksField(6),
+                GoldilocksField(u32::MAX as u64),
+                GoldilocksField(1 << 32),
+                GoldilocksField(1 << 40),
+                GoldilocksField::NEG_ONE,
+            ]
+            .map(ScalarOrVec::Scalar)
+            .to_vec();
+            let roots = [members().root(), revoked().root()]
+                .map(ScalarOrVec::Scalar)
+                .to_vec();
+            let vectors = [
+                [0, 1, 3, -1, -3, i64::MIN, i64::MAX]
+                    .map(ScalarOrVec::from)
+                    .to_vec(),
+                vec![ScalarOrVec::from(u64::MAX)],
+                vec![
+                    ScalarOrVec::from_hash(
+                        OwnerSecretKey::new([GoldilocksField(OWNER_SECRET); 4]).public_key(),
+                    ),
+                    ScalarOrVec::from_hash(PoseidonHash::hash_no_pad(&[
+                        GoldilocksField(1),
+                        GoldilocksField(2),
+                    ])),
+                ],
+            ]
+            .concat();
+            prop_oneof![3 => select(scalars), 1 => select(vectors), 1 => select(roots)]
+        }
+
+        fn operand() -> impl Strategy<Value = Operand> {
+            prop_oneof![
+                3 => (0..NP, 0..NS).prop_map(|(pod, statement)| Operand::Input(pod, statement)),
+                1 => (0..NS).prop_map(Operand::Output),
+            ]
+        }
+
+        fn case() -> impl Strategy<Value = Case> {
+            let op_spec = (
+                0..NUM_OP_KINDS,
+                uniform3(operand()),
+                value(),
+                select(vec![OWNER_SECRET, OWNER_SECRET + 1]),
+            )
+                .prop_map(|(kind, operands, value, secret)| OpSpec {
+                    kind,
+                    operands,
+                    value,
+                    secret,
+                });
+            (
+                vec(vec((value(), option::of(0..NUM_ORIGINS)), NS), NP),
+                vec(vec(select(NEW_ORIGIN_NAMES.to_vec()), NUM_ORIGINS), NP),
+                vec(op_spec, 1..=NS),
+            )
+                .prop_map(|(pods, renaming, ops)| Case {
+                    pods,
+                    renaming,
+                    ops,
+                })
+        }
+
+        struct Harness {
+            data: CircuitData<F, C, D>,
+            targets: Targets,
+            template: POD,
+        }
+
+        impl Harness {
+            fn new() -> Result<Self> {
+                let mut builder =
+                    CircuitBuilder::<F, D>::new(CircuitConfig::standard_recursion_config());
+                let targets = OpExecutorGadget::<NP, NS, VL>::add_targets(&mut builder)?;
+                Ok(Self {
+                    data: builder.build::<C>(),
+                    targets,
+                    template: POD::execute_schnorr_gadget::<NS, VL>(
+                        &[],
+                        &EcSchnorrSecretKey::from(42),
+                    )?,
+                })
+            }
+
+            /// Proves and verifies the witness, returning the public inputs. A panic of the
+            /// prover counts as a failure.
+            fn prove(
+                &self,
+                set_witness: impl FnOnce(&mut PartialWitness<F>) -> Result<()>,
+            ) -> Result<Vec<F>> {
+                let mut pw = PartialWitness::new();
+                set_witness(&mut pw)?;
+                let proof = catch_unwind(AssertUnwindSafe(|| self.data.prove(pw)))
+                    .map_err(|_| anyhow!("The prover panicked"))??;
+                let public_inputs = proof.public_inputs.clone();
+                self.data.verify(proof)?;
+                Ok(public_inputs)
+            }
+
+            fn check(&self, case: &Case) -> Result<()> {
+                let gpg_input = case.gpg_input(&self.template);
+                let (cmds, failing_cmd) = case.eval(&gpg_input)?;
+
+                // The operations which hold natively are proven, with the same output.
+                let op_list = OpList(cmds).pad::<NS>()?;
+                let output = POD::execute_ops(&gpg_input, &op_list.0)?.statements_list;
+                let input = (gpg_input.clone(), op_list);
+                let public_inputs = self.prove(|pw| {
+                    OpExecutorGadget::<NP, NS, VL>::set_targets(
+                        pw,
+                        &self.targets,
+                        &input,
+                        &output,
+                    )?;
+                    Ok(())
+                })?;
+                ensure!(
+                    public_inputs
+                        == output
+                            .iter()
+                            .flat_map(|(_, s)| s.to_fields())
+                            .collect::<Vec<_>>(),
+                    "The circuit outputs other statements than {:?}",
+                    output
+                );
+
+                // The one which fails natively can not be proven. Its output is not known, so it
+                // must not be the input of another operation.
+                if let Some(cmd) =
+                    failing_cmd.filter(|OpCmd(op, _)| op.operands().iter().all(|r| r.0 != "_SELF"))
+                {
+                    let result = self.prove(|pw| {
+                        set_unchecked_witness(pw, &self.targets, &gpg_input, cmd.clone())
+                    });
+                    ensure!(
+                        result.is_err(),
+                        "The circuit proves {:?}, which does not hold natively",
+                        cmd
+                    );
+                }
+                Ok(())
+            }
+        }
+
+        /// Sets the witness as `OpExecutorGadget::set_targets` does for the single operation, but
+        /// without evaluating it natively, leaving the output statements to the circuit.
+        fn set_unchecked_witness(
+            pw: &mut PartialWitness<F>,
+            targets: &Targets,
+            gpg_input: &GPGInput,
+            cmd: OpCmd,
+        ) -> Result<()> {
+            let (statement_targets, origin_id_map_targets, op_list_target, _) = targets;
+            zip(statement_targets, &gpg_input.pods_list).try_for_each(
+                |(s_targets, (_, pod))| {
+                    zip(s_targets, &pod.payload.statements_list)
+                        .try_for_each(|(s_target, (_, s))| s_target.set_witness(pw, s))
+                },
+            )?;
+            zip(origin_id_map_targets, gpg_input.origin_id_map_fields()?).try_for_each(
+                |(target_row, row)| {
+                    zip(target_row, row)
+                        .try_for_each(|(target, value)| pw.set_target(*target, value))
+                },
+            )?;
+
+            let payloads: Vec<(String, PODPayload)> = gpg_input
+                .pods_list
+                .iter()
+                .map(|(pod_name, pod)| (pod_name.clone(), pod.payload.clone()))
+                .collect();
+            let ref_index_map = StatementRef::index_map(&payloads);
+            let statement_table: <StatementRef as StatementOrRef>::StatementTable = payloads
+                .iter()
+                .map(|(pod_name, payload)| (pod_name.clone(), payload.statements_map.clone()))
+                .collect();
+            zip(
+                &op_list_target.0,
+                OpList(vec![cmd]).pad::<NS>()?.sort(&payloads).0,
+            )
+            .try_for_each(|(op_target, OpCmd(op, _))| {
+                op_target.set_witness(pw, &op, &ref_index_map, &statement_table)
+            })
+        }
+
+        #[test]
+        fn test_op_executor_gadget_matches_native() -> Result<()> {
+            let harness = Harness::new()?;
+            // Each case takes two proofs, hence fewer cases than proptest's default of 256.
+            let cases = std::env::var("PROPTEST_CASES")
+                .ok()
+                .and_then(|cases| cases.parse().ok())
+                .unwrap_or(64);
+            let mut runner = TestRunner::new(Config {
+                cases,
+                failure_persistence: None,
+                ..Config::default()
+            });
+            runner
+                .run(&case(), |case| {
+                    harness
+                        .check(&case)
+                        .map_err(|e| TestCaseError::fail(e.to_string()))
+                })
+                .map_err(|e| anyhow!("{}", e))
+        }
+    }
 }
@@ -1,27 +1,39 @@
 use anyhow::{anyhow, Result};
 use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::PrimeField64;
+use plonky2::hash::hash_types::HashOut;
 use plonky2::iop::witness::PartialWitness;
 use plonky2::plonk::circuit_builder::CircuitBuilder;
 use plonky2::plonk::circuit_data::{
     CircuitConfig, CircuitData, ProverCircuitData, VerifierCircuitData,
 };
+use serde::{Deserialize, Serialize};
+use std::any::Any;
 use std::array;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::iter::zip;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Instant;
 
 use crate::pod::entry::Entry;
 use crate::pod::gadget::GadgetID;
 use crate::pod::gadget::{IntroducerCircuit, OpExecutorGadget, SchnorrPODGadget};
-use crate::pod::operation::OpList;
+use crate::pod::operation::{OpList, Operation, OperationCmd};
 use crate::pod::payload::{PODPayload, StatementList};
-use crate::pod::statement::Statement;
+use crate::pod::shape::CircuitShape;
+use crate::pod::statement::{Statement, StatementRef};
 use crate::pod::{GPGInput, PODProof, POD};
 use crate::recursion::{
     traits_examples::ExampleIntroducer, IntroducerCircuitTrait, RecursionCircuit,
 };
-use crate::signature::schnorr::SchnorrSecretKey;
+use crate::serialization::{
+    prover_data_from_bytes, prover_data_to_bytes, verifier_data_from_bytes, verifier_data_to_bytes,
+};
+use crate::signature::ecschnorr::EcSchnorrSecretKey;
 
-use crate::{PlonkyProof, C, D, F};
+use crate::{Error, PlonkyProof, C, D, F};
 
 pub struct ProverParams<
     const L: usize,
@@ -34,7 +46,7 @@ pub struct ProverParams<
     [(); L + N]:,
 {
     circuit: RecursionCircuit<
-        SchnorrPODGadget<NS>,
+        SchnorrPODGadget<NS, VL>,
         OpExecutorGadget<{ L + M + N }, NS, VL>,
         L,
         M,
@@ -43,10 +55,218 @@ pub struct ProverParams<
         VL,
     >,
     prover: ProverCircuitData<F, C, D>,
-    pod1_dummy_proof: PlonkyProof,
+    pod1_dummy_pod: POD,
     dummy_proof: PlonkyProof,
 }
 
+/// Prefix of the names of the intermediate PODs of `PlonkyButNotPlonkyGadget::aggregate`.
+const AGGREGATE_NODE_PREFIX: &str = "_AGGREGATE";
+
+/// Prefixes of the names of the dummy PODs `PlonkyButNotPlonkyGadget::execute` pads its inputs
+/// with.
+const DUMMY_POD1_PREFIX: &str = "_DUMMYPOD1";
+const DUMMY_SCHNORR_PREFIX: &str = "_DUMMYSCHNORR";
+const DUMMY_PLONKY_PREFIX: &str = "_DUMMYPLONKY";
+
+/// Input POD of a node of `PlonkyButNotPlonkyGadget::aggregate`, with the names under which it
+/// holds the referenced statements.
+struct AggregateInput {
+    name: String,
+    pod: POD,
+    forwarded: Vec<(StatementRef, String)>,
+}
+
+impl AggregateInput {
+    fn pods_list(inputs: &[Self]) -> Vec<(String, POD)> {
+        inputs
+            .iter()
+            .map(|input| (input.name.clone(), input.pod.clone()))
+            .collect()
+    }
+
+    /// Keeps the names of the origins other than _SELF.
+    fn origin_renaming_map(inputs: &[Self]) -> HashMap<(String, String), String> {
+        inputs
+            .iter()
+            .flat_map(|input| {
+                input
+                    .pod
+                    .payload
+                    .statements_list
+                    .iter()
+                    .flat_map(|(_, s)| s.anchored_keys())
+                    .filter(|anchkey| !anchkey.0.is_self())
+                    .map(|anchkey| {
+                        (
+                            (input.name.clone(), anchkey.0.origin_name.clone()),
+                            anchkey.0.origin_name,
+                        )
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Slot of the process-wide prover params for one circuit shape, empty until they are built.
+type ProverParamsSlot = Arc<Mutex<Option<Arc<dyn Any + Send + Sync>>>>;
+
+/// Process-wide prover params, keyed by their circuit shape. Each shape has its own slot, so
+/// that building the params of one shape does not block callers of the others.
+static PROVER_PARAMS: OnceLock<Mutex<HashMap<CircuitShape, ProverParamsSlot>>> = OnceLock::new();
+
+/// Version of the ProverParams cache format, to be increased whenever the format or the circuits
+/// change, as loading the cache does not build the circuits to compare them.
+const PROVER_PARAMS_CACHE_VERSION: u32 = 2;
+
+/// Header of the ProverParams cache, checked before reading the rest of it.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct ProverParamsCacheHeader {
+    version: u32,
+    pod2_version: String,
+    /// [L, M, N, NS, VL]
+    params: [usize; 5],
+    /// Number of gates and of public inputs of the RecursionCircuit before building it, which
+    /// are cheap to recompute and change along with the circuit logic.
+    shape: [usize; 2],
+    circuit_digest: HashOut<F>,
+}
+
+impl<const L: usize, const M: usize, const N: usize, const NS: usize, const VL: usize>
+    ProverParams<L, M, N, NS, VL>
+where
+    [(); L + M + N]:,
+    [(); L + N]:,
+{
+    /// Digest of the RecursionCircuit the params prove with.
+    pub fn circuit_digest(&self) -> HashOut<F> {
+        self.prover.prover_only.circuit_digest
+    }
+
+    /// Serializes the params into a versioned cache, made of length-prefixed sections: a header
+    /// followed by the pod1 and RecursionCircuit verifier data, the prover data, the dummy proof
+    /// and the dummy POD1.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let pod1_verifier_data = self.circuit.pod1_verifier_data();
+        let verifier_data = self.circuit.verifier_data();
+        let (_, shape) = Self::circuit_targets(pod1_verifier_data.clone(), verifier_data.clone())?;
+        let header = ProverParamsCacheHeader {
+            version: PROVER_PARAMS_CACHE_VERSION,
+            pod2_version: env!("CARGO_PKG_VERSION").to_string(),
+            params: [L, M, N, NS, VL],
+            shape,
+            circuit_digest: self.circuit_digest(),
+        };
+
+        let sections = [
+            serde_json::to_vec(&header)?,
+            verifier_data_to_bytes(pod1_verifier_data)?,
+            verifier_data_to_bytes(verifier_data)?,
+            prover_data_to_bytes(&self.prover)?,
+            serde_json::to_vec(&self.dummy_proof)?,
+            serde_json::to_vec(&self.pod1_dummy_pod)?,
+        ];
+        let mut bytes = vec![];
+        for section in sections {
+            bytes.extend_from_slice(&(section.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&section);
+        }
+        Ok(bytes)
+    }
+
+    /// Deserializes params written by `to_bytes`, checking that they were generated for the
+    /// same cache version, pod2 version and parameters, and that the circuit data matches the
+    /// digest of the header. No circuit is built: the RecursionCircuit targets, which proving
+    /// sets, are only laid out again on a new builder.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self> {
+        let header: ProverParamsCacheHeader = serde_json::from_slice(next_section(&mut bytes)?)?;
+        if header.version != PROVER_PARAMS_CACHE_VERSION
+            || header.pod2_version != env!("CARGO_PKG_VERSION")
+        {
+            return Err(anyhow!(
+                "ProverParams cache version mismatch: got cache v{} from pod2 v{}.",
+                header.version,
+                header.pod2_version
+            ));
+        }
+        if header.params != [L, M, N, NS, VL] {
+            return Err(anyhow!(
+                "ProverParams cache parameters mismatch: expected {:?}, got {:?}.",
+                [L, M, N, NS, VL],
+                header.params
+            ));
+        }
+
+        let pod1_verifier_data = verifier_data_from_bytes(next_section(&mut bytes)?)?;
+        let verifier_data = verifier_data_from_bytes(next_section(&mut bytes)?)?;
+        let (circuit, shape) = Self::circuit_targets(pod1_verifier_data, verifier_data)?;
+        if shape != header.shape {
+            return Err(anyhow!(
+                "ProverParams cache does not match the current RecursionCircuit."
+            ));
+        }
+
+        let prover = prover_data_from_bytes(next_section(&mut bytes)?)?;
+        if prover.prover_only.circuit_digest != header.circuit_digest
+            || circuit.verifier_data().verifier_only.circuit_digest != header.circuit_digest
+        {
+            return Err(anyhow!("ProverParams cache circuit digest mismatch."));
+        }
+
+        let dummy_proof = serde_json::from_slice(next_section(&mut bytes)?)?;
+        let pod1_dummy_pod = serde_json::from_slice(next_section(&mut bytes)?)?;
+
+        Ok(Self {
+            circuit,
+            prover,
+            pod1_dummy_pod,
+            dummy_proof,
+        })
+    }
+
+    /// Adds the RecursionCircuit targets to a new builder, returning them together with the
+    /// circuit shape stored in the cache header. This does not build the circuit, so it is cheap.
+    fn circuit_targets(
+        pod1_verifier_data: VerifierCircuitData<F, C, D>,
+        verifier_data: VerifierCircuitData<F, C, D>,
+    ) -> Result<(
+        RecursionCircuit<
+            SchnorrPODGadget<NS, VL>,
+            OpExecutorGadget<{ L + M + N }, NS, VL>,
+            L,
+            M,
+            N,
+            NS,
+            VL,
+        >,
+        [usize; 2],
+    )> {
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::new(config);
+        let circuit = RecursionCircuit::<
+            SchnorrPODGadget<NS, VL>,
+            OpExecutorGadget<{ L + M + N }, NS, VL>,
+            L,
+            M,
+            N,
+            NS,
+            VL,
+        >::add_targets(&mut builder, pod1_verifier_data, verifier_data)?;
+        Ok((circuit, [builder.num_gates(), builder.num_public_inputs()]))
+    }
+}
+
+/// Reads the next length-prefixed section of a ProverParams cache.
+fn next_section<'a>(bytes: &mut &'a [u8]) -> Result<&'a [u8]> {
+    let err = || anyhow!("Truncated ProverParams cache.");
+    let len_bytes: [u8; 8] = bytes.get(..8).ok_or_else(err)?.try_into()?;
+    let end = usize::try_from(u64::from_le_bytes(len_bytes))?
+        .checked_add(8)
+        .ok_or_else(err)?;
+    let section = bytes.get(8..end).ok_or_else(err)?;
+    *bytes = &bytes[end..];
+    Ok(section)
+}
+
 /// PlonkyPOD constructor taking a list of named input PODs (which could be either Schnorr or
 /// Plonky PODs) as well as operations to be carried out on them as inputs.
 /// Example usage:
@@ -94,18 +314,19 @@ where
         pod1_verifier_data: VerifierCircuitData<F, C, D>,
     ) -> Result<CircuitData<F, C, D>> {
         // generate circuit data
-        RecursionCircuit::<
-            SchnorrPODGadget<NS>,
+        Ok(RecursionCircuit::<
+            SchnorrPODGadget<NS, VL>,
             OpExecutorGadget<{ L + M + N }, NS, VL>, // NP=L+M+N
             L,
             M,
             N,
             NS,
             VL,
-        >::circuit_data(pod1_verifier_data)
+        >::circuit_data(pod1_verifier_data)?)
     }
 
-    /// returns ProverCircuitData
+    /// returns ProverCircuitData. The pod1_circuit_data is the IntroducerCircuit's one if L > 0,
+    /// and can be the ExampleIntroducer's one otherwise.
     pub fn build_prover_params(
         pod1_circuit_data: CircuitData<F, C, D>,
         circuit_data: CircuitData<F, C, D>,
@@ -117,7 +338,7 @@ where
         let mut builder = CircuitBuilder::new(config);
 
         let circuit = RecursionCircuit::<
-            SchnorrPODGadget<NS>,
+            SchnorrPODGadget<NS, VL>,
             OpExecutorGadget<{ L + M + N }, NS, VL>,
             L,
             M,
@@ -131,7 +352,7 @@ where
         )?;
 
         let prover = RecursionCircuit::<
-            SchnorrPODGadget<NS>,
+            SchnorrPODGadget<NS, VL>,
             OpExecutorGadget<{ L + M + N }, NS, VL>, // NP=L+M+N
             L,
             M,
@@ -141,7 +362,7 @@ where
         >::build_prover(pod1_verifier_data, verifier_data)?;
 
         let dummy_proof = RecursionCircuit::<
-            SchnorrPODGadget<NS>,
+            SchnorrPODGadget<NS, VL>,
             OpExecutorGadget<{ L + M + N }, NS, VL>,
             L,
             M,
@@ -149,17 +370,129 @@ where
             NS,
             VL,
         >::dummy_proof(circuit_data);
-        // let pod1_dummy_proof = IntroducerCircuit::dummy_proof(pod1_circuit_data)?;
-        let pod1_dummy_proof = ExampleIntroducer::dummy_proof(pod1_circuit_data)?;
+        let pod1_dummy_pod = if L > 0 {
+            POD::introduce_pod1::<NS, VL>(
+                IntroducerCircuit::<NS, VL>::dummy_inputs()?,
+                &pod1_circuit_data,
+            )?
+        } else {
+            // there are no POD1 slots, so this POD is never used
+            POD {
+                payload: PODPayload {
+                    statements_list: vec![],
+                    statements_map: std::collections::HashMap::new(),
+                },
+                proof: PODProof::Introducer(ExampleIntroducer::dummy_proof(pod1_circuit_data)?),
+                proof_type: GadgetID::INTRODUCER,
+            }
+        };
 
         Ok(ProverParams {
             circuit,
             prover,
-            pod1_dummy_proof,
+            pod1_dummy_pod,
             dummy_proof,
         })
     }
 
+    /// Builds the circuits and returns the ProverParams, which takes a considerable amount of
+    /// time.
+    pub fn new_prover_params() -> Result<ProverParams<L, M, N, NS, VL>> {
+        let pod1_circuit_data = Self::pod1_circuit_data()?;
+        let circuit_data = Self::circuit_data(pod1_circuit_data.verifier_data())?;
+        Self::build_prover_params(pod1_circuit_data, circuit_data)
+    }
+
+    /// Circuit data of the POD1 introducer: the IntroducerCircuit's if L > 0, and the
+    /// ExampleIntroducer's otherwise.
+    fn pod1_circuit_data() -> Result<CircuitData<F, C, D>> {
+        Ok(if L > 0 {
+            IntroducerCircuit::<NS, VL>::circuit_data()?
+        } else {
+            ExampleIntroducer::circuit_data()?
+        })
+    }
+
+    /// Returns the ProverParams shared by the whole process for these parameters, building them
+    /// on first use. Concurrent first callers of the same shape wait for a single build.
+    pub fn cached_prover_params() -> Result<Arc<Mutex<ProverParams<L, M, N, NS, VL>>>> {
+        // only hold the cache lock to look up the slot, and the slot's lock while building
+        let slot = PROVER_PARAMS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(CircuitShape::of::<L, M, N, NS, VL>())
+            .or_default()
+            .clone();
+        let mut slot = slot.lock().unwrap_or_else(|e| e.into_inner());
+        let prover_params = match slot.as_ref() {
+            Some(prover_params) => prover_params.clone(),
+            None => {
+                let prover_params: Arc<dyn Any + Send + Sync> =
+                    Arc::new(Mutex::new(Self::new_prover_params()?));
+                *slot = Some(prover_params.clone());
+                prover_params
+            }
+        };
+        prover_params
+            .downcast::<Mutex<ProverParams<L, M, N, NS, VL>>>()
+            .map_err(|_| anyhow!("Prover params are keyed by their circuit shape"))
+    }
+
+    /// Loads the ProverParams from the cache file at `path`. If it is missing or does not match
+    /// the current circuit, builds them as `new_prover_params` does and writes them to `path`.
+    pub fn load_or_build_prover_params(path: &Path) -> Result<ProverParams<L, M, N, NS, VL>> {
+        match fs::read(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|bytes| ProverParams::from_bytes(&bytes))
+        {
+            Ok(prover_params) => return Ok(prover_params),
+            Err(e) => log::info!(
+                "Building the prover params, as the cache at {} can not be used: {}",
+                path.display(),
+                e
+            ),
+        }
+
+        let prover_params = Self::new_prover_params()?;
+
+        // write to a temporary file first, so that an interrupted write does not leave a
+        // truncated cache behind. The params are usable even if the cache can't be written.
+        let tmp_path = path.with_extension("tmp");
+        if let Err(e) = prover_params
+            .to_bytes()
+            .and_then(|bytes| Ok(fs::write(&tmp_path, bytes)?))
+            .and_then(|()| Ok(fs::rename(&tmp_path, path)?))
+        {
+            log::warn!(
+                "Could not write the prover params cache to {}: {}",
+                path.display(),
+                e
+            );
+        }
+        Ok(prover_params)
+    }
+
+    /// Names of the dummy PODs which `execute` pads the given input PODs with.
+    fn dummy_pod_names(input_pods: &[(String, POD)]) -> Vec<String> {
+        let count = |gadget_id| {
+            input_pods
+                .iter()
+                .filter(|(_, pod)| pod.proof_type == gadget_id)
+                .count()
+        };
+        [
+            (DUMMY_POD1_PREFIX, GadgetID::INTRODUCER, L),
+            (DUMMY_SCHNORR_PREFIX, GadgetID::ECSCHNORR, M),
+            (DUMMY_PLONKY_PREFIX, GadgetID::PLONKY, N),
+        ]
+        .into_iter()
+        .flat_map(|(prefix, gadget_id, max)| {
+            (count(gadget_id)..max).map(move |i| format!("{}{}", prefix, i))
+        })
+        .collect()
+    }
+
     /// Generates a new POD from the given input PODs and the OpList (operations list)
     pub fn execute(
         prover_params: &mut ProverParams<L, M, N, NS, VL>,
@@ -168,15 +501,23 @@ where
         origin_renaming_map: HashMap<(String, String), String>,
     ) -> Result<POD> {
         let start_execute = Instant::now();
-        // Check that the input data is valid, i.e. that we have at most M
-        // SchnorrPODs and N PlonkyPODs in our list, *and each POD
-        // contains exactly `NS` statements*.
-        //
-        // TODO do the same with L pod1 pods
+        // Check that the input data is valid, i.e. that we have at most L
+        // POD1-Introducer PODs, M SchnorrPODs and N PlonkyPODs in our list,
+        // *and each POD contains exactly `NS` statements*, and that the
+        // operations hold, before generating any proof.
+        Self::dry_run(input_pods, &op_list, &origin_renaming_map)?;
+
+        let mut pod1_pods: Vec<(String, POD)> = input_pods
+            .to_vec()
+            .into_iter()
+            .filter(|(_, pod)| pod.proof_type == GadgetID::INTRODUCER)
+            .collect::<Vec<_>>();
+        let pod1_count = pod1_pods.len();
+
         let mut schnorr_pods: Vec<(String, POD)> = input_pods
             .to_vec()
             .into_iter()
-            .filter(|(_, pod)| pod.proof_type == GadgetID::SCHNORR16)
+            .filter(|(_, pod)| pod.proof_type == GadgetID::ECSCHNORR)
             .collect::<Vec<_>>();
         let schnorr_count = schnorr_pods.len();
 
@@ -187,35 +528,13 @@ where
             .collect::<Vec<_>>();
         let plonky_count = plonky_pods.len();
 
-        if schnorr_count > M {
-            return Err(anyhow!(
-                "Number of SchnorrPODs ({}) exceeds allowed maximum ({}).",
-                schnorr_count,
-                M
-            ));
-        }
-        if plonky_count > N {
-            return Err(anyhow!(
-                "Number of PlonkyPODs ({}) exceeds allowed maximum ({}).",
-                plonky_count,
-                N
-            ));
-        }
-
-        let statement_check = input_pods
-            .iter()
-            .all(|(_, pod)| pod.payload.statements_list.len() == NS);
-        if !statement_check {
-            return Err(anyhow!(
-                "All input PODs must contain exactly {} statements.",
-                NS
-            ));
-        }
+        let shape = CircuitShape::of::<L, M, N, NS, VL>();
 
         // Pad op list
         let op_list = op_list.pad::<NS>()?;
 
         // Sort POD lists.
+        pod1_pods.sort_by(|a, b| a.0.cmp(&b.0));
         schnorr_pods.sort_by(|a, b| a.0.cmp(&b.0));
         plonky_pods.sort_by(|a, b| a.0.cmp(&b.0));
 
@@ -227,7 +546,7 @@ where
                     .collect(),
                 statements_map: std::collections::HashMap::new(),
             },
-            proof: crate::pod::PODProof::Plonky(prover_params.dummy_proof.clone()),
+            proof: PODProof::Plonky(shape, prover_params.dummy_proof.clone()),
             proof_type: GadgetID::PLONKY,
         };
 
@@ -236,46 +555,62 @@ where
             &(0..(NS - 1))
                 .map(|i| Entry::new_from_scalar(&format!("Dummy entry {}", i), GoldilocksField(0)))
                 .collect::<Vec<_>>(),
-            &SchnorrSecretKey { sk: 0 },
+            &EcSchnorrSecretKey::from(1),
         )?;
 
-        // TODO do the same with L POD1-Introducer PODs
-        //
-        // Arrange input PODs as a list of M SchnorrPODs followed by N
-        // PlonkyPODs. Pad with appropriate dummy data.
+        // Arrange input PODs as a list of L POD1-Introducer PODs, followed by
+        // M SchnorrPODs, followed by N PlonkyPODs. Pad with appropriate dummy
+        // data.
+        let pod1_pods_padded: [(String, POD); L] = array::from_fn(|i| {
+            if i < pod1_count {
+                pod1_pods[i].clone()
+            } else {
+                (
+                    format!("{}{}", DUMMY_POD1_PREFIX, i),
+                    prover_params.pod1_dummy_pod.clone(),
+                )
+            }
+        });
         let schnorr_pods_padded: [(String, POD); M] = array::from_fn(|i| {
             if i < schnorr_count {
                 schnorr_pods[i].clone()
             } else {
-                (format!("_DUMMYSCHNORR{}", i), dummy_schnorr_pod.clone())
+                (
+                    format!("{}{}", DUMMY_SCHNORR_PREFIX, i),
+                    dummy_schnorr_pod.clone(),
+                )
             }
         });
         let plonky_pods_padded: [(String, POD); N] = array::from_fn(|i| {
             if i < plonky_count {
                 plonky_pods[i].clone()
             } else {
-                (format!("_DUMMYPLONKY{}", i), dummy_plonky_pod.clone())
+                (
+                    format!("{}{}", DUMMY_PLONKY_PREFIX, i),
+                    dummy_plonky_pod.clone(),
+                )
             }
         });
-        // TODO add L pod1introducer_pods_padded here:
         let padded_pod_list: [(String, POD); L + M + N] = array::from_fn(|i| {
-            if i < M {
-                schnorr_pods_padded[i].clone()
+            if i < L {
+                pod1_pods_padded[i].clone()
+            } else if i < L + M {
+                schnorr_pods_padded[i - L].clone()
             } else {
-                plonky_pods_padded[i - M].clone()
+                plonky_pods_padded[i - L - M].clone()
             }
         });
 
-        // Prepare selectors. Set them enabled for the given schnorr & plonky pods, and disabled for
-        // the padding ones
-        //
-        // TODO add L selectors here for the pod1_introducer_pods
+        // Prepare selectors. Set them enabled for the given pod1, schnorr & plonky pods, and
+        // disabled for the padding ones
         let selectors: [F; L + M + N] = array::from_fn(|i| {
             // TODO match i? maybe can not bcs L,M,N are consts
-            if i < M {
-                GoldilocksField(if i < schnorr_count - 1 { 1 } else { 0 })
+            if i < L {
+                GoldilocksField(if i < pod1_count { 1 } else { 0 })
+            } else if i < L + M {
+                GoldilocksField(if i - L < schnorr_count { 1 } else { 0 })
             } else {
-                GoldilocksField(if i < M + plonky_count - 1 { 1 } else { 0 })
+                GoldilocksField(if i - L - M < plonky_count { 1 } else { 0 })
             }
         });
 
@@ -294,9 +629,8 @@ where
         };
 
         // Output Plonky POD should have this as its statement_list in its payload.
-        let output_statements: StatementList = POD::execute_oracle_gadget(&gpg_input, &op_list.0)?
-            .payload
-            .statements_list;
+        let output_statements: StatementList =
+            POD::execute_ops(&gpg_input, &op_list.0)?.statements_list;
 
         // Verify SchnorrPODs in circuit by routing the first `M` elements of `padded_pod_list`
         // (ignoring the string part of the tuple) and the first `M` elements of `selectors` into the
@@ -308,17 +642,29 @@ where
 
         // prepare inputs for the circuit
 
-        // TODO add prepare also the L POD1introducer PODs
-        let pod1_proofs: [PlonkyProof; L] =
-            array::from_fn(|k| prover_params.pod1_dummy_proof.clone());
-        let pod1_public_inputs: [Vec<F>; L] = array::from_fn(|k| vec![]);
+        let pod1_proofs: [PlonkyProof; L] = array::from_fn(|i| {
+            // convert the PODProof.proof into an actual PlonkyProof:
+            match pod1_pods_padded[i].1.proof.clone() {
+                PODProof::Introducer(p) => p,
+                _ => panic!("Expected PODProof's Introducer variant"),
+            }
+        });
+        let pod1_public_inputs: [Vec<F>; L] = array::from_fn(|i| {
+            pod1_pods_padded[i]
+                .1
+                .payload
+                .statements_list
+                .iter()
+                .flat_map(|(_, s)| s.to_fields())
+                .collect()
+        });
 
         let inner_circuit_input: [POD; M] = array::from_fn(|i| schnorr_pods_padded[i].1.clone());
 
         let recursive_proofs: [PlonkyProof; N] = array::from_fn(|i| {
             // convert the PODProof.proof into an actual PlonkyProof:
             match plonky_pods_padded[i].1.proof.clone() {
-                PODProof::Plonky(p) => p,
+                PODProof::Plonky(_, p) => p,
                 _ => panic!("Expected PODProof's Plonky variant"),
             }
         });
@@ -376,16 +722,245 @@ where
                 statements_list: output_statements.clone(),
                 statements_map: output_statements.into_iter().collect(),
             },
-            proof: PODProof::Plonky(plonky_proof.proof),
+            proof: PODProof::Plonky(shape, plonky_proof.proof),
             proof_type: GadgetID::PLONKY,
         })
     }
 
+    /// Generates a new POD from arbitrarily many input PODs and the OpList, by proving a tree
+    /// of PODs whose nodes take at most L POD1-Introducer PODs, M SchnorrPODs and N PlonkyPODs
+    /// each. The intermediate PODs copy the input statements referenced by the OpList up the
+    /// tree, and the OpList is applied at its root. Origins keep their names, so these must not
+    /// clash across the input PODs.
+    pub fn aggregate(
+        prover_params: &mut ProverParams<L, M, N, NS, VL>,
+        input_pods: &[(String, POD)],
+        op_list: OpList,
+    ) -> Result<POD> {
+        let mut origin_owners: HashMap<String, &String> = HashMap::new();
+        for (name, pod) in input_pods {
+            if input_pods.iter().filter(|(other, _)| other == name).count() > 1 {
+                return Err(
+                    Error::InvalidInput(format!("Input POD name {} is not unique.", name)).into(),
+                );
+            }
+            if name.starts_with(AGGREGATE_NODE_PREFIX) {
+                return Err(Error::InvalidInput(format!(
+                    "Input POD names must not start with {}.",
+                    AGGREGATE_NODE_PREFIX
+                ))
+                .into());
+            }
+            if ![GadgetID::INTRODUCER, GadgetID::ECSCHNORR, GadgetID::PLONKY]
+                .contains(&pod.proof_type)
+            {
+                return Err(Error::InvalidInput(format!(
+                    "POD {} of type {:?} can not be aggregated.",
+                    name, pod.proof_type
+                ))
+                .into());
+            }
+            let origin_names = std::iter::once(name.clone()).chain(
+                pod.payload
+                    .statements_list
+                    .iter()
+                    .flat_map(|(_, s)| s.anchored_keys())
+                    .filter(|anchkey| !anchkey.0.is_self())
+                    .map(|anchkey| anchkey.0.origin_name),
+            );
+            for origin_name in origin_names {
+                match origin_owners.insert(origin_name.clone(), name) {
+                    Some(owner) if owner != name => {
+                        return Err(Error::OriginRemapError(format!(
+                            "Origin {} is used by both input PODs {} and {}.",
+                            origin_name, owner, name
+                        ))
+                        .into())
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Input statements referenced by the OpList, in order of first use.
+        let mut referenced: Vec<StatementRef> = Vec::new();
+        for OperationCmd(op, _) in op_list.0.iter() {
+            for statement_ref in op.operands() {
+                if statement_ref.0 != "_SELF" && !referenced.contains(statement_ref) {
+                    referenced.push(statement_ref.clone());
+                }
+            }
+        }
+        if let Some(StatementRef(pod_name, _)) = referenced
+            .iter()
+            .find(|r| !input_pods.iter().any(|(name, _)| name == &r.0))
+        {
+            return Err(Error::MissingStatement(format!(
+                "POD {} is not among the input PODs.",
+                pod_name
+            ))
+            .into());
+        }
+
+        let mut queues: [VecDeque<AggregateInput>; 3] = array::from_fn(|_| VecDeque::new());
+        for (name, pod) in input_pods {
+            let queue = match pod.proof_type {
+                GadgetID::INTRODUCER => 0,
+                GadgetID::ECSCHNORR => 1,
+                _ => 2,
+            };
+            queues[queue].push_back(AggregateInput {
+                name: name.clone(),
+                pod: pod.clone(),
+                forwarded: referenced
+                    .iter()
+                    .filter(|r| &r.0 == name)
+                    .map(|r| (r.clone(), r.1.clone()))
+                    .collect(),
+            });
+        }
+
+        let mut node_count = 0;
+        while queues[0].len() > L || queues[1].len() > M || queues[2].len() > N {
+            // Each node takes as many inputs as it can, and then becomes a PlonkyPOD input.
+            let counts: Vec<usize> = [L, M, N]
+                .iter()
+                .zip(queues.iter())
+                .map(|(max, queue)| queue.len().min(*max))
+                .collect();
+            if N == 0 || counts.iter().sum::<usize>() < 2 {
+                return Err(Error::InvalidInput(format!(
+                    "{} input PODs can not be aggregated with at most {} POD1-Introducer PODs, {} SchnorrPODs and {} PlonkyPODs per POD.",
+                    input_pods.len(),
+                    L,
+                    M,
+                    N
+                ))
+                .into());
+            }
+            let node_inputs: Vec<AggregateInput> = zip(counts, queues.iter_mut())
+                .flat_map(|(count, queue)| queue.drain(..count).collect::<Vec<_>>())
+                .collect();
+
+            // Copy the referenced statements of the node's inputs.
+            let mut forwarded = Vec::new();
+            let mut copy_ops = Vec::new();
+            for input in node_inputs.iter() {
+                for (statement_ref, statement_name) in input.forwarded.iter() {
+                    let statement = input.pod.payload.statements_map.get(statement_name).ok_or(
+                        Error::MissingStatement(format!(
+                            "Statement {} missing from POD {}.",
+                            statement_ref.1, statement_ref.0
+                        )),
+                    )?;
+                    let out_name = format!("{}/{}", statement_ref.0, statement_ref.1);
+                    forwarded.push((
+                        statement_ref.clone(),
+                        format!("{}:{}", statement.predicate(), out_name),
+                    ));
+                    copy_ops.push(OperationCmd::new(
+                        Operation::CopyStatement(StatementRef::new(&input.name, statement_name)),
+                        out_name,
+                    ));
+                }
+            }
+            if copy_ops.len() > NS {
+                return Err(Error::CapacityExceeded {
+                    what: format!(
+                        "referenced statements of the inputs of an intermediate POD, got {}",
+                        copy_ops.len()
+                    ),
+                    max: NS,
+                }
+                .into());
+            }
+
+            let name = format!("{}{}", AGGREGATE_NODE_PREFIX, node_count);
+            node_count += 1;
+            let pods_list = AggregateInput::pods_list(&node_inputs);
+            let origin_renaming_map = AggregateInput::origin_renaming_map(&node_inputs);
+            let op_list = OpList(copy_ops);
+            // The origin IDs of a PlonkyPOD's statements are remapped in circuit through a
+            // table of NS + 2 columns. They are checked before proving, numbering the origins
+            // as `execute` does, ie. together with those of the dummy input PODs.
+            let mut numbering_map = origin_renaming_map.clone();
+            numbering_map.extend(
+                Self::dummy_pod_names(&pods_list)
+                    .into_iter()
+                    .map(|name| ((name.clone(), "_SELF".to_string()), name)),
+            );
+            let payload = POD::execute_ops(
+                &GPGInput::new(pods_list.iter().cloned().collect(), numbering_map),
+                &op_list.0,
+            )?;
+            if let Some(anchkey) = payload
+                .statements_list
+                .iter()
+                .flat_map(|(_, s)| s.anchored_keys())
+                .find(|anchkey| anchkey.0.origin_id.to_canonical_u64() as usize >= NS + 2)
+            {
+                return Err(Error::OriginRemapError(format!(
+                    "Origin {} of intermediate POD {} has ID {}, which exceeds the maximum of {}.",
+                    anchkey.0.origin_name,
+                    name,
+                    anchkey.0.origin_id,
+                    NS + 1
+                ))
+                .into());
+            }
+            let pod = Self::execute(prover_params, &pods_list, op_list, origin_renaming_map)?;
+            queues[2].push_back(AggregateInput {
+                name,
+                pod,
+                forwarded,
+            });
+        }
+
+        // Apply the OpList at the root, to the forwarded copies of the referenced statements.
+        let root_inputs: Vec<AggregateInput> = queues.into_iter().flatten().collect();
+        let op_list = op_list
+            .0
+            .iter()
+            .map(|OperationCmd(op, out_name)| {
+                let op = op.map_operands(&|statement_ref: &StatementRef| {
+                    if statement_ref.0 == "_SELF" {
+                        return Ok(statement_ref.clone());
+                    }
+                    root_inputs
+                        .iter()
+                        .find_map(|input| {
+                            input
+                                .forwarded
+                                .iter()
+                                .find(|(r, _)| r == statement_ref)
+                                .map(|(_, statement_name)| {
+                                    StatementRef::new(&input.name, statement_name)
+                                })
+                        })
+                        .ok_or(
+                            Error::MissingStatement(format!(
+                                "Statement {} of POD {} was not forwarded.",
+                                statement_ref.1, statement_ref.0
+                            ))
+                            .into(),
+                        )
+                })?;
+                Ok(OperationCmd(op, out_name.clone()))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::execute(
+            prover_params,
+            &AggregateInput::pods_list(&root_inputs),
+            OpList(op_list),
+            AggregateInput::origin_renaming_map(&root_inputs),
+        )
+    }
+
     /// This is a helper method that just verifies the PlonkyProof contained inside the POD
     pub fn verify_plonky_pod(verifier_data: VerifierCircuitData<F, C, D>, pod: POD) -> Result<()> {
         // get the PlonkyProof from the pod.proof
         let proof = match pod.proof.clone() {
-            PODProof::Plonky(p) => Ok(p),
+            PODProof::Plonky(_, p) => Ok(p),
             _ => Err(anyhow!("Expected PODProof's Plonky variant")),
         }?;
 
@@ -433,21 +1008,25 @@ where
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
+    use parcnet_pod::{pod::create_pod, pod_entries};
     use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::hash::hash_types::HashOut;
     use std::collections::HashMap;
     use std::time::Instant;
 
-    use super::PlonkyButNotPlonkyGadget;
+    use super::{next_section, PlonkyButNotPlonkyGadget, ProverParams, ProverParamsCacheHeader};
     use crate::{
         pod::{
             entry::Entry,
             gadget::{IntroducerCircuit, OpExecutorGadget, SchnorrPODGadget},
+            merkle::SortedMerkleTree,
             operation::{OpList, Operation as Op, OperationCmd as OpCmd},
-            statement::StatementRef,
+            owner::OwnerSecretKey,
+            statement::{Statement, StatementRef},
             POD,
         },
         recursion::{traits_examples::ExampleIntroducer, IntroducerCircuitTrait},
-        signature::schnorr::SchnorrSecretKey,
+        signature::ecschnorr::EcSchnorrSecretKey,
     };
 
     /// returns M Schnorr PODs
@@ -458,7 +1037,7 @@ mod tests {
                 Entry::new_from_scalar("s1", GoldilocksField(55)),
                 Entry::new_from_scalar("s2", GoldilocksField(56)),
             ],
-            &SchnorrSecretKey { sk: 27 },
+            &EcSchnorrSecretKey::from(27),
         )?;
         let schnorr_pod2_name = "Test POD 2".to_string();
         let schnorr_pod2 = POD::execute_schnorr_gadget::<NS, VL>(
@@ -466,7 +1045,7 @@ mod tests {
                 Entry::new_from_scalar("s3", GoldilocksField(57)),
                 Entry::new_from_scalar("s4", GoldilocksField(55)),
             ],
-            &SchnorrSecretKey { sk: 29 },
+            &EcSchnorrSecretKey::from(29),
         )?;
 
         let pods_list = vec![
@@ -555,6 +1134,362 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_prover_params_cache() -> Result<()> {
+        const L: usize = 0;
+        const M: usize = 2;
+        const N: usize = 1;
+        const NS: usize = 3;
+        const VL: usize = 0;
+
+        let path = std::env::temp_dir().join(format!(
+            "pod2_test_prover_params_{}.bin",
+            std::process::id()
+        ));
+        // builds the params and writes the cache
+        let _ = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::load_or_build_prover_params(&path)?;
+        let bytes = std::fs::read(&path)?;
+        std::fs::remove_file(&path)?;
+
+        // params generated for other parameters are rejected
+        assert!(ProverParams::<L, M, N, 4, VL>::from_bytes(&bytes).is_err());
+        // as well as truncated caches
+        assert!(ProverParams::<L, M, N, NS, VL>::from_bytes(&bytes[..bytes.len() / 2]).is_err());
+        // and caches whose header does not match the current version or their circuit data
+        let tampered = |f: &dyn Fn(&mut ProverParamsCacheHeader)| -> Result<Vec<u8>> {
+            let mut rest = &bytes[..];
+            let mut header = serde_json::from_slice(next_section(&mut rest)?)?;
+            f(&mut header);
+            let header = serde_json::to_vec(&header)?;
+            Ok([&(header.len() as u64).to_le_bytes()[..], &header, rest].concat())
+        };
+        assert!(ProverParams::<L, M, N, NS, VL>::from_bytes(&tampered(&|_| {})?).is_ok());
+        assert!(
+            ProverParams::<L, M, N, NS, VL>::from_bytes(&tampered(&|header| {
+                header.version -= 1
+            })?)
+            .is_err()
+        );
+        assert!(
+            ProverParams::<L, M, N, NS, VL>::from_bytes(&tampered(&|header| {
+                header.circuit_digest = HashOut::ZERO
+            })?)
+            .is_err()
+        );
+
+        // the loaded params can be used to generate new PODs
+        let mut prover_params = ProverParams::<L, M, N, NS, VL>::from_bytes(&bytes)?;
+        let pods_list = prepare_pods::<NS, VL>()?;
+        let op_list = OpList(vec![
+            OpCmd::new(Op::None, "pop"),
+            OpCmd::new(
+                Op::CopyStatement(StatementRef::new(&pods_list[0].0, "VALUEOF:s2")),
+                "op2",
+            ),
+            OpCmd::new(
+                Op::CopyStatement(StatementRef::new(&pods_list[1].0, "VALUEOF:s3")),
+                "op3",
+            ),
+        ]);
+        let verifier_data = prover_params.circuit.verifier_data().clone();
+        let new_pod = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::execute(
+            &mut prover_params,
+            &pods_list,
+            op_list,
+            HashMap::new(),
+        )?;
+        PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::verify_plonky_pod(verifier_data, new_pod)
+    }
+
+    #[test]
+    fn test_PlonkyButNotPlonkyGadget_revocation() -> Result<()> {
+        const L: usize = 0;
+        const M: usize = 2;
+        const N: usize = 1;
+        const NS: usize = 3;
+        const VL: usize = 0;
+
+        let revocation_list =
+            SortedMerkleTree::new(&[3, 1 << 20, 1 << 40, 5].map(GoldilocksField))?;
+        let authority_pod_name = "Revocation authority".to_string();
+        let authority_pod = POD::execute_schnorr_gadget::<NS, VL>(
+            &[Entry::new_from_scalar("revoked", revocation_list.root())],
+            &EcSchnorrSecretKey::from(31),
+        )?;
+        let holder_pod_name = "Holder".to_string();
+        let holder_pod = POD::execute_schnorr_gadget::<NS, VL>(
+            &[
+                Entry::new_from_scalar("key", GoldilocksField(1 << 30)),
+                Entry::new_from_scalar("old key", GoldilocksField(1 << 20)),
+            ],
+            &EcSchnorrSecretKey::from(32),
+        )?;
+        let pods_list = vec![
+            (authority_pod_name.clone(), authority_pod),
+            (holder_pod_name.clone(), holder_pod),
+        ];
+
+        let pod1_circuit_data = ExampleIntroducer::circuit_data()?;
+        let circuit_data = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::circuit_data(
+            pod1_circuit_data.verifier_data(),
+        )?;
+        let verifier_data = circuit_data.verifier_data();
+        let mut prover_params = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::build_prover_params(
+            pod1_circuit_data,
+            circuit_data,
+        )?;
+
+        let not_revoked = |key: &str, value: u64| -> Result<OpList> {
+            Ok(OpList(vec![
+                OpCmd::new(
+                    Op::NotContainedInRootFromEntries(
+                        StatementRef::new(&holder_pod_name, format!("VALUEOF:{}", key)),
+                        StatementRef::new(&authority_pod_name, "VALUEOF:revoked"),
+                        revocation_list
+                            .prove_non_membership(GoldilocksField(value))
+                            .ok_or(anyhow::anyhow!("{} is revoked", key))?,
+                    ),
+                    "notrevoked",
+                ),
+                OpCmd::new(
+                    Op::CopyStatement(StatementRef::new(
+                        &holder_pod_name,
+                        format!("VALUEOF:{}", key),
+                    )),
+                    "key",
+                ),
+            ]))
+        };
+
+        let new_pod = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::execute(
+            &mut prover_params,
+            &pods_list,
+            not_revoked("key", 1 << 30)?,
+            HashMap::new(),
+        )?;
+        assert!(new_pod
+            .payload
+            .statements_map
+            .contains_key("NOTCONTAINEDINROOT:notrevoked"));
+        PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::verify_plonky_pod(verifier_data, new_pod)?;
+
+        // a revoked key has no proof, and the proof of another key does not apply to it
+        assert!(not_revoked("old key", 1 << 20).is_err());
+        let mut op_list = not_revoked("key", 1 << 30)?;
+        op_list.0[0].0 = match &op_list.0[0].0 {
+            Op::NotContainedInRootFromEntries(_, root, proof) => Op::NotContainedInRootFromEntries(
+                StatementRef::new(&holder_pod_name, "VALUEOF:old key"),
+                root.clone(),
+                proof.clone(),
+            ),
+            op => op.clone(),
+        };
+        assert!(PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::execute(
+            &mut prover_params,
+            &pods_list,
+            op_list,
+            HashMap::new(),
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_PlonkyButNotPlonkyGadget_nullifier() -> Result<()> {
+        const L: usize = 0;
+        const M: usize = 1;
+        const N: usize = 1;
+        const NS: usize = 3;
+        // owner keys are vectors of 4 elements
+        const VL: usize = 4;
+
+        let secret = OwnerSecretKey::new([GoldilocksField(4242); 4]);
+        let event = GoldilocksField(2024);
+        let ticket_pod_name = "Ticket".to_string();
+        let ticket_pod = POD::execute_schnorr_gadget::<NS, VL>(
+            &[
+                Entry::new_from_vec("owner", secret.public_key().elements.to_vec()),
+                Entry::new_from_scalar("event", event),
+            ],
+            &EcSchnorrSecretKey::from(33),
+        )?;
+        let pods_list = vec![(ticket_pod_name.clone(), ticket_pod)];
+
+        let pod1_circuit_data = ExampleIntroducer::circuit_data()?;
+        let circuit_data = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::circuit_data(
+            pod1_circuit_data.verifier_data(),
+        )?;
+        let verifier_data = circuit_data.verifier_data();
+        let mut prover_params = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::build_prover_params(
+            pod1_circuit_data,
+            circuit_data,
+        )?;
+
+        let use_ticket = |secret: OwnerSecretKey| {
+            OpList(vec![OpCmd::new(
+                Op::NullifierFromOwnerKey(
+                    StatementRef::new(&ticket_pod_name, "VALUEOF:owner"),
+                    StatementRef::new(&ticket_pod_name, "VALUEOF:event"),
+                    secret,
+                ),
+                "ticket",
+            )])
+        };
+
+        let new_pod = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::execute(
+            &mut prover_params,
+            &pods_list,
+            use_ticket(secret),
+            HashMap::new(),
+        )?;
+        match new_pod.payload.statements_map.get("NULLIFIER:ticket") {
+            Some(Statement::Nullifier(_, _, nullifier)) => {
+                assert_eq!(*nullifier, secret.nullifier(event))
+            }
+            s => anyhow::bail!("Expected a nullifier statement, got {:?}", s),
+        }
+        PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::verify_plonky_pod(verifier_data, new_pod)?;
+
+        // the secret of another owner key gives no nullifier
+        assert!(PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::execute(
+            &mut prover_params,
+            &pods_list,
+            use_ticket(OwnerSecretKey::new([GoldilocksField(4243); 4])),
+            HashMap::new(),
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_PlonkyButNotPlonkyGadget_aggregate() -> Result<()> {
+        const L: usize = 0;
+        const M: usize = 2;
+        const N: usize = 1;
+        const NS: usize = 3;
+        const VL: usize = 0;
+
+        // more SchnorrPODs than a single PlonkyPOD takes
+        let pods_list = (0..5)
+            .map(|i| {
+                Ok((
+                    format!("pod{}", i),
+                    POD::execute_schnorr_gadget::<NS, VL>(
+                        &[
+                            Entry::new_from_scalar("score", GoldilocksField(10 * (i + 1))),
+                            Entry::new_from_scalar("round", GoldilocksField(i)),
+                        ],
+                        &EcSchnorrSecretKey::from(40 + i),
+                    )?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let pod1_circuit_data = ExampleIntroducer::circuit_data()?;
+        let circuit_data = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::circuit_data(
+            pod1_circuit_data.verifier_data(),
+        )?;
+        let verifier_data = circuit_data.verifier_data();
+        let mut prover_params = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::build_prover_params(
+            pod1_circuit_data,
+            circuit_data,
+        )?;
+
+        let op_list = OpList(vec![
+            OpCmd::new(
+                Op::GtFromEntries(
+                    StatementRef::new("pod4", "VALUEOF:score"),
+                    StatementRef::new("pod0", "VALUEOF:score"),
+                ),
+                "top score",
+            ),
+            OpCmd::new(
+                Op::CopyStatement(StatementRef::new("pod2", "VALUEOF:round")),
+                "middle round",
+            ),
+        ]);
+        let new_pod = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::aggregate(
+            &mut prover_params,
+            &pods_list,
+            op_list,
+        )?;
+        match new_pod.payload.statements_map.get("GT:top score") {
+            Some(Statement::Gt(anchkey1, anchkey2)) => {
+                assert_eq!(anchkey1.0.origin_name, "pod4");
+                assert_eq!(anchkey2.0.origin_name, "pod0");
+            }
+            s => anyhow::bail!("Expected a gt statement, got {:?}", s),
+        }
+        assert!(new_pod
+            .payload
+            .statements_map
+            .contains_key("VALUEOF:middle round"));
+        PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::verify_plonky_pod(verifier_data, new_pod)?;
+
+        // statements of PODs which are not among the inputs can not be referenced
+        assert!(PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::aggregate(
+            &mut prover_params,
+            &pods_list,
+            OpList(vec![OpCmd::new(
+                Op::CopyStatement(StatementRef::new("pod5", "VALUEOF:score")),
+                "score",
+            )]),
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_PlonkyButNotPlonkyGadget_with_pod1() -> Result<()> {
+        const L: usize = 1; // max num POD1-Introducer PODs
+        const M: usize = 1; // max num SchnorrPOD
+        const N: usize = 1; // max num Plonky2 recursive proof
+        const NS: usize = 5; // num statements
+        const VL: usize = 8; // vec length, at least 8 for POD1 values
+
+        let pod1_circuit_data = IntroducerCircuit::<NS, VL>::circuit_data()?;
+        let pod1 = create_pod(
+            &[0u8; 32],
+            pod_entries![
+                "speed" => 5,
+                "owner" => "gub"
+            ],
+        )?;
+        let pod1_name = "Test POD1".to_string();
+        let introduced_pod = POD::introduce_pod1::<NS, VL>(pod1, &pod1_circuit_data)?;
+        let pods_list = vec![(pod1_name.clone(), introduced_pod)];
+
+        let op_list = OpList(vec![
+            OpCmd::new(
+                Op::CopyStatement(StatementRef::new(&pod1_name, "VALUEOF:speed")),
+                "speed",
+            ),
+            OpCmd::new(
+                Op::CopyStatement(StatementRef::new(&pod1_name, "VALUEOF:_pod1_signer")),
+                "signer",
+            ),
+        ]);
+
+        let pod1_verifier_data = pod1_circuit_data.verifier_data();
+        let circuit_data =
+            PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::circuit_data(pod1_verifier_data)?;
+        let verifier_data = circuit_data.verifier_data();
+        let mut prover_params = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::build_prover_params(
+            pod1_circuit_data,
+            circuit_data,
+        )?;
+
+        let new_pod = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::execute(
+            &mut prover_params,
+            &pods_list,
+            op_list,
+            HashMap::new(),
+        )?;
+
+        PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::verify_plonky_pod(verifier_data, new_pod)?;
+        Ok(())
+    }
+
     #[test]
     fn get_numbers_PlonkyButNotPlonkyGadget() -> Result<()> {
         println!("| L | M | N | NS | VL | prove | total |");
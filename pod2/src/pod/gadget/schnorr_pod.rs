@@ -1,4 +1,3 @@
-use anyhow::Result;
 use plonky2::{
     iop::{target::BoolTarget, witness::PartialWitness},
     plonk::circuit_builder::CircuitBuilder,
@@ -8,13 +7,14 @@ use crate::pod::circuit::pod::SchnorrPODTarget;
 use crate::{
     pod::POD,
     recursion::{utils::assert_one_if_enabled, InnerCircuitTrait},
-    D, F,
+    Result, D, F,
 };
 
-/// NS stands for NumStatements, the number of statements checked in the POD.
-pub struct SchnorrPODGadget<const NS: usize>;
+/// NS stands for NumStatements, the number of statements checked in the POD, and VL for the
+/// length its vector values are padded to.
+pub struct SchnorrPODGadget<const NS: usize, const VL: usize>;
 
-impl<const NS: usize> InnerCircuitTrait for SchnorrPODGadget<NS> {
+impl<const NS: usize, const VL: usize> InnerCircuitTrait for SchnorrPODGadget<NS, VL> {
     type Input = POD;
     type Targets = SchnorrPODTarget;
 
@@ -32,7 +32,8 @@ impl<const NS: usize> InnerCircuitTrait for SchnorrPODGadget<NS> {
         let hash_target = schnorr_pod_target.compute_hash_target(builder);
 
         // Add POD in-circuit verification logic.
-        let (_, verified) = schnorr_pod_target.compute_targets_and_verify(builder, &hash_target)?;
+        let verified =
+            schnorr_pod_target.compute_targets_and_verify::<VL>(builder, &hash_target)?;
 
         // If selector_booltarg=1, check verified.target.
         assert_one_if_enabled(builder, verified.target, selector_booltarg);
@@ -63,7 +64,7 @@ mod tests {
     use super::*;
     use crate::{
         pod::{entry::Entry, payload::HashablePayload, POD},
-        signature::schnorr::SchnorrSecretKey,
+        signature::ecschnorr::EcSchnorrSecretKey,
     };
     use crate::{C, D, F};
 
@@ -74,8 +75,10 @@ mod tests {
 
         let scalar1 = GoldilocksField(36);
         let entry1 = Entry::new_from_scalar("some key", scalar1);
-        let schnorr_pod3 =
-            POD::execute_schnorr_gadget::<NS, VL>(&[entry1.clone()], &SchnorrSecretKey { sk: 25 })?;
+        let schnorr_pod3 = POD::execute_schnorr_gadget::<NS, VL>(
+            &[entry1.clone()],
+            &EcSchnorrSecretKey::from(25),
+        )?;
         let payload_hash = schnorr_pod3.payload.hash_payload();
 
         let config = CircuitConfig::standard_recursion_config();
@@ -87,7 +90,7 @@ mod tests {
         let hash_target = builder.add_virtual_hash_public_input();
 
         let schnorr_pod_target =
-            SchnorrPODGadget::<NS>::add_targets(&mut builder, &selector_booltarg)?;
+            SchnorrPODGadget::<NS, VL>::add_targets(&mut builder, &selector_booltarg)?;
 
         // set selector=1, so that the pod is verified in the InnerCircuit
         let selector = F::ONE;
@@ -96,7 +99,7 @@ mod tests {
         let mut pw: PartialWitness<F> = PartialWitness::new();
         pw.set_target(selector_targ, selector)?;
         pw.set_hash_target(hash_target, payload_hash)?;
-        SchnorrPODGadget::<NS>::set_targets(&mut pw, &schnorr_pod_target, &schnorr_pod3)?;
+        SchnorrPODGadget::<NS, VL>::set_targets(&mut pw, &schnorr_pod_target, &schnorr_pod3)?;
 
         // Build and prove.
         let data = builder.build::<C>();
@@ -128,7 +131,7 @@ mod tests {
                 .map(|i| {
                     let scalar1 = GoldilocksField(36 + (i as u64));
                     let entry1 = Entry::new_from_scalar("some key", scalar1);
-                    POD::execute_schnorr_gadget(&vec![entry1.clone()], &SchnorrSecretKey { sk: 25 })
+                    POD::execute_schnorr_gadget(&vec![entry1.clone()], &EcSchnorrSecretKey::from(25))
                 })
                 .collect();
 
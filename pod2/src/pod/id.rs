@@ -0,0 +1,164 @@
+//! Content-addressed identifiers of PODs, shared by the stores, origin names and references to
+//! remote PODs.
+use plonky2::{
+    field::{
+        goldilocks_field::GoldilocksField,
+        types::{Field, Field64, PrimeField64},
+    },
+    hash::{hash_types::HashOut, poseidon::PoseidonHash},
+    plonk::config::Hasher,
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use super::POD;
+use crate::{Error, Result, F};
+
+/// Poseidon hash of the canonical encoding of the proof type and payload of a POD, names
+/// included, and optionally of its proof.
+/// Its string encoding is `pod_` followed by the canonical values of the four hash elements as
+/// 16 hex digits each.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PodId(pub HashOut<F>);
+
+impl PodId {
+    pub const PREFIX: &'static str = "pod_";
+}
+
+impl fmt::Display for PodId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Self::PREFIX)?;
+        self.0
+            .elements
+            .iter()
+            .try_for_each(|x| write!(f, "{:016x}", x.to_canonical_u64()))
+    }
+}
+
+impl FromStr for PodId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidInput(format!("{} is not a POD ID", s));
+        let digits = s.strip_prefix(Self::PREFIX).ok_or_else(invalid)?;
+        if digits.len() != 64 || !digits.is_ascii() {
+            return Err(invalid());
+        }
+        let mut elements = [GoldilocksField::ZERO; 4];
+        for (i, x) in elements.iter_mut().enumerate() {
+            let value =
+                u64::from_str_radix(&digits[16 * i..16 * (i + 1)], 16).map_err(|_| invalid())?;
+            if value >= GoldilocksField::ORDER {
+                return Err(invalid());
+            }
+            *x = GoldilocksField(value);
+        }
+        Ok(Self(HashOut { elements }))
+    }
+}
+
+impl POD {
+    /// Canonical ID of the POD, covering its proof type and payload, including statement and
+    /// origin names and full vector values. PODs making the same claims with different signatures
+    /// or proofs share it.
+    pub fn id(&self) -> PodId {
+        PodId(PoseidonHash::hash_no_pad(&self.id_fields()))
+    }
+
+    /// ID of the POD also covering its proof, as serialized to JSON.
+    pub fn id_with_proof(&self) -> Result<PodId> {
+        let proof_bytes = serde_json::to_vec(&self.proof).map_err(|e| Error::Other(e.into()))?;
+        let fields = [self.id_fields(), bytes_to_fields(&proof_bytes)].concat();
+        Ok(PodId(PoseidonHash::hash_no_pad(&fields)))
+    }
+
+    fn id_fields(&self) -> Vec<GoldilocksField> {
+        bytes_to_fields(&self.claims_to_bytes())
+    }
+}
+
+/// Length of the bytes followed by the bytes themselves, 7 per element, which keeps them
+/// canonical.
+fn bytes_to_fields(bytes: &[u8]) -> Vec<GoldilocksField> {
+    [GoldilocksField::from_canonical_usize(bytes.len())]
+        .into_iter()
+        .chain(bytes.chunks(7).map(|chunk| {
+            GoldilocksField(
+                chunk
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, byte| (acc << 8) | *byte as u64),
+            )
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+
+    use super::PodId;
+    use crate::pod::{
+        entry::Entry,
+        gadget::GadgetID,
+        payload::HashablePayload,
+        statement::{AnchoredKey, Statement},
+        POD,
+    };
+    use crate::signature::ecschnorr::EcSchnorrSecretKey;
+
+    #[test]
+    fn test_pod_id() -> Result<()> {
+        let entries = [Entry::new_from_scalar("a", GoldilocksField(1))];
+        let sk = EcSchnorrSecretKey::from(25);
+        let pod = POD::execute_schnorr_gadget::<2, 0>(&entries, &sk)?;
+
+        // Signatures are randomised, but the claims are the same.
+        let resigned_pod = POD::execute_schnorr_gadget::<2, 0>(&entries, &sk)?;
+        assert_eq!(pod.id(), resigned_pod.id());
+        assert_ne!(pod.id_with_proof()?, resigned_pod.id_with_proof()?);
+        assert_eq!(pod.id_with_proof()?, pod.clone().id_with_proof()?);
+
+        let other_pod = POD::execute_schnorr_gadget::<2, 0>(
+            &[Entry::new_from_scalar("a", GoldilocksField(2))],
+            &sk,
+        )?;
+        assert_ne!(pod.id(), other_pod.id());
+        let mut relabelled_pod = pod.clone();
+        relabelled_pod.proof_type = GadgetID::ORACLE;
+        assert_ne!(pod.id(), relabelled_pod.id());
+
+        // Names are covered, although the payload hash does not cover them.
+        let rename = |f: &dyn Fn(&mut String, &mut Statement)| {
+            let mut renamed_pod = pod.clone();
+            for (name, statement) in renamed_pod.payload.statements_list.iter_mut() {
+                if name == "VALUEOF:a" {
+                    f(name, statement);
+                }
+            }
+            assert_eq!(
+                renamed_pod.payload.hash_payload(),
+                pod.payload.hash_payload()
+            );
+            assert_ne!(renamed_pod.id(), pod.id());
+        };
+        rename(&|name, _| *name = "VALUEOF:b".to_string());
+        rename(&|_, statement| {
+            if let Statement::ValueOf(AnchoredKey(origin, _), _) = statement {
+                origin.origin_name = "alice".to_string();
+            }
+        });
+
+        // The string encoding round-trips.
+        let id = pod.id().to_string();
+        assert!(id.starts_with(PodId::PREFIX) && id.len() == PodId::PREFIX.len() + 64);
+        assert_eq!(id.parse::<PodId>()?, pod.id());
+        assert!("pod_1234".parse::<PodId>().is_err());
+        assert!(format!("{}{}", PodId::PREFIX, "f".repeat(64))
+            .parse::<PodId>()
+            .is_err());
+
+        Ok(())
+    }
+}
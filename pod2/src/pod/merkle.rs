@@ -0,0 +1,350 @@
+//! Poseidon Merkle trees of field elements, for membership in sets too large to be held in a
+//! vector entry. As for vector values, a node is the first element of the Poseidon hash of its
+//! children. Leaves are hashed, so that the empty leaves (0) of a tree are not members of it.
+//! Sorted trees also prove non-membership, eg. in revocation lists. They hash in their own
+//! domain, so that their roots are not roots of plain trees.
+use plonky2::{
+    field::{
+        goldilocks_field::GoldilocksField,
+        types::{Field, PrimeField64},
+    },
+    hash::poseidon::PoseidonHash,
+    plonk::config::{GenericHashOut, Hasher},
+};
+use std::array;
+
+use crate::{Error, Result};
+
+/// Depth of the trees, which hold up to 2^MERKLE_DEPTH members.
+pub const MERKLE_DEPTH: usize = 16;
+
+/// Tags of the hashes of the leaves and nodes of sorted trees, which follow the hashed elements
+/// where plain trees have 0.
+pub const SORTED_LEAF_TAG: GoldilocksField = GoldilocksField(1);
+pub const SORTED_NODE_TAG: GoldilocksField = GoldilocksField(2);
+
+/// Hash domain of a tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MerkleDomain {
+    Plain,
+    Sorted,
+}
+
+impl MerkleDomain {
+    /// Tags of the leaf and node hashes.
+    fn tags(self) -> [GoldilocksField; 2] {
+        match self {
+            Self::Plain => [GoldilocksField::ZERO; 2],
+            Self::Sorted => [SORTED_LEAF_TAG, SORTED_NODE_TAG],
+        }
+    }
+
+    pub fn leaf_hash(self, value: GoldilocksField) -> GoldilocksField {
+        PoseidonHash::hash_no_pad(&[value, GoldilocksField::ZERO, self.tags()[0]]).to_vec()[0]
+    }
+
+    pub fn node_hash(self, left: GoldilocksField, right: GoldilocksField) -> GoldilocksField {
+        PoseidonHash::hash_no_pad(&[left, right, self.tags()[1]]).to_vec()[0]
+    }
+
+    /// Roots of the empty subtrees of each height.
+    fn empty_nodes(self) -> [GoldilocksField; MERKLE_DEPTH + 1] {
+        let mut nodes = [GoldilocksField::ZERO; MERKLE_DEPTH + 1];
+        for height in 0..MERKLE_DEPTH {
+            nodes[height + 1] = self.node_hash(nodes[height], nodes[height]);
+        }
+        nodes
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MerkleTree {
+    domain: MerkleDomain,
+    leaves: Vec<GoldilocksField>,
+    /// Non-empty nodes of each height, from the hashed leaves to the root
+    levels: Vec<Vec<GoldilocksField>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: &[GoldilocksField]) -> Result<Self> {
+        Self::with_domain(MerkleDomain::Plain, leaves)
+    }
+
+    fn with_domain(domain: MerkleDomain, leaves: &[GoldilocksField]) -> Result<Self> {
+        if leaves.len() > 1 << MERKLE_DEPTH {
+            return Err(Error::CapacityExceeded {
+                what: "Merkle tree leaves".to_string(),
+                max: 1 << MERKLE_DEPTH,
+            });
+        }
+        let empty_nodes = domain.empty_nodes();
+        let mut levels = vec![leaves
+            .iter()
+            .map(|leaf| domain.leaf_hash(*leaf))
+            .collect::<Vec<_>>()];
+        for height in 0..MERKLE_DEPTH {
+            let level = levels[height]
+                .chunks(2)
+                .map(|pair| domain.node_hash(pair[0], *pair.get(1).unwrap_or(&empty_nodes[height])))
+                .collect();
+            levels.push(level);
+        }
+        Ok(Self {
+            domain,
+            leaves: leaves.to_vec(),
+            levels,
+        })
+    }
+
+    pub fn root(&self) -> GoldilocksField {
+        self.levels[MERKLE_DEPTH]
+            .first()
+            .copied()
+            .unwrap_or(self.domain.empty_nodes()[MERKLE_DEPTH])
+    }
+
+    pub fn leaves(&self) -> &[GoldilocksField] {
+        &self.leaves
+    }
+
+    /// Proof that `value` is a leaf of the tree, if it is one.
+    pub fn prove(&self, value: GoldilocksField) -> Option<MerkleProof> {
+        let index = self.leaves.iter().position(|leaf| *leaf == value)?;
+        let empty_nodes = self.domain.empty_nodes();
+        Some(MerkleProof {
+            index,
+            siblings: array::from_fn(|height| {
+                *self.levels[height]
+                    .get((index >> height) ^ 1)
+                    .unwrap_or(&empty_nodes[height])
+            }),
+        })
+    }
+}
+
+/// Path from a leaf to the root, given by the index of the leaf and the siblings of the nodes
+/// on the path, starting from the leaf.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MerkleProof {
+    pub index: usize,
+    pub siblings: [GoldilocksField; MERKLE_DEPTH],
+}
+
+impl MerkleProof {
+    /// Root of the plain tree having `value` as leaf along this path.
+    pub fn root(&self, value: GoldilocksField) -> GoldilocksField {
+        self.root_in(MerkleDomain::Plain, value)
+    }
+
+    pub fn root_in(&self, domain: MerkleDomain, value: GoldilocksField) -> GoldilocksField {
+        self.siblings
+            .iter()
+            .enumerate()
+            .fold(domain.leaf_hash(value), |node, (height, sibling)| {
+                if (self.index >> height) & 1 == 1 {
+                    domain.node_hash(*sibling, node)
+                } else {
+                    domain.node_hash(node, *sibling)
+                }
+            })
+    }
+
+    pub fn verify(&self, root: GoldilocksField, value: GoldilocksField) -> bool {
+        self.verify_in(MerkleDomain::Plain, root, value)
+    }
+
+    pub fn verify_in(
+        &self,
+        domain: MerkleDomain,
+        root: GoldilocksField,
+        value: GoldilocksField,
+    ) -> bool {
+        self.index < 1 << MERKLE_DEPTH && self.root_in(domain, value) == root
+    }
+
+    /// Field representation as a vector of length 1 + MERKLE_DEPTH.
+    pub fn to_fields(&self) -> Vec<GoldilocksField> {
+        [
+            vec![GoldilocksField::from_canonical_usize(self.index)],
+            self.siblings.to_vec(),
+        ]
+        .concat()
+    }
+}
+
+/// Merkle tree of a set whose leaves are sorted, as u64s, and bounded by 0 and -1, so that a
+/// value which is not a member lies between two adjacent leaves. The bounds are leaves of the
+/// tree but not members of the set, which can not hold them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SortedMerkleTree(MerkleTree);
+
+impl SortedMerkleTree {
+    const BOUNDS: [GoldilocksField; 2] = [GoldilocksField::ZERO, GoldilocksField::NEG_ONE];
+
+    pub fn new(members: &[GoldilocksField]) -> Result<Self> {
+        if members.iter().any(|member| Self::BOUNDS.contains(member)) {
+            return Err(Error::InvalidInput(
+                "0 and -1 bound sorted Merkle trees and can not be members".to_string(),
+            ));
+        }
+        let mut leaves = [Self::BOUNDS.to_vec(), members.to_vec()].concat();
+        leaves.sort_by_key(|leaf| leaf.to_canonical_u64());
+        leaves.dedup();
+        Ok(Self(MerkleTree::with_domain(
+            MerkleDomain::Sorted,
+            &leaves,
+        )?))
+    }
+
+    pub fn root(&self) -> GoldilocksField {
+        self.0.root()
+    }
+
+    /// Members of the set, sorted, without the bounds.
+    pub fn members(&self) -> &[GoldilocksField] {
+        let leaves = self.0.leaves();
+        &leaves[1..leaves.len() - 1]
+    }
+
+    /// Proof that `value` is a member of the set, which the bounds are not, to be verified in
+    /// the sorted domain.
+    pub fn prove(&self, value: GoldilocksField) -> Option<MerkleProof> {
+        if Self::BOUNDS.contains(&value) {
+            return None;
+        }
+        self.0.prove(value)
+    }
+
+    /// Proof that `value` is not a leaf of the tree, if it is not one.
+    pub fn prove_non_membership(&self, value: GoldilocksField) -> Option<NonMembershipProof> {
+        let leaves = self.0.leaves();
+        let upper_index = leaves
+            .iter()
+            .position(|leaf| leaf.to_canonical_u64() > value.to_canonical_u64())?;
+        let lower = *leaves.get(upper_index.checked_sub(1)?)?;
+        let upper = leaves[upper_index];
+        if lower == value {
+            return None;
+        }
+        Some(NonMembershipProof {
+            lower,
+            upper,
+            lower_proof: self.0.prove(lower)?,
+            upper_proof: self.0.prove(upper)?,
+        })
+    }
+}
+
+/// Proof that a value is not a leaf of a sorted tree: two adjacent leaves, which the value lies
+/// strictly between, and their paths to the root.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NonMembershipProof {
+    pub lower: GoldilocksField,
+    pub upper: GoldilocksField,
+    pub lower_proof: MerkleProof,
+    pub upper_proof: MerkleProof,
+}
+
+impl NonMembershipProof {
+    pub fn verify(&self, root: GoldilocksField, value: GoldilocksField) -> bool {
+        self.lower_proof.index + 1 == self.upper_proof.index
+            && self
+                .lower_proof
+                .verify_in(MerkleDomain::Sorted, root, self.lower)
+            && self
+                .upper_proof
+                .verify_in(MerkleDomain::Sorted, root, self.upper)
+            && self.lower.to_canonical_u64() < value.to_canonical_u64()
+            && value.to_canonical_u64() < self.upper.to_canonical_u64()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merkle_tree() -> Result<()> {
+        let leaves = (0..1000)
+            .map(|i| GoldilocksField(3 * i))
+            .collect::<Vec<_>>();
+        let tree = MerkleTree::new(&leaves)?;
+        let root = tree.root();
+        for value in [0, 3, 2997] {
+            let proof = tree.prove(GoldilocksField(value)).unwrap();
+            assert!(proof.verify(root, GoldilocksField(value)));
+            assert!(!proof.verify(root, GoldilocksField(value + 1)));
+        }
+        assert!(tree.prove(GoldilocksField(1)).is_none());
+
+        // empty leaves are not members
+        let tree = MerkleTree::new(&[GoldilocksField(5)])?;
+        let plain = MerkleDomain::Plain;
+        let mut siblings: [GoldilocksField; MERKLE_DEPTH] =
+            plain.empty_nodes()[..MERKLE_DEPTH].try_into()?;
+        siblings[0] = plain.leaf_hash(GoldilocksField(5));
+        let empty_leaf_proof = MerkleProof { index: 1, siblings };
+        let empty_leaf_root = siblings[1..].iter().fold(
+            plain.node_hash(siblings[0], GoldilocksField::ZERO),
+            |node, sibling| plain.node_hash(node, *sibling),
+        );
+        assert_eq!(empty_leaf_root, tree.root());
+        assert!(!empty_leaf_proof.verify(tree.root(), GoldilocksField::ZERO));
+
+        assert_eq!(
+            MerkleTree::new(&[])?.root(),
+            plain.empty_nodes()[MERKLE_DEPTH]
+        );
+        assert!(MerkleTree::new(&vec![GoldilocksField::ZERO; (1 << MERKLE_DEPTH) + 1]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sorted_merkle_tree() -> Result<()> {
+        let revoked = [40, 7, 1 << 40, 7, 13].map(GoldilocksField);
+        let tree = SortedMerkleTree::new(&revoked)?;
+        let root = tree.root();
+        assert_eq!(tree.members(), [7, 13, 40, 1 << 40].map(GoldilocksField));
+
+        for value in [1, 8, 39, 41, (1 << 40) + 1, u64::MAX >> 1] {
+            let value = GoldilocksField(value);
+            let proof = tree.prove_non_membership(value).unwrap();
+            assert!(proof.verify(root, value));
+            assert!(tree.prove(value).is_none());
+        }
+        for value in revoked {
+            assert!(tree.prove_non_membership(value).is_none());
+            assert!(tree
+                .prove(value)
+                .unwrap()
+                .verify_in(MerkleDomain::Sorted, root, value));
+        }
+
+        // the bounds are neither members nor non-members, and can not be added
+        for bound in [GoldilocksField::ZERO, GoldilocksField::NEG_ONE] {
+            assert!(tree.prove(bound).is_none());
+            assert!(tree.prove_non_membership(bound).is_none());
+            assert!(SortedMerkleTree::new(&[GoldilocksField(7), bound]).is_err());
+        }
+
+        // sorted trees hash in their own domain, so that their roots are not plain roots
+        let plain_tree = MerkleTree::new(tree.0.leaves())?;
+        assert_ne!(plain_tree.root(), root);
+        let proof = tree.prove(GoldilocksField(7)).unwrap();
+        assert!(!proof.verify(root, GoldilocksField(7)));
+        let bound_proof = tree.0.prove(GoldilocksField::ZERO).unwrap();
+        assert!(!bound_proof.verify(root, GoldilocksField::ZERO));
+
+        // the leaves must be adjacent and the value strictly between them
+        let proof = tree.prove_non_membership(GoldilocksField(20)).unwrap();
+        assert!(!proof.verify(root, GoldilocksField(13)));
+        assert!(!proof.verify(root, GoldilocksField(40)));
+        let wide_proof = NonMembershipProof {
+            upper: GoldilocksField(1 << 40),
+            upper_proof: tree.prove(GoldilocksField(1 << 40)).unwrap(),
+            ..proof
+        };
+        assert!(!wide_proof.verify(root, GoldilocksField(20)));
+        Ok(())
+    }
+}
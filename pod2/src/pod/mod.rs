@@ -1,5 +1,4 @@
 use anyhow::anyhow;
-use anyhow::Result;
 use parcnet_pod::pod::{Pod, PodValue};
 use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2::field::types::Field;
@@ -7,20 +6,23 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use plonky2::field::types::PrimeField64;
+use plonky2::plonk::circuit_data::CircuitData;
+use plonky2::plonk::proof::ProofWithPublicInputs;
 use std::collections::HashMap;
 
-use crate::pod::gadget::{IntroducerCircuit, PlonkyButNotPlonkyGadget};
+use crate::pod::gadget::{CompressionCircuit, IntroducerCircuit, PlonkyButNotPlonkyGadget};
 use crate::pod::{
     entry::Entry,
     gadget::GadgetID,
-    payload::{HashablePayload, PODPayload},
+    payload::{validate_statement_name, HashablePayload, PODPayload},
+    shape::ShapeVisitor,
     value::ScalarOrVec,
 };
-use crate::recursion::{traits_examples::ExampleIntroducer, IntroducerCircuitTrait};
-use crate::signature::schnorr::{
-    SchnorrPublicKey, SchnorrSecretKey, SchnorrSignature, SchnorrSigner,
+use crate::signature::ecschnorr::{
+    EcSchnorrPublicKey, EcSchnorrSecretKey, EcSchnorrSignature, EcSchnorrSigner,
 };
-use crate::PlonkyProof;
+use crate::signature::schnorr::{SchnorrPublicKey, SchnorrSignature, SchnorrSigner};
+use crate::{Error, PlonkyProof, Result, C, D, F};
 
 pub use operation::Operation as Op;
 pub use operation::OperationCmd as OpCmd;
@@ -28,24 +30,45 @@ pub use statement::Statement;
 
 pub mod entry;
 pub mod gadget;
+pub mod id;
+pub mod merkle;
 pub mod operation;
+pub mod oracle;
 pub mod origin;
+pub mod owner;
 pub mod payload;
+pub mod predicate;
+pub mod provenance;
+pub mod shape;
 pub mod statement;
 pub mod util;
 pub mod value;
+pub mod verifier;
+pub mod wire;
 
 // submodule
 pub mod circuit;
+pub mod disclosure;
+pub use id::PodId;
+pub use oracle::Oracle;
 pub use origin::Origin;
+pub use shape::CircuitShape;
+pub use verifier::Verifier;
 
 pub const SIGNER_PK_KEY: &str = "_signer";
+pub const ORACLE_PK_KEY: &str = "_oracle";
+pub const POD1_SIGNER_KEY: &str = "_pod1_signer";
 
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub enum PODProof {
     Schnorr(SchnorrSignature),
-    Oracle(SchnorrSignature),
-    Plonky(PlonkyProof),
+    Oracle(EcSchnorrPublicKey, EcSchnorrSignature),
+    /// The shape of the circuit which produced the proof, and the proof
+    Plonky(CircuitShape, PlonkyProof),
+    Introducer(PlonkyProof),
+    EcSchnorr(EcSchnorrPublicKey, EcSchnorrSignature),
+    /// The shape of the circuit which produced the Plonky proof, and its compressed proof
+    CompressedPlonky(CircuitShape, PlonkyProof),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -55,12 +78,21 @@ pub struct POD {
     pub proof_type: GadgetID,
 }
 
+impl AsRef<PODPayload> for POD {
+    fn as_ref(&self) -> &PODPayload {
+        &self.payload
+    }
+}
+
 impl POD {
     /// L: number of POD1-Introducer PODs
     /// M: number of PODs
     /// N: number of Plonky PODs
     /// NS: number of Statements
     /// VL: vector length
+    ///
+    /// Plonky and Introducer PODs are checked against the process-wide `Verifier` for these
+    /// parameters, which builds the circuits on first use only.
     pub fn verify<
         const L: usize,
         const M: usize,
@@ -74,82 +106,277 @@ impl POD {
         [(); L + M + N]:,
         [(); L + N]:,
     {
+        self.verify_with(&Verifier::<L, M, N, NS, VL>::cached())
+    }
+
+    /// Same as `verify`, but using the given `Verifier`.
+    pub fn verify_with<
+        const L: usize,
+        const M: usize,
+        const N: usize,
+        const NS: usize,
+        const VL: usize,
+    >(
+        &self,
+        verifier: &Verifier<L, M, N, NS, VL>,
+    ) -> Result<bool>
+    where
+        [(); L + M + N]:,
+        [(); L + N]:,
+    {
+        self.payload.validate().map_err(Error::verification)?;
         match &self.proof {
             PODProof::Schnorr(p) => {
                 if self.proof_type != GadgetID::SCHNORR16 {
-                    return Err(anyhow!("Proof and POD proofType mismatch"));
+                    return Err(Error::InvalidInput(
+                        "Proof and POD proofType mismatch".to_string(),
+                    ));
                 }
 
                 let payload_hash = self.payload.hash_payload();
                 let protocol = SchnorrSigner::new();
-
-                let pk: GoldilocksField = self
-                    .payload
-                    .statements_map
-                    .get(&format!("VALUEOF:{}", SIGNER_PK_KEY))
-                    .ok_or(anyhow!("No signer found in payload"))
-                    .and_then(|s| match s {
-                        Statement::ValueOf(_, ScalarOrVec::Scalar(v)) => Ok(*v),
-                        _ => Err(anyhow!("Invalid signer entry in payload")),
-                    })?;
+                let ScalarOrVec::Scalar(pk) = self.signer()? else {
+                    return Ok(false);
+                };
 
                 Ok(protocol.verify(p, &payload_hash.elements.to_vec(), &SchnorrPublicKey { pk }))
             }
+            PODProof::EcSchnorr(pk, sig) => {
+                if self.proof_type != GadgetID::ECSCHNORR {
+                    return Err(Error::InvalidInput(
+                        "Proof and POD proofType mismatch".to_string(),
+                    ));
+                }
 
-            PODProof::Oracle(p) => {
-                if self.proof_type != GadgetID::ORACLE {
-                    return Err(anyhow!("Proof and POD proofType mismatch"));
+                // the payload only holds a hash of the signer's public key
+                if !self.signer()?.holds_hash(pk.hash()) {
+                    return Ok(false);
                 }
 
                 let payload_hash = self.payload.hash_payload();
-                let protocol = SchnorrSigner::new();
-
-                Ok(protocol.verify(
-                    p,
-                    &payload_hash.elements.to_vec(),
-                    &protocol.keygen(&SchnorrSecretKey { sk: 0 }), // hardcoded secret key
-                ))
+                Ok(EcSchnorrSigner::new().verify(sig, &payload_hash.elements, pk))
             }
-            PODProof::Plonky(_p) => {
+
+            PODProof::Oracle(_, _) => Err(Error::InvalidInput(
+                "ORACLE PODs must be verified against trusted oracle keys with `verify_oracle`"
+                    .to_string(),
+            )),
+            PODProof::Plonky(shape, _) => {
+                if self.proof_type != GadgetID::PLONKY {
+                    return Err(Error::InvalidInput(
+                        "Proof and POD proofType mismatch".to_string(),
+                    ));
+                }
+                let expected_shape = CircuitShape::of::<L, M, N, NS, VL>();
+                if *shape != expected_shape {
+                    return Err(Error::InvalidInput(format!(
+                        "POD was produced with the circuit shape {}, not {}",
+                        shape, expected_shape
+                    )));
+                }
                 // ensure that the amount of statements match the NS parameter
-                assert_eq!(NS, self.payload.statements_list.len());
-
-                // TODO the verifier_data currently is computed here on the fly, but it will not be
-                // computed here and will be passed as parameter, bcs the circuit_data (needed to
-                // get the verifier_data on the fly) takes a considerable amount of time to
-                // compute. Need to think how we modify the interface to pass the verifier_data to
-                // this method.
-                // let pod1_circuit_data = IntroducerCircuit::circuit_data()?; // TODO
-                let pod1_circuit_data = ExampleIntroducer::circuit_data()?;
-                let pod1_verifier_data = pod1_circuit_data.verifier_data();
-                let circuit_data =
-                    PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::circuit_data(pod1_verifier_data)?;
-                let verifier_data = circuit_data.verifier_data();
+                if self.payload.statements_list.len() != NS {
+                    return Err(Error::InvalidInput(format!(
+                        "POD must contain exactly {} statements",
+                        NS
+                    )));
+                }
+
+                let verifier_data = verifier
+                    .plonky_verifier_data()
+                    .map_err(Error::proving)?
+                    .clone();
                 PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::verify_plonky_pod(
                     verifier_data,
                     self.clone(),
-                )?;
+                )
+                .map_err(Error::verification)?;
+                Ok(true)
+            }
+            PODProof::CompressedPlonky(shape, p) => {
+                if self.proof_type != GadgetID::COMPRESSED_PLONKY {
+                    return Err(Error::InvalidInput(
+                        "Proof and POD proofType mismatch".to_string(),
+                    ));
+                }
+                let expected_shape = CircuitShape::of::<L, M, N, NS, VL>();
+                if *shape != expected_shape {
+                    return Err(Error::InvalidInput(format!(
+                        "POD was produced with the circuit shape {}, not {}",
+                        shape, expected_shape
+                    )));
+                }
+                if self.payload.statements_list.len() != NS {
+                    return Err(Error::InvalidInput(format!(
+                        "POD must contain exactly {} statements",
+                        NS
+                    )));
+                }
+
+                let verifier_data = verifier
+                    .compressed_verifier_data()
+                    .map_err(Error::proving)?;
+                let public_inputs: Vec<F> = self
+                    .payload
+                    .statements_list
+                    .iter()
+                    .flat_map(|(_, s)| s.to_fields())
+                    .collect();
+                CompressionCircuit::<L, M, N, NS, VL>::verify_proof(
+                    verifier_data,
+                    p.clone(),
+                    public_inputs,
+                )
+                .map_err(Error::verification)?;
+                Ok(true)
+            }
+            PODProof::Introducer(p) => {
+                if self.proof_type != GadgetID::INTRODUCER {
+                    return Err(Error::InvalidInput(
+                        "Proof and POD proofType mismatch".to_string(),
+                    ));
+                }
+                if self.payload.statements_list.len() != NS {
+                    return Err(Error::InvalidInput(format!(
+                        "POD must contain exactly {} statements",
+                        NS
+                    )));
+                }
+
+                let verifier_data = verifier
+                    .introducer_verifier_data()
+                    .map_err(Error::proving)?;
+                let public_inputs: Vec<F> = self
+                    .payload
+                    .statements_list
+                    .iter()
+                    .flat_map(|(_, s)| s.to_fields())
+                    .collect();
+                verifier_data
+                    .verify(ProofWithPublicInputs {
+                        proof: p.clone(),
+                        public_inputs,
+                    })
+                    .map_err(Error::verification)?;
                 Ok(true)
             }
         }
     }
 
-    pub fn execute_schnorr_gadget<const NS: usize, const VL: usize>(
-        entries: &[Entry],
-        sk: &SchnorrSecretKey,
-    ) -> Result<Self> {
-        let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
-        let protocol = SchnorrSigner::new();
+    /// Verifies the POD with the registered circuit shape it was produced with, for when it is not
+    /// known at compile time. Introducer PODs, which do not record their shape, are verified with
+    /// the registered shape having their number of statements.
+    pub fn verify_by_shape(&self) -> Result<bool> {
+        let shape = match &self.proof {
+            PODProof::Plonky(shape, _) | PODProof::CompressedPlonky(shape, _) => *shape,
+            PODProof::Introducer(_) => *CircuitShape::ALL
+                .iter()
+                .find(|shape| shape.ns == self.payload.statements_list.len())
+                .ok_or(Error::InvalidInput(format!(
+                    "No registered circuit shape has {} statements",
+                    self.payload.statements_list.len()
+                )))?,
+            // signed PODs do not depend on the circuit shape
+            _ => CircuitShape::DEFAULT,
+        };
+        shape
+            .dispatch(VerifyPOD(self))
+            .map_err(|e| Error::InvalidInput(e.to_string()))?
+    }
+
+    /// Shape of the circuit which produced the POD, for Plonky and compressed Plonky PODs.
+    pub fn shape(&self) -> Option<CircuitShape> {
+        match &self.proof {
+            PODProof::Plonky(shape, _) | PODProof::CompressedPlonky(shape, _) => Some(*shape),
+            _ => None,
+        }
+    }
+
+    /// Verifies an ORACLE POD: its signature must be valid and made by one of the trusted
+    /// oracles.
+    pub fn verify_oracle(&self, trusted_oracles: &[EcSchnorrPublicKey]) -> Result<bool> {
+        let PODProof::Oracle(pk, sig) = &self.proof else {
+            return Err(Error::InvalidInput(
+                "Expected PODProof's Oracle variant".to_string(),
+            ));
+        };
+        if self.proof_type != GadgetID::ORACLE {
+            return Err(Error::InvalidInput(
+                "Proof and POD proofType mismatch".to_string(),
+            ));
+        }
+        self.payload.validate().map_err(Error::verification)?;
+
+        // the payload only holds a hash of the oracle's public key
+        if !trusted_oracles.contains(pk) || !self.oracle()?.holds_hash(pk.hash()) {
+            return Ok(false);
+        }
+
+        let payload_hash = self.payload.hash_payload();
+        Ok(EcSchnorrSigner::new().verify(sig, &payload_hash.elements, pk))
+    }
+
+    /// Value of the signer's public key entry, as stored in the payload of SchnorrPODs: the full
+    /// hash of the key (see `ScalarOrVec::from_hash`), or the key itself for SCHNORR16 PODs.
+    pub fn signer(&self) -> Result<ScalarOrVec> {
+        self.self_value(SIGNER_PK_KEY)
+            .ok_or(Error::MissingStatement(format!(
+                "No signer found in payload, expected a VALUEOF:{}",
+                SIGNER_PK_KEY
+            )))
+    }
+
+    /// Value of the oracle's public key entry, as stored in the payload of OraclePODs: the full
+    /// hash of the key.
+    pub fn oracle(&self) -> Result<ScalarOrVec> {
+        self.self_value(ORACLE_PK_KEY)
+            .ok_or(Error::MissingStatement(format!(
+                "No oracle found in payload, expected a VALUEOF:{}",
+                ORACLE_PK_KEY
+            )))
+    }
 
+    /// Value of the `VALUEOF` statement about one of the POD's own entries. It is looked up in
+    /// the statements list, which is covered by the proof, rather than by name, and must be
+    /// unique.
+    fn self_value(&self, key: &str) -> Option<ScalarOrVec> {
+        let values = self
+            .payload
+            .statements_list
+            .iter()
+            .filter_map(|(_, statement)| match statement {
+                Statement::ValueOf(anchored_key, value)
+                    if anchored_key.0.is_self() && anchored_key.1 == key =>
+                {
+                    Some(value)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        match values.as_slice() {
+            [value] => Some((*value).clone()),
+            _ => None,
+        }
+    }
+
+    /// Payload of a SchnorrPOD: one `VALUEOF` statement per entry plus one for the signer,
+    /// padded to NS statements. The signer is padded as the other vectors if it fits in VL
+    /// elements, so that it equals entries holding it, and is left as such otherwise.
+    fn schnorr_payload<const NS: usize, const VL: usize>(
+        entries: &[Entry],
+        signer: ScalarOrVec,
+        gadget_id: GadgetID,
+    ) -> Result<PODPayload> {
+        let signer = Entry {
+            key: SIGNER_PK_KEY.to_string(),
+            value: signer,
+        };
         let kv_pairs = [
             entries
                 .iter()
                 .map(|e| e.pad_if_vec::<VL>())
-                .collect::<Result<Vec<_>>>()?,
-            vec![Entry {
-                key: SIGNER_PK_KEY.to_string(),
-                value: ScalarOrVec::Scalar(protocol.keygen(sk).pk),
-            }],
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            vec![signer.pad_if_vec::<VL>().unwrap_or(signer)],
         ]
         .concat();
 
@@ -159,7 +386,7 @@ impl POD {
                 .map(|e| {
                     (
                         format!("VALUEOF:{}", e.key),
-                        Statement::from_entry(e, GadgetID::SCHNORR16),
+                        Statement::from_entry(e, gadget_id),
                     )
                 })
                 .collect::<Vec<_>>()
@@ -167,28 +394,36 @@ impl POD {
         )?;
 
         let statement_map: HashMap<String, Statement> = statement_list.into_iter().collect();
+        Ok(PODPayload::new(&statement_map))
+    }
+
+    /// Signs the given entries with a Schnorr signature over ecgfp5. The signer entry holds the
+    /// full hash of the public key, which is carried in the proof.
+    pub fn execute_schnorr_gadget<const NS: usize, const VL: usize>(
+        entries: &[Entry],
+        sk: &EcSchnorrSecretKey,
+    ) -> Result<Self> {
+        let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
+        let protocol = EcSchnorrSigner::new();
+        let pk = protocol.keygen(sk);
 
-        let payload = PODPayload::new(&statement_map);
+        let payload = Self::schnorr_payload::<NS, VL>(
+            entries,
+            ScalarOrVec::from_hash(pk.hash()),
+            GadgetID::ECSCHNORR,
+        )?;
         let payload_hash = payload.hash_payload();
         let proof = protocol.sign(payload_hash.elements.as_ref(), sk, &mut rng);
         Ok(Self {
             payload,
-            proof: PODProof::Schnorr(proof),
-            proof_type: GadgetID::SCHNORR16,
+            proof: PODProof::EcSchnorr(pk, proof),
+            proof_type: GadgetID::ECSCHNORR,
         })
     }
 
-    pub fn introduce_pod1<const NS: usize, const VL: usize>(pod: Pod) -> Result<Self> {
-        // Check input POD.
-        let pod_is_valid = pod
-            .verify()
-            .map_err(|e| anyhow!("Could not verify POD: {:?}", e))?;
-
-        if !pod_is_valid {
-            return Err(anyhow!("POD verification failed."));
-        }
-
-        // Form POD entries.
+    /// Payload of the POD introducing the given POD1: one `VALUEOF` statement per entry plus one
+    /// for the signer's public key, padded to NS statements.
+    pub fn pod1_payload<const NS: usize, const VL: usize>(pod: &Pod) -> Result<PODPayload> {
         let signer_key: ScalarOrVec =
             Into::<ScalarOrVec>::into(PodValue::EdDSAPublicKey(pod.signer_public_key()));
         let entries = pod
@@ -196,49 +431,111 @@ impl POD {
             .iter()
             .map(|(s, pod_value)| Entry::new_from_pod_value(s, pod_value))
             .chain([Entry {
-                key: "_pod1_signer".to_string(),
+                key: POD1_SIGNER_KEY.to_string(),
                 value: signer_key,
             }])
-            .collect::<Vec<_>>();
+            .map(|e| e.pad_if_vec::<VL>())
+            .collect::<anyhow::Result<Vec<_>>>()?;
 
-        Self::execute_schnorr_gadget::<NS, VL>(&entries, &SchnorrSecretKey { sk: 0 })
+        let statement_list = POD::pad_statements::<NS>(
+            entries
+                .iter()
+                .map(|e| {
+                    (
+                        format!("VALUEOF:{}", e.key),
+                        Statement::from_entry(e, GadgetID::INTRODUCER),
+                    )
+                })
+                .collect::<Vec<_>>()
+                .as_ref(),
+        )?;
+
+        let statement_map: HashMap<String, Statement> = statement_list.into_iter().collect();
+        Ok(PODPayload::new(&statement_map))
+    }
+
+    /// Introduces a POD1 by proving its signature with the IntroducerCircuit, whose circuit_data
+    /// is passed as parameter for the same reasons as in `execute_plonky_gadget`.
+    pub fn introduce_pod1<const NS: usize, const VL: usize>(
+        pod: Pod,
+        pod1_circuit_data: &CircuitData<F, C, D>,
+    ) -> Result<Self> {
+        // Check input POD.
+        if !pod.verify()? {
+            return Err(Error::VerificationFailed(
+                "POD1 signature does not hold.".to_string(),
+            ));
+        }
+
+        let payload = Self::pod1_payload::<NS, VL>(&pod)?;
+        let proof =
+            IntroducerCircuit::<NS, VL>::prove(pod1_circuit_data, &pod).map_err(Error::proving)?;
+        Ok(Self {
+            payload,
+            proof: PODProof::Introducer(proof),
+            proof_type: GadgetID::INTRODUCER,
+        })
     }
 
-    pub fn execute_oracle_gadget(input: &GPGInput, cmds: &[OpCmd]) -> Result<Self> {
+    /// Applies the operations to the input PODs, returning the payload of the resulting POD.
+    pub fn execute_ops(input: &GPGInput, cmds: &[OpCmd]) -> Result<PODPayload> {
         let mut statements = input.remap_origin_ids_by_name()?;
         statements.insert("_SELF".to_string(), HashMap::new());
         for cmd in cmds {
             let OpCmd(op, output_name) = cmd;
-            let new_statement = op.execute(GadgetID::ORACLE, &statements)?;
-            statements.get_mut("_SELF").unwrap().insert(
-                format!(
-                    "{}:{}",
-                    new_statement.predicate(),
-                    Into::<String>::into(output_name.clone())
-                ),
-                new_statement,
+            let new_statement = op
+                .execute(GadgetID::ORACLE, &statements)
+                .map_err(|e| Error::InvalidInput(e.to_string()))?;
+            let name = format!(
+                "{}:{}",
+                new_statement.predicate(),
+                Into::<String>::into(output_name.clone())
             );
+            validate_statement_name(&name, &new_statement)
+                .map_err(|e| Error::InvalidInput(e.to_string()))?;
+            statements
+                .get_mut("_SELF")
+                .unwrap()
+                .insert(name, new_statement);
         }
         let out_statements = statements.get("_SELF").unwrap();
-        let out_payload = PODPayload::new(out_statements);
-        // println!("{:?}", out_payload);
-        let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
-        let protocol = SchnorrSigner::new();
-        let payload_hash = out_payload.hash_payload();
+        Ok(PODPayload::new(out_statements))
+    }
 
-        // signature is a hardcoded skey (currently 0)
-        // todo is to build a limited version of this with a ZKP
-        // would start by making it so that the ZKP only allows
-        // a max number of input PODs, max number of entries/statements per input POD,
-        // max number of statements for output POD, and some max number of each type of operation
-        let proof = protocol.sign(
-            payload_hash.elements.as_ref(),
-            &SchnorrSecretKey { sk: 0 },
-            &mut rng,
+    /// Applies the operations to the input PODs, the oracle signing the resulting payload
+    /// together with an entry holding the hash of its public key.
+    // todo is to build a limited version of this with a ZKP
+    // would start by making it so that the ZKP only allows
+    // a max number of input PODs, max number of entries/statements per input POD,
+    // max number of statements for output POD, and some max number of each type of operation
+    pub fn execute_oracle_gadget(
+        oracle: &Oracle,
+        input: &GPGInput,
+        cmds: &[OpCmd],
+    ) -> Result<Self> {
+        let mut out_statements = Self::execute_ops(input, cmds)?.statements_map;
+        let oracle_entry = Entry {
+            key: ORACLE_PK_KEY.to_string(),
+            value: ScalarOrVec::from_hash(oracle.public_key().hash()),
+        };
+        let oracle_statement_name = format!("VALUEOF:{}", ORACLE_PK_KEY);
+        if out_statements.contains_key(&oracle_statement_name) {
+            return Err(Error::InvalidInput(format!(
+                "The output statement name {} is reserved for the oracle's public key",
+                oracle_statement_name
+            )));
+        }
+        out_statements.insert(
+            oracle_statement_name,
+            Statement::from_entry(&oracle_entry, GadgetID::ORACLE),
         );
+        let out_payload = PODPayload::new(&out_statements);
+
+        let payload_hash = out_payload.hash_payload();
+        let signature = oracle.sign(&payload_hash.elements);
         Ok(Self {
             payload: out_payload,
-            proof: PODProof::Oracle(proof),
+            proof: PODProof::Oracle(oracle.public_key(), signature),
             proof_type: GadgetID::ORACLE,
         })
     }
@@ -266,18 +563,77 @@ impl POD {
             crate::pod::operation::OpList(cmds.to_vec()),
             input.origin_renaming_map.clone(),
         )
+        .map_err(Error::proving)
+    }
+
+    /// Re-proves a Plonky POD in a size-optimised config, for transport. The compressed POD
+    /// can be verified, but not used as an input of further PODs.
+    pub fn compress<
+        const L: usize,
+        const M: usize,
+        const N: usize,
+        const NS: usize,
+        const VL: usize,
+    >(
+        &self,
+    ) -> Result<Self>
+    where
+        [(); L + M + N]:,
+        [(); L + N]:,
+    {
+        CompressionCircuit::<L, M, N, NS, VL>::cached()
+            .and_then(|circuit| circuit.compress(self))
+            .map_err(Error::proving)
+    }
+
+    /// Same as `execute_plonky_gadget`, for any number of input PODs: these are verified in a
+    /// tree of Plonky PODs which copy the statements referenced by the operations up to its
+    /// root, where the operations are applied.
+    pub fn aggregate<
+        const L: usize,
+        const M: usize,
+        const N: usize,
+        const NS: usize,
+        const VL: usize,
+    >(
+        prover_params: &mut crate::pod::gadget::plonky_pod::ProverParams<L, M, N, NS, VL>,
+        inputs: Vec<(String, POD)>,
+        cmds: &[OpCmd],
+    ) -> Result<Self>
+    where
+        [(); L + M + N]:,
+        [(); L + N]:,
+    {
+        PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::aggregate(
+            prover_params,
+            &inputs,
+            crate::pod::operation::OpList(cmds.to_vec()),
+        )
+        .map_err(Error::proving)
+    }
+
+    /// Same as `execute_plonky_gadget`, with the circuit shape chosen at runtime among the
+    /// registered ones. The prover params of each shape are built on first use and then reused.
+    pub fn execute_plonky_gadget_with_shape(
+        shape: CircuitShape,
+        input: &GPGInput,
+        cmds: &[OpCmd],
+    ) -> Result<Self> {
+        shape
+            .dispatch(ExecutePlonkyGadget { input, cmds })
+            .map_err(|e| Error::InvalidInput(e.to_string()))?
     }
+
     fn pad_statements<const SIZE: usize>(
         statement_list: &[(String, Statement)],
     ) -> Result<Vec<(String, Statement)>> {
         let slice_len = statement_list.len();
 
         if slice_len > SIZE {
-            Err(anyhow!(
-                "Number of statements in {:?} exceeds maximum size {}.",
-                statement_list,
-                SIZE
-            ))
+            Err(Error::CapacityExceeded {
+                what: format!("statements, got {}", slice_len),
+                max: SIZE,
+            })
         } else {
             Ok([
                 statement_list.to_vec(),
@@ -290,6 +646,46 @@ impl POD {
     }
 }
 
+/// Verifies a POD with the circuits of the visited shape.
+struct VerifyPOD<'a>(&'a POD);
+
+impl ShapeVisitor for VerifyPOD<'_> {
+    type Output = Result<bool>;
+
+    fn visit<const L: usize, const M: usize, const N: usize, const NS: usize, const VL: usize>(
+        self,
+    ) -> Result<bool>
+    where
+        [(); L + M + N]:,
+        [(); L + N]:,
+    {
+        self.0.verify::<L, M, N, NS, VL>()
+    }
+}
+
+/// Generates a Plonky POD with the circuits of the visited shape.
+struct ExecutePlonkyGadget<'a> {
+    input: &'a GPGInput,
+    cmds: &'a [OpCmd],
+}
+
+impl ShapeVisitor for ExecutePlonkyGadget<'_> {
+    type Output = Result<POD>;
+
+    fn visit<const L: usize, const M: usize, const N: usize, const NS: usize, const VL: usize>(
+        self,
+    ) -> Result<POD>
+    where
+        [(); L + M + N]:,
+        [(); L + N]:,
+    {
+        let prover_params = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::cached_prover_params()
+            .map_err(Error::proving)?;
+        let mut prover_params = prover_params.lock().unwrap_or_else(|e| e.into_inner());
+        POD::execute_plonky_gadget(&mut prover_params, self.input, self.cmds)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GPGInput {
     /// ORDERED list of pods, ordered by names
@@ -342,7 +738,10 @@ impl GPGInput {
                     .pods_list
                     .iter()
                     .position(|(name, _)| name == pod_name)
-                    .ok_or(anyhow!("Error: POD {} missing from list!", pod_name))?;
+                    .ok_or(Error::OriginRemapError(format!(
+                        "POD {} missing from list!",
+                        pod_name
+                    )))?;
                 let (_, pod) = &self.pods_list[pod_index];
                 let origin_id = if origin_name == "NONE" {
                     GoldilocksField(0)
@@ -359,11 +758,10 @@ impl GPGInput {
                                 .collect::<Vec<_>>()
                         })
                         .find(|o| &o.origin_name == origin_name)
-                        .ok_or(anyhow!(
+                        .ok_or(Error::OriginRemapError(format!(
                             "Origin {} missing from the list of statements of POD '{}'!",
-                            origin_name,
-                            pod_name
-                        ))?
+                            origin_name, pod_name
+                        )))?
                         .origin_id
                 };
                 Ok((
@@ -372,12 +770,14 @@ impl GPGInput {
                         .get(
                             self.origin_renaming_map
                                 .get(&(pod_name.clone(), origin_name.clone()))
-                                .ok_or(anyhow!(
+                                .ok_or(Error::OriginRemapError(format!(
                                     "Missing pair {:?} in origin renaming map!",
                                     (pod_name, origin_name)
-                                ))?,
+                                )))?,
                         )
-                        .ok_or(anyhow!("Invalid new origin name to ID map!"))?,
+                        .ok_or(Error::OriginRemapError(
+                            "Invalid new origin name to ID map!".to_string(),
+                        ))?,
                 ))
             })
             .collect::<Result<HashMap<(usize, usize), usize>>>()
@@ -394,7 +794,9 @@ impl GPGInput {
             .iter()
             .map(|(_, p)| p.payload.statements_list.len())
             .max()
-            .ok_or(anyhow!("POD with empty statement list encountered!"))?;
+            .ok_or(Error::InvalidInput(
+                "POD with empty statement list encountered!".to_string(),
+            ))?;
         Ok((0..num_pods)
             .map(|i| {
                 (0..(num_statements + 2))
@@ -452,7 +854,9 @@ impl GPGInput {
                         .map(|(statement_name, statement)| {
                             Ok((
                                 statement_name.to_string(),
-                                statement.remap_origins(&origin_remapper)?,
+                                statement
+                                    .remap_origins(&origin_remapper)
+                                    .map_err(|e| Error::OriginRemapError(e.to_string()))?,
                             ))
                         })
                         .collect::<Result<HashMap<String, Statement>>>()?,
@@ -467,11 +871,16 @@ impl GPGInput {
 #[cfg(test)]
 mod tests {
     use crate::recursion::{traits_examples::ExampleIntroducer, IntroducerCircuitTrait};
+    use crate::signature::schnorr::SchnorrSecretKey;
     use operation::Operation as Op;
     use parcnet_pod::{pod::create_pod, pod_entries};
-    use statement::StatementRef;
+    use plonky2::hash::poseidon::PoseidonHash;
+    use plonky2::plonk::config::Hasher;
+    use statement::{AnchoredKey, StatementRef};
+    use value::HashableEntryValue;
 
     use super::*;
+    use anyhow::Result;
     #[test]
     fn op_test() -> Result<()> {
         // Start with some values.
@@ -499,7 +908,7 @@ mod tests {
         // Anchored keys for later reference
         let anchkeys1 = entry_statement1.anchored_keys();
         let anchkeys2 = entry_statement2.anchored_keys();
-        let _anchkeys3 = entry_statement3.anchored_keys();
+        let anchkeys3 = entry_statement3.anchored_keys();
         let anchkeys4 = entry_statement4.anchored_keys();
         let _anchkeys5 = entry_statement5.anchored_keys();
         let anchkeys6 = entry_statement6.anchored_keys();
@@ -521,6 +930,244 @@ mod tests {
                 )
         );
 
+        // Entry 7's value = hash(entry 1's value, entry 3's value), where entry 3 holds a vector
+        let entry7 = Entry::new_from_vec(
+            "hash",
+            PoseidonHash::hash_no_pad(&[scalar1, entry3.value.hash_or_value()])
+                .elements
+                .to_vec(),
+        );
+        let entry_statement7 = Statement::from_entry(&entry7, GadgetID::NONE);
+        assert!(
+            Op::HashOf(
+                entry_statement7.clone(),
+                vec![entry_statement1.clone(), entry_statement3.clone()]
+            )
+            .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::HashOf(
+                    entry_statement7.anchored_keys()[0].clone(),
+                    vec![anchkeys1[0].clone(), anchkeys3[0].clone()]
+                )
+        );
+        assert!(Op::HashOf(
+            entry_statement7.clone(),
+            vec![entry_statement3.clone(), entry_statement1.clone()]
+        )
+        .eval_with_gadget_id(GadgetID::NONE)
+        .is_err());
+
+        // Single inputs are hashed alone, and more than `MAX_HASH_INPUTS` are refused.
+        let entry7_single = Entry::new_from_vec(
+            "hash_single",
+            PoseidonHash::hash_no_pad(&[scalar1]).elements.to_vec(),
+        );
+        let entry_statement7_single = Statement::from_entry(&entry7_single, GadgetID::NONE);
+        let hash_of_single = Op::HashOf(
+            entry_statement7_single.clone(),
+            vec![entry_statement1.clone()],
+        );
+        assert_eq!(hash_of_single.code(), Op::<Statement>::HASH_OF_SINGLE);
+        assert!(
+            hash_of_single.eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::HashOf(
+                    entry_statement7_single.anchored_keys()[0].clone(),
+                    vec![anchkeys1[0].clone()]
+                )
+        );
+        assert!(
+            Op::HashOf(entry_statement7.clone(), vec![entry_statement1.clone()])
+                .eval_with_gadget_id(GadgetID::NONE)
+                .is_err()
+        );
+        assert!(Op::HashOf(
+            entry_statement7.clone(),
+            vec![
+                entry_statement1.clone(),
+                entry_statement3.clone(),
+                entry_statement1.clone()
+            ]
+        )
+        .eval_with_gadget_id(GadgetID::NONE)
+        .is_err());
+        assert!(Op::HashOf(entry_statement7.clone(), vec![])
+            .eval_with_gadget_id(GadgetID::NONE)
+            .is_err());
+
+        // Nullifier of the secret of the owner key in entry 8, in the scope of entry 2
+        let secret = owner::OwnerSecretKey::new([GoldilocksField(1234); 4]);
+        let entry8 = Entry::new_from_vec("owner", secret.public_key().elements.to_vec());
+        let entry_statement8 = Statement::from_entry(&entry8, GadgetID::NONE);
+        assert!(
+            Op::NullifierFromOwnerKey(entry_statement8.clone(), entry_statement2.clone(), secret)
+                .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::Nullifier(
+                    entry_statement8.anchored_keys()[0].clone(),
+                    anchkeys2[0].clone(),
+                    secret.nullifier(scalar2)
+                )
+        );
+        assert_ne!(secret.nullifier(scalar2), secret.nullifier(scalar1));
+        let wrong_secret_op = Op::NullifierFromOwnerKey(
+            entry_statement8.clone(),
+            entry_statement2.clone(),
+            owner::OwnerSecretKey::new([GoldilocksField(4321); 4]),
+        );
+        assert!(wrong_secret_op.eval_with_gadget_id(GadgetID::NONE).is_err());
+        assert!(!format!("{:?}", wrong_secret_op).contains("4321"));
+
+        // Entry 6's value = entry 2's value - entry 1's value, but not the other way round
+        assert!(
+            Op::DifferenceOf(
+                entry_statement6.clone(),
+                entry_statement2.clone(),
+                entry_statement1.clone()
+            )
+            .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::DifferenceOf(
+                    anchkeys6[0].clone(),
+                    anchkeys2[0].clone(),
+                    anchkeys1[0].clone()
+                )
+        );
+        assert!(Op::DifferenceOf(
+            entry_statement6.clone(),
+            entry_statement1.clone(),
+            entry_statement2.clone()
+        )
+        .eval_with_gadget_id(GadgetID::NONE)
+        .is_err());
+        // Entry 6's value = min(entry 1's value, entry 6's value)
+        assert!(
+            Op::MinOf(
+                entry_statement6.clone(),
+                entry_statement1.clone(),
+                entry_statement6.clone()
+            )
+            .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::MinOf(
+                    anchkeys6[0].clone(),
+                    anchkeys1[0].clone(),
+                    anchkeys6[0].clone()
+                )
+        );
+        // 52 = 3 * 16 + 4, and 36 = 2 * 16 + 4
+        let entry9 = Entry::new_from_scalar("quotient", GoldilocksField(3));
+        let entry10 = Entry::new_from_scalar("remainder", GoldilocksField(4));
+        let entry_statement9 = Statement::from_entry(&entry9, GadgetID::NONE);
+        let entry_statement10 = Statement::from_entry(&entry10, GadgetID::NONE);
+        assert!(
+            Op::QuotientOf(
+                entry_statement9.clone(),
+                entry_statement2.clone(),
+                entry_statement6.clone()
+            )
+            .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::QuotientRemainderOf(
+                    entry_statement9.anchored_keys()[0].clone(),
+                    anchkeys2[0].clone(),
+                    anchkeys6[0].clone(),
+                    false
+                )
+        );
+        assert!(
+            Op::RemainderOf(
+                entry_statement10.clone(),
+                entry_statement1.clone(),
+                entry_statement6.clone()
+            )
+            .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::QuotientRemainderOf(
+                    entry_statement10.anchored_keys()[0].clone(),
+                    anchkeys1[0].clone(),
+                    anchkeys6[0].clone(),
+                    true
+                )
+        );
+        assert!(Op::QuotientOf(
+            entry_statement9.clone(),
+            entry_statement1.clone(),
+            entry_statement6.clone()
+        )
+        .eval_with_gadget_id(GadgetID::NONE)
+        .is_err());
+        // Division by zero
+        let zero_statement = Statement::from_entry(
+            &Entry::new_from_scalar("zero", GoldilocksField::ZERO),
+            GadgetID::NONE,
+        );
+        assert!(Op::RemainderOf(
+            entry_statement10.clone(),
+            entry_statement1.clone(),
+            zero_statement
+        )
+        .eval_with_gadget_id(GadgetID::NONE)
+        .is_err());
+        // Negations are of signed integers, as in `ScalarOrVec::from(i64)`.
+        let int_statement = |key: &str, x: i64| {
+            Statement::from_entry(
+                &Entry {
+                    key: key.to_string(),
+                    value: ScalarOrVec::from(x),
+                },
+                GadgetID::NONE,
+            )
+        };
+        let entry_statement11 = int_statement("negated", -36);
+        let entry_statement12 = int_statement("signed", 36);
+        assert!(
+            Op::NegationOf(entry_statement11.clone(), entry_statement12.clone())
+                .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::NegationOf(
+                    entry_statement11.anchored_keys()[0].clone(),
+                    entry_statement12.anchored_keys()[0].clone()
+                )
+        );
+        assert!(
+            Op::NegationOf(entry_statement12.clone(), entry_statement12.clone())
+                .eval_with_gadget_id(GadgetID::NONE)
+                .is_err()
+        );
+        // -36 is the negation of 36 in the field, but field elements are not signed integers
+        let entry_statement13 = Statement::from_entry(
+            &Entry::new_from_scalar("field negated", -scalar1),
+            GadgetID::NONE,
+        );
+        assert!(
+            Op::NegationOf(entry_statement13.clone(), entry_statement1.clone())
+                .eval_with_gadget_id(GadgetID::NONE)
+                .is_err()
+        );
+        assert_eq!(
+            ScalarOrVec::from(i64::MIN).negation(),
+            Some(ScalarOrVec::from(i64::MAX as u64 + 1))
+        );
+        assert_eq!(
+            ScalarOrVec::from(u64::MAX)
+                .negation()
+                .and_then(|x| x.negation()),
+            Some(ScalarOrVec::from(u64::MAX))
+        );
+        // Padding keeps negations.
+        let [negated, signed] = [-36i64, 36].map(|x| {
+            Entry {
+                key: "padded".to_string(),
+                value: ScalarOrVec::from(x),
+            }
+            .pad_if_vec::<5>()
+        });
+        assert_eq!(signed?.value.negation(), Some(negated?.value));
+        // Limbs must be u32s and the first one a sign extension, in the input and the result.
+        [
+            vec![1 << 31, 0, 0],
+            vec![0, 1 << 32],
+            vec![u32::MAX as u64, 0, 0],
+        ]
+        .into_iter()
+        .for_each(|limbs| {
+            let x = ScalarOrVec::Vector(limbs.into_iter().map(GoldilocksField).collect());
+            assert_eq!(x.negation(), None);
+        });
+
         let entries = [&entry_statement1, &entry_statement2, &entry_statement3];
 
         // Copy statements and check for equality of entries.
@@ -569,6 +1216,151 @@ mod tests {
             .eval_with_gadget_id(GadgetID::NONE)?;
         assert!(lt_statement == Statement::Lt(anchkeys1[0].clone(), anchkeys2[0].clone()));
 
+        // Gte, Lte and InRange checks, whose bounds are inclusive
+        assert!(
+            Op::GteFromEntries(entry_statement2.clone(), entry_statement1.clone())
+                .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::Gte(anchkeys2[0].clone(), anchkeys1[0].clone())
+        );
+        assert!(
+            Op::GteFromEntries(entry_statement4.clone(), entry_statement1.clone())
+                .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::Gte(anchkeys4[0].clone(), anchkeys1[0].clone())
+        );
+        assert!(
+            Op::GteFromEntries(entry_statement1.clone(), entry_statement2.clone())
+                .eval_with_gadget_id(GadgetID::NONE)
+                .is_err()
+        );
+        assert!(
+            Op::LteFromEntries(entry_statement1.clone(), entry_statement4.clone())
+                .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::Lte(anchkeys1[0].clone(), anchkeys4[0].clone())
+        );
+        assert!(
+            Op::LteFromEntries(entry_statement2.clone(), entry_statement1.clone())
+                .eval_with_gadget_id(GadgetID::NONE)
+                .is_err()
+        );
+        assert!(
+            Op::InRangeFromEntries(
+                entry_statement1.clone(),
+                entry_statement4.clone(),
+                entry_statement2.clone()
+            )
+            .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::InRange(
+                    anchkeys1[0].clone(),
+                    anchkeys4[0].clone(),
+                    anchkeys2[0].clone()
+                )
+        );
+        assert!(Op::InRangeFromEntries(
+            entry_statement2.clone(),
+            entry_statement1.clone(),
+            entry_statement4.clone()
+        )
+        .eval_with_gadget_id(GadgetID::NONE)
+        .is_err());
+
+        // Membership check
+        let tree = merkle::MerkleTree::new(&[scalar3, scalar1])?;
+        let root_statement = Statement::from_entry(
+            &Entry::new_from_scalar("allowlist", tree.root()),
+            GadgetID::NONE,
+        );
+        let proof = tree.prove(scalar1).ok_or(anyhow!("scalar1 is a member"))?;
+        assert!(
+            Op::ContainedInRootFromEntries(
+                entry_statement1.clone(),
+                root_statement.clone(),
+                proof.clone()
+            )
+            .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::ContainedInRoot(
+                    anchkeys1[0].clone(),
+                    root_statement.anchored_keys()[0].clone()
+                )
+        );
+        assert!(Op::ContainedInRootFromEntries(
+            entry_statement2.clone(),
+            root_statement.clone(),
+            proof
+        )
+        .eval_with_gadget_id(GadgetID::NONE)
+        .is_err());
+        // vectors are members by their hash
+        let tree = merkle::MerkleTree::new(&[scalar3, vector_value.hash_or_value()])?;
+        let root_statement = Statement::from_entry(
+            &Entry::new_from_scalar("allowlist", tree.root()),
+            GadgetID::NONE,
+        );
+        let proof = tree
+            .prove(vector_value.hash_or_value())
+            .ok_or(anyhow!("vector_value is a member"))?;
+        assert!(
+            Op::ContainedInRootFromEntries(entry_statement3.clone(), root_statement.clone(), proof)
+                .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::ContainedInRoot(
+                    anchkeys3[0].clone(),
+                    root_statement.anchored_keys()[0].clone()
+                )
+        );
+        let hashes_entry =
+            Entry::new_from_vec("hashes", vec![scalar3, vector_value.hash_or_value()]);
+        let hashes_statement = Statement::from_entry(&hashes_entry, GadgetID::NONE);
+        assert!(
+            Op::ContainsFromEntries(hashes_statement.clone(), entry_statement3.clone())
+                .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::Contains(
+                    hashes_statement.anchored_keys()[0].clone(),
+                    anchkeys3[0].clone()
+                )
+        );
+        assert!(
+            Op::ContainsFromEntries(entry_statement3.clone(), entry_statement3.clone())
+                .eval_with_gadget_id(GadgetID::NONE)
+                .is_err()
+        );
+
+        // Non-membership check
+        let tree = merkle::SortedMerkleTree::new(&[scalar3, scalar1])?;
+        let root_statement = Statement::from_entry(
+            &Entry::new_from_scalar("revoked", tree.root()),
+            GadgetID::NONE,
+        );
+        let proof = tree
+            .prove_non_membership(scalar2)
+            .ok_or(anyhow!("scalar2 is not revoked"))?;
+        assert!(
+            Op::NotContainedInRootFromEntries(
+                entry_statement2.clone(),
+                root_statement.clone(),
+                proof.clone()
+            )
+            .eval_with_gadget_id(GadgetID::NONE)?
+                == Statement::NotContainedInRoot(
+                    anchkeys2[0].clone(),
+                    root_statement.anchored_keys()[0].clone()
+                )
+        );
+        assert!(Op::NotContainedInRootFromEntries(
+            entry_statement1.clone(),
+            root_statement.clone(),
+            proof
+        )
+        .eval_with_gadget_id(GadgetID::NONE)
+        .is_err());
+        // Sorted trees hash in their own domain, so their members are not contained in them.
+        let sorted_proof = tree.prove(scalar1).ok_or(anyhow!("scalar1 is revoked"))?;
+        assert!(Op::ContainedInRootFromEntries(
+            entry_statement1.clone(),
+            root_statement.clone(),
+            sorted_proof
+        )
+        .eval_with_gadget_id(GadgetID::NONE)
+        .is_err());
+
         // Eq transitivity check
         let eq_statement1 =
             Op::EqualityFromEntries(entry_statement4.clone(), entry_statement1.clone())
@@ -603,6 +1395,101 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn comparison_test() -> Result<()> {
+        let statement = |key: &str, value: ScalarOrVec| -> Result<Statement> {
+            let entry = Entry {
+                key: key.to_string(),
+                value,
+            };
+            Ok(Statement::from_entry(
+                &entry.pad_if_vec::<10>()?,
+                GadgetID::NONE,
+            ))
+        };
+        let gt = |s1: &Statement, s2: &Statement| {
+            Op::GtFromEntries(s1.clone(), s2.clone())
+                .eval_with_gadget_id(GadgetID::NONE)
+                .is_ok()
+        };
+        let lt = |s1: &Statement, s2: &Statement| {
+            Op::LtFromEntries(s1.clone(), s2.clone())
+                .eval_with_gadget_id(GadgetID::NONE)
+                .is_ok()
+        };
+
+        // Scalars are compared over the whole field.
+        let big1 = statement("big1", ScalarOrVec::Scalar(GoldilocksField(1 << 40)))?;
+        let big2 = statement("big2", ScalarOrVec::Scalar(-GoldilocksField::ONE))?;
+        assert!(gt(&big2, &big1) && lt(&big1, &big2));
+        assert!(!gt(&big1, &big2) && !lt(&big2, &big1) && !gt(&big1, &big1));
+
+        // Statements of increasing values are ordered as such.
+        let assert_increasing = |values: &[ScalarOrVec]| -> Result<Vec<Statement>> {
+            let statements = values
+                .iter()
+                .enumerate()
+                .map(|(i, x)| statement(&format!("v{}", i), x.clone()))
+                .collect::<Result<Vec<_>>>()?;
+            for (i, s1) in statements.iter().enumerate() {
+                for (j, s2) in statements.iter().enumerate() {
+                    assert_eq!(gt(s1, s2), i > j);
+                    assert_eq!(lt(s1, s2), i < j);
+                    assert_eq!(
+                        Op::GteFromEntries(s1.clone(), s2.clone())
+                            .eval_with_gadget_id(GadgetID::NONE)
+                            .is_ok(),
+                        i >= j
+                    );
+                }
+            }
+            Ok(statements)
+        };
+
+        // Vectors are compared as integers, be they i64s or u64s, also once padded.
+        let statements = assert_increasing(&[
+            ScalarOrVec::from(i64::MIN),
+            ScalarOrVec::from(-(1i64 << 40)),
+            ScalarOrVec::from(-1i64),
+            ScalarOrVec::from(0u64),
+            ScalarOrVec::from(1u64 << 32),
+            ScalarOrVec::from(1_700_000_000_000i64),
+            ScalarOrVec::from(i64::MAX),
+            ScalarOrVec::from(i64::MAX as u64 + 1),
+            ScalarOrVec::from(u64::MAX),
+        ])?;
+        assert_eq!(
+            ScalarOrVec::from(i64::MAX),
+            ScalarOrVec::from(i64::MAX as u64)
+        );
+
+        // All limbs are compared, most significant first, the first one holding the sign.
+        let limbs =
+            |v: &[u64]| ScalarOrVec::Vector(v.iter().map(|x| GoldilocksField(*x)).collect());
+        assert_increasing(&[
+            limbs(&[u32::MAX as u64, 0, 0, 0]),
+            limbs(&[
+                u32::MAX as u64,
+                u32::MAX as u64,
+                u32::MAX as u64,
+                u32::MAX as u64,
+            ]),
+            limbs(&[0, 0, 0, 0]),
+            limbs(&[0, 0, 0, 1]),
+            limbs(&[0, 0, 1, 0]),
+            limbs(&[0, 1, 0, 0]),
+            limbs(&[(1 << 31) - 1, 0, 0, 0]),
+        ])?;
+        // Vectors of limbs beyond u32 or of different lengths are not comparable.
+        assert_eq!(limbs(&[0, 1 << 32]).numeric_cmp(&limbs(&[0, 0])), None);
+        assert_eq!(limbs(&[0, 0, 1]).numeric_cmp(&limbs(&[0, 0])), None);
+
+        // Scalars and vectors are not comparable.
+        let small = statement("small", ScalarOrVec::Scalar(GoldilocksField(0)))?;
+        assert!(!gt(&statements[6], &small) && !lt(&small, &statements[6]));
+        Ok(())
+    }
+
     #[test]
     fn schnorr_pod_test() -> Result<()> {
         const NS: usize = 3;
@@ -618,23 +1505,25 @@ mod tests {
         let entry3 = Entry::new_from_vec("vector entry", vector_value.clone());
 
         let other_entry = Entry::new_from_scalar("some key", GoldilocksField(37));
-        let other_statement = Statement::from_entry(&other_entry, GadgetID::SCHNORR16);
+        let other_statement = Statement::from_entry(&other_entry, GadgetID::ECSCHNORR);
 
         let schnorr_pod1 = POD::execute_schnorr_gadget::<NS, VL>(
             &[entry1.clone(), entry2.clone()],
-            &SchnorrSecretKey { sk: 25 },
+            &EcSchnorrSecretKey::from(25),
         )?;
 
         let schnorr_pod2 = POD::execute_schnorr_gadget::<NS, VL>(
             &[entry2.clone(), entry3.clone()],
-            &SchnorrSecretKey { sk: 42 },
+            &EcSchnorrSecretKey::from(42),
         )?;
 
         assert!(schnorr_pod1.verify::<0, 3, 2, 2, 0>()?); // TODO use L!=0
         assert!(schnorr_pod2.verify::<0, 3, 2, 2, 0>()?); // TODO use L!=0
 
-        let mut schnorr_pod3 =
-            POD::execute_schnorr_gadget::<NS, VL>(&[entry1.clone()], &SchnorrSecretKey { sk: 25 })?;
+        let mut schnorr_pod3 = POD::execute_schnorr_gadget::<NS, VL>(
+            &[entry1.clone()],
+            &EcSchnorrSecretKey::from(25),
+        )?;
 
         // modify the internal value of the valueOf statement in schnorrPOD3
         schnorr_pod3
@@ -646,6 +1535,132 @@ mod tests {
         // now signature shouldn't verify
         assert!(!(schnorr_pod3.verify::<0, 3, 2, 2, 0>()?)); // TODO use L!=0
 
+        // neither should a signature by a key other than the signer's
+        let mut schnorr_pod4 = schnorr_pod1.clone();
+        schnorr_pod4.proof = schnorr_pod2.proof.clone();
+        assert!(!(schnorr_pod4.verify::<0, 3, 2, 2, 0>()?)); // TODO use L!=0
+
+        Ok(())
+    }
+
+    #[test]
+    fn legacy_schnorr16_pod_test() -> Result<()> {
+        const NS: usize = 3;
+        const VL: usize = 0;
+
+        // PODs issued before ECSCHNORR still verify.
+        let protocol = SchnorrSigner::new();
+        let sk = SchnorrSecretKey { sk: 25 };
+        let entry1 = Entry::new_from_scalar("some key", GoldilocksField(36));
+        let payload = POD::schnorr_payload::<NS, VL>(
+            &[entry1],
+            ScalarOrVec::Scalar(protocol.keygen(&sk).pk),
+            GadgetID::SCHNORR16,
+        )?;
+        let proof = protocol.sign(
+            payload.hash_payload().elements.as_ref(),
+            &sk,
+            &mut rand::thread_rng(),
+        );
+        let pod = POD {
+            payload,
+            proof: PODProof::Schnorr(proof),
+            proof_type: GadgetID::SCHNORR16,
+        };
+        assert!(pod.verify::<0, 3, 2, 2, 0>()?); // TODO use L!=0
+
+        // the signer's key is read from the signed statements, so a map pointing at another key
+        // whose owner signed the payload is rejected
+        let other_sk = SchnorrSecretKey { sk: 42 };
+        let other_signer = Entry::new_from_scalar(SIGNER_PK_KEY, protocol.keygen(&other_sk).pk);
+        let mut forged_pod = pod.clone();
+        forged_pod.payload.statements_map.insert(
+            format!("VALUEOF:{}", SIGNER_PK_KEY),
+            Statement::from_entry(&other_signer, GadgetID::SCHNORR16),
+        );
+        forged_pod.proof = PODProof::Schnorr(protocol.sign(
+            pod.payload.hash_payload().elements.as_ref(),
+            &other_sk,
+            &mut rand::thread_rng(),
+        ));
+        assert!(forged_pod.verify::<0, 3, 2, 2, 0>().is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn payload_validation_test() -> Result<()> {
+        const NS: usize = 4;
+        const VL: usize = 0;
+
+        let pod = POD::execute_schnorr_gadget::<NS, VL>(
+            &[
+                Entry::new_from_scalar("a", GoldilocksField(36)),
+                Entry::new_from_scalar("b", GoldilocksField(52)),
+            ],
+            &EcSchnorrSecretKey::from(25),
+        )?;
+        pod.payload.validate()?;
+        let serialized = serde_json::to_string(&pod)?;
+        assert_eq!(serde_json::from_str::<POD>(&serialized)?, pod);
+
+        let tamper = |f: &dyn Fn(&mut PODPayload)| -> Result<()> {
+            let mut tampered_pod = pod.clone();
+            f(&mut tampered_pod.payload);
+            assert!(tampered_pod.payload.validate().is_err());
+            assert!(tampered_pod.verify::<0, 3, 2, NS, VL>().is_err());
+            let serialized = serde_json::to_string(&tampered_pod)?;
+            assert!(serde_json::from_str::<POD>(&serialized).is_err());
+            Ok(())
+        };
+        // map and list disagree
+        tamper(&|payload| {
+            payload.statements_map.insert(
+                "VALUEOF:a".to_string(),
+                Statement::from_entry(
+                    &Entry::new_from_scalar("a", GoldilocksField(37)),
+                    GadgetID::ECSCHNORR,
+                ),
+            );
+        })?;
+        tamper(&|payload| {
+            payload.statements_map.remove("VALUEOF:a");
+        })?;
+        // unsorted list
+        tamper(&|payload| payload.statements_list.swap(0, 1))?;
+        // duplicate names
+        tamper(&|payload| {
+            payload.statements_list[1].0 = payload.statements_list[0].0.clone();
+        })?;
+        // name not matching the predicate, the list being sorted as
+        // [VALUEOF:_signer, VALUEOF:a, VALUEOF:b, _DUMMY_STATEMENT3]
+        tamper(&|payload| {
+            let statement = payload.statements_map.remove("VALUEOF:b").unwrap();
+            payload
+                .statements_map
+                .insert("XOR:b".to_string(), statement);
+            payload.statements_list[2].0 = "XOR:b".to_string();
+        })?;
+        // entries relabelled in the same order, a's value being looked up as b's
+        tamper(&|payload| {
+            let a = payload.statements_map.remove("VALUEOF:a").unwrap();
+            let b = payload.statements_map.remove("VALUEOF:b").unwrap();
+            payload.statements_map.insert("VALUEOF:b".to_string(), a);
+            payload.statements_map.insert("VALUEOF:b0".to_string(), b);
+            payload.statements_list[1].0 = "VALUEOF:b".to_string();
+            payload.statements_list[2].0 = "VALUEOF:b0".to_string();
+        })?;
+        // origin renamed
+        tamper(&|payload| {
+            let rename = |statement: &mut Statement| {
+                if let Statement::ValueOf(AnchoredKey(origin, _), _) = statement {
+                    origin.origin_name = "alice".to_string();
+                }
+            };
+            rename(payload.statements_map.get_mut("VALUEOF:a").unwrap());
+            rename(&mut payload.statements_list[1].1);
+        })?;
+
         Ok(())
     }
 
@@ -653,6 +1668,7 @@ mod tests {
     // but i've manually inspected output and it looks good
     #[test]
     fn oracle_pod_from_schnorr_test() -> Result<()> {
+        let oracle = Oracle::random(&mut rand::thread_rng());
         const NS: usize = 4;
         const VL: usize = 10;
 
@@ -676,12 +1692,12 @@ mod tests {
         // three schnorr pods
         let schnorr_pod1 = POD::execute_schnorr_gadget::<NS, VL>(
             &[entry1.clone(), entry2.clone()],
-            &SchnorrSecretKey { sk: 25 },
+            &EcSchnorrSecretKey::from(25),
         )?;
 
         let schnorr_pod2 = POD::execute_schnorr_gadget::<NS, VL>(
             &[entry3.clone(), entry4.clone()],
-            &SchnorrSecretKey { sk: 42 },
+            &EcSchnorrSecretKey::from(42),
         )?;
         // make an OraclePOD using from_pods called on the two schnorr PODs
 
@@ -757,14 +1773,31 @@ mod tests {
             ),
         ];
 
-        let oracle_pod = POD::execute_oracle_gadget(&gpg_input, &ops).unwrap();
-        assert!(oracle_pod.verify::<0, 3, 2, 2, 0>()?); // TODO use L!=0
+        let oracle_pod = POD::execute_oracle_gadget(&oracle, &gpg_input, &ops).unwrap();
+        assert!(oracle_pod.verify_oracle(&[oracle.public_key()])?);
+        assert_eq!(
+            oracle_pod.oracle()?,
+            ScalarOrVec::from_hash(oracle.public_key().hash())
+        );
+
+        // the POD is only accepted if its oracle is trusted
+        let other_oracle = Oracle::random(&mut rand::thread_rng());
+        assert!(!oracle_pod.verify_oracle(&[other_oracle.public_key()])?);
+        assert!(oracle_pod.verify::<0, 3, 2, 2, 0>().is_err());
+        // and its proof can not be reused with another payload, or with another oracle's key
+        let mut tampered_pod = oracle_pod.clone();
+        tampered_pod.payload = POD::execute_oracle_gadget(&oracle, &gpg_input, &ops[..2])?.payload;
+        assert!(!tampered_pod.verify_oracle(&[oracle.public_key()])?);
+        let other_pod = POD::execute_oracle_gadget(&other_oracle, &gpg_input, &ops)?;
+        let mut tampered_pod = oracle_pod.clone();
+        tampered_pod.proof = other_pod.proof.clone();
+        assert!(!tampered_pod.verify_oracle(&[oracle.public_key(), other_oracle.public_key()])?);
 
         // make another oracle POD which takes that oracle POD and a schnorr POD
 
         let schnorr_pod3 = POD::execute_schnorr_gadget::<NS, VL>(
             &[entry5.clone(), entry6.clone(), entry7.clone()],
-            &SchnorrSecretKey { sk: 83 },
+            &EcSchnorrSecretKey::from(83),
         )?;
 
         // make the GPG input
@@ -805,10 +1838,10 @@ mod tests {
                 ),
                 "EQUAL:transitive eq",
             ),
-            OpCmd::new(Op::NewEntry(entry9.clone()), "entry for claimed sum"),
+            OpCmd::new(Op::NewEntry(entry9.clone()), "claimed sum"),
             OpCmd::new(
                 Op::SumOf(
-                    StatementRef::new("_SELF", "VALUEOF:entry for claimed sum"),
+                    StatementRef::new("_SELF", "VALUEOF:claimed sum"),
                     StatementRef::new("oraclePODParent", "VALUEOF:p1-apple"),
                     StatementRef::new("oraclePODParent", "VALUEOF:a scalar entry"),
                 ),
@@ -837,11 +1870,11 @@ mod tests {
             ),
         ];
 
-        let oracle_pod2 = POD::execute_oracle_gadget(&gpg_input, &ops).unwrap();
+        let oracle_pod2 = POD::execute_oracle_gadget(&oracle, &gpg_input, &ops).unwrap();
         for statement in oracle_pod2.payload.statements_list.iter() {
             println!("{:?}", statement);
         }
-        assert!(oracle_pod2.verify::<0, 3, 2, 2, 0>()?); // TODO use L!=0
+        assert!(oracle_pod2.verify_oracle(&[oracle.public_key()])?);
         Ok(())
     }
 
@@ -868,12 +1901,12 @@ mod tests {
         // two schnorr pods
         let schnorr_pod1 = POD::execute_schnorr_gadget::<NS, VL>(
             &[entry1.clone(), entry2.clone()],
-            &SchnorrSecretKey { sk: 25 },
+            &EcSchnorrSecretKey::from(25),
         )?;
 
         let schnorr_pod2 = POD::execute_schnorr_gadget::<NS, VL>(
             &[entry3.clone(), entry4.clone()],
-            &SchnorrSecretKey { sk: 42 },
+            &EcSchnorrSecretKey::from(42),
         )?;
         // make a PlonkyPOD using from_pods called on the two schnorr PODs
 
@@ -912,6 +1945,8 @@ mod tests {
         let pod1_verifier_data = pod1_circuit_data.verifier_data();
         let circuit_data =
             PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::circuit_data(pod1_verifier_data)?;
+        let verifier =
+            Verifier::<L, M, N, NS, VL>::from_verifier_data(None, circuit_data.verifier_data());
         let mut prover_params = PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::build_prover_params(
             pod1_circuit_data,
             circuit_data,
@@ -919,6 +1954,16 @@ mod tests {
         let plonky_pod =
             POD::execute_plonky_gadget::<L, M, N, NS, VL>(&mut prover_params, &gpg_input, &ops)?;
         assert!(plonky_pod.verify::<L, M, N, NS, VL>()?);
+        // reusing the verifier data of the circuit that generated the proof
+        assert!(plonky_pod.verify_with(&verifier)?);
+        // the POD records the shape of its circuit, and is rejected by the other ones
+        assert_eq!(
+            plonky_pod.shape(),
+            Some(CircuitShape::of::<L, M, N, NS, VL>())
+        );
+        assert!(plonky_pod.verify::<L, M, N, 4, VL>().is_err());
+        // and as its shape is not registered, it can not be verified by shape
+        assert!(plonky_pod.verify_by_shape().is_err());
 
         // make another oracle POD which takes that oracle POD and a schnorr POD
 
@@ -942,11 +1987,11 @@ mod tests {
         // make a list of the operations we want to call
 
         let ops = vec![
-            OpCmd::new(Op::NewEntry(entry4.clone()), "new entry for equality"),
+            OpCmd::new(Op::NewEntry(entry4.clone()), "scalar entry"),
             OpCmd::new(
                 Op::EqualityFromEntries(
                     StatementRef::new("parent", "VALUEOF:p1-banana"),
-                    StatementRef::new("_SELF", "VALUEOF:new entry for equality"),
+                    StatementRef::new("_SELF", "VALUEOF:scalar entry"),
                 ),
                 "equality of banana and new entry",
             ),
@@ -968,46 +2013,51 @@ mod tests {
 
     #[test]
     fn goodboy_test() -> Result<()> {
+        let oracle = Oracle::random(&mut rand::thread_rng());
         const NS: usize = 3;
-        const VL: usize = 0;
+        const VL: usize = 4;
 
         // A HackMD detailing execution and how each statement gets deduced is available here https://hackmd.io/@gubsheep/B1Rajmik1g
 
-        let protocol = SchnorrSigner::new();
-
-        let alice_sk = SchnorrSecretKey { sk: 25 };
-        let alice_pk = protocol.keygen(&alice_sk).pk;
-        let bob_sk = SchnorrSecretKey { sk: 26 };
-        let bob_pk = protocol.keygen(&bob_sk).pk;
-        let charlie_sk = SchnorrSecretKey { sk: 27 };
-        let charlie_pk = protocol.keygen(&charlie_sk).pk;
-
-        let goog_sk = SchnorrSecretKey { sk: 28 };
-        let goog_pk = protocol.keygen(&goog_sk).pk;
-        let msft_sk = SchnorrSecretKey { sk: 29 };
-        let msft_pk = protocol.keygen(&msft_sk).pk;
-        let fb_sk = SchnorrSecretKey { sk: 30 };
-        let fb_pk = protocol.keygen(&fb_sk).pk;
-
-        let known_attestors = vec![goog_pk, msft_pk, fb_pk];
-
-        let gb1_user = Entry::new_from_scalar("user", bob_pk);
+        let protocol = EcSchnorrSigner::new();
+
+        let alice_sk = EcSchnorrSecretKey::from(25);
+        let alice_pk = protocol.keygen(&alice_sk).hash();
+        let bob_sk = EcSchnorrSecretKey::from(26);
+        let bob_pk = protocol.keygen(&bob_sk).hash();
+        let charlie_sk = EcSchnorrSecretKey::from(27);
+        let charlie_pk = protocol.keygen(&charlie_sk).hash();
+
+        let goog_sk = EcSchnorrSecretKey::from(28);
+        let goog_pk = protocol.keygen(&goog_sk).hash();
+        let msft_sk = EcSchnorrSecretKey::from(29);
+        let msft_pk = protocol.keygen(&msft_sk).hash();
+        let fb_sk = EcSchnorrSecretKey::from(30);
+        let fb_pk = protocol.keygen(&fb_sk).hash();
+
+        // signer entries hold the full hash of their key, and are members of vectors by their
+        // own hash
+        let known_attestors = [goog_pk, msft_pk, fb_pk]
+            .map(|pk| ScalarOrVec::from_hash(pk).hash_or_value())
+            .to_vec();
+
+        let gb1_user = Entry::new_from_vec("user", bob_pk.elements.to_vec());
         let gb1_age = Entry::new_from_scalar("age", GoldilocksField(27));
         let gb1 =
             POD::execute_schnorr_gadget::<NS, VL>(&[gb1_user.clone(), gb1_age.clone()], &goog_sk)?;
 
-        let gb2_user = Entry::new_from_scalar("user", bob_pk);
+        let gb2_user = Entry::new_from_vec("user", bob_pk.elements.to_vec());
         let gb2 = POD::execute_schnorr_gadget::<NS, VL>(&[gb2_user.clone()], &msft_sk)?;
 
-        let gb3_user = Entry::new_from_scalar("user", charlie_pk);
+        let gb3_user = Entry::new_from_vec("user", charlie_pk.elements.to_vec());
         let gb3_age = Entry::new_from_scalar("age", GoldilocksField(18));
         let gb3 =
             POD::execute_schnorr_gadget::<NS, VL>(&[gb3_user.clone(), gb3_age.clone()], &msft_sk)?;
 
-        let gb4_user = Entry::new_from_scalar("user", charlie_pk);
+        let gb4_user = Entry::new_from_vec("user", charlie_pk.elements.to_vec());
         let gb4 = POD::execute_schnorr_gadget::<NS, VL>(&[gb4_user.clone()], &fb_sk)?;
 
-        let alice_user_entry = Entry::new_from_scalar("user", alice_pk);
+        let alice_user_entry = Entry::new_from_vec("user", alice_pk.elements.to_vec());
         let known_attestors_entry = Entry::new_from_vec("known_attestors", known_attestors.clone());
 
         let bob_alice =
@@ -1077,8 +2127,8 @@ mod tests {
             ),
         ];
 
-        let bob_tf = POD::execute_oracle_gadget(&bob_tf_input, &bob_tf_ops).unwrap();
-        assert!(bob_tf.verify::<0, 3, 2, 2, 0>()?); // TODO L=0, use L!=0
+        let bob_tf = POD::execute_oracle_gadget(&oracle, &bob_tf_input, &bob_tf_ops).unwrap();
+        assert!(bob_tf.verify_oracle(&[oracle.public_key()])?);
 
         // make the "bob trusted friend" POD
         let mut charlie_tf_input_pods = HashMap::new();
@@ -1142,11 +2192,12 @@ mod tests {
             ),
         ];
 
-        let charlie_tf = POD::execute_oracle_gadget(&charlie_tf_input, &charlie_tf_ops).unwrap();
-        assert!(charlie_tf.verify::<0, 3, 2, 2, 0>()?); // TODO L=0, use L!=0
+        let charlie_tf =
+            POD::execute_oracle_gadget(&oracle, &charlie_tf_input, &charlie_tf_ops).unwrap();
+        assert!(charlie_tf.verify_oracle(&[oracle.public_key()])?);
 
         // make the "great boy" POD
-        let age_bound_entry = Entry::new_from_scalar("known_attestors", GoldilocksField(17));
+        let age_bound_entry = Entry::new_from_scalar("age_bound", GoldilocksField(17));
         let age_sum_entry = Entry::new_from_scalar("age_sum", GoldilocksField(45));
         let mut grb_input_pods = HashMap::new();
         grb_input_pods.insert("friend1".to_string(), bob_tf.clone());
@@ -1312,8 +2363,8 @@ mod tests {
             ),
         ];
 
-        let alice_grb = POD::execute_oracle_gadget(&grb_input, &grb_ops).unwrap();
-        assert!(alice_grb.verify::<0, 3, 2, 2, 0>()?); // TODO L=0, use L!=0
+        let alice_grb = POD::execute_oracle_gadget(&oracle, &grb_input, &grb_ops).unwrap();
+        assert!(alice_grb.verify_oracle(&[oracle.public_key()])?);
 
         for statement in alice_grb.payload.statements_list {
             println!("{:?}", statement);
@@ -1324,6 +2375,7 @@ mod tests {
 
     #[test]
     fn final_pod_test() -> Result<()> {
+        let oracle = Oracle::random(&mut rand::thread_rng());
         const NS: usize = 5;
         const VL: usize = 0;
         // In this test we will execute this PEX script below and generate final-pod using
@@ -1338,9 +2390,9 @@ mod tests {
         //  overall-max [max remote-max
         //                   local-sum]]
 
-        let alice_sk = SchnorrSecretKey { sk: 25 };
-        let bob_sk = SchnorrSecretKey { sk: 26 };
-        let charlie_sk = SchnorrSecretKey { sk: 27 };
+        let alice_sk = EcSchnorrSecretKey::from(25);
+        let bob_sk = EcSchnorrSecretKey::from(26);
+        let charlie_sk = EcSchnorrSecretKey::from(27);
         // Let's create simple-pod-1
 
         // [createpod simple-pod-1  ; Alice's first pod
@@ -1452,8 +2504,8 @@ mod tests {
             ),
         ];
 
-        let sum_pod = POD::execute_oracle_gadget(&sum_pod_input, &sum_pod_ops).unwrap();
-        assert!(sum_pod.verify::<0, 3, 2, 2, 0>()?); // TODO L=0, use L!=0
+        let sum_pod = POD::execute_oracle_gadget(&oracle, &sum_pod_input, &sum_pod_ops).unwrap();
+        assert!(sum_pod.verify_oracle(&[oracle.public_key()])?);
 
         // [defpod sum-pod
         //   result 25
@@ -1487,8 +2539,9 @@ mod tests {
             ),
         ];
 
-        let product_pod = POD::execute_oracle_gadget(&product_pod_input, &product_pod_ops)?;
-        assert!(product_pod.verify::<0, 3, 2, 2, 0>()?); // TODO L=0, use L!=0
+        let product_pod =
+            POD::execute_oracle_gadget(&oracle, &product_pod_input, &product_pod_ops)?;
+        assert!(product_pod.verify_oracle(&[oracle.public_key()])?);
 
         // [defpod product-pod
         //   result 1200
@@ -1601,8 +2654,9 @@ mod tests {
             ),
         ];
 
-        let final_pod = POD::execute_oracle_gadget(&final_pod_input, &final_pod_ops).unwrap();
-        assert!(final_pod.verify::<0, 3, 2, 2, 0>()?); // TODO L=0, use L!=0
+        let final_pod =
+            POD::execute_oracle_gadget(&oracle, &final_pod_input, &final_pod_ops).unwrap();
+        assert!(final_pod.verify_oracle(&[oracle.public_key()])?);
 
         // If you are curious what the statements in this POD are
         // for statement in final_pod.payload.statements_list {
@@ -1634,11 +2688,10 @@ mod tests {
             ],
         )?;
 
-        let introduced_pod = POD::introduce_pod1::<5, 8>(test_pod)?;
+        let payload = POD::pod1_payload::<5, 8>(&test_pod)?;
 
         assert!(
-            introduced_pod
-                .payload
+            payload
                 .statements_map
                 .get("VALUEOF:speed")
                 .ok_or(anyhow!(""))?
@@ -1655,8 +2708,7 @@ mod tests {
                 ])
         );
         assert!(
-            introduced_pod
-                .payload
+            payload
                 .statements_map
                 .get("VALUEOF:jump")
                 .ok_or(anyhow!(""))?
@@ -1,16 +1,23 @@
 use anyhow::{anyhow, Result};
-use plonky2::field::{
-    goldilocks_field::GoldilocksField,
-    types::{Field, PrimeField64},
+use plonky2::{
+    field::{
+        goldilocks_field::GoldilocksField,
+        types::{Field, PrimeField64},
+    },
+    hash::poseidon::PoseidonHash,
+    plonk::config::Hasher,
 };
-use std::{collections::HashMap, fmt::Debug};
+use std::{cmp::Ordering, collections::HashMap, fmt::Debug};
 
 use super::{
     entry::Entry,
     gadget::GadgetID,
-    statement::{Statement, StatementOrRef, StatementRef},
-    value::ScalarOrVec,
-    POD,
+    merkle::{MerkleProof, NonMembershipProof, MERKLE_DEPTH},
+    owner::OwnerSecretKey,
+    payload::PODPayload,
+    predicate::{CustomPredicate, CUSTOM_PREDICATE_LEN},
+    statement::{Statement, StatementOrRef, StatementRef, MAX_HASH_INPUTS},
+    value::{HashableEntryValue, ScalarOrVec},
 };
 
 #[derive(Clone, Debug)]
@@ -25,11 +32,39 @@ pub enum Operation<S: StatementOrRef> {
     TransitiveEqualityFromStatements(S, S),
     GtToNonequality(S),
     LtToNonequality(S),
+    /// Vector and element entries, vector elements being looked up by their hash as in
+    /// `HashableEntryValue`
     ContainsFromEntries(S, S),
     RenameContainedBy(S, S),
     SumOf(S, S, S),
     ProductOf(S, S, S),
     MaxOf(S, S, S),
+    GteFromEntries(S, S),
+    LteFromEntries(S, S),
+    /// Value, lower bound and upper bound entries
+    InRangeFromEntries(S, S, S),
+    /// Value and Merkle root entries, with the path from the value to the root. Vector values
+    /// are leaves by their hash, as in `HashableEntryValue`.
+    ContainedInRootFromEntries(S, S, MerkleProof),
+    /// Value and sorted Merkle root entries, with the adjacent leaves the value, or its hash for
+    /// vectors, lies between
+    NotContainedInRootFromEntries(S, S, NonMembershipProof),
+    /// Result entry and 1 to `MAX_HASH_INPUTS` input entries, the inputs being hashed as in
+    /// `HashableEntryValue`. The circuit opens the result, so it needs `VL` of at least 4.
+    HashOf(S, Vec<S>),
+    /// Owner key and scope entries, with the secret of the owner key
+    NullifierFromOwnerKey(S, S, OwnerSecretKey),
+    DifferenceOf(S, S, S),
+    MinOf(S, S, S),
+    /// Quotient, dividend and divisor entries of a u32 division
+    QuotientOf(S, S, S),
+    /// Remainder, dividend and divisor entries of a u32 division
+    RemainderOf(S, S, S),
+    /// Statements matching the templates of the custom predicate, in order
+    CustomFromStatements(Vec<S>, CustomPredicate),
+    /// Result and input entries, the result being the negation of the input as a signed
+    /// integer, see `ScalarOrVec::negation`
+    NegationOf(S, S),
 }
 
 impl Operation<Statement> {
@@ -48,15 +83,15 @@ impl Operation<Statement> {
                 Statement::ValueOf(anchkey2, v2),
             ) if v1 != v2 => Ok(Statement::NotEqual(anchkey1.clone(), anchkey2.clone())),
             Self::GtFromEntries(
-                Statement::ValueOf(anchkey1, ScalarOrVec::Scalar(v1)),
-                Statement::ValueOf(anchkey2, ScalarOrVec::Scalar(v2)),
-            ) if v1.to_canonical_u64() > v2.to_canonical_u64() => {
+                Statement::ValueOf(anchkey1, v1),
+                Statement::ValueOf(anchkey2, v2),
+            ) if v1.numeric_cmp(v2) == Some(Ordering::Greater) => {
                 Ok(Statement::Gt(anchkey1.clone(), anchkey2.clone()))
             }
             Self::LtFromEntries(
-                Statement::ValueOf(anchkey1, ScalarOrVec::Scalar(v1)),
-                Statement::ValueOf(anchkey2, ScalarOrVec::Scalar(v2)),
-            ) if v1.to_canonical_u64() < v2.to_canonical_u64() => {
+                Statement::ValueOf(anchkey1, v1),
+                Statement::ValueOf(anchkey2, v2),
+            ) if v1.numeric_cmp(v2) == Some(Ordering::Less) => {
                 Ok(Statement::Lt(anchkey1.clone(), anchkey2.clone()))
             }
             Self::TransitiveEqualityFromStatements(
@@ -71,8 +106,10 @@ impl Operation<Statement> {
             }
             Self::ContainsFromEntries(
                 Statement::ValueOf(anchkey1, ScalarOrVec::Vector(vec)),
-                Statement::ValueOf(anchkey2, ScalarOrVec::Scalar(scal)),
-            ) if vec.contains(scal) => Ok(Statement::Contains(anchkey1.clone(), anchkey2.clone())),
+                Statement::ValueOf(anchkey2, x),
+            ) if vec.contains(&x.hash_or_value()) => {
+                Ok(Statement::Contains(anchkey1.clone(), anchkey2.clone()))
+            }
             Self::RenameContainedBy(
                 Statement::Contains(anchkey1, anchkey2),
                 Statement::Equal(anchkey3, anchkey4),
@@ -110,66 +147,203 @@ impl Operation<Statement> {
                     anchkey3.clone(),
                 ))
             }
+            Self::GteFromEntries(
+                Statement::ValueOf(anchkey1, v1),
+                Statement::ValueOf(anchkey2, v2),
+            ) if v1.numeric_cmp(v2).is_some_and(Ordering::is_ge) => {
+                Ok(Statement::Gte(anchkey1.clone(), anchkey2.clone()))
+            }
+            Self::LteFromEntries(
+                Statement::ValueOf(anchkey1, v1),
+                Statement::ValueOf(anchkey2, v2),
+            ) if v1.numeric_cmp(v2).is_some_and(Ordering::is_le) => {
+                Ok(Statement::Lte(anchkey1.clone(), anchkey2.clone()))
+            }
+            Self::InRangeFromEntries(
+                Statement::ValueOf(anchkey1, ScalarOrVec::Scalar(x)),
+                Statement::ValueOf(anchkey2, ScalarOrVec::Scalar(lower)),
+                Statement::ValueOf(anchkey3, ScalarOrVec::Scalar(upper)),
+            ) if (lower.to_canonical_u64()..=upper.to_canonical_u64())
+                .contains(&x.to_canonical_u64()) =>
+            {
+                Ok(Statement::InRange(
+                    anchkey1.clone(),
+                    anchkey2.clone(),
+                    anchkey3.clone(),
+                ))
+            }
+            Self::ContainedInRootFromEntries(
+                Statement::ValueOf(anchkey1, x),
+                Statement::ValueOf(anchkey2, ScalarOrVec::Scalar(root)),
+                proof,
+            ) if proof.verify(*root, x.hash_or_value()) => Ok(Statement::ContainedInRoot(
+                anchkey1.clone(),
+                anchkey2.clone(),
+            )),
+            Self::NotContainedInRootFromEntries(
+                Statement::ValueOf(anchkey1, x),
+                Statement::ValueOf(anchkey2, ScalarOrVec::Scalar(root)),
+                proof,
+            ) if proof.verify(*root, x.hash_or_value()) => Ok(Statement::NotContainedInRoot(
+                anchkey1.clone(),
+                anchkey2.clone(),
+            )),
+            Self::HashOf(Statement::ValueOf(anchkey1, v1), inputs)
+                if (1..=MAX_HASH_INPUTS).contains(&inputs.len()) =>
+            {
+                let (anchkeys, values): (Vec<_>, Vec<_>) = inputs
+                    .iter()
+                    .map(|input| match input {
+                        Statement::ValueOf(anchkey, v) => Ok((anchkey.clone(), v.hash_or_value())),
+                        _ => Err(anyhow!("Invalid claim: {:?}", self)),
+                    })
+                    .collect::<Result<Vec<_>>>()?
+                    .into_iter()
+                    .unzip();
+                if v1.holds_hash(PoseidonHash::hash_no_pad(&values)) {
+                    Ok(Statement::HashOf(anchkey1.clone(), anchkeys))
+                } else {
+                    Err(anyhow!("Invalid claim: {:?}", self))
+                }
+            }
+            Self::NullifierFromOwnerKey(
+                Statement::ValueOf(anchkey1, owner_key),
+                Statement::ValueOf(anchkey2, scope),
+                secret,
+            ) if owner_key.holds_hash(secret.public_key()) => Ok(Statement::Nullifier(
+                anchkey1.clone(),
+                anchkey2.clone(),
+                secret.nullifier(scope.hash_or_value()),
+            )),
+            Self::DifferenceOf(
+                Statement::ValueOf(anchkey1, ScalarOrVec::Scalar(x1)),
+                Statement::ValueOf(anchkey2, ScalarOrVec::Scalar(x2)),
+                Statement::ValueOf(anchkey3, ScalarOrVec::Scalar(x3)),
+            ) if x2.to_canonical_u64().checked_sub(x3.to_canonical_u64())
+                == Some(x1.to_canonical_u64()) =>
+            {
+                Ok(Statement::DifferenceOf(
+                    anchkey1.clone(),
+                    anchkey2.clone(),
+                    anchkey3.clone(),
+                ))
+            }
+            Self::MinOf(
+                Statement::ValueOf(anchkey1, ScalarOrVec::Scalar(x1)),
+                Statement::ValueOf(anchkey2, ScalarOrVec::Scalar(x2)),
+                Statement::ValueOf(anchkey3, ScalarOrVec::Scalar(x3)),
+            ) if x1.to_canonical_u64()
+                == Ord::min(x2.to_canonical_u64(), x3.to_canonical_u64()) =>
+            {
+                Ok(Statement::MinOf(
+                    anchkey1.clone(),
+                    anchkey2.clone(),
+                    anchkey3.clone(),
+                ))
+            }
+            Self::QuotientOf(
+                Statement::ValueOf(anchkey1, ScalarOrVec::Scalar(x1)),
+                Statement::ValueOf(anchkey2, ScalarOrVec::Scalar(x2)),
+                Statement::ValueOf(anchkey3, ScalarOrVec::Scalar(x3)),
+            ) if Self::u32_division(*x2, *x3).is_some_and(|(q, _)| q == x1.to_canonical_u64()) => {
+                Ok(Statement::QuotientRemainderOf(
+                    anchkey1.clone(),
+                    anchkey2.clone(),
+                    anchkey3.clone(),
+                    false,
+                ))
+            }
+            Self::RemainderOf(
+                Statement::ValueOf(anchkey1, ScalarOrVec::Scalar(x1)),
+                Statement::ValueOf(anchkey2, ScalarOrVec::Scalar(x2)),
+                Statement::ValueOf(anchkey3, ScalarOrVec::Scalar(x3)),
+            ) if Self::u32_division(*x2, *x3).is_some_and(|(_, r)| r == x1.to_canonical_u64()) => {
+                Ok(Statement::QuotientRemainderOf(
+                    anchkey1.clone(),
+                    anchkey2.clone(),
+                    anchkey3.clone(),
+                    true,
+                ))
+            }
+            Self::CustomFromStatements(statements, predicate) => Ok(predicate.eval(statements)?),
+            Self::NegationOf(
+                Statement::ValueOf(anchkey1, x1),
+                Statement::ValueOf(anchkey2, x2),
+            ) if x2.negation().as_ref() == Some(x1) => {
+                Ok(Statement::NegationOf(anchkey1.clone(), anchkey2.clone()))
+            }
             _ => Err(anyhow!("Invalid claim: {:?}", self)),
         }
     }
+
+    /// Quotient and remainder of a division of u32s, if the divisor is
+    /// nonzero.
+    fn u32_division(dividend: GoldilocksField, divisor: GoldilocksField) -> Option<(u64, u64)> {
+        let (dividend, divisor) = (dividend.to_canonical_u64(), divisor.to_canonical_u64());
+        (dividend <= u32::MAX as u64 && (1..=u32::MAX as u64).contains(&divisor))
+            .then(|| (dividend / divisor, dividend % divisor))
+    }
 }
 
 impl<S: StatementOrRef> Operation<S> {
     /// Resolution of indirect operation specification.
     pub fn deref_args(&self, table: &S::StatementTable) -> Result<Operation<Statement>> {
-        type Op = Operation<Statement>;
+        self.map_operands(&|s| s.deref_cloned(table))
+    }
+    /// Operation with each of its operands mapped by `f`.
+    pub fn map_operands<T: StatementOrRef>(
+        &self,
+        f: &dyn Fn(&S) -> Result<T>,
+    ) -> Result<Operation<T>> {
         match self {
-            Self::None => Ok(Op::None),
-            Self::NewEntry(e) => Ok(Op::NewEntry(e.clone())),
-            Self::CopyStatement(s) => Ok(Op::CopyStatement(s.deref_cloned(table)?)),
-            Self::EqualityFromEntries(s1, s2) => Ok(Op::EqualityFromEntries(
-                s1.deref_cloned(table)?,
-                s2.deref_cloned(table)?,
-            )),
-            Self::NonequalityFromEntries(s1, s2) => Ok(Op::NonequalityFromEntries(
-                s1.deref_cloned(table)?,
-                s2.deref_cloned(table)?,
-            )),
-            Self::GtFromEntries(s1, s2) => Ok(Op::GtFromEntries(
-                s1.deref_cloned(table)?,
-                s2.deref_cloned(table)?,
-            )),
-            Self::LtFromEntries(s1, s2) => Ok(Op::LtFromEntries(
-                s1.deref_cloned(table)?,
-                s2.deref_cloned(table)?,
-            )),
+            Self::None => Ok(Operation::None),
+            Self::NewEntry(e) => Ok(Operation::NewEntry(e.clone())),
+            Self::CopyStatement(s) => Ok(Operation::CopyStatement(f(s)?)),
+            Self::EqualityFromEntries(s1, s2) => Ok(Operation::EqualityFromEntries(f(s1)?, f(s2)?)),
+            Self::NonequalityFromEntries(s1, s2) => {
+                Ok(Operation::NonequalityFromEntries(f(s1)?, f(s2)?))
+            }
+            Self::GtFromEntries(s1, s2) => Ok(Operation::GtFromEntries(f(s1)?, f(s2)?)),
+            Self::LtFromEntries(s1, s2) => Ok(Operation::LtFromEntries(f(s1)?, f(s2)?)),
             Self::TransitiveEqualityFromStatements(s1, s2) => {
-                Ok(Op::TransitiveEqualityFromStatements(
-                    s1.deref_cloned(table)?,
-                    s2.deref_cloned(table)?,
-                ))
+                Ok(Operation::TransitiveEqualityFromStatements(f(s1)?, f(s2)?))
             }
-            Self::GtToNonequality(s) => Ok(Op::GtToNonequality(s.deref_cloned(table)?)),
-            Self::LtToNonequality(s) => Ok(Op::LtToNonequality(s.deref_cloned(table)?)),
-            Self::ContainsFromEntries(s1, s2) => Ok(Op::ContainsFromEntries(
-                s1.deref_cloned(table)?,
-                s2.deref_cloned(table)?,
-            )),
-            Self::RenameContainedBy(s1, s2) => Ok(Op::RenameContainedBy(
-                s1.deref_cloned(table)?,
-                s2.deref_cloned(table)?,
-            )),
-            Self::SumOf(s1, s2, s3) => Ok(Op::SumOf(
-                s1.deref_cloned(table)?,
-                s2.deref_cloned(table)?,
-                s3.deref_cloned(table)?,
-            )),
-            Self::ProductOf(s1, s2, s3) => Ok(Op::ProductOf(
-                s1.deref_cloned(table)?,
-                s2.deref_cloned(table)?,
-                s3.deref_cloned(table)?,
-            )),
-            Self::MaxOf(s1, s2, s3) => Ok(Op::MaxOf(
-                s1.deref_cloned(table)?,
-                s2.deref_cloned(table)?,
-                s3.deref_cloned(table)?,
+            Self::GtToNonequality(s) => Ok(Operation::GtToNonequality(f(s)?)),
+            Self::LtToNonequality(s) => Ok(Operation::LtToNonequality(f(s)?)),
+            Self::ContainsFromEntries(s1, s2) => Ok(Operation::ContainsFromEntries(f(s1)?, f(s2)?)),
+            Self::RenameContainedBy(s1, s2) => Ok(Operation::RenameContainedBy(f(s1)?, f(s2)?)),
+            Self::SumOf(s1, s2, s3) => Ok(Operation::SumOf(f(s1)?, f(s2)?, f(s3)?)),
+            Self::ProductOf(s1, s2, s3) => Ok(Operation::ProductOf(f(s1)?, f(s2)?, f(s3)?)),
+            Self::MaxOf(s1, s2, s3) => Ok(Operation::MaxOf(f(s1)?, f(s2)?, f(s3)?)),
+            Self::GteFromEntries(s1, s2) => Ok(Operation::GteFromEntries(f(s1)?, f(s2)?)),
+            Self::LteFromEntries(s1, s2) => Ok(Operation::LteFromEntries(f(s1)?, f(s2)?)),
+            Self::InRangeFromEntries(s1, s2, s3) => {
+                Ok(Operation::InRangeFromEntries(f(s1)?, f(s2)?, f(s3)?))
+            }
+            Self::ContainedInRootFromEntries(s1, s2, proof) => Ok(
+                Operation::ContainedInRootFromEntries(f(s1)?, f(s2)?, proof.clone()),
+            ),
+            Self::NotContainedInRootFromEntries(s1, s2, proof) => Ok(
+                Operation::NotContainedInRootFromEntries(f(s1)?, f(s2)?, proof.clone()),
+            ),
+            Self::HashOf(s1, inputs) => Ok(Operation::HashOf(
+                f(s1)?,
+                inputs.iter().map(f).collect::<Result<_>>()?,
             )),
+            Self::NullifierFromOwnerKey(s1, s2, secret) => {
+                Ok(Operation::NullifierFromOwnerKey(f(s1)?, f(s2)?, *secret))
+            }
+            Self::DifferenceOf(s1, s2, s3) => Ok(Operation::DifferenceOf(f(s1)?, f(s2)?, f(s3)?)),
+            Self::MinOf(s1, s2, s3) => Ok(Operation::MinOf(f(s1)?, f(s2)?, f(s3)?)),
+            Self::QuotientOf(s1, s2, s3) => Ok(Operation::QuotientOf(f(s1)?, f(s2)?, f(s3)?)),
+            Self::RemainderOf(s1, s2, s3) => Ok(Operation::RemainderOf(f(s1)?, f(s2)?, f(s3)?)),
+            Self::CustomFromStatements(statements, predicate) => {
+                Ok(Operation::CustomFromStatements(
+                    statements.iter().map(f).collect::<Result<_>>()?,
+                    predicate.clone(),
+                ))
+            }
+            Self::NegationOf(s1, s2) => Ok(Operation::NegationOf(f(s1)?, f(s2)?)),
         }
     }
     /// Opcodes
@@ -188,6 +362,20 @@ impl<S: StatementOrRef> Operation<S> {
     pub const MAX_OF: GoldilocksField = GoldilocksField(12);
     pub const LT_FROM_ENTRIES: GoldilocksField = GoldilocksField(13);
     pub const LT_TO_NONEQUALITY: GoldilocksField = GoldilocksField(14);
+    pub const GTE_FROM_ENTRIES: GoldilocksField = GoldilocksField(15);
+    pub const LTE_FROM_ENTRIES: GoldilocksField = GoldilocksField(16);
+    pub const IN_RANGE_FROM_ENTRIES: GoldilocksField = GoldilocksField(17);
+    pub const CONTAINED_IN_ROOT_FROM_ENTRIES: GoldilocksField = GoldilocksField(18);
+    pub const NOT_CONTAINED_IN_ROOT_FROM_ENTRIES: GoldilocksField = GoldilocksField(19);
+    pub const HASH_OF: GoldilocksField = GoldilocksField(20);
+    pub const NULLIFIER_FROM_OWNER_KEY: GoldilocksField = GoldilocksField(21);
+    pub const DIFFERENCE_OF: GoldilocksField = GoldilocksField(22);
+    pub const MIN_OF: GoldilocksField = GoldilocksField(23);
+    pub const QUOTIENT_OF: GoldilocksField = GoldilocksField(24);
+    pub const REMAINDER_OF: GoldilocksField = GoldilocksField(25);
+    pub const CUSTOM_FROM_STATEMENTS: GoldilocksField = GoldilocksField(26);
+    pub const NEGATION_OF: GoldilocksField = GoldilocksField(27);
+    pub const HASH_OF_SINGLE: GoldilocksField = GoldilocksField(28);
 
     /// Method specifying opcodes.
     pub fn code(&self) -> GoldilocksField {
@@ -209,6 +397,22 @@ impl<S: StatementOrRef> Operation<S> {
             Self::MaxOf(_, _, _) => Self::MAX_OF,
             Self::LtFromEntries(_, _) => Self::LT_FROM_ENTRIES,
             Self::LtToNonequality(_) => Self::LT_TO_NONEQUALITY,
+            Self::GteFromEntries(_, _) => Self::GTE_FROM_ENTRIES,
+            Self::LteFromEntries(_, _) => Self::LTE_FROM_ENTRIES,
+            Self::InRangeFromEntries(_, _, _) => Self::IN_RANGE_FROM_ENTRIES,
+            Self::ContainedInRootFromEntries(_, _, _) => Self::CONTAINED_IN_ROOT_FROM_ENTRIES,
+            Self::NotContainedInRootFromEntries(_, _, _) => {
+                Self::NOT_CONTAINED_IN_ROOT_FROM_ENTRIES
+            }
+            Self::HashOf(_, inputs) if inputs.len() == 1 => Self::HASH_OF_SINGLE,
+            Self::HashOf(_, _) => Self::HASH_OF,
+            Self::NullifierFromOwnerKey(_, _, _) => Self::NULLIFIER_FROM_OWNER_KEY,
+            Self::DifferenceOf(_, _, _) => Self::DIFFERENCE_OF,
+            Self::MinOf(_, _, _) => Self::MIN_OF,
+            Self::QuotientOf(_, _, _) => Self::QUOTIENT_OF,
+            Self::RemainderOf(_, _, _) => Self::REMAINDER_OF,
+            Self::CustomFromStatements(_, _) => Self::CUSTOM_FROM_STATEMENTS,
+            Self::NegationOf(_, _) => Self::NEGATION_OF,
         }
     }
     /// Method specifying operands.
@@ -218,13 +422,28 @@ impl<S: StatementOrRef> Operation<S> {
             Self::EqualityFromEntries(s1, s2) => vec![s1, s2],
             Self::NonequalityFromEntries(s1, s2) => vec![s1, s2],
             Self::GtFromEntries(s1, s2) => vec![s1, s2],
+            Self::LtFromEntries(s1, s2) => vec![s1, s2],
             Self::TransitiveEqualityFromStatements(s1, s2) => vec![s1, s2],
             Self::GtToNonequality(s) => vec![s],
+            Self::LtToNonequality(s) => vec![s],
             Self::ContainsFromEntries(s1, s2) => vec![s1, s2],
             Self::RenameContainedBy(s1, s2) => vec![s1, s2],
             Self::SumOf(s1, s2, s3) => vec![s1, s2, s3],
             Self::ProductOf(s1, s2, s3) => vec![s1, s2, s3],
             Self::MaxOf(s1, s2, s3) => vec![s1, s2, s3],
+            Self::GteFromEntries(s1, s2) => vec![s1, s2],
+            Self::LteFromEntries(s1, s2) => vec![s1, s2],
+            Self::InRangeFromEntries(s1, s2, s3) => vec![s1, s2, s3],
+            Self::ContainedInRootFromEntries(s1, s2, _) => vec![s1, s2],
+            Self::NotContainedInRootFromEntries(s1, s2, _) => vec![s1, s2],
+            Self::HashOf(s1, inputs) => [vec![s1], inputs.iter().collect()].concat(),
+            Self::NullifierFromOwnerKey(s1, s2, _) => vec![s1, s2],
+            Self::DifferenceOf(s1, s2, s3) => vec![s1, s2, s3],
+            Self::MinOf(s1, s2, s3) => vec![s1, s2, s3],
+            Self::QuotientOf(s1, s2, s3) => vec![s1, s2, s3],
+            Self::RemainderOf(s1, s2, s3) => vec![s1, s2, s3],
+            Self::CustomFromStatements(statements, _) => statements.iter().collect(),
+            Self::NegationOf(s1, s2) => vec![s1, s2],
             _ => vec![],
         }
     }
@@ -252,13 +471,19 @@ impl OperationCmd {
 
 impl Operation<StatementRef> {
     /// Representation of operation command as field vector of length
-    /// 9 + VL of the form
+    /// 18 + 2 * VL + 2 * MERKLE_DEPTH + CUSTOM_PREDICATE_LEN of the form
     /// [code] ++ [pod_num1, statement_num1] ++ [pod_num2,
     ///   statement_num2] ++ [pod_num3, statement_num3] ++ [entry]
-    ///   ++ contains_proof,
+    ///   ++ contains_proof ++ operand2_vector ++ merkle_proof
+    ///   ++ [lower_leaf, upper_leaf] ++ upper_merkle_proof
+    ///   ++ owner_secret ++ custom_predicate,
     /// where `VL` stands for the length of the vector involved in a
     /// `contains` op and we substitute 0s for unused operands and
-    /// entries.
+    /// entries. Comparison ops open the vector values of their two
+    /// operands in `contains_proof` and `operand2_vector`, and hash
+    /// ops that of their result in `contains_proof`.
+    /// Non-membership ops prove the lower adjacent leaf in
+    /// `merkle_proof`.
     pub fn to_fields<const VL: usize>(
         &self,
         ref_index_map: &HashMap<StatementRef, (usize, usize)>,
@@ -277,6 +502,9 @@ impl Operation<StatementRef> {
             })
             .collect::<Result<Vec<Vec<_>>>>()?;
         let num_operands = operands.len();
+        if num_operands > 3 {
+            return Err(anyhow!("Operation {:?} has more than 3 operands.", self));
+        }
         let padded_operands = [
             operands.into_iter().flatten().collect::<Vec<_>>(),
             (0..(2 * (3 - num_operands))).map(|_| 0).collect(),
@@ -289,7 +517,7 @@ impl Operation<StatementRef> {
         // Check for entry.
         let entry = self
             .entry()
-            .map_or(vec![GoldilocksField::ZERO; 2], |e| e.to_fields());
+            .map_or(vec![GoldilocksField::ZERO; 3], |e| e.to_fields());
 
         // Check for `contains` op.
         let contains_proof = match self {
@@ -314,10 +542,80 @@ impl Operation<StatementRef> {
                     )),
                 }
             }
+            Self::GtFromEntries(s_ref, _)
+            | Self::LtFromEntries(s_ref, _)
+            | Self::GteFromEntries(s_ref, _)
+            | Self::LteFromEntries(s_ref, _)
+            | Self::NegationOf(s_ref, _)
+            | Self::HashOf(s_ref, _) => Self::comparison_vector::<VL>(s_ref, statement_table),
+            _ => Ok(vec![GoldilocksField::ZERO; VL]),
+        }?;
+        let operand2_vector = match self {
+            Self::GtFromEntries(_, s_ref)
+            | Self::LtFromEntries(_, s_ref)
+            | Self::GteFromEntries(_, s_ref)
+            | Self::LteFromEntries(_, s_ref)
+            | Self::NegationOf(_, s_ref) => Self::comparison_vector::<VL>(s_ref, statement_table),
             _ => Ok(vec![GoldilocksField::ZERO; VL]),
         }?;
 
-        Ok([vec![op_code], padded_operands, entry, contains_proof].concat())
+        let (merkle_proof, non_membership_proof) = match self {
+            Self::ContainedInRootFromEntries(_, _, proof) => (
+                proof.to_fields(),
+                vec![GoldilocksField::ZERO; 3 + MERKLE_DEPTH],
+            ),
+            Self::NotContainedInRootFromEntries(_, _, proof) => (
+                proof.lower_proof.to_fields(),
+                [
+                    vec![proof.lower, proof.upper],
+                    proof.upper_proof.to_fields(),
+                ]
+                .concat(),
+            ),
+            _ => (
+                vec![GoldilocksField::ZERO; 1 + MERKLE_DEPTH],
+                vec![GoldilocksField::ZERO; 3 + MERKLE_DEPTH],
+            ),
+        };
+
+        let owner_secret = match self {
+            Self::NullifierFromOwnerKey(_, _, secret) => secret.to_fields(),
+            _ => vec![GoldilocksField::ZERO; 4],
+        };
+
+        let custom_predicate = match self {
+            Self::CustomFromStatements(_, predicate) => predicate.to_fields(),
+            _ => vec![GoldilocksField::ZERO; CUSTOM_PREDICATE_LEN],
+        };
+
+        Ok([
+            vec![op_code],
+            padded_operands,
+            entry,
+            contains_proof,
+            operand2_vector,
+            merkle_proof,
+            non_membership_proof,
+            owner_secret,
+            custom_predicate,
+        ]
+        .concat())
+    }
+
+    /// Vector value of a comparison, negation or hash operand, or 0s if it is a scalar.
+    fn comparison_vector<const VL: usize>(
+        s_ref: &StatementRef,
+        statement_table: &<StatementRef as StatementOrRef>::StatementTable,
+    ) -> Result<Vec<GoldilocksField>> {
+        match s_ref.deref_cloned(statement_table)? {
+            Statement::ValueOf(_, ScalarOrVec::Vector(v)) if v.len() == VL => Ok(v),
+            Statement::ValueOf(_, ScalarOrVec::Vector(v)) => Err(anyhow!(
+                "Vector {:?} in comparison, negation or hash op is not of length {}.",
+                v,
+                VL
+            )),
+            _ => Ok(vec![GoldilocksField::ZERO; VL]),
+        }
     }
 }
 
@@ -328,7 +626,7 @@ pub struct OpList(pub Vec<OperationCmd>);
 
 #[allow(dead_code)]
 impl OpList {
-    pub fn sort(&self, pods_list: &[(String, POD)]) -> Self {
+    pub fn sort<P: AsRef<PODPayload>>(&self, pods_list: &[(String, P)]) -> Self {
         // Map from StatementRef to pair of the form (pod index, statement index)
         let ref_index_map = StatementRef::index_map(pods_list);
 
@@ -339,7 +637,7 @@ impl OpList {
                 Operation::NewEntry(_) => 1,
                 Operation::CopyStatement(s_ref) => {
                     let (pod_index, statement_index) = ref_index_map.get(&s_ref).unwrap();
-                    pods_list[*pod_index].1.payload.statements_list[*statement_index]
+                    pods_list[*pod_index].1.as_ref().statements_list[*statement_index]
                         .1
                         .code()
                         .to_canonical_u64()
@@ -356,6 +654,19 @@ impl OpList {
                 Operation::MaxOf(_, _, _) => 8,
                 Operation::LtFromEntries(_, _) => 9,
                 Operation::LtToNonequality(_) => 3,
+                Operation::GteFromEntries(_, _) => 10,
+                Operation::LteFromEntries(_, _) => 11,
+                Operation::InRangeFromEntries(_, _, _) => 12,
+                Operation::ContainedInRootFromEntries(_, _, _) => 13,
+                Operation::NotContainedInRootFromEntries(_, _, _) => 14,
+                Operation::HashOf(_, _) => 15,
+                Operation::NullifierFromOwnerKey(_, _, _) => 16,
+                Operation::DifferenceOf(_, _, _) => 17,
+                Operation::MinOf(_, _, _) => 18,
+                Operation::QuotientOf(_, _, _) => 19,
+                Operation::RemainderOf(_, _, _) => 19,
+                Operation::CustomFromStatements(_, _) => 20,
+                Operation::NegationOf(_, _) => 21,
             }))
         };
 
@@ -0,0 +1,34 @@
+//! The Oracle computes PODs natively out of input PODs and vouches for the result by signing it.
+//! Its PODs are only as good as the trust put in its key, so they are verified against a set of
+//! trusted oracle public keys (see `POD::verify_oracle`).
+use rand::Rng;
+
+use crate::signature::ecschnorr::{
+    EcSchnorrPublicKey, EcSchnorrSecretKey, EcSchnorrSignature, EcSchnorrSigner,
+};
+use crate::F;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Oracle {
+    sk: EcSchnorrSecretKey,
+    pk: EcSchnorrPublicKey,
+}
+
+impl Oracle {
+    pub fn new(sk: EcSchnorrSecretKey) -> Self {
+        let pk = EcSchnorrSigner::new().keygen(&sk);
+        Self { sk, pk }
+    }
+
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        Self::new(EcSchnorrSecretKey::random(rng))
+    }
+
+    pub fn public_key(&self) -> EcSchnorrPublicKey {
+        self.pk
+    }
+
+    pub(crate) fn sign(&self, msg: &[F]) -> EcSchnorrSignature {
+        EcSchnorrSigner::new().sign(msg, &self.sk, &mut rand::thread_rng())
+    }
+}
@@ -0,0 +1,53 @@
+//! Owner keys, for PODs issued to a holder rather than to their signer: the POD holds the public
+//! key of the holder's secret as an entry, eg. `owner`, and the holder proves that they own it by
+//! deriving a nullifier from the secret and a scope value, eg. an event id. The nullifier is the
+//! same each time the POD is used in the same scope, so that it can be used only once there, and
+//! reveals nothing else about the secret, which is only a witness of the proof. Keys and
+//! nullifiers are full hashes, owner keys being held as vectors (see `ScalarOrVec::from_hash`).
+use plonky2::{
+    field::{goldilocks_field::GoldilocksField, types::Sample},
+    hash::{hash_types::HashOut, poseidon::PoseidonHash},
+    plonk::config::Hasher,
+};
+use std::fmt;
+
+use crate::F;
+
+/// Domain separators, which the hashes of the secret start with.
+pub const OWNER_KEY_DOMAIN: GoldilocksField = GoldilocksField(1);
+pub const NULLIFIER_DOMAIN: GoldilocksField = GoldilocksField(2);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct OwnerSecretKey([GoldilocksField; 4]);
+
+impl OwnerSecretKey {
+    pub fn new(secret: [GoldilocksField; 4]) -> Self {
+        Self(secret)
+    }
+
+    pub fn random() -> Self {
+        Self(GoldilocksField::rand_array())
+    }
+
+    /// Field representation, as a witness of the operations deriving nullifiers.
+    pub(crate) fn to_fields(&self) -> Vec<GoldilocksField> {
+        self.0.to_vec()
+    }
+
+    /// Public key, ie. `hash(OWNER_KEY_DOMAIN, secret)`.
+    pub fn public_key(&self) -> HashOut<F> {
+        PoseidonHash::hash_no_pad(&[&[OWNER_KEY_DOMAIN][..], &self.0].concat())
+    }
+
+    /// Nullifier in the given scope, ie. `hash(NULLIFIER_DOMAIN, secret, scope)`.
+    pub fn nullifier(&self, scope: GoldilocksField) -> HashOut<F> {
+        PoseidonHash::hash_no_pad(&[&[NULLIFIER_DOMAIN][..], &self.0, &[scope]].concat())
+    }
+}
+
+// The secret is left out, as operations are printed in errors.
+impl fmt::Debug for OwnerSecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OwnerSecretKey(..)")
+    }
+}
@@ -1,12 +1,13 @@
+use anyhow::{anyhow, Result};
 use plonky2::{
-    field::goldilocks_field::GoldilocksField,
+    field::{goldilocks_field::GoldilocksField, types::PrimeField64},
     hash::{hash_types::HashOut, poseidon::PoseidonHash},
     plonk::config::Hasher,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use super::statement::Statement;
+use super::statement::{AnchoredKey, Statement};
 use crate::F;
 
 pub type StatementList = Vec<(String, Statement)>;
@@ -21,12 +22,35 @@ pub trait HashablePayload: Clone + PartialEq {
     }
 }
 
+/// Only the statements of `statements_list` are hashed, without their names nor the names of
+/// their origins, so `statements_map` and these names are checked against it with `validate`
+/// when deserializing and verifying.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(try_from = "UncheckedPODPayload")]
 pub struct PODPayload {
     pub statements_list: Vec<(String, Statement)>, // ORDERED list of statements, ordered by names
     pub statements_map: HashMap<String, Statement>,
 }
 
+#[derive(Deserialize)]
+struct UncheckedPODPayload {
+    statements_list: Vec<(String, Statement)>,
+    statements_map: HashMap<String, Statement>,
+}
+
+impl TryFrom<UncheckedPODPayload> for PODPayload {
+    type Error = anyhow::Error;
+
+    fn try_from(payload: UncheckedPODPayload) -> Result<Self> {
+        let payload = Self {
+            statements_list: payload.statements_list,
+            statements_map: payload.statements_map,
+        };
+        payload.validate()?;
+        Ok(payload)
+    }
+}
+
 impl PODPayload {
     pub fn new(statements: &HashMap<String, Statement>) -> Self {
         let mut statements_and_names_list = Vec::new();
@@ -39,6 +63,107 @@ impl PODPayload {
             statements_map: statements.clone(),
         }
     }
+
+    /// Checks that the statements are listed by strictly increasing names, that the map holds
+    /// exactly the listed statements, that the statements are named as in
+    /// `validate_statement_name`, and that origin names are in the order of their IDs, as
+    /// assigned by `GPGInput`.
+    pub fn validate(&self) -> Result<()> {
+        if let Some(w) = self.statements_list.windows(2).find(|w| w[0].0 >= w[1].0) {
+            return Err(anyhow!(
+                "Statements {} and {} are not listed in increasing order",
+                w[0].0,
+                w[1].0
+            ));
+        }
+        if self.statements_map.len() != self.statements_list.len() {
+            return Err(anyhow!(
+                "The payload maps {} statements but lists {}",
+                self.statements_map.len(),
+                self.statements_list.len()
+            ));
+        }
+        for (name, statement) in self.statements_list.iter() {
+            if self.statements_map.get(name).map(Statement::to_fields)
+                != Some(statement.to_fields())
+            {
+                return Err(anyhow!(
+                    "Statement {} differs between the payload's list and map",
+                    name
+                ));
+            }
+            validate_statement_name(name, statement)?;
+        }
+
+        // origin IDs are hashed, and each of them names a single origin
+        let mut origin_names = BTreeMap::new();
+        for AnchoredKey(origin, _) in self
+            .statements_list
+            .iter()
+            .flat_map(|(_, statement)| statement.anchored_keys())
+        {
+            let origin_id = origin.origin_id.to_canonical_u64();
+            match origin_names.insert(origin_id, origin.origin_name.clone()) {
+                Some(origin_name) if origin_name != origin.origin_name => {
+                    return Err(anyhow!(
+                        "Origin {} is named both {} and {}",
+                        origin_id,
+                        origin_name,
+                        origin.origin_name
+                    ))
+                }
+                _ => {}
+            }
+        }
+        if let Some(origin_name) = origin_names.get(&1).filter(|name| *name != "_SELF") {
+            return Err(anyhow!(
+                "Origin 1 is named {} instead of _SELF",
+                origin_name
+            ));
+        }
+        let foreign_names = origin_names
+            .range(2..)
+            .map(|(_, name)| name)
+            .collect::<Vec<_>>();
+        if let Some(w) = foreign_names.windows(2).find(|w| w[0] >= w[1]) {
+            return Err(anyhow!(
+                "Origins {} and {} are not named in the order of their IDs",
+                w[0],
+                w[1]
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Checks that a statement is named after its predicate, as the operations name the statements
+/// they output, and that the POD's own VALUEOF statements, which are looked up by key, are named
+/// after their key. Padding statements have arbitrary names.
+pub fn validate_statement_name(name: &str, statement: &Statement) -> Result<()> {
+    match statement {
+        Statement::None => Ok(()),
+        Statement::ValueOf(AnchoredKey(origin, key), _)
+            if origin.is_self() && name != format!("{}:{}", statement.predicate(), key) =>
+        {
+            Err(anyhow!(
+                "Statement {} about the entry {} is not named after its key",
+                name,
+                key
+            ))
+        }
+        _ if !name.starts_with(&format!("{}:", statement.predicate())) => Err(anyhow!(
+            "Statement {} is not named after its predicate {}",
+            name,
+            statement.predicate()
+        )),
+        _ => Ok(()),
+    }
+}
+
+impl AsRef<PODPayload> for PODPayload {
+    fn as_ref(&self) -> &PODPayload {
+        self
+    }
 }
 
 impl HashablePayload for Vec<Statement> {
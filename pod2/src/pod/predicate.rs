@@ -0,0 +1,369 @@
+//! Custom predicates, i.e. conjunctions of statements whose anchored keys are bound to shared
+//! variables. A custom statement is derived from statements matching the definition of its
+//! predicate, which it refers to by hash.
+use plonky2::{
+    field::{goldilocks_field::GoldilocksField, types::Field},
+    hash::poseidon::PoseidonHash,
+    plonk::config::Hasher,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    statement::{AnchoredKey, Statement},
+    util::hash_string_to_field,
+};
+use crate::{Error, Result};
+
+/// Maximum number of statements of a custom predicate, which are the operands of the operation
+/// deriving it.
+pub const MAX_CUSTOM_STATEMENTS: usize = 3;
+/// Maximum number of variables of a custom predicate, the first ones being its arguments.
+pub const MAX_CUSTOM_VARS: usize = 6;
+/// Length of the field representation of a custom predicate.
+pub const CUSTOM_PREDICATE_LEN: usize = 2 + 6 * MAX_CUSTOM_STATEMENTS;
+
+/// Statement of a custom predicate, whose anchored keys are bound to variables and whose value
+/// may be fixed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatementTemplate {
+    code: GoldilocksField,
+    /// Variable of each anchored key, if it is bound to one.
+    keys: [Option<usize>; 3],
+    /// Value of a `VALUEOF` statement, or identifier of a nested custom predicate.
+    value: Option<GoldilocksField>,
+}
+
+impl StatementTemplate {
+    /// Template of a statement whose leftmost anchored keys are bound to the given variables.
+    pub fn new(code: GoldilocksField, vars: &[usize]) -> Self {
+        Self {
+            code,
+            keys: std::array::from_fn(|i| vars.get(i).copied()),
+            value: None,
+        }
+    }
+    pub fn with_value(self, value: GoldilocksField) -> Self {
+        Self {
+            value: Some(value),
+            ..self
+        }
+    }
+    pub fn code(&self) -> GoldilocksField {
+        self.code
+    }
+    pub fn keys(&self) -> [Option<usize>; 3] {
+        self.keys
+    }
+    pub fn value(&self) -> Option<GoldilocksField> {
+        self.value
+    }
+    /// Field representation as a vector of length 6 of the form
+    /// [code, has_value, value] ++ [var1 + 1, var2 + 1, var3 + 1],
+    /// unbound anchored keys being represented by 0.
+    fn to_fields(&self) -> Vec<GoldilocksField> {
+        [
+            vec![
+                self.code,
+                GoldilocksField::from_bool(self.value.is_some()),
+                self.value.unwrap_or(GoldilocksField::ZERO),
+            ],
+            self.keys
+                .iter()
+                .map(|var| GoldilocksField::from_canonical_usize(var.map_or(0, |var| var + 1)))
+                .collect(),
+        ]
+        .concat()
+    }
+}
+
+/// Named predicate over `arity` anchored keys, holding if there are statements matching its
+/// templates. Variables which are not arguments are existentially quantified. Definitions are
+/// checked by `new` when deserializing as well.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "UncheckedCustomPredicate")]
+pub struct CustomPredicate {
+    name: String,
+    arity: usize,
+    statements: Vec<StatementTemplate>,
+}
+
+#[derive(Deserialize)]
+struct UncheckedCustomPredicate {
+    name: String,
+    arity: usize,
+    statements: Vec<StatementTemplate>,
+}
+
+impl TryFrom<UncheckedCustomPredicate> for CustomPredicate {
+    type Error = Error;
+
+    fn try_from(predicate: UncheckedCustomPredicate) -> Result<Self> {
+        Self::new(predicate.name, predicate.arity, predicate.statements)
+    }
+}
+
+impl CustomPredicate {
+    pub fn new(
+        name: impl Into<String>,
+        arity: usize,
+        statements: Vec<StatementTemplate>,
+    ) -> Result<Self> {
+        let name = name.into();
+        if statements.is_empty() || statements.len() > MAX_CUSTOM_STATEMENTS {
+            return Err(Error::InvalidPredicate(format!(
+                "Custom predicate {} must consist of 1 to {} statements.",
+                name, MAX_CUSTOM_STATEMENTS
+            )));
+        }
+        if arity > 3 {
+            return Err(Error::InvalidPredicate(format!(
+                "Custom predicate {} must have at most 3 arguments.",
+                name
+            )));
+        }
+        if let Some(template) = statements.iter().find(|template| {
+            template.code == Statement::NONE
+                || Statement::code_to_predicate(template.code).is_empty()
+        }) {
+            return Err(Error::InvalidPredicate(format!(
+                "Invalid statement code {} in custom predicate {}.",
+                template.code, name
+            )));
+        }
+        // Anchored keys are bound in slots which hold keys, and not e.g. the value type of
+        // `VALUEOF` statements.
+        if let Some(template) = statements.iter().find(|template| {
+            template.keys[Statement::code_to_num_anchored_keys(template.code)..]
+                .iter()
+                .any(Option::is_some)
+        }) {
+            return Err(Error::InvalidPredicate(format!(
+                "Template {:?} of custom predicate {} binds more anchored keys than {} statements have.",
+                template,
+                name,
+                Statement::code_to_predicate(template.code)
+            )));
+        }
+        let vars = statements
+            .iter()
+            .flat_map(|template| template.keys.iter().flatten())
+            .collect::<Vec<_>>();
+        if let Some(var) = vars.iter().find(|var| ***var >= MAX_CUSTOM_VARS) {
+            return Err(Error::InvalidPredicate(format!(
+                "Variable {} of custom predicate {} is out of range, there being at most {} variables.",
+                var,
+                name,
+                MAX_CUSTOM_VARS
+            )));
+        }
+        if let Some(arg) = (0..arity).find(|arg| !vars.contains(&arg)) {
+            return Err(Error::InvalidPredicate(format!(
+                "Argument {} of custom predicate {} is not bound by any statement.",
+                arg, name
+            )));
+        }
+        Ok(Self {
+            name,
+            arity,
+            statements,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+    pub fn statements(&self) -> &[StatementTemplate] {
+        &self.statements
+    }
+
+    /// Field representation as a vector of length `CUSTOM_PREDICATE_LEN` of the form
+    /// [name_hash, arity] ++ template1 ++ template2 ++ template3,
+    /// missing templates being represented by 0s.
+    pub fn to_fields(&self) -> Vec<GoldilocksField> {
+        [
+            vec![
+                hash_string_to_field(&self.name),
+                GoldilocksField::from_canonical_usize(self.arity),
+            ],
+            self.statements
+                .iter()
+                .flat_map(|template| template.to_fields())
+                .collect(),
+            vec![GoldilocksField::ZERO; 6 * (MAX_CUSTOM_STATEMENTS - self.statements.len())],
+        ]
+        .concat()
+    }
+
+    /// Identifier of the predicate, which is the value of the statements it derives.
+    pub fn id(&self) -> GoldilocksField {
+        PoseidonHash::hash_no_pad(&self.to_fields()).elements[0]
+    }
+
+    /// Custom statement derived from statements matching the templates, its arguments being
+    /// the anchored keys the first variables are bound to.
+    pub fn eval(&self, statements: &[Statement]) -> Result<Statement> {
+        if statements.len() != self.statements.len() {
+            return Err(Error::InvalidPredicate(format!(
+                "Custom predicate {} takes {} statements, not {}.",
+                self.name,
+                self.statements.len(),
+                statements.len()
+            )));
+        }
+        let mut vars: [Option<AnchoredKey>; MAX_CUSTOM_VARS] = Default::default();
+        for (template, statement) in self.statements.iter().zip(statements) {
+            let mismatch = || {
+                Error::InvalidPredicate(format!(
+                    "Statement {} does not match {:?} in custom predicate {}.",
+                    statement, template, self.name
+                ))
+            };
+            if statement.code() != template.code
+                || template
+                    .value
+                    .is_some_and(|value| statement.to_fields()[10] != value)
+            {
+                return Err(mismatch());
+            }
+            let anchored_keys = statement.anchored_keys();
+            for (i, var) in template.keys.iter().enumerate() {
+                let Some(var) = var else { continue };
+                let anchored_key = anchored_keys.get(i).ok_or_else(mismatch)?;
+                match &vars[*var] {
+                    // Keys are compared as in circuit.
+                    Some(bound_key) if bound_key.to_fields() != anchored_key.to_fields() => {
+                        return Err(mismatch())
+                    }
+                    Some(_) => {}
+                    None => vars[*var] = Some(anchored_key.clone()),
+                }
+            }
+        }
+        Ok(Statement::Custom(
+            self.id(),
+            vars.into_iter().take(self.arity).flatten().collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+
+    use super::{CustomPredicate, StatementTemplate};
+    use crate::pod::{
+        gadget::GadgetID,
+        origin::Origin,
+        statement::{AnchoredKey, Statement},
+        value::ScalarOrVec,
+    };
+
+    #[test]
+    fn test_custom_predicate() -> Result<()> {
+        let key = |origin_id: u64, key: &str| {
+            AnchoredKey(
+                Origin::new(
+                    GoldilocksField(origin_id),
+                    format!("origin {}", origin_id),
+                    GadgetID::SCHNORR16,
+                ),
+                key.to_string(),
+            )
+        };
+        let (age, eighteen, issuer, trusted) =
+            (key(2, "age"), key(1, "18"), key(2, "issuer"), key(3, "id"));
+
+        // Over18(age) := Gt(age, x) and ValueOf(x = 18)
+        let over_18 = CustomPredicate::new(
+            "Over18",
+            1,
+            vec![
+                StatementTemplate::new(Statement::GT, &[0, 1]),
+                StatementTemplate::new(Statement::VALUE_OF, &[1]).with_value(GoldilocksField(18)),
+            ],
+        )?;
+        let gt = Statement::Gt(age.clone(), eighteen.clone());
+        let value_of =
+            Statement::ValueOf(eighteen.clone(), ScalarOrVec::Scalar(GoldilocksField(18)));
+        let is_over_18 = over_18.eval(&[gt.clone(), value_of.clone()])?;
+        assert_eq!(
+            is_over_18,
+            Statement::Custom(over_18.id(), vec![age.clone()])
+        );
+
+        // Variables and values are checked.
+        let value_of_17 =
+            Statement::ValueOf(eighteen.clone(), ScalarOrVec::Scalar(GoldilocksField(17)));
+        assert!(over_18.eval(&[gt.clone(), value_of_17]).is_err());
+        let other_value_of =
+            Statement::ValueOf(key(4, "18"), ScalarOrVec::Scalar(GoldilocksField(18)));
+        assert!(over_18.eval(&[gt.clone(), other_value_of]).is_err());
+        assert!(over_18.eval(&[value_of.clone(), gt.clone()]).is_err());
+        assert!(over_18.eval(&[gt.clone()]).is_err());
+
+        // IsAdult(age, issuer) := Over18(age) and Equal(issuer, trusted)
+        let is_adult = CustomPredicate::new(
+            "IsAdult",
+            2,
+            vec![
+                StatementTemplate::new(Statement::CUSTOM, &[0]).with_value(over_18.id()),
+                StatementTemplate::new(Statement::EQUAL, &[1, 2]),
+            ],
+        )?;
+        let equal = Statement::Equal(issuer.clone(), trusted.clone());
+        assert_eq!(
+            is_adult.eval(&[is_over_18.clone(), equal.clone()])?,
+            Statement::Custom(is_adult.id(), vec![age.clone(), issuer.clone()])
+        );
+        let other_custom = Statement::Custom(is_adult.id(), vec![age.clone()]);
+        assert!(is_adult.eval(&[other_custom, equal]).is_err());
+
+        // Identifiers cover the whole definition.
+        let renamed = CustomPredicate::new("Over17", 1, over_18.statements().to_vec())?;
+        assert_ne!(renamed.id(), over_18.id());
+        assert_ne!(is_adult.id(), over_18.id());
+
+        // Definitions are bounded and bind their arguments.
+        assert!(CustomPredicate::new("Empty", 0, vec![]).is_err());
+        assert!(CustomPredicate::new(
+            "Unbound",
+            2,
+            vec![StatementTemplate::new(Statement::GT, &[0, 2])]
+        )
+        .is_err());
+        assert!(CustomPredicate::new(
+            "OutOfRange",
+            1,
+            vec![StatementTemplate::new(Statement::GT, &[0, 6])]
+        )
+        .is_err());
+        assert!(CustomPredicate::new(
+            "ValueType",
+            1,
+            vec![
+                StatementTemplate::new(Statement::GT, &[0]),
+                StatementTemplate::new(Statement::VALUE_OF, &[0, 1, 2])
+            ]
+        )
+        .is_err());
+
+        // Deserialized definitions are checked likewise.
+        let json = serde_json::to_string(&is_adult)?;
+        assert_eq!(serde_json::from_str::<CustomPredicate>(&json)?, is_adult);
+        let too_many_args = json.replace("\"arity\":2", "\"arity\":4");
+        assert!(serde_json::from_str::<CustomPredicate>(&too_many_args).is_err());
+        let statements = serde_json::to_value(is_adult.statements())?;
+        let too_many_statements = serde_json::json!({
+            "name": "TooMany",
+            "arity": 1,
+            "statements": [statements[0], statements[1], statements[1], statements[1]],
+        });
+        assert!(serde_json::from_value::<CustomPredicate>(too_many_statements).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,380 @@
+//! Provenance graph of a POD, tracing its statements back through their origins to the input
+//! PODs they were derived from, as far as these are known.
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use super::{gadget::GadgetID, statement::AnchoredKey, Origin, Statement, POD};
+use crate::{Error, Result};
+
+/// Name of the POD the graph is built from.
+pub const ROOT: &str = "_SELF";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ProvenanceNode {
+    /// Known POD, with the value of its signer's or oracle's public key entry if it has one.
+    Pod {
+        id: String,
+        name: String,
+        gadget_id: GadgetID,
+        signer: Option<String>,
+    },
+    /// Origin referenced by a statement, whose POD is not known.
+    Origin {
+        id: String,
+        name: String,
+        gadget_id: GadgetID,
+    },
+    /// Statement of a known POD.
+    Statement {
+        id: String,
+        pod: String,
+        name: String,
+        statement: String,
+    },
+}
+
+impl ProvenanceNode {
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Pod { id, .. } | Self::Origin { id, .. } | Self::Statement { id, .. } => id,
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Self::Pod {
+                name,
+                gadget_id,
+                signer,
+                ..
+            } => match signer {
+                Some(signer) => format!("{} ({})\nsigner {}", name, gadget_id, signer),
+                None => format!("{} ({})", name, gadget_id),
+            },
+            Self::Origin {
+                name, gadget_id, ..
+            } => format!("{} ({})", name, gadget_id),
+            Self::Statement {
+                name, statement, ..
+            } => format!("{}\n{}", name, statement),
+        }
+    }
+}
+
+/// Edge from a node to one derived from it, labelled with the operation deriving it, or with
+/// `IN` from a statement to its POD.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ProvenanceEdge {
+    pub from: String,
+    pub to: String,
+    pub label: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProvenanceGraph {
+    pub nodes: Vec<ProvenanceNode>,
+    pub edges: Vec<ProvenanceEdge>,
+}
+
+impl ProvenanceGraph {
+    /// Builds the graph of the POD, whose origins are looked up by name among the known PODs.
+    /// Known PODs are traced back in turn, so that chains of copied statements are followed.
+    pub fn new(pod: &POD, known_pods: &HashMap<String, &POD>) -> Self {
+        let mut graph = Self::default();
+        let mut visited = HashSet::new();
+        graph.add_pod(ROOT, pod, known_pods, &mut visited);
+        graph
+    }
+
+    fn add_pod(
+        &mut self,
+        pod_name: &str,
+        pod: &POD,
+        known_pods: &HashMap<String, &POD>,
+        visited: &mut HashSet<String>,
+    ) {
+        visited.insert(pod_name.to_string());
+        let pod_id = pod_node_id(pod_name);
+        self.nodes.push(ProvenanceNode::Pod {
+            id: pod_id.clone(),
+            name: pod_name.to_string(),
+            gadget_id: pod.proof_type,
+            signer: pod
+                .signer()
+                .or_else(|_| pod.oracle())
+                .ok()
+                .map(|pk| format!("{:?}", pk)),
+        });
+
+        for (statement_name, statement) in &pod.payload.statements_list {
+            if *statement == Statement::None {
+                continue;
+            }
+            let statement_id = statement_node_id(pod_name, statement_name);
+            self.nodes.push(ProvenanceNode::Statement {
+                id: statement_id.clone(),
+                pod: pod_name.to_string(),
+                name: statement_name.clone(),
+                statement: statement.to_string(),
+            });
+            self.edges.push(ProvenanceEdge {
+                from: statement_id.clone(),
+                to: pod_id.clone(),
+                label: "IN".to_string(),
+            });
+
+            // Entries of the POD itself are where provenance starts.
+            if let Statement::ValueOf(AnchoredKey(origin, _), _) = statement {
+                if origin.is_self() {
+                    continue;
+                }
+            }
+            let mut sources = Vec::new();
+            for AnchoredKey(origin, key) in statement.anchored_keys() {
+                if origin.origin_id == Origin::NONE.origin_id {
+                    continue;
+                }
+                let source = if origin.is_self() {
+                    entry_statement_name(pod, &key)
+                        .map(|name| (statement_node_id(pod_name, &name), operation(statement)))
+                } else {
+                    Some(self.origin_source(
+                        &origin.origin_name,
+                        origin.gadget_id,
+                        statement,
+                        &key,
+                        known_pods,
+                        visited,
+                    ))
+                };
+                if let Some(source) = source.filter(|source| !sources.contains(source)) {
+                    sources.push(source);
+                }
+            }
+            self.edges
+                .extend(sources.into_iter().map(|(from, label)| ProvenanceEdge {
+                    from,
+                    to: statement_id.clone(),
+                    label: label.to_string(),
+                }));
+        }
+    }
+
+    /// Node from which the key of the statement comes through the origin, and the operation
+    /// deriving the statement from it. A statement of a known POD which is about the same keys
+    /// is taken to have been copied.
+    fn origin_source(
+        &mut self,
+        origin_name: &str,
+        gadget_id: GadgetID,
+        statement: &Statement,
+        key: &str,
+        known_pods: &HashMap<String, &POD>,
+        visited: &mut HashSet<String>,
+    ) -> (String, &'static str) {
+        let Some(origin_pod) = known_pods.get(origin_name) else {
+            let id = format!("origin:{}", origin_name);
+            if !self.nodes.iter().any(|node| node.id() == id) {
+                self.nodes.push(ProvenanceNode::Origin {
+                    id: id.clone(),
+                    name: origin_name.to_string(),
+                    gadget_id,
+                });
+            }
+            return (id, operation(statement));
+        };
+        if !visited.contains(origin_name) {
+            self.add_pod(origin_name, origin_pod, known_pods, visited);
+        }
+        let keys = |s: &Statement| {
+            s.anchored_keys()
+                .into_iter()
+                .map(|k| k.1)
+                .collect::<Vec<_>>()
+        };
+        if let Some((name, _)) = origin_pod.payload.statements_list.iter().find(|(_, s)| {
+            s.predicate() == statement.predicate()
+                && keys(s) == keys(statement)
+                && s.value().ok() == statement.value().ok()
+        }) {
+            return (statement_node_id(origin_name, name), "CopyStatement");
+        }
+        match entry_statement_name(origin_pod, key) {
+            Some(name) => (statement_node_id(origin_name, &name), operation(statement)),
+            None => (pod_node_id(origin_name), operation(statement)),
+        }
+    }
+
+    /// Graphviz DOT representation.
+    pub fn to_dot(&self) -> String {
+        let mut dot = "digraph provenance {\n    rankdir=LR;\n".to_string();
+        for node in &self.nodes {
+            let shape = match node {
+                ProvenanceNode::Pod { .. } => "box",
+                ProvenanceNode::Origin { .. } => "box, style=dashed",
+                ProvenanceNode::Statement { .. } => "ellipse",
+            };
+            dot.push_str(&format!(
+                "    \"{}\" [shape={}, label=\"{}\"];\n",
+                escape(node.id()),
+                shape,
+                escape(&node.label())
+            ));
+        }
+        for edge in &self.edges {
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                escape(&edge.from),
+                escape(&edge.to),
+                escape(&edge.label)
+            ));
+        }
+        dot.push('}');
+        dot
+    }
+
+    /// JSON representation.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| Error::Other(e.into()))
+    }
+}
+
+/// Operation which derives a statement of the given kind from entries. Statements which may
+/// also be derived from other statements, e.g. by transitivity, are labelled the same way.
+fn operation(statement: &Statement) -> &'static str {
+    match statement {
+        Statement::None => "None",
+        Statement::ValueOf(_, _) => "CopyStatement",
+        Statement::Equal(_, _) => "EqualityFromEntries",
+        Statement::NotEqual(_, _) => "NonequalityFromEntries",
+        Statement::Gt(_, _) => "GtFromEntries",
+        Statement::Lt(_, _) => "LtFromEntries",
+        Statement::Contains(_, _) => "ContainsFromEntries",
+        Statement::SumOf(_, _, _) => "SumOf",
+        Statement::ProductOf(_, _, _) => "ProductOf",
+        Statement::MaxOf(_, _, _) => "MaxOf",
+        Statement::Gte(_, _) => "GteFromEntries",
+        Statement::Lte(_, _) => "LteFromEntries",
+        Statement::InRange(_, _, _) => "InRangeFromEntries",
+        Statement::ContainedInRoot(_, _) => "ContainedInRootFromEntries",
+        Statement::NotContainedInRoot(_, _) => "NotContainedInRootFromEntries",
+        Statement::HashOf(_, _) => "HashOf",
+        Statement::Nullifier(_, _, _) => "NullifierFromOwnerKey",
+        Statement::DifferenceOf(_, _, _) => "DifferenceOf",
+        Statement::MinOf(_, _, _) => "MinOf",
+        Statement::QuotientRemainderOf(_, _, _, true) => "RemainderOf",
+        Statement::QuotientRemainderOf(_, _, _, false) => "QuotientOf",
+        Statement::Custom(_, _) => "CustomFromStatements",
+        Statement::NegationOf(_, _) => "NegationOf",
+    }
+}
+
+/// Name of the statement holding the value of one of the POD's own entries.
+fn entry_statement_name(pod: &POD, key: &str) -> Option<String> {
+    pod.payload
+        .statements_list
+        .iter()
+        .find(|(_, statement)| {
+            matches!(statement, Statement::ValueOf(AnchoredKey(origin, k), _)
+                if origin.is_self() && k == key)
+        })
+        .map(|(name, _)| name.clone())
+}
+
+fn pod_node_id(pod_name: &str) -> String {
+    format!("pod:{}", pod_name)
+}
+
+fn statement_node_id(pod_name: &str, statement_name: &str) -> String {
+    format!("{}/{}", pod_name, statement_name)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use std::collections::HashMap;
+
+    use super::{ProvenanceEdge, ProvenanceGraph, ProvenanceNode};
+    use crate::pod::{
+        entry::Entry, gadget::GadgetID, statement::StatementRef, GPGInput, Op, OpCmd, Oracle, POD,
+    };
+    use crate::signature::ecschnorr::EcSchnorrSecretKey;
+
+    #[test]
+    fn test_provenance_graph() -> Result<()> {
+        let oracle = Oracle::random(&mut rand::thread_rng());
+        let alice = POD::execute_schnorr_gadget::<3, 0>(
+            &[Entry::new_from_scalar("age", GoldilocksField(30))],
+            &EcSchnorrSecretKey::from(25),
+        )?;
+        let bob = POD::execute_schnorr_gadget::<3, 0>(
+            &[Entry::new_from_scalar("age", GoldilocksField(20))],
+            &EcSchnorrSecretKey::from(42),
+        )?;
+        let gpg_input = GPGInput::new(
+            HashMap::from([
+                ("alice".to_string(), alice.clone()),
+                ("bob".to_string(), bob.clone()),
+            ]),
+            HashMap::new(),
+        );
+        let ops = [
+            OpCmd::new(
+                Op::CopyStatement(StatementRef::new("alice", "VALUEOF:age")),
+                "alice-age",
+            ),
+            OpCmd::new(
+                Op::GtFromEntries(
+                    StatementRef::new("alice", "VALUEOF:age"),
+                    StatementRef::new("bob", "VALUEOF:age"),
+                ),
+                "older",
+            ),
+        ];
+        let pod = POD::execute_oracle_gadget(&oracle, &gpg_input, &ops)?;
+
+        // Both input PODs are known: statements are traced back to their entries.
+        let known_pods = HashMap::from([("alice".to_string(), &alice), ("bob".to_string(), &bob)]);
+        let graph = ProvenanceGraph::new(&pod, &known_pods);
+        let edge = |from: &str, to: &str, label: &str| ProvenanceEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            label: label.to_string(),
+        };
+        assert!(graph.edges.contains(&edge(
+            "alice/VALUEOF:age",
+            "_SELF/VALUEOF:alice-age",
+            "CopyStatement"
+        )));
+        assert!(graph
+            .edges
+            .contains(&edge("bob/VALUEOF:age", "_SELF/GT:older", "GtFromEntries")));
+        assert!(graph
+            .edges
+            .contains(&edge("_SELF/GT:older", "pod:_SELF", "IN")));
+        assert!(graph.nodes.iter().any(|node| matches!(node,
+            ProvenanceNode::Pod { name, gadget_id: GadgetID::ECSCHNORR, signer: Some(_), .. }
+                if name == "bob")));
+
+        // Unknown input PODs are left as origins.
+        let graph = ProvenanceGraph::new(&pod, &HashMap::new());
+        assert!(graph
+            .edges
+            .contains(&edge("origin:bob", "_SELF/GT:older", "GtFromEntries")));
+        assert!(graph
+            .to_dot()
+            .contains("\"origin:bob\" -> \"_SELF/GT:older\""));
+        let json: ProvenanceGraph = serde_json::from_str(&graph.to_json()?)?;
+        assert_eq!(json, graph);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,153 @@
+//! Registry of the circuit shapes, ie. of the `L, M, N, NS, VL` parameters the POD circuits can
+//! be used with. The circuits are parameterised by const generics, so only the registered shapes
+//! are compiled in, and `CircuitShape::dispatch` selects one of them at runtime.
+use anyhow::{anyhow, Result};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CircuitShape {
+    /// Max number of POD1-Introducer PODs
+    pub l: usize,
+    /// Max number of SchnorrPODs
+    pub m: usize,
+    /// Max number of PlonkyPODs
+    pub n: usize,
+    /// Number of statements
+    pub ns: usize,
+    /// Max length of vectors
+    pub vl: usize,
+}
+
+/// Code generic over the circuit parameters, to be run for a shape only known at runtime.
+pub trait ShapeVisitor {
+    type Output;
+
+    fn visit<const L: usize, const M: usize, const N: usize, const NS: usize, const VL: usize>(
+        self,
+    ) -> Self::Output
+    where
+        [(); L + M + N]:,
+        [(); L + N]:;
+}
+
+macro_rules! circuit_shapes {
+    ($($(#[$attr:meta])* $name:ident = [$l:literal, $m:literal, $n:literal, $ns:literal, $vl:literal]),* $(,)?) => {
+        impl CircuitShape {
+            $(
+                $(#[$attr])*
+                pub const $name: CircuitShape = CircuitShape {
+                    l: $l,
+                    m: $m,
+                    n: $n,
+                    ns: $ns,
+                    vl: $vl,
+                };
+            )*
+
+            /// All the registered shapes, by increasing number of statements.
+            pub const ALL: &'static [CircuitShape] = &[$(CircuitShape::$name),*];
+
+            /// Runs the visitor with the const generics of this shape, which must be registered.
+            pub fn dispatch<V: ShapeVisitor>(&self, visitor: V) -> Result<V::Output> {
+                $(
+                    if *self == CircuitShape::$name {
+                        return Ok(visitor.visit::<$l, $m, $n, $ns, $vl>());
+                    }
+                )*
+                Err(anyhow!("Circuit shape {} is not registered", self))
+            }
+        }
+    };
+}
+
+circuit_shapes! {
+    SMALL = [0, 2, 2, 3, 10],
+    /// Shape of the `constants` crate
+    DEFAULT = [0, 2, 2, 10, 10],
+    LARGE = [0, 2, 2, 30, 10],
+}
+
+impl CircuitShape {
+    pub const fn of<
+        const L: usize,
+        const M: usize,
+        const N: usize,
+        const NS: usize,
+        const VL: usize,
+    >() -> Self {
+        Self {
+            l: L,
+            m: M,
+            n: N,
+            ns: NS,
+            vl: VL,
+        }
+    }
+
+    /// Smallest registered shape whose PODs can hold `num_statements` statements.
+    pub fn fitting(num_statements: usize) -> Result<Self> {
+        Self::ALL
+            .iter()
+            .find(|shape| shape.ns >= num_statements)
+            .copied()
+            .ok_or(anyhow!(
+                "No registered circuit shape holds {} statements",
+                num_statements
+            ))
+    }
+}
+
+impl fmt::Display for CircuitShape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[L={}, M={}, N={}, NS={}, VL={}]",
+            self.l, self.m, self.n, self.ns, self.vl
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ShapeOf;
+
+    impl ShapeVisitor for ShapeOf {
+        type Output = CircuitShape;
+
+        fn visit<const L: usize, const M: usize, const N: usize, const NS: usize, const VL: usize>(
+            self,
+        ) -> CircuitShape
+        where
+            [(); L + M + N]:,
+            [(); L + N]:,
+        {
+            CircuitShape::of::<L, M, N, NS, VL>()
+        }
+    }
+
+    #[test]
+    fn test_dispatch() -> Result<()> {
+        for shape in CircuitShape::ALL {
+            assert_eq!(shape.dispatch(ShapeOf)?, *shape);
+        }
+        let unregistered = CircuitShape::of::<0, 2, 2, 11, 10>();
+        assert!(unregistered.dispatch(ShapeOf).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_registry() -> Result<()> {
+        // Introducer PODs do not record their shape, and are verified with the registered shape
+        // having their number of statements, so there must be at most one
+        assert!(CircuitShape::ALL.windows(2).all(|w| w[0].ns < w[1].ns));
+
+        assert_eq!(CircuitShape::fitting(3)?, CircuitShape::SMALL);
+        assert_eq!(CircuitShape::fitting(4)?, CircuitShape::DEFAULT);
+        assert_eq!(CircuitShape::fitting(30)?, CircuitShape::LARGE);
+        assert!(CircuitShape::fitting(31).is_err());
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::{anyhow, Result};
-use plonky2::field::{
-    goldilocks_field::GoldilocksField,
-    types::{Field, PrimeField64},
+use plonky2::{
+    field::{
+        goldilocks_field::GoldilocksField,
+        types::{Field, PrimeField64},
+    },
+    hash::hash_types::HashOut,
 };
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fmt, fmt::Debug};
@@ -10,10 +13,15 @@ use super::{
     entry::Entry,
     gadget::GadgetID,
     origin::Origin,
+    payload::PODPayload,
     util::hash_string_to_field,
     value::{HashableEntryValue, ScalarOrVec},
-    POD,
 };
+use crate::F;
+
+/// Maximum number of inputs of `HASHOF` statements, whose anchored keys share the slots of
+/// statements with the result.
+pub const MAX_HASH_INPUTS: usize = 2;
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, Hash, Default)]
 pub struct AnchoredKey(pub Origin, pub String);
@@ -71,6 +79,58 @@ impl fmt::Display for Statement {
             Statement::MaxOf(result, op1, op2) => {
                 write!(f, "MaxOf({} = max({}, {}))", result, op1, op2)
             }
+            Statement::Gte(op1, op2) => write!(f, "Gte({} ≥ {})", op1, op2),
+            Statement::Lte(op1, op2) => write!(f, "Lte({} ≤ {})", op1, op2),
+            Statement::InRange(op, lower, upper) => {
+                write!(f, "InRange({} ∈ [{}, {}])", op, lower, upper)
+            }
+            Statement::ContainedInRoot(op, root) => {
+                write!(f, "ContainedInRoot({} ∈ {})", op, root)
+            }
+            Statement::NotContainedInRoot(op, root) => {
+                write!(f, "NotContainedInRoot({} ∉ {})", op, root)
+            }
+            Statement::HashOf(result, inputs) => write!(
+                f,
+                "HashOf({} = hash({}))",
+                result,
+                inputs
+                    .iter()
+                    .map(|input| input.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Statement::Nullifier(owner, scope, nullifier) => {
+                write!(
+                    f,
+                    "Nullifier({:?} of {} in {})",
+                    nullifier.elements, owner, scope
+                )
+            }
+            Statement::DifferenceOf(result, op1, op2) => {
+                write!(f, "DifferenceOf({} = {} - {})", result, op1, op2)
+            }
+            Statement::MinOf(result, op1, op2) => {
+                write!(f, "MinOf({} = min({}, {}))", result, op1, op2)
+            }
+            Statement::QuotientRemainderOf(result, op1, op2, is_remainder) => write!(
+                f,
+                "QuotientRemainderOf({} = {} {} {})",
+                result,
+                op1,
+                if *is_remainder { "mod" } else { "div" },
+                op2
+            ),
+            Statement::NegationOf(result, op) => write!(f, "NegationOf({} = -{})", result, op),
+            Statement::Custom(id, args) => write!(
+                f,
+                "Custom({}: {})",
+                id,
+                args.iter()
+                    .map(|arg| arg.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
         }
     }
 }
@@ -87,6 +147,29 @@ pub enum Statement {
     SumOf(AnchoredKey, AnchoredKey, AnchoredKey),
     ProductOf(AnchoredKey, AnchoredKey, AnchoredKey),
     MaxOf(AnchoredKey, AnchoredKey, AnchoredKey),
+    Gte(AnchoredKey, AnchoredKey),
+    Lte(AnchoredKey, AnchoredKey),
+    /// Value, lower bound and upper bound, the bounds being inclusive.
+    InRange(AnchoredKey, AnchoredKey, AnchoredKey),
+    /// Value and root of a Merkle tree it is a leaf of.
+    ContainedInRoot(AnchoredKey, AnchoredKey),
+    /// Value and root of a sorted Merkle tree it is not a leaf of.
+    NotContainedInRoot(AnchoredKey, AnchoredKey),
+    /// Result and inputs, of which there are 1 to `MAX_HASH_INPUTS`, the result holding the
+    /// full Poseidon hash of the input values (see `ScalarOrVec::from_hash`).
+    HashOf(AnchoredKey, Vec<AnchoredKey>),
+    /// Owner key and scope entries, and the nullifier of the owner's secret in this scope.
+    Nullifier(AnchoredKey, AnchoredKey, HashOut<F>),
+    /// Result and inputs, the second input being at most the first.
+    DifferenceOf(AnchoredKey, AnchoredKey, AnchoredKey),
+    MinOf(AnchoredKey, AnchoredKey, AnchoredKey),
+    /// Result, dividend and divisor of a u32 division, the result being
+    /// the remainder if the flag is set and the quotient otherwise.
+    QuotientRemainderOf(AnchoredKey, AnchoredKey, AnchoredKey, bool),
+    /// Result and input, the result being the negation of the input as a signed integer.
+    NegationOf(AnchoredKey, AnchoredKey),
+    /// Identifier of a custom predicate and its arguments, of which there are at most 3.
+    Custom(GoldilocksField, Vec<AnchoredKey>),
 }
 
 impl Statement {
@@ -102,6 +185,18 @@ impl Statement {
             Statement::SumOf(_, _, _) => "SUMOF",
             Statement::ProductOf(_, _, _) => "PRODUCTOF",
             Statement::MaxOf(_, _, _) => "MAXOF",
+            Statement::Gte(_, _) => "GTE",
+            Statement::Lte(_, _) => "LTE",
+            Statement::InRange(_, _, _) => "INRANGE",
+            Statement::ContainedInRoot(_, _) => "CONTAINEDINROOT",
+            Statement::NotContainedInRoot(_, _) => "NOTCONTAINEDINROOT",
+            Statement::HashOf(_, _) => "HASHOF",
+            Statement::Nullifier(_, _, _) => "NULLIFIER",
+            Statement::DifferenceOf(_, _, _) => "DIFFERENCEOF",
+            Statement::MinOf(_, _, _) => "MINOF",
+            Statement::QuotientRemainderOf(_, _, _, _) => "QUOTIENTREMAINDEROF",
+            Statement::NegationOf(_, _) => "NEGATIONOF",
+            Statement::Custom(_, _) => "CUSTOM",
         }
     }
     pub fn code_to_predicate(code: GoldilocksField) -> &'static str {
@@ -116,9 +211,31 @@ impl Statement {
             7 => "PRODUCTOF",
             8 => "MAXOF",
             9 => "LT",
+            10 => "GTE",
+            11 => "LTE",
+            12 => "INRANGE",
+            13 => "CONTAINEDINROOT",
+            14 => "NOTCONTAINEDINROOT",
+            15 => "HASHOF",
+            16 => "NULLIFIER",
+            17 => "DIFFERENCEOF",
+            18 => "MINOF",
+            19 => "QUOTIENTREMAINDEROF",
+            20 => "CUSTOM",
+            21 => "NEGATIONOF",
             _ => "",
         }
     }
+    /// Number of anchored keys of statements of the given code, which is at most 3 for
+    /// custom and hash ones.
+    pub fn code_to_num_anchored_keys(code: GoldilocksField) -> usize {
+        match code.to_canonical_u64() {
+            0 => 0,
+            1 => 1,
+            2 | 3 | 4 | 5 | 9 | 10 | 11 | 13 | 14 | 16 | 21 => 2,
+            _ => 3,
+        }
+    }
     pub fn from_entry(entry: &Entry, this_gadget_id: GadgetID) -> Self {
         Self::ValueOf(
             AnchoredKey(
@@ -139,6 +256,18 @@ impl Statement {
     pub const PRODUCT_OF: GoldilocksField = GoldilocksField(7);
     pub const MAX_OF: GoldilocksField = GoldilocksField(8);
     pub const LT: GoldilocksField = GoldilocksField(9);
+    pub const GTE: GoldilocksField = GoldilocksField(10);
+    pub const LTE: GoldilocksField = GoldilocksField(11);
+    pub const IN_RANGE: GoldilocksField = GoldilocksField(12);
+    pub const CONTAINED_IN_ROOT: GoldilocksField = GoldilocksField(13);
+    pub const NOT_CONTAINED_IN_ROOT: GoldilocksField = GoldilocksField(14);
+    pub const HASH_OF: GoldilocksField = GoldilocksField(15);
+    pub const NULLIFIER: GoldilocksField = GoldilocksField(16);
+    pub const DIFFERENCE_OF: GoldilocksField = GoldilocksField(17);
+    pub const MIN_OF: GoldilocksField = GoldilocksField(18);
+    pub const QUOTIENT_REMAINDER_OF: GoldilocksField = GoldilocksField(19);
+    pub const CUSTOM: GoldilocksField = GoldilocksField(20);
+    pub const NEGATION_OF: GoldilocksField = GoldilocksField(21);
     pub fn code(&self) -> GoldilocksField {
         match self {
             Self::None => Self::NONE,
@@ -151,13 +280,28 @@ impl Statement {
             Self::ProductOf(_, _, _) => Self::PRODUCT_OF,
             Self::MaxOf(_, _, _) => Self::MAX_OF,
             Self::Lt(_, _) => Self::LT,
+            Self::Gte(_, _) => Self::GTE,
+            Self::Lte(_, _) => Self::LTE,
+            Self::InRange(_, _, _) => Self::IN_RANGE,
+            Self::ContainedInRoot(_, _) => Self::CONTAINED_IN_ROOT,
+            Self::NotContainedInRoot(_, _) => Self::NOT_CONTAINED_IN_ROOT,
+            Self::HashOf(_, _) => Self::HASH_OF,
+            Self::Nullifier(_, _, _) => Self::NULLIFIER,
+            Self::DifferenceOf(_, _, _) => Self::DIFFERENCE_OF,
+            Self::MinOf(_, _, _) => Self::MIN_OF,
+            Self::QuotientRemainderOf(_, _, _, _) => Self::QUOTIENT_REMAINDER_OF,
+            Self::Custom(_, _) => Self::CUSTOM,
+            Self::NegationOf(_, _) => Self::NEGATION_OF,
         }
     }
     /// Field representation as a vector of length 11.
     /// Each statement is arranged as
     /// [code] ++ anchored_key1 ++ anchored_key2 ++ anchored_key3 ++ [value],
     /// where the leftmost keys are populated first and 0s are substituted in
-    /// for empty fields.
+    /// for empty fields. `VALUEOF` statements hold the type of their value
+    /// (`ScalarOrVec::type_field`) in place of the key of anchored_key3, so
+    /// that vectors may not be taken for scalars in circuit. `NULLIFIER`
+    /// statements hold their nullifier in place of anchored_key3 and value.
     pub fn to_fields(&self) -> Vec<GoldilocksField> {
         [
             vec![self.code()],
@@ -165,8 +309,8 @@ impl Statement {
                 Self::None => vec![GoldilocksField::ZERO; 10],
                 Self::ValueOf(anchkey, value) => [
                     anchkey.to_fields(),
-                    vec![GoldilocksField::ZERO; 6],
-                    vec![value.hash_or_value()],
+                    vec![GoldilocksField::ZERO; 5],
+                    vec![value.type_field(), value.hash_or_value()],
                 ]
                 .concat(),
                 Self::Equal(anchkey1, anchkey2) => [
@@ -213,13 +357,67 @@ impl Statement {
                     vec![GoldilocksField::ZERO],
                 ]
                 .concat(),
-                Self::MaxOf(anchkey1, anchkey2, anchkey3) => [
+                Self::MaxOf(anchkey1, anchkey2, anchkey3)
+                | Self::DifferenceOf(anchkey1, anchkey2, anchkey3)
+                | Self::MinOf(anchkey1, anchkey2, anchkey3) => [
                     anchkey1.to_fields(),
                     anchkey2.to_fields(),
                     anchkey3.to_fields(),
                     vec![GoldilocksField::ZERO],
                 ]
                 .concat(),
+                Self::Gte(anchkey1, anchkey2) => [
+                    anchkey1.to_fields(),
+                    anchkey2.to_fields(),
+                    vec![GoldilocksField::ZERO; 4],
+                ]
+                .concat(),
+                Self::Lte(anchkey1, anchkey2) => [
+                    anchkey1.to_fields(),
+                    anchkey2.to_fields(),
+                    vec![GoldilocksField::ZERO; 4],
+                ]
+                .concat(),
+                Self::InRange(anchkey1, anchkey2, anchkey3) => [
+                    anchkey1.to_fields(),
+                    anchkey2.to_fields(),
+                    anchkey3.to_fields(),
+                    vec![GoldilocksField::ZERO],
+                ]
+                .concat(),
+                Self::ContainedInRoot(anchkey1, anchkey2)
+                | Self::NotContainedInRoot(anchkey1, anchkey2)
+                | Self::NegationOf(anchkey1, anchkey2) => [
+                    anchkey1.to_fields(),
+                    anchkey2.to_fields(),
+                    vec![GoldilocksField::ZERO; 4],
+                ]
+                .concat(),
+                Self::Nullifier(anchkey1, anchkey2, nullifier) => [
+                    anchkey1.to_fields(),
+                    anchkey2.to_fields(),
+                    nullifier.elements.to_vec(),
+                ]
+                .concat(),
+                Self::QuotientRemainderOf(anchkey1, anchkey2, anchkey3, is_remainder) => [
+                    anchkey1.to_fields(),
+                    anchkey2.to_fields(),
+                    anchkey3.to_fields(),
+                    vec![GoldilocksField::from_bool(*is_remainder)],
+                ]
+                .concat(),
+                Self::HashOf(result, inputs) => [
+                    result.to_fields(),
+                    inputs.iter().flat_map(|input| input.to_fields()).collect(),
+                    vec![GoldilocksField::ZERO; 3 * (MAX_HASH_INPUTS - inputs.len()) + 1],
+                ]
+                .concat(),
+                Self::Custom(id, args) => [
+                    args.iter().flat_map(|arg| arg.to_fields()).collect(),
+                    vec![GoldilocksField::ZERO; 3 * (3 - args.len())],
+                    vec![*id],
+                ]
+                .concat(),
             },
         ]
         .concat()
@@ -266,6 +464,67 @@ impl Statement {
                 anchkey2.remap_origin(f)?,
                 anchkey3.remap_origin(f)?,
             )),
+            Self::HashOf(result, inputs) => Ok(Self::HashOf(
+                result.remap_origin(f)?,
+                inputs
+                    .iter()
+                    .map(|input| input.remap_origin(f))
+                    .collect::<Result<_>>()?,
+            )),
+            Self::Nullifier(anchkey1, anchkey2, nullifier) => Ok(Self::Nullifier(
+                anchkey1.remap_origin(f)?,
+                anchkey2.remap_origin(f)?,
+                *nullifier,
+            )),
+            Self::DifferenceOf(anchkey1, anchkey2, anchkey3) => Ok(Self::DifferenceOf(
+                anchkey1.remap_origin(f)?,
+                anchkey2.remap_origin(f)?,
+                anchkey3.remap_origin(f)?,
+            )),
+            Self::MinOf(anchkey1, anchkey2, anchkey3) => Ok(Self::MinOf(
+                anchkey1.remap_origin(f)?,
+                anchkey2.remap_origin(f)?,
+                anchkey3.remap_origin(f)?,
+            )),
+            Self::QuotientRemainderOf(anchkey1, anchkey2, anchkey3, is_remainder) => {
+                Ok(Self::QuotientRemainderOf(
+                    anchkey1.remap_origin(f)?,
+                    anchkey2.remap_origin(f)?,
+                    anchkey3.remap_origin(f)?,
+                    *is_remainder,
+                ))
+            }
+            Self::NegationOf(anchkey1, anchkey2) => Ok(Self::NegationOf(
+                anchkey1.remap_origin(f)?,
+                anchkey2.remap_origin(f)?,
+            )),
+            Self::Gte(anchkey1, anchkey2) => Ok(Self::Gte(
+                anchkey1.remap_origin(f)?,
+                anchkey2.remap_origin(f)?,
+            )),
+            Self::Lte(anchkey1, anchkey2) => Ok(Self::Lte(
+                anchkey1.remap_origin(f)?,
+                anchkey2.remap_origin(f)?,
+            )),
+            Self::InRange(anchkey1, anchkey2, anchkey3) => Ok(Self::InRange(
+                anchkey1.remap_origin(f)?,
+                anchkey2.remap_origin(f)?,
+                anchkey3.remap_origin(f)?,
+            )),
+            Self::ContainedInRoot(anchkey1, anchkey2) => Ok(Self::ContainedInRoot(
+                anchkey1.remap_origin(f)?,
+                anchkey2.remap_origin(f)?,
+            )),
+            Self::NotContainedInRoot(anchkey1, anchkey2) => Ok(Self::NotContainedInRoot(
+                anchkey1.remap_origin(f)?,
+                anchkey2.remap_origin(f)?,
+            )),
+            Self::Custom(id, args) => Ok(Self::Custom(
+                *id,
+                args.iter()
+                    .map(|arg| arg.remap_origin(f))
+                    .collect::<Result<_>>()?,
+            )),
         }
     }
     // Misc helpers
@@ -293,6 +552,22 @@ impl Statement {
             Self::MaxOf(anchkey1, anchkey2, anchkey3) => {
                 vec![anchkey1.clone(), anchkey2.clone(), anchkey3.clone()]
             }
+            Self::DifferenceOf(anchkey1, anchkey2, anchkey3)
+            | Self::MinOf(anchkey1, anchkey2, anchkey3)
+            | Self::QuotientRemainderOf(anchkey1, anchkey2, anchkey3, _) => {
+                vec![anchkey1.clone(), anchkey2.clone(), anchkey3.clone()]
+            }
+            Self::Gte(anchkey1, anchkey2) => vec![anchkey1.clone(), anchkey2.clone()],
+            Self::Lte(anchkey1, anchkey2) => vec![anchkey1.clone(), anchkey2.clone()],
+            Self::InRange(anchkey1, anchkey2, anchkey3) => {
+                vec![anchkey1.clone(), anchkey2.clone(), anchkey3.clone()]
+            }
+            Self::ContainedInRoot(anchkey1, anchkey2)
+            | Self::NotContainedInRoot(anchkey1, anchkey2)
+            | Self::Nullifier(anchkey1, anchkey2, _)
+            | Self::NegationOf(anchkey1, anchkey2) => vec![anchkey1.clone(), anchkey2.clone()],
+            Self::HashOf(result, inputs) => [vec![result.clone()], inputs.clone()].concat(),
+            Self::Custom(_, args) => args.clone(),
         }
     }
     // Helper to get the anchoredkey of a value of statement
@@ -307,7 +582,12 @@ impl Statement {
         match self {
             Statement::SumOf(result, _, _)
             | Statement::ProductOf(result, _, _)
-            | Statement::MaxOf(result, _, _) => Some(result.clone()),
+            | Statement::MaxOf(result, _, _)
+            | Statement::HashOf(result, _)
+            | Statement::DifferenceOf(result, _, _)
+            | Statement::MinOf(result, _, _)
+            | Statement::QuotientRemainderOf(result, _, _, _)
+            | Statement::NegationOf(result, _) => Some(result.clone()),
             _ => None,
         }
     }
@@ -358,12 +638,16 @@ impl StatementRef {
     pub fn new(pod_name: impl Into<String>, statement_name: impl Into<String>) -> Self {
         Self(pod_name.into(), statement_name.into())
     }
-    pub fn index_map(pods_list: &[(String, POD)]) -> HashMap<Self, (usize, usize)> {
+    /// Map from StatementRef to (pod index, statement index), for a list of PODs or of bare
+    /// payloads.
+    pub fn index_map<P: AsRef<PODPayload>>(
+        pods_list: &[(String, P)],
+    ) -> HashMap<Self, (usize, usize)> {
         pods_list
             .iter()
             .enumerate()
             .flat_map(|(pod_num, (pod_name, pod))| {
-                pod.payload.statements_list.iter().enumerate().map(
+                pod.as_ref().statements_list.iter().enumerate().map(
                     move |(statement_num, (statement_name, _))| {
                         (
                             StatementRef(pod_name.clone(), statement_name.clone()),
@@ -2,11 +2,14 @@ use ark_ff::BigInteger;
 use ark_ff::PrimeField;
 use parcnet_pod::pod::{Fq, PodValue};
 use plonky2::{
-    field::goldilocks_field::GoldilocksField,
-    hash::poseidon::PoseidonHash,
+    field::{goldilocks_field::GoldilocksField, types::PrimeField64},
+    hash::{hash_types::HashOut, poseidon::PoseidonHash},
     plonk::config::{GenericHashOut, Hasher},
 };
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use crate::F;
 
 // EntryValue trait, and ScalarOrVec type which implements it.
 // This is a field element or array of field elements.
@@ -32,18 +35,112 @@ pub enum ScalarOrVec {
     Vector(Vec<GoldilocksField>),
 }
 
+impl ScalarOrVec {
+    /// Order of the comparison statements. Scalars are compared as canonical field elements.
+    /// Vectors of u32 limbs and of the same length are compared as two's complement integers
+    /// with the most significant limb first, as in `From<i64>`, ie. lexicographically once the
+    /// sign bit of their first limb is flipped. The padding of `Entry::pad_if_vec` repeats the
+    /// first limb, so it does not change the order. Other values are not comparable.
+    pub fn numeric_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Scalar(x), Self::Scalar(y)) => {
+                Some(x.to_canonical_u64().cmp(&y.to_canonical_u64()))
+            }
+            (Self::Vector(x), Self::Vector(y)) if x.len() == y.len() => {
+                Some(comparison_limbs(x)?.cmp(&comparison_limbs(y)?))
+            }
+            _ => None,
+        }
+    }
+
+    /// Negation of a signed integer, ie. of a vector of u32 limbs as in `From<i64>` whose first
+    /// limb extends the sign (0 or `u32::MAX`), if the result is one too. Such vectors keep
+    /// their value when padded by `Entry::pad_if_vec`, and so do their negations.
+    pub fn negation(&self) -> Option<Self> {
+        let sign_extensions = [0, u32::MAX];
+        let Self::Vector(v) = self else {
+            return None;
+        };
+        let limbs = v
+            .iter()
+            .map(|x| u32::try_from(x.to_canonical_u64()).ok())
+            .collect::<Option<Vec<_>>>()?;
+        if !sign_extensions.contains(limbs.first()?) {
+            return None;
+        }
+        // Two's complement: flip the bits and add 1, from the least significant limb.
+        let mut carry = true;
+        let mut negated = limbs
+            .iter()
+            .rev()
+            .map(|limb| {
+                let (sum, overflow) = (!limb).overflowing_add(carry as u32);
+                carry = overflow;
+                sum
+            })
+            .collect::<Vec<_>>();
+        negated.reverse();
+        sign_extensions.contains(&negated[0]).then(|| {
+            Self::Vector(
+                negated
+                    .into_iter()
+                    .map(|limb| GoldilocksField(limb as u64))
+                    .collect(),
+            )
+        })
+    }
+
+    /// Type of the value in field representations: 1 for vectors and 0 for scalars.
+    pub fn type_field(&self) -> GoldilocksField {
+        GoldilocksField(matches!(self, Self::Vector(_)) as u64)
+    }
+
+    /// Value holding a full hash, ie. the vector of its elements.
+    pub fn from_hash(hash: HashOut<F>) -> Self {
+        Self::Vector(hash.elements.to_vec())
+    }
+
+    /// Whether the value holds the given hash, possibly padded as by `Entry::pad_if_vec`.
+    pub fn holds_hash(&self, hash: HashOut<F>) -> bool {
+        match self {
+            Self::Vector(v) if v.len() >= hash.elements.len() => {
+                let (elements, padding) = v.split_at(hash.elements.len());
+                elements == hash.elements && padding.iter().all(|x| *x == hash.elements[0])
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Limbs of a vector, which must be u32, with the sign bit of the first one flipped so that
+/// two's complement integers compare as their limbs.
+fn comparison_limbs(v: &[GoldilocksField]) -> Option<Vec<u32>> {
+    v.iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let limb: u32 = x.to_canonical_u64().try_into().ok()?;
+            Some(if i == 0 { limb ^ (1 << 31) } else { limb })
+        })
+        .collect()
+}
+
+/// Limbs of an integer as a 96-bit two's complement one, as u32s with the most significant
+/// first, which hold both i64s and u64s, with the same limbs when they are equal.
+fn int_limbs(x: i128) -> Vec<GoldilocksField> {
+    [64, 32, 0]
+        .map(|shift| GoldilocksField(((x >> shift) as u32) as u64))
+        .to_vec()
+}
+
 impl From<u64> for ScalarOrVec {
     fn from(x: u64) -> Self {
-        Self::Vector(vec![
-            GoldilocksField((x as u32) as u64),
-            GoldilocksField(x >> 32),
-        ])
+        Self::Vector(int_limbs(x as i128))
     }
 }
 
 impl From<i64> for ScalarOrVec {
     fn from(x: i64) -> Self {
-        Self::from(x as u64)
+        Self::Vector(int_limbs(x as i128))
     }
 }
 
@@ -54,7 +151,7 @@ impl From<Fq> for ScalarOrVec {
         let x_fields = x_bytes
             .chunks(4)
             .map(|chunk| {
-                GoldilocksField(chunk.iter().rev().fold(0, |acc, b| 8 * acc + *b as u32) as u64)
+                GoldilocksField(chunk.iter().rev().fold(0, |acc, b| 256 * acc + *b as u32) as u64)
             })
             .collect::<Vec<_>>();
         ScalarOrVec::Vector(x_fields)
@@ -69,7 +166,7 @@ impl From<PodValue> for ScalarOrVec {
             // have not fixed the representations of other types in
             // this system yet, hence the hashing in other cases.
 
-            // Represent i64 as LE u32 array of corresponding u64.
+            // Represent i64 as in `From<i64>`.
             PodValue::Int(n) => Self::from(n),
             // Represent BN254 scalar field element as LE u32 array.
             PodValue::Cryptographic(n) => Self::from(n),
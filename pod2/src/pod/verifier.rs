@@ -0,0 +1,129 @@
+//! Verifier data of the recursive circuits, needed to verify Plonky, compressed Plonky and
+//! Introducer PODs. Getting it requires building the circuits, which takes seconds, so it is
+//! built once per set of `L, M, N, NS, VL` parameters and then reused.
+use anyhow::Result;
+use plonky2::plonk::circuit_data::VerifierCircuitData;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::pod::gadget::{CompressionCircuit, IntroducerCircuit, PlonkyButNotPlonkyGadget};
+use crate::pod::shape::CircuitShape;
+use crate::recursion::{traits_examples::ExampleIntroducer, IntroducerCircuitTrait};
+use crate::{C, D, F};
+
+/// Process-wide verifiers, keyed by their circuit shape.
+static VERIFIERS: OnceLock<Mutex<HashMap<CircuitShape, Arc<dyn Any + Send + Sync>>>> =
+    OnceLock::new();
+
+/// Holds the verifier data of the IntroducerCircuit, of the PlonkyButNotPlonkyGadget circuit and
+/// of the CompressionCircuit. Each one is lazily built the first time it is needed.
+pub struct Verifier<
+    const L: usize,
+    const M: usize,
+    const N: usize,
+    const NS: usize,
+    const VL: usize,
+> {
+    introducer: OnceLock<VerifierCircuitData<F, C, D>>,
+    plonky: OnceLock<VerifierCircuitData<F, C, D>>,
+    compressed: OnceLock<VerifierCircuitData<F, C, D>>,
+}
+
+impl<const L: usize, const M: usize, const N: usize, const NS: usize, const VL: usize>
+    Verifier<L, M, N, NS, VL>
+where
+    [(); L + M + N]:,
+    [(); L + N]:,
+{
+    pub fn new() -> Self {
+        Self {
+            introducer: OnceLock::new(),
+            plonky: OnceLock::new(),
+            compressed: OnceLock::new(),
+        }
+    }
+
+    /// Builds a Verifier from already computed verifier data, eg. the one of the circuits used
+    /// to generate the proofs.
+    pub fn from_verifier_data(
+        introducer: Option<VerifierCircuitData<F, C, D>>,
+        plonky: VerifierCircuitData<F, C, D>,
+    ) -> Self {
+        let verifier = Self::new();
+        if let Some(introducer) = introducer {
+            let _ = verifier.introducer.set(introducer);
+        }
+        let _ = verifier.plonky.set(plonky);
+        verifier
+    }
+
+    /// Returns the Verifier shared by the whole process for these parameters, so that the
+    /// circuits are built at most once.
+    pub fn cached() -> Arc<Self> {
+        let mut verifiers = VERIFIERS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        verifiers
+            .entry(CircuitShape::of::<L, M, N, NS, VL>())
+            .or_insert_with(|| Arc::new(Self::new()))
+            .clone()
+            .downcast::<Self>()
+            .expect("verifiers are keyed by their circuit shape")
+    }
+
+    /// Verifier data of the IntroducerCircuit.
+    pub fn introducer_verifier_data(&self) -> Result<&VerifierCircuitData<F, C, D>> {
+        if let Some(verifier_data) = self.introducer.get() {
+            return Ok(verifier_data);
+        }
+        let verifier_data = IntroducerCircuit::<NS, VL>::circuit_data()?.verifier_data();
+        Ok(self.introducer.get_or_init(|| verifier_data))
+    }
+
+    /// Verifier data of the PlonkyButNotPlonkyGadget circuit.
+    pub fn plonky_verifier_data(&self) -> Result<&VerifierCircuitData<F, C, D>> {
+        if let Some(verifier_data) = self.plonky.get() {
+            return Ok(verifier_data);
+        }
+        // the pod1 circuit is the IntroducerCircuit if L > 0, and the ExampleIntroducer otherwise
+        let pod1_verifier_data = if L > 0 {
+            self.introducer_verifier_data()?.clone()
+        } else {
+            ExampleIntroducer::circuit_data()?.verifier_data()
+        };
+        let verifier_data =
+            PlonkyButNotPlonkyGadget::<L, M, N, NS, VL>::circuit_data(pod1_verifier_data)?
+                .verifier_data();
+        Ok(self.plonky.get_or_init(|| verifier_data))
+    }
+
+    /// Verifier data of the CompressionCircuit.
+    pub fn compressed_verifier_data(&self) -> Result<&VerifierCircuitData<F, C, D>> {
+        if let Some(verifier_data) = self.compressed.get() {
+            return Ok(verifier_data);
+        }
+        let verifier_data = CompressionCircuit::<L, M, N, NS, VL>::cached()?.verifier_data();
+        Ok(self.compressed.get_or_init(|| verifier_data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cached_verifier() {
+        // the circuits are only built on demand, so getting the verifiers is cheap
+        let v0 = Verifier::<0, 3, 2, 2, 0>::cached();
+        let v1 = Verifier::<0, 3, 2, 2, 0>::cached();
+        assert!(Arc::ptr_eq(&v0, &v1));
+
+        let v2 = Verifier::<1, 3, 2, 2, 0>::cached();
+        assert!(!std::ptr::eq(
+            Arc::as_ptr(&v0) as *const (),
+            Arc::as_ptr(&v2) as *const ()
+        ));
+    }
+}
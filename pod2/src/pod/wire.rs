@@ -0,0 +1,679 @@
+//! Versioned wire formats of PODs. Both carry the magic, format version, gadget ID, payload and
+//! proof of the POD, which are mirrored by types frozen for each format version so that changes
+//! to `Statement` or `PODProof` do not silently change the encodings.
+//!
+//! The binary format is the magic, the format version as a little-endian u16 and the
+//! postcard-encoded envelope of that version. The JSON format is an object with `magic`,
+//! `version` and the envelope's fields, field elements being written as hex strings and
+//! predicates, gadget IDs and proof kinds by name. In both, the contents of proofs are
+//! postcard-encoded, as hex in JSON.
+use plonky2::{
+    field::{
+        goldilocks_field::GoldilocksField,
+        types::{Field64, PrimeField64},
+    },
+    hash::hash_types::HashOut,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::HashMap, fmt};
+
+use super::{
+    gadget::GadgetID,
+    payload::PODPayload,
+    statement::{AnchoredKey, MAX_HASH_INPUTS},
+    value::ScalarOrVec,
+    Origin, PODProof, Statement, POD,
+};
+use crate::{Error, Result};
+
+pub const MAGIC: [u8; 4] = *b"POD2";
+/// Format version written by `POD::to_bytes` and `POD::to_json`.
+pub const FORMAT_VERSION: u16 = 2;
+
+impl POD {
+    /// Compact binary encoding of the POD in the current format version.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let envelope = postcard::to_stdvec(&Envelope::new(self)?).map_err(malformed)?;
+        Ok([&MAGIC[..], &FORMAT_VERSION.to_le_bytes(), &envelope].concat())
+    }
+
+    /// Decodes a POD from its binary encoding in any supported format version.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 6 || bytes[..4] != MAGIC {
+            return Err(malformed("missing magic"));
+        }
+        match u16::from_le_bytes([bytes[4], bytes[5]]) {
+            version @ (1 | 2) => postcard::from_bytes::<Envelope>(&bytes[6..])
+                .map_err(malformed)?
+                .pod(version),
+            version => Err(unsupported_version(version)),
+        }
+    }
+
+    /// Human-readable JSON encoding of the POD in the current format version.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&JsonEnvelope {
+            magic: String::from_utf8_lossy(&MAGIC).to_string(),
+            version: FORMAT_VERSION,
+            envelope: Envelope::new(self)?,
+        })
+        .map_err(malformed)
+    }
+
+    /// Decodes a POD from its JSON encoding in any supported format version.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(malformed)?;
+        let header: JsonHeader = serde_json::from_value(value.clone()).map_err(malformed)?;
+        if header.magic.as_bytes() != MAGIC {
+            return Err(malformed("missing magic"));
+        }
+        match header.version {
+            version @ (1 | 2) => serde_json::from_value::<JsonEnvelope<Envelope>>(value)
+                .map_err(malformed)?
+                .envelope
+                .pod(version),
+            version => Err(unsupported_version(version)),
+        }
+    }
+
+    /// Binary encoding of the gadget ID and payload of the POD in format version 2, which covers
+    /// statement and origin names as well as full vector values, but not the proof.
+    pub(super) fn claims_to_bytes(&self) -> Vec<u8> {
+        let claims = (
+            WireGadgetId(self.proof_type),
+            WireStatement::list(&self.payload),
+        );
+        postcard::to_stdvec(&claims).expect("gadget IDs and statements are encodable")
+    }
+}
+
+fn malformed(e: impl fmt::Display) -> Error {
+    Error::InvalidInput(format!("Malformed POD encoding: {}", e))
+}
+
+fn unsupported_version(version: u16) -> Error {
+    Error::InvalidInput(format!(
+        "Unsupported POD format version {}, at most {} is supported",
+        version, FORMAT_VERSION
+    ))
+}
+
+#[derive(Deserialize)]
+struct JsonHeader {
+    magic: String,
+    version: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonEnvelope<E> {
+    magic: String,
+    version: u16,
+    #[serde(flatten)]
+    envelope: E,
+}
+
+/// Format versions 1 and 2, which only differ in the encoding of nullifiers: a single element in
+/// version 1 and the 4 elements of their hash in version 2.
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    gadget_id: WireGadgetId,
+    /// Statements in the order of the payload's list
+    payload: Vec<WireStatement>,
+    proof: WireProof,
+}
+
+impl Envelope {
+    fn new(pod: &POD) -> Result<Self> {
+        Ok(Self {
+            gadget_id: WireGadgetId(pod.proof_type),
+            payload: WireStatement::list(&pod.payload),
+            proof: WireProof::new(&pod.proof)?,
+        })
+    }
+
+    fn pod(self, version: u16) -> Result<POD> {
+        let statements_list = self
+            .payload
+            .into_iter()
+            .map(|statement| statement.statement(version))
+            .collect::<Result<Vec<_>>>()?;
+        let statements_map: HashMap<_, _> = statements_list.iter().cloned().collect();
+        let payload = PODPayload {
+            statements_list,
+            statements_map,
+        };
+        payload.validate().map_err(malformed)?;
+        Ok(POD {
+            payload,
+            proof: self.proof.proof()?,
+            proof_type: self.gadget_id.0,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct WireStatement {
+    name: String,
+    predicate: WirePredicate,
+    args: Vec<WireAnchoredKey>,
+    /// Value of `VALUEOF` statements, nullifier of `NULLIFIER` ones, remainder flag of
+    /// `QUOTIENTREMAINDEROF` ones and predicate identifier of `CUSTOM` ones.
+    value: Option<WireValue>,
+}
+
+impl WireStatement {
+    /// Statements in the order of the payload's list.
+    fn list(payload: &PODPayload) -> Vec<Self> {
+        payload
+            .statements_list
+            .iter()
+            .map(|(name, statement)| Self::new(name, statement))
+            .collect()
+    }
+
+    fn new(name: &str, statement: &Statement) -> Self {
+        let value = match statement {
+            Statement::ValueOf(_, ScalarOrVec::Scalar(x)) => Some(WireValue::Scalar(x.into())),
+            Statement::ValueOf(_, ScalarOrVec::Vector(v)) => {
+                Some(WireValue::Vector(v.iter().map(WireField::from).collect()))
+            }
+            Statement::Nullifier(_, _, nullifier) => Some(WireValue::Vector(
+                nullifier.elements.iter().map(WireField::from).collect(),
+            )),
+            Statement::QuotientRemainderOf(_, _, _, is_remainder) => {
+                Some(WireValue::Scalar(WireField(*is_remainder as u64)))
+            }
+            Statement::Custom(id, _) => Some(WireValue::Scalar(id.into())),
+            _ => None,
+        };
+        Self {
+            name: name.to_string(),
+            predicate: WirePredicate(statement.code().0 as u8),
+            args: statement
+                .anchored_keys()
+                .into_iter()
+                .map(WireAnchoredKey::from)
+                .collect(),
+            value,
+        }
+    }
+
+    /// Decodes the statement from an envelope of the given format version.
+    fn statement(self, version: u16) -> Result<(String, Statement)> {
+        let keys: Vec<AnchoredKey> = self.args.into_iter().map(AnchoredKey::from).collect();
+        // Statement codes, which are frozen by the circuits.
+        let statement = match (self.predicate.0, keys.as_slice(), self.value) {
+            (0, [], None) => Statement::None,
+            (1, [k], Some(WireValue::Scalar(x))) => {
+                Statement::ValueOf(k.clone(), ScalarOrVec::Scalar(x.into()))
+            }
+            (1, [k], Some(WireValue::Vector(v))) => Statement::ValueOf(
+                k.clone(),
+                ScalarOrVec::Vector(v.into_iter().map(GoldilocksField::from).collect()),
+            ),
+            (2, [k1, k2], None) => Statement::Equal(k1.clone(), k2.clone()),
+            (3, [k1, k2], None) => Statement::NotEqual(k1.clone(), k2.clone()),
+            (4, [k1, k2], None) => Statement::Gt(k1.clone(), k2.clone()),
+            (5, [k1, k2], None) => Statement::Contains(k1.clone(), k2.clone()),
+            (6, [k1, k2, k3], None) => Statement::SumOf(k1.clone(), k2.clone(), k3.clone()),
+            (7, [k1, k2, k3], None) => Statement::ProductOf(k1.clone(), k2.clone(), k3.clone()),
+            (8, [k1, k2, k3], None) => Statement::MaxOf(k1.clone(), k2.clone(), k3.clone()),
+            (9, [k1, k2], None) => Statement::Lt(k1.clone(), k2.clone()),
+            (10, [k1, k2], None) => Statement::Gte(k1.clone(), k2.clone()),
+            (11, [k1, k2], None) => Statement::Lte(k1.clone(), k2.clone()),
+            (12, [k1, k2, k3], None) => Statement::InRange(k1.clone(), k2.clone(), k3.clone()),
+            (13, [k1, k2], None) => Statement::ContainedInRoot(k1.clone(), k2.clone()),
+            (14, [k1, k2], None) => Statement::NotContainedInRoot(k1.clone(), k2.clone()),
+            (15, [k1, inputs @ ..], None) if (1..=MAX_HASH_INPUTS).contains(&inputs.len()) => {
+                Statement::HashOf(k1.clone(), inputs.to_vec())
+            }
+            // Version 1 nullifiers are decoded as the first element of the hash.
+            (16, [k1, k2], Some(WireValue::Scalar(nullifier))) if version == 1 => {
+                let elements = [
+                    nullifier.into(),
+                    GoldilocksField(0),
+                    GoldilocksField(0),
+                    GoldilocksField(0),
+                ];
+                Statement::Nullifier(k1.clone(), k2.clone(), HashOut { elements })
+            }
+            (16, [k1, k2], Some(WireValue::Vector(nullifier)))
+                if version >= 2 && nullifier.len() == 4 =>
+            {
+                let elements = nullifier.into_iter().map(GoldilocksField::from).collect();
+                Statement::Nullifier(k1.clone(), k2.clone(), HashOut::from_vec(elements))
+            }
+            (17, [k1, k2, k3], None) => Statement::DifferenceOf(k1.clone(), k2.clone(), k3.clone()),
+            (18, [k1, k2, k3], None) => Statement::MinOf(k1.clone(), k2.clone(), k3.clone()),
+            (19, [k1, k2, k3], Some(WireValue::Scalar(WireField(flag @ (0 | 1))))) => {
+                Statement::QuotientRemainderOf(k1.clone(), k2.clone(), k3.clone(), flag == 1)
+            }
+            (20, keys, Some(WireValue::Scalar(id))) if keys.len() <= 3 => {
+                Statement::Custom(id.into(), keys.to_vec())
+            }
+            (21, [k1, k2], None) => Statement::NegationOf(k1.clone(), k2.clone()),
+            (code, _, _) => {
+                return Err(malformed(format!(
+                    "statement {} does not match predicate {}",
+                    self.name,
+                    Statement::code_to_predicate(GoldilocksField(code as u64))
+                )))
+            }
+        };
+        Ok((self.name, statement))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct WireAnchoredKey {
+    origin_id: WireField,
+    origin_name: String,
+    gadget_id: WireGadgetId,
+    key: String,
+}
+
+impl From<AnchoredKey> for WireAnchoredKey {
+    fn from(AnchoredKey(origin, key): AnchoredKey) -> Self {
+        Self {
+            origin_id: origin.origin_id.into(),
+            origin_name: origin.origin_name,
+            gadget_id: WireGadgetId(origin.gadget_id),
+            key,
+        }
+    }
+}
+
+impl From<WireAnchoredKey> for AnchoredKey {
+    fn from(k: WireAnchoredKey) -> Self {
+        AnchoredKey(
+            Origin::new(k.origin_id.into(), k.origin_name, k.gadget_id.0),
+            k.key,
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+enum WireValue {
+    Scalar(WireField),
+    Vector(Vec<WireField>),
+}
+
+#[derive(Serialize, Deserialize)]
+enum WireProofKind {
+    Schnorr,
+    Oracle,
+    Plonky,
+    Introducer,
+    EcSchnorr,
+    CompressedPlonky,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WireProof {
+    kind: WireProofKind,
+    /// Postcard encoding of the signature, keys, circuit shape or proof the variant holds.
+    data: WireBytes,
+}
+
+impl WireProof {
+    fn new(proof: &PODProof) -> Result<Self> {
+        let (kind, data) = match proof {
+            PODProof::Schnorr(sig) => (WireProofKind::Schnorr, postcard::to_stdvec(sig)),
+            PODProof::Oracle(pk, sig) => (WireProofKind::Oracle, postcard::to_stdvec(&(pk, sig))),
+            PODProof::Plonky(shape, proof) => {
+                (WireProofKind::Plonky, postcard::to_stdvec(&(shape, proof)))
+            }
+            PODProof::Introducer(proof) => (WireProofKind::Introducer, postcard::to_stdvec(proof)),
+            PODProof::EcSchnorr(pk, sig) => {
+                (WireProofKind::EcSchnorr, postcard::to_stdvec(&(pk, sig)))
+            }
+            PODProof::CompressedPlonky(shape, proof) => (
+                WireProofKind::CompressedPlonky,
+                postcard::to_stdvec(&(shape, proof)),
+            ),
+        };
+        Ok(Self {
+            kind,
+            data: WireBytes(data.map_err(malformed)?),
+        })
+    }
+
+    fn proof(self) -> Result<PODProof> {
+        let data = &self.data.0;
+        Ok(match self.kind {
+            WireProofKind::Schnorr => {
+                PODProof::Schnorr(postcard::from_bytes(data).map_err(malformed)?)
+            }
+            WireProofKind::Oracle => {
+                let (pk, sig) = postcard::from_bytes(data).map_err(malformed)?;
+                PODProof::Oracle(pk, sig)
+            }
+            WireProofKind::Plonky => {
+                let (shape, proof) = postcard::from_bytes(data).map_err(malformed)?;
+                PODProof::Plonky(shape, proof)
+            }
+            WireProofKind::Introducer => {
+                PODProof::Introducer(postcard::from_bytes(data).map_err(malformed)?)
+            }
+            WireProofKind::EcSchnorr => {
+                let (pk, sig) = postcard::from_bytes(data).map_err(malformed)?;
+                PODProof::EcSchnorr(pk, sig)
+            }
+            WireProofKind::CompressedPlonky => {
+                let (shape, proof) = postcard::from_bytes(data).map_err(malformed)?;
+                PODProof::CompressedPlonky(shape, proof)
+            }
+        })
+    }
+}
+
+/// Canonical field element, as a u64 in binary and as 16 hex digits in JSON.
+struct WireField(u64);
+
+impl From<&GoldilocksField> for WireField {
+    fn from(x: &GoldilocksField) -> Self {
+        Self(x.to_canonical_u64())
+    }
+}
+
+impl From<GoldilocksField> for WireField {
+    fn from(x: GoldilocksField) -> Self {
+        Self::from(&x)
+    }
+}
+
+impl From<WireField> for GoldilocksField {
+    fn from(x: WireField) -> Self {
+        GoldilocksField(x.0)
+    }
+}
+
+impl Serialize for WireField {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&format!("0x{:016x}", self.0))
+        } else {
+            serializer.serialize_u64(self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WireField {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let x = if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            s.strip_prefix("0x")
+                .filter(|digits| digits.len() == 16)
+                .and_then(|digits| u64::from_str_radix(digits, 16).ok())
+                .ok_or_else(|| de::Error::custom(format!("{} is not a hex field element", s)))?
+        } else {
+            u64::deserialize(deserializer)?
+        };
+        if x >= GoldilocksField::ORDER {
+            return Err(de::Error::custom(format!(
+                "{} is not a canonical field element",
+                x
+            )));
+        }
+        Ok(Self(x))
+    }
+}
+
+/// Gadget ID, as its code in binary and by name in JSON.
+struct WireGadgetId(GadgetID);
+
+const GADGET_IDS: [GadgetID; 7] = [
+    GadgetID::NONE,
+    GadgetID::SCHNORR16,
+    GadgetID::ORACLE,
+    GadgetID::PLONKY,
+    GadgetID::INTRODUCER,
+    GadgetID::ECSCHNORR,
+    GadgetID::COMPRESSED_PLONKY,
+];
+
+impl Serialize for WireGadgetId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.0.to_string())
+        } else {
+            serializer.serialize_u8(self.0 as u8)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WireGadgetId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let gadget_id = if deserializer.is_human_readable() {
+            let name = String::deserialize(deserializer)?;
+            GADGET_IDS.into_iter().find(|id| id.to_string() == name)
+        } else {
+            let code = u8::deserialize(deserializer)?;
+            GADGET_IDS.into_iter().find(|id| *id as u8 == code)
+        };
+        gadget_id
+            .map(Self)
+            .ok_or_else(|| de::Error::custom("unknown gadget ID"))
+    }
+}
+
+/// Statement code, as such in binary and as the predicate's name in JSON.
+struct WirePredicate(u8);
+
+const NUM_PREDICATES: u8 = 22;
+
+impl Serialize for WirePredicate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(Statement::code_to_predicate(GoldilocksField(self.0 as u64)))
+        } else {
+            serializer.serialize_u8(self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WirePredicate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let code = if deserializer.is_human_readable() {
+            let name = String::deserialize(deserializer)?;
+            (0..NUM_PREDICATES)
+                .find(|code| Statement::code_to_predicate(GoldilocksField(*code as u64)) == name)
+        } else {
+            Some(u8::deserialize(deserializer)?).filter(|code| *code < NUM_PREDICATES)
+        };
+        code.map(Self)
+            .ok_or_else(|| de::Error::custom("unknown predicate"))
+    }
+}
+
+/// Bytes, as such in binary and as a hex string in JSON.
+struct WireBytes(Vec<u8>);
+
+impl Serialize for WireBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let hex: String = self.0.iter().map(|b| format!("{:02x}", b)).collect();
+            serializer.serialize_str(&format!("0x{}", hex))
+        } else {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WireBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            return deserializer.deserialize_byte_buf(BytesVisitor).map(Self);
+        }
+        let s = String::deserialize(deserializer)?;
+        s.strip_prefix("0x")
+            .filter(|digits| digits.len() % 2 == 0 && digits.is_ascii())
+            .and_then(|digits| {
+                (0..digits.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).ok())
+                    .collect::<Option<Vec<_>>>()
+            })
+            .map(Self)
+            .ok_or_else(|| de::Error::custom(format!("{} is not a hex string", s)))
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> de::Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> std::result::Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> std::result::Result<Self::Value, E> {
+        Ok(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::{
+        field::{goldilocks_field::GoldilocksField, types::Field64},
+        hash::hash_types::HashOut,
+    };
+    use std::collections::HashMap;
+
+    use super::FORMAT_VERSION;
+    use crate::pod::{
+        entry::Entry, gadget::GadgetID, payload::PODPayload, statement::AnchoredKey,
+        value::ScalarOrVec, Origin, PODProof, Statement, POD,
+    };
+    use crate::signature::{ecschnorr::EcSchnorrSecretKey, schnorr::SchnorrSignature};
+    use crate::Error;
+
+    /// POD encoded by the golden files, with the given nullifier. Its signature is not valid.
+    fn golden_pod(nullifier: [u64; 4]) -> POD {
+        let self_key = |key: &str| {
+            AnchoredKey(
+                Origin::auto("_SELF".to_string(), GadgetID::SCHNORR16),
+                key.to_string(),
+            )
+        };
+        let alice_age = AnchoredKey(
+            Origin::new(GoldilocksField(2), "alice".to_string(), GadgetID::ECSCHNORR),
+            "age".to_string(),
+        );
+        let statements = HashMap::from([
+            ("GT:c".to_string(), Statement::Gt(alice_age, self_key("b"))),
+            (
+                "NULLIFIER:n".to_string(),
+                Statement::Nullifier(
+                    self_key("_signer"),
+                    self_key("a"),
+                    HashOut {
+                        elements: nullifier.map(GoldilocksField),
+                    },
+                ),
+            ),
+            (
+                "QUOTIENTREMAINDEROF:q".to_string(),
+                Statement::QuotientRemainderOf(self_key("b"), self_key("b"), self_key("b"), true),
+            ),
+            (
+                "VALUEOF:_signer".to_string(),
+                Statement::ValueOf(self_key("_signer"), ScalarOrVec::Scalar(GoldilocksField(5))),
+            ),
+            (
+                "VALUEOF:a".to_string(),
+                Statement::ValueOf(
+                    self_key("a"),
+                    ScalarOrVec::Vector(vec![GoldilocksField(1), GoldilocksField(2)]),
+                ),
+            ),
+            (
+                "VALUEOF:b".to_string(),
+                Statement::ValueOf(
+                    self_key("b"),
+                    ScalarOrVec::Scalar(GoldilocksField(GoldilocksField::ORDER - 1)),
+                ),
+            ),
+            ("_DUMMY_STATEMENT6".to_string(), Statement::None),
+        ]);
+        POD {
+            payload: PODPayload::new(&statements),
+            proof: PODProof::Schnorr(SchnorrSignature { s: 11, e: 12 }),
+            proof_type: GadgetID::SCHNORR16,
+        }
+    }
+
+    #[test]
+    fn test_golden_encodings_v1() -> Result<()> {
+        let bytes = include_bytes!("testdata/pod_v1.bin");
+        let json = include_str!("testdata/pod_v1.json");
+        // Version 1 nullifiers are a single element.
+        let pod = golden_pod([7, 0, 0, 0]);
+
+        assert_eq!(POD::from_bytes(bytes)?, pod);
+        assert_eq!(POD::from_json(json)?, pod);
+        Ok(())
+    }
+
+    #[test]
+    fn test_golden_encodings_v2() -> Result<()> {
+        let bytes = include_bytes!("testdata/pod_v2.bin");
+        let json = include_str!("testdata/pod_v2.json");
+        let pod = golden_pod([7, 8, 9, 10]);
+
+        assert_eq!(POD::from_bytes(bytes)?, pod);
+        assert_eq!(POD::from_json(json)?, pod);
+        // Encodings in the current version must not change.
+        assert_eq!(FORMAT_VERSION, 2);
+        assert_eq!(pod.to_bytes()?, bytes);
+        assert_eq!(pod.to_json()?, json.trim_end());
+        Ok(())
+    }
+
+    #[test]
+    fn test_wire_roundtrip() -> Result<()> {
+        let pod = POD::execute_schnorr_gadget::<3, 2>(
+            &[
+                Entry::new_from_scalar("a", GoldilocksField(1)),
+                Entry::new_from_vec("b", vec![GoldilocksField(2)]),
+            ],
+            &EcSchnorrSecretKey::from(25),
+        )?;
+        let decoded_pod = POD::from_bytes(&pod.to_bytes()?)?;
+        assert_eq!(decoded_pod, pod);
+        assert!(decoded_pod.verify::<0, 3, 2, 3, 2>()?);
+        assert_eq!(POD::from_json(&pod.to_json()?)?, pod);
+
+        // Custom statements have up to 3 arguments.
+        let mut custom_pod = golden_pod([7, 8, 9, 10]);
+        let mut statements = custom_pod.payload.statements_map.clone();
+        let args = statements["GT:c"].anchored_keys();
+        statements.insert(
+            "CUSTOM:d".to_string(),
+            Statement::Custom(GoldilocksField(3), args),
+        );
+        custom_pod.payload = PODPayload::new(&statements);
+        assert_eq!(POD::from_bytes(&custom_pod.to_bytes()?)?, custom_pod);
+        assert_eq!(POD::from_json(&custom_pod.to_json()?)?, custom_pod);
+
+        // Other formats and versions are rejected.
+        let mut bytes = pod.to_bytes()?;
+        bytes[4] = 3;
+        assert!(matches!(
+            POD::from_bytes(&bytes),
+            Err(Error::InvalidInput(_))
+        ));
+        assert!(POD::from_bytes(b"POD1").is_err());
+        let json = pod
+            .to_json()?
+            .replacen("\"version\": 2", "\"version\": 3", 1);
+        assert!(matches!(POD::from_json(&json), Err(Error::InvalidInput(_))));
+        Ok(())
+    }
+}
@@ -32,15 +32,14 @@ c''_1...c''_M          │π''_1        │     │π''_N
  To run the tests that checks this logic:
  cargo test --release test_recursion -- --nocapture
 */
-use anyhow::{anyhow, Result};
 use hashbrown::HashMap;
-use plonky2::field::types::Field;
 use plonky2::gates::noop::NoopGate;
 use plonky2::iop::target::{BoolTarget, Target};
 use plonky2::iop::witness::{PartialWitness, WitnessWrite};
 use plonky2::plonk::circuit_builder::CircuitBuilder;
 use plonky2::plonk::circuit_data::{
-    CircuitConfig, CircuitData, ProverCircuitData, VerifierCircuitData, VerifierCircuitTarget,
+    CircuitConfig, CircuitData, CommonCircuitData, ProverCircuitData, VerifierCircuitData,
+    VerifierCircuitTarget,
 };
 use plonky2::plonk::proof::{ProofWithPublicInputs, ProofWithPublicInputsTarget};
 use plonky2::recursion::dummy_circuit::cyclic_base_proof;
@@ -50,7 +49,7 @@ use std::time::Instant;
 
 use super::utils::*;
 use super::{InnerCircuitTrait, OpsExecutorTrait};
-use crate::{PlonkyProof, C, D, F};
+use crate::{Error, PlonkyProof, Result, C, D, F};
 
 /// RecursionTree defines the tree, where each recursive node executes
 /// - L POD1-Introducer proof verifiers
@@ -111,31 +110,6 @@ where
         inner_circuits_input: [I::Input; M],
         recursive_proofs: &[PlonkyProof; N],
     ) -> Result<PlonkyProof> {
-        println!("prove_node with L={}, M={}, N={}:", L, M, N);
-        for i in 0..L + M + N {
-            let what = if i < L {
-                "pod1 proof"
-            } else if i >= L && i < L + M {
-                "inner circuit"
-            } else if i >= L + M && i < L + M + N {
-                "recursive proof"
-            } else {
-                "unknown"
-            };
-            // let what = match i {
-            //     0..L => "pod1 proof",
-            //     L..(L+M) => "inner circuit",
-            //     (L+M)..(L+M+N) => "recursive proof",
-            //     _ => "unknown",
-            // };
-            let action = if selectors[i].is_nonzero() {
-                "verify"
-            } else {
-                "skip"
-            };
-            println!("  (selectors[{}]={}), {} {}", i, selectors[i], action, what);
-        }
-
         // fill the targets
         let mut pw = PartialWitness::new();
         let start = Instant::now();
@@ -149,11 +123,11 @@ where
             inner_circuits_input,
             recursive_proofs,
         )?;
-        println!("circuit.set_targets(): {:?}", start.elapsed());
+        log::debug!("circuit.set_targets(): {:?}", start.elapsed());
 
         let start = Instant::now();
         let new_proof = prover.prove(pw)?;
-        println!("generate new_proof: {:?}", start.elapsed());
+        log::debug!("generate new_proof: {:?}", start.elapsed());
 
         Ok(new_proof.proof)
     }
@@ -209,7 +183,8 @@ where
     pub fn circuit_data(
         pod1_verifier_data: VerifierCircuitData<F, C, D>,
     ) -> Result<CircuitData<F, C, D>> {
-        let mut data = common_data_for_recursion::<I, O, L, M, N, NS, VL>()?;
+        let mut data =
+            common_data_for_recursion::<I, O, L, M, N, NS, VL>(&pod1_verifier_data.common)?;
 
         // build the actual RecursionCircuit circuit data
         let config = CircuitConfig::standard_recursion_config();
@@ -234,6 +209,16 @@ where
         Ok(builder.build_prover::<C>())
     }
 
+    /// verifier_data of the POD1-Introducer circuit verified inside this circuit
+    pub fn pod1_verifier_data(&self) -> &VerifierCircuitData<F, C, D> {
+        &self.pod1_verifier_data
+    }
+
+    /// verifier_data of this circuit itself
+    pub fn verifier_data(&self) -> &VerifierCircuitData<F, C, D> {
+        &self.verifier_data
+    }
+
     pub fn dummy_proof(circuit_data: CircuitData<F, C, D>) -> PlonkyProof {
         let verifier_data = circuit_data.verifier_data();
         let dummy_proof_pis = cyclic_base_proof(
@@ -385,18 +370,13 @@ where
             &self.pod1_verifier_data.verifier_only,
         )?;
         for i in 0..L {
-            // put together the public inputs with the verifier_data
-            // let pub_inp = Self::prepare_public_inputs(vec![], self.pod1_verifier_data.clone());
-            // Self::prepare_public_inputs(pod1_public_inputs[i], self.pod1_verifier_data.clone());
             pw.set_proof_with_pis_target(
                 &self.pod1_proofs_targ[i],
                 &ProofWithPublicInputs {
                     proof: pod1_recursive_proofs[i].clone(),
-                    // public_inputs: pod1_public_inputs[i].clone(),
-                    public_inputs: vec![],
+                    public_inputs: pod1_public_inputs[i].clone(),
                 },
             )?;
-            // pw.set_proof_target(&self.pod1_proofs_targ[i], pod1_recursive_proofs[i].clone())?;
         }
 
         // set the InnerCircuit related values
@@ -438,7 +418,9 @@ pub fn common_data_for_recursion<
     const N: usize,
     const NS: usize,
     const VL: usize,
->() -> Result<CircuitData<F, C, D>>
+>(
+    pod1_common_data: &CommonCircuitData<F, D>,
+) -> Result<CircuitData<F, C, D>>
 where
     [(); L + M + N]:,
     [(); L + N]:,
@@ -487,10 +469,10 @@ where
 
     // pod1 proofs // TODO group with N in a single loop
     let pod1_verifier_data =
-        builder.add_virtual_verifier_data(data.common.config.fri_config.cap_height);
+        builder.add_virtual_verifier_data(pod1_common_data.config.fri_config.cap_height);
     for _ in 0..L {
-        let proof = builder.add_virtual_proof_with_pis(&data.common);
-        builder.verify_proof::<C>(&proof, &pod1_verifier_data, &data.common);
+        let proof = builder.add_virtual_proof_with_pis(pod1_common_data);
+        builder.verify_proof::<C>(&proof, &pod1_verifier_data, pod1_common_data);
     }
     // proofs
     let verifier_data = builder.add_verifier_data_public_inputs();
@@ -499,8 +481,10 @@ where
         builder.verify_proof::<C>(&proof, &verifier_data, &data.common);
     }
 
-    // pad min gates
-    let n_gates = compute_num_gates::<L, N, NS, VL>()?;
+    // pad min gates. The pod1 proofs can come from a bigger circuit than the ones used to tune
+    // `compute_num_gates`, in which case we just pad to the next power of two.
+    let n_gates =
+        compute_num_gates::<L, N, NS, VL>()?.max(builder.num_gates().next_power_of_two());
     while builder.num_gates() < n_gates {
         builder.add_gate(NoopGate, vec![]);
     }
@@ -529,10 +513,10 @@ where
         _ => 0,
     };
     if n_gates == 0 {
-        return Err(anyhow!(
+        return Err(Error::ProofGeneration(format!(
             "arity of L+N={} not supported yet. Currently supported L+N from 1 to 6 (both included)",
-            L+N
-        ));
+            L + N
+        )));
     }
     Ok(n_gates)
 }
@@ -554,7 +538,7 @@ mod tests {
     use crate::recursion::traits_examples::{
         ExampleGadget, ExampleGadgetInput, ExampleIntroducer, ExampleOpsExecutor,
     };
-    use crate::signature::schnorr::*;
+    use crate::signature::ecschnorr::*;
 
     // this sets the plonky2 internal logs level
     fn set_log() {
@@ -599,7 +583,7 @@ mod tests {
         );
 
         let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
-        let schnorr = SchnorrSigner::new();
+        let schnorr = EcSchnorrSigner::new();
         // generate M random messages, each of length 4
         let msg_vec: Vec<Vec<F>> = (0..M)
             .map(|_| {
@@ -610,15 +594,16 @@ mod tests {
             .collect();
 
         // generate M*N key pairs (M for each of the N recursive nodes at the base level)
-        let sk_vec: Vec<SchnorrSecretKey> =
-            (0..M).map(|i| SchnorrSecretKey { sk: i as u64 }).collect();
-        let pk_vec: Vec<SchnorrPublicKey> = sk_vec.iter().map(|&sk| schnorr.keygen(&sk)).collect();
+        let sk_vec: Vec<EcSchnorrSecretKey> = (0..M)
+            .map(|_| EcSchnorrSecretKey::random(&mut rng))
+            .collect();
+        let pk_vec: Vec<EcSchnorrPublicKey> = sk_vec.iter().map(|sk| schnorr.keygen(sk)).collect();
 
         // sign the messages
-        let sig_vec: Vec<SchnorrSignature> = sk_vec
+        let sig_vec: Vec<EcSchnorrSignature> = sk_vec
             .iter()
             .zip(msg_vec.clone())
-            .map(|(&sk, msg)| schnorr.sign(&msg.to_vec(), &sk, &mut rng))
+            .map(|(sk, msg)| schnorr.sign(&msg.to_vec(), sk, &mut rng))
             .collect();
         assert_eq!(sig_vec.len(), M);
 
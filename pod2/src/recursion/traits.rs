@@ -1,4 +1,3 @@
-use anyhow::Result;
 use plonky2::iop::target::BoolTarget;
 use plonky2::iop::witness::PartialWitness;
 use plonky2::plonk::circuit_builder::CircuitBuilder;
@@ -6,7 +5,7 @@ use plonky2::plonk::circuit_builder::CircuitBuilder;
 use plonky2::gates::noop::NoopGate;
 use plonky2::plonk::circuit_data::{CircuitConfig, CircuitData, ProverCircuitData};
 
-use crate::{PlonkyProof, C, D, F};
+use crate::{PlonkyProof, Result, C, D, F};
 
 /// IntroducerCircuitTrait defines the circuit whose plonky2 proof is verified in the
 /// RecursiveCircuit (1-level recursion). This is, the POD1-Introducer circuit.
@@ -1,6 +1,5 @@
 /// This file contains a simple example implementing the InnerCircuit trait, by a circuit that
 /// checks a signature over the given msg.
-use anyhow::Result;
 use plonky2::field::types::Field;
 use plonky2::iop::target::BoolTarget;
 use plonky2::iop::witness::PartialWitness;
@@ -8,13 +7,13 @@ use plonky2::plonk::circuit_builder::CircuitBuilder;
 use std::array;
 
 use crate::pod::circuit::operation::OperationTarget;
-use crate::signature::schnorr::*;
+use crate::signature::ecschnorr::*;
 use crate::signature::schnorr_prover::*;
 
 use super::{
     utils::assert_one_if_enabled, InnerCircuitTrait, IntroducerCircuitTrait, OpsExecutorTrait,
 };
-use crate::{C, D, F};
+use crate::{Result, C, D, F};
 
 pub struct ExampleIntroducer {}
 
@@ -28,11 +27,11 @@ impl IntroducerCircuitTrait for ExampleIntroducer {
 
     fn dummy_inputs() -> Result<Self::Input> {
         let mut rng: rand::rngs::ThreadRng = rand::thread_rng();
-        let schnorr = SchnorrSigner::new();
+        let schnorr = EcSchnorrSigner::new();
         let msg: Vec<F> = vec![F::ZERO, F::ZERO, F::ZERO, F::ZERO];
-        let sk: SchnorrSecretKey = SchnorrSecretKey { sk: 0u64 };
-        let pk: SchnorrPublicKey = schnorr.keygen(&sk);
-        let sig: SchnorrSignature = schnorr.sign(&msg.to_vec(), &sk, &mut rng);
+        let sk: EcSchnorrSecretKey = EcSchnorrSecretKey::from(1);
+        let pk: EcSchnorrPublicKey = schnorr.keygen(&sk);
+        let sig: EcSchnorrSignature = schnorr.sign(&msg.to_vec(), &sk, &mut rng);
         Ok(Self::Input { pk, sig, msg })
     }
 
@@ -68,17 +67,17 @@ impl IntroducerCircuitTrait for ExampleIntroducer {
     ) -> Result<()> {
         assert_eq!(pod.msg.len(), 4);
         // set signature related values:
-        targets.pk_targ.set_witness(pw, &pod.pk).unwrap();
-        targets.sig_targ.set_witness(pw, &pod.sig).unwrap();
-        targets.msg_targ.set_witness(pw, &pod.msg).unwrap();
+        targets.pk_targ.set_witness(pw, &pod.pk)?;
+        targets.sig_targ.set_witness(pw, &pod.sig)?;
+        targets.msg_targ.set_witness(pw, &pod.msg)?;
 
         Ok(())
     }
 }
 
 pub struct ExampleGadgetInput {
-    pub pk: SchnorrPublicKey,
-    pub sig: SchnorrSignature,
+    pub pk: EcSchnorrPublicKey,
+    pub sig: EcSchnorrSignature,
     pub msg: Vec<F>,
 }
 
@@ -128,9 +127,9 @@ impl InnerCircuitTrait for ExampleGadget {
         pod: &Self::Input,
     ) -> Result<Vec<F>> {
         // set signature related values:
-        targets.pk_targ.set_witness(pw, &pod.pk).unwrap();
-        targets.sig_targ.set_witness(pw, &pod.sig).unwrap();
-        targets.msg_targ.set_witness(pw, &pod.msg).unwrap();
+        targets.pk_targ.set_witness(pw, &pod.pk)?;
+        targets.sig_targ.set_witness(pw, &pod.sig)?;
+        targets.msg_targ.set_witness(pw, &pod.msg)?;
 
         Ok(vec![])
     }
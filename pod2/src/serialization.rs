@@ -0,0 +1,230 @@
+//! Gate and witness generator serializers covering all the gates and generators used by the pod2
+//! circuits, ie. the plonky2 default ones plus the u32, biguint, jubjub and gfp5 ones. They are
+//! needed to write the circuits' data to disk and to read it back.
+use anyhow::{anyhow, Result};
+use plonky2::gates::arithmetic_base::ArithmeticGate;
+use plonky2::gates::arithmetic_extension::ArithmeticExtensionGate;
+use plonky2::gates::base_sum::BaseSumGate;
+use plonky2::gates::constant::ConstantGate;
+use plonky2::gates::coset_interpolation::CosetInterpolationGate;
+use plonky2::gates::exponentiation::ExponentiationGate;
+use plonky2::gates::lookup::LookupGate;
+use plonky2::gates::lookup_table::LookupTableGate;
+use plonky2::gates::multiplication_extension::MulExtensionGate;
+use plonky2::gates::noop::NoopGate;
+use plonky2::gates::poseidon::PoseidonGate;
+use plonky2::gates::poseidon_mds::PoseidonMdsGate;
+use plonky2::gates::public_input::PublicInputGate;
+use plonky2::gates::random_access::RandomAccessGate;
+use plonky2::gates::reducing::ReducingGate;
+use plonky2::gates::reducing_extension::ReducingExtensionGate;
+use plonky2::iop::generator::{SimpleGenerator, WitnessGeneratorRef};
+use plonky2::plonk::circuit_data::{
+    CircuitData, CommonCircuitData, ProverCircuitData, VerifierCircuitData,
+};
+use plonky2::util::serialization::{
+    Buffer, DefaultGeneratorSerializer, GateSerializer, IoError, IoResult, Read,
+    WitnessGeneratorSerializer, Write,
+};
+use plonky2::{get_gate_tag_impl, impl_gate_serializer, read_gate_impl};
+
+use crate::plonky2_u32::gadgets::arithmetic_u32::SplitToU32Generator;
+use crate::plonky2_u32::gates::add_many_u32::{U32AddManyGate, U32AddManyGenerator};
+use crate::plonky2_u32::gates::arithmetic_u32::{U32ArithmeticGate, U32ArithmeticGenerator};
+use crate::plonky2_u32::gates::comparison::{ComparisonGate, ComparisonGenerator};
+use crate::plonky2_u32::gates::range_check_u32::{U32RangeCheckGate, U32RangeCheckGenerator};
+use crate::plonky2_u32::gates::subtraction_u32::{U32SubtractionGate, U32SubtractionGenerator};
+use crate::signature::biguint::BigUintDivRemGenerator;
+use crate::signature::gfp5::GFp5InverseGenerator;
+use crate::signature::jubjubfield::JubjubFieldReciprocalGenerator;
+use crate::{C, D, F};
+
+#[derive(Debug, Default)]
+pub struct PodGateSerializer;
+
+impl GateSerializer<F, D> for PodGateSerializer {
+    impl_gate_serializer! {
+        PodGateSerializer,
+        ArithmeticGate,
+        ArithmeticExtensionGate<D>,
+        BaseSumGate<2>,
+        ConstantGate,
+        CosetInterpolationGate<F, D>,
+        ExponentiationGate<F, D>,
+        LookupGate,
+        LookupTableGate,
+        MulExtensionGate<D>,
+        NoopGate,
+        PoseidonMdsGate<F, D>,
+        PoseidonGate<F, D>,
+        PublicInputGate,
+        RandomAccessGate<F, D>,
+        ReducingExtensionGate<D>,
+        ReducingGate<D>,
+        U32AddManyGate<F, D>,
+        U32ArithmeticGate<F, D>,
+        ComparisonGate<F, D>,
+        U32RangeCheckGate<F, D>,
+        U32SubtractionGate<F, D>
+    }
+}
+
+// Each serialized generator is prefixed by one of these tags, telling whether it is handled by
+// the plonky2 default serializer or by `PodGeneratorSerializer` itself.
+const DEFAULT_GENERATOR_TAG: u32 = 0;
+const POD_GENERATOR_TAG: u32 = 1;
+
+/// Generator ids of the generators defined in this crate, the position in the list being used
+/// as their tag.
+const POD_GENERATOR_IDS: [&str; 9] = [
+    "SplitToU32Generator",
+    "U32AddManyGenerator",
+    "U32ArithmeticGenerator",
+    "ComparisonGenerator",
+    "U32RangeCheckGenerator",
+    "U32SubtractionGenerator",
+    "BigUintDivRemGenerator",
+    "GFp5InverseGenerator",
+    "JubjubFieldReciprocalGenerator",
+];
+
+#[derive(Debug, Default)]
+pub struct PodGeneratorSerializer {
+    default: DefaultGeneratorSerializer<C, D>,
+}
+
+fn read_pod_generator<G: SimpleGenerator<F, D>>(
+    buf: &mut Buffer,
+    common_data: &CommonCircuitData<F, D>,
+) -> IoResult<WitnessGeneratorRef<F, D>> {
+    Ok(WitnessGeneratorRef::new(
+        G::deserialize(buf, common_data)?.adapter(),
+    ))
+}
+
+impl WitnessGeneratorSerializer<F, D> for PodGeneratorSerializer {
+    fn read_generator(
+        &self,
+        buf: &mut Buffer,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> IoResult<WitnessGeneratorRef<F, D>> {
+        if buf.read_u32()? == DEFAULT_GENERATOR_TAG {
+            return self.default.read_generator(buf, common_data);
+        }
+        match buf.read_u32()? {
+            0 => read_pod_generator::<SplitToU32Generator<F, D>>(buf, common_data),
+            1 => read_pod_generator::<U32AddManyGenerator<F, D>>(buf, common_data),
+            2 => read_pod_generator::<U32ArithmeticGenerator<F, D>>(buf, common_data),
+            3 => read_pod_generator::<ComparisonGenerator<F, D>>(buf, common_data),
+            4 => read_pod_generator::<U32RangeCheckGenerator<F, D>>(buf, common_data),
+            5 => read_pod_generator::<U32SubtractionGenerator<F, D>>(buf, common_data),
+            6 => read_pod_generator::<BigUintDivRemGenerator<F, D>>(buf, common_data),
+            7 => read_pod_generator::<GFp5InverseGenerator>(buf, common_data),
+            8 => read_pod_generator::<JubjubFieldReciprocalGenerator>(buf, common_data),
+            _ => Err(IoError),
+        }
+    }
+
+    fn write_generator(
+        &self,
+        buf: &mut Vec<u8>,
+        generator: &WitnessGeneratorRef<F, D>,
+        common_data: &CommonCircuitData<F, D>,
+    ) -> IoResult<()> {
+        let id = generator.0.id();
+        match POD_GENERATOR_IDS.iter().position(|&pod_id| pod_id == id) {
+            Some(tag) => {
+                buf.write_u32(POD_GENERATOR_TAG)?;
+                buf.write_u32(tag as u32)?;
+                generator.0.serialize(buf, common_data)
+            }
+            None => {
+                buf.write_u32(DEFAULT_GENERATOR_TAG)?;
+                self.default.write_generator(buf, generator, common_data)
+            }
+        }
+    }
+}
+
+pub fn circuit_data_to_bytes(circuit_data: &CircuitData<F, C, D>) -> Result<Vec<u8>> {
+    circuit_data
+        .to_bytes(&PodGateSerializer, &PodGeneratorSerializer::default())
+        .map_err(|_| anyhow!("Failed to serialize the circuit data"))
+}
+
+pub fn circuit_data_from_bytes(bytes: &[u8]) -> Result<CircuitData<F, C, D>> {
+    CircuitData::from_bytes(
+        bytes,
+        &PodGateSerializer,
+        &PodGeneratorSerializer::default(),
+    )
+    .map_err(|_| anyhow!("Failed to deserialize the circuit data"))
+}
+
+pub fn prover_data_to_bytes(prover_data: &ProverCircuitData<F, C, D>) -> Result<Vec<u8>> {
+    prover_data
+        .to_bytes(&PodGateSerializer, &PodGeneratorSerializer::default())
+        .map_err(|_| anyhow!("Failed to serialize the prover circuit data"))
+}
+
+pub fn prover_data_from_bytes(bytes: &[u8]) -> Result<ProverCircuitData<F, C, D>> {
+    ProverCircuitData::from_bytes(
+        bytes,
+        &PodGateSerializer,
+        &PodGeneratorSerializer::default(),
+    )
+    .map_err(|_| anyhow!("Failed to deserialize the prover circuit data"))
+}
+
+pub fn verifier_data_to_bytes(verifier_data: &VerifierCircuitData<F, C, D>) -> Result<Vec<u8>> {
+    verifier_data
+        .to_bytes(&PodGateSerializer)
+        .map_err(|_| anyhow!("Failed to serialize the verifier circuit data"))
+}
+
+pub fn verifier_data_from_bytes(bytes: &[u8]) -> Result<VerifierCircuitData<F, C, D>> {
+    VerifierCircuitData::from_bytes(bytes.to_vec(), &PodGateSerializer)
+        .map_err(|_| anyhow!("Failed to deserialize the verifier circuit data"))
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::types::Field;
+    use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+
+    use super::*;
+    use crate::plonky2_u32::gadgets::arithmetic_u32::CircuitBuilderU32;
+    use crate::signature::gfp5::{CircuitBuilderGFp5, GFp5};
+
+    #[test]
+    fn test_circuit_data_serialization() -> Result<()> {
+        // a circuit using both default and pod2 generators
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let a = builder.constant_gfp5(GFp5::from_canonical_u64(5));
+        let a_inv = builder.inverse_gfp5(&a);
+        builder.register_public_inputs(&a_inv.0);
+        let x = builder.add_virtual_u32_target();
+        let y = builder.add_virtual_u32_target();
+        let (low, high) = builder.mul_u32(x, y);
+        builder.register_public_input(low.0);
+        builder.register_public_input(high.0);
+        let circuit_data = builder.build::<C>();
+
+        let bytes = circuit_data_to_bytes(&circuit_data)?;
+        let circuit_data2 = circuit_data_from_bytes(&bytes)?;
+        assert_eq!(
+            circuit_data.verifier_only.circuit_digest,
+            circuit_data2.verifier_only.circuit_digest
+        );
+
+        // the deserialized circuit can still generate proofs
+        let mut pw = PartialWitness::new();
+        pw.set_target(x.0, F::from_canonical_u32(u32::MAX))?;
+        pw.set_target(y.0, F::from_canonical_u32(3))?;
+        let proof = circuit_data2.prove(pw)?;
+        circuit_data.verify(proof)
+    }
+}
@@ -0,0 +1,323 @@
+//! The ecgfp5 curve y^2 = x(x^2 + a*x + b) over GF(p^5), with a = 2 and b = 263*z. Its order is
+//! 2*n, n being a 319-bit prime, and we work in the subgroup of order n.
+use num::BigUint;
+use plonky2::field::extension::quintic::QuinticExtension;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::Field;
+use plonky2::iop::target::BoolTarget;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+use super::gfp5::{CircuitBuilderGFp5, GFp5, GFp5Target};
+
+type GoldF = GoldilocksField;
+
+pub const CURVE_A: GFp5 = QuinticExtension([
+    GoldilocksField(2),
+    GoldilocksField(0),
+    GoldilocksField(0),
+    GoldilocksField(0),
+    GoldilocksField(0),
+]);
+
+pub const CURVE_B: GFp5 = QuinticExtension([
+    GoldilocksField(0),
+    GoldilocksField(263),
+    GoldilocksField(0),
+    GoldilocksField(0),
+    GoldilocksField(0),
+]);
+
+// 1067993516717146951041484916571792702745057740581727230159139685185762082554198619328292418486241
+pub fn ecgfp5_order() -> BigUint {
+    BigUint::parse_bytes(
+        b"1067993516717146951041484916571792702745057740581727230159139685185762082554198619328292418486241",
+        10,
+    )
+    .unwrap()
+}
+
+/// Generator of the prime order subgroup: the double of the point with x = 1 (taking the root
+/// y with the smaller first limb).
+pub const GENERATOR: ECPoint = ECPoint {
+    x: QuinticExtension([
+        GoldilocksField(13751537904594739872),
+        GoldilocksField(8859161600321272310),
+        GoldilocksField(2721769971267987483),
+        GoldilocksField(1199172087896026830),
+        GoldilocksField(5553044710935820496),
+    ]),
+    y: QuinticExtension([
+        GoldilocksField(18146711375748607373),
+        GoldilocksField(11035877302916390812),
+        GoldilocksField(12326466918881407707),
+        GoldilocksField(17988590196751087656),
+        GoldilocksField(10165058723446039306),
+    ]),
+    is_inf: false,
+};
+
+/// Starting point of the in-circuit double-and-add, so that the incomplete addition formulas never
+/// hit the point at infinity. Derived as `GENERATOR`, from the first x > 1 (x = 5) lying on the
+/// curve, so nobody knows its discrete log.
+const OFFSET: ECPoint = ECPoint {
+    x: QuinticExtension([
+        GoldilocksField(17916557705177269897),
+        GoldilocksField(6882745890748397332),
+        GoldilocksField(1802991170920644084),
+        GoldilocksField(682154626344696645),
+        GoldilocksField(13320839305738720960),
+    ]),
+    y: QuinticExtension([
+        GoldilocksField(9479147856063507039),
+        GoldilocksField(12011814620287442048),
+        GoldilocksField(2939292879902705331),
+        GoldilocksField(1332695501201533282),
+        GoldilocksField(1724816550039760771),
+    ]),
+    is_inf: false,
+};
+
+/// Number of bits of the scalars handled in circuit.
+pub const SCALAR_BITS: usize = 320;
+
+/// Affine point, `is_inf` marking the point at infinity (in which case x and y are meaningless).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ECPoint {
+    pub x: GFp5,
+    pub y: GFp5,
+    pub is_inf: bool,
+}
+
+impl ECPoint {
+    pub const INFINITY: Self = Self {
+        x: QuinticExtension([GoldilocksField(0); 5]),
+        y: QuinticExtension([GoldilocksField(0); 5]),
+        is_inf: true,
+    };
+
+    pub fn new(x: GFp5, y: GFp5) -> Self {
+        Self {
+            x,
+            y,
+            is_inf: false,
+        }
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        self.is_inf || self.y.square() == self.x * (self.x.square() + CURVE_A * self.x + CURVE_B)
+    }
+
+    pub fn neg(&self) -> Self {
+        Self {
+            x: self.x,
+            y: -self.y,
+            is_inf: self.is_inf,
+        }
+    }
+
+    pub fn double(&self) -> Self {
+        if self.is_inf || self.y == GFp5::ZERO {
+            return Self::INFINITY;
+        }
+        let three = GFp5::from_canonical_u64(3);
+        let num = three * self.x.square() + CURVE_A.double() * self.x + CURVE_B;
+        let lambda = num / self.y.double();
+        let x3 = lambda.square() - CURVE_A - self.x.double();
+        let y3 = lambda * (self.x - x3) - self.y;
+        Self::new(x3, y3)
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        if self.is_inf {
+            return *other;
+        }
+        if other.is_inf {
+            return *self;
+        }
+        if self.x == other.x {
+            if self.y == other.y {
+                return self.double();
+            }
+            return Self::INFINITY;
+        }
+        let lambda = (other.y - self.y) / (other.x - self.x);
+        let x3 = lambda.square() - CURVE_A - self.x - other.x;
+        let y3 = lambda * (self.x - x3) - self.y;
+        Self::new(x3, y3)
+    }
+
+    pub fn mul(&self, scalar: &BigUint) -> Self {
+        (0..scalar.bits()).rev().fold(Self::INFINITY, |acc, i| {
+            let acc = acc.double();
+            if scalar.bit(i) {
+                acc.add(self)
+            } else {
+                acc
+            }
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ECPointTarget {
+    pub x: GFp5Target,
+    pub y: GFp5Target,
+}
+
+pub trait CircuitBuilderECGFp5 {
+    fn add_virtual_point_target(&mut self) -> ECPointTarget;
+
+    fn constant_point(&mut self, p: &ECPoint) -> ECPointTarget;
+
+    fn is_on_curve(&mut self, p: &ECPointTarget) -> BoolTarget;
+
+    fn select_point(
+        &mut self,
+        b: BoolTarget,
+        p: &ECPointTarget,
+        q: &ECPointTarget,
+    ) -> ECPointTarget;
+
+    /// Incomplete addition: the circuit is only satisfiable if p.x != q.x.
+    fn add_points(&mut self, p: &ECPointTarget, q: &ECPointTarget) -> ECPointTarget;
+
+    /// Incomplete doubling: the circuit is only satisfiable if p.y != 0.
+    fn double_point(&mut self, p: &ECPointTarget) -> ECPointTarget;
+
+    /// Computes s*G + e*q, G being `GENERATOR`, where `s_bits` and `e_bits` are little-endian
+    /// decompositions of at most `SCALAR_BITS` bits. The result must not be the point at
+    /// infinity.
+    fn mul_add_generator(
+        &mut self,
+        s_bits: &[BoolTarget],
+        e_bits: &[BoolTarget],
+        q: &ECPointTarget,
+    ) -> ECPointTarget;
+}
+
+impl CircuitBuilderECGFp5 for CircuitBuilder<GoldilocksField, 2> {
+    fn add_virtual_point_target(&mut self) -> ECPointTarget {
+        ECPointTarget {
+            x: self.add_virtual_gfp5_target(),
+            y: self.add_virtual_gfp5_target(),
+        }
+    }
+
+    fn constant_point(&mut self, p: &ECPoint) -> ECPointTarget {
+        assert!(!p.is_inf, "the point at infinity has no affine coordinates");
+        ECPointTarget {
+            x: self.constant_gfp5(p.x),
+            y: self.constant_gfp5(p.y),
+        }
+    }
+
+    fn is_on_curve(&mut self, p: &ECPointTarget) -> BoolTarget {
+        // a lies in the base field
+        let b = self.constant_gfp5(CURVE_B);
+        let y2 = self.square_gfp5(&p.y);
+        let x2 = self.square_gfp5(&p.x);
+        let ax = self.scalar_mul_gfp5(CURVE_A.0[0], &p.x);
+        let t = self.add_gfp5(&x2, &ax);
+        let t = self.add_gfp5(&t, &b);
+        let rhs = self.mul_gfp5(&p.x, &t);
+        self.is_equal_gfp5(&y2, &rhs)
+    }
+
+    fn select_point(
+        &mut self,
+        b: BoolTarget,
+        p: &ECPointTarget,
+        q: &ECPointTarget,
+    ) -> ECPointTarget {
+        ECPointTarget {
+            x: self.select_gfp5(b, &p.x, &q.x),
+            y: self.select_gfp5(b, &p.y, &q.y),
+        }
+    }
+
+    fn add_points(&mut self, p: &ECPointTarget, q: &ECPointTarget) -> ECPointTarget {
+        let a = self.constant_gfp5(CURVE_A);
+        let dx = self.sub_gfp5(&q.x, &p.x);
+        let dy = self.sub_gfp5(&q.y, &p.y);
+        let dx_inv = self.inverse_gfp5(&dx);
+        let lambda = self.mul_gfp5(&dy, &dx_inv);
+        let lambda2 = self.square_gfp5(&lambda);
+        let x3 = self.sub_gfp5(&lambda2, &a);
+        let x3 = self.sub_gfp5(&x3, &p.x);
+        let x3 = self.sub_gfp5(&x3, &q.x);
+        let t = self.sub_gfp5(&p.x, &x3);
+        let y3 = self.mul_gfp5(&lambda, &t);
+        let y3 = self.sub_gfp5(&y3, &p.y);
+        ECPointTarget { x: x3, y: y3 }
+    }
+
+    fn double_point(&mut self, p: &ECPointTarget) -> ECPointTarget {
+        let a = self.constant_gfp5(CURVE_A);
+        let b = self.constant_gfp5(CURVE_B);
+        let two = GoldF::TWO;
+        let three = GoldF::from_canonical_u64(3);
+        // lambda = (3x^2 + 2ax + b) / 2y
+        let x2 = self.square_gfp5(&p.x);
+        let num = self.scalar_mul_gfp5(three, &x2);
+        let two_ax = self.scalar_mul_gfp5(two * CURVE_A.0[0], &p.x);
+        let num = self.add_gfp5(&num, &two_ax);
+        let num = self.add_gfp5(&num, &b);
+        let two_y = self.scalar_mul_gfp5(two, &p.y);
+        let two_y_inv = self.inverse_gfp5(&two_y);
+        let lambda = self.mul_gfp5(&num, &two_y_inv);
+        // x3 = lambda^2 - a - 2x, y3 = lambda(x - x3) - y
+        let lambda2 = self.square_gfp5(&lambda);
+        let x3 = self.sub_gfp5(&lambda2, &a);
+        let two_x = self.scalar_mul_gfp5(two, &p.x);
+        let x3 = self.sub_gfp5(&x3, &two_x);
+        let t = self.sub_gfp5(&p.x, &x3);
+        let y3 = self.mul_gfp5(&lambda, &t);
+        let y3 = self.sub_gfp5(&y3, &p.y);
+        ECPointTarget { x: x3, y: y3 }
+    }
+
+    fn mul_add_generator(
+        &mut self,
+        s_bits: &[BoolTarget],
+        e_bits: &[BoolTarget],
+        q: &ECPointTarget,
+    ) -> ECPointTarget {
+        assert!(s_bits.len() <= SCALAR_BITS && e_bits.len() <= SCALAR_BITS);
+        let g = self.constant_point(&GENERATOR);
+        // Start from OFFSET instead of the point at infinity, and remove 2^SCALAR_BITS * OFFSET
+        // at the end.
+        let mut acc = self.constant_point(&OFFSET);
+        for i in (0..SCALAR_BITS).rev() {
+            acc = self.double_point(&acc);
+            if let Some(&bit) = s_bits.get(i) {
+                let sum = self.add_points(&acc, &g);
+                acc = self.select_point(bit, &sum, &acc);
+            }
+            if let Some(&bit) = e_bits.get(i) {
+                let sum = self.add_points(&acc, q);
+                acc = self.select_point(bit, &sum, &acc);
+            }
+        }
+        let offset_correction = OFFSET.mul(&(BigUint::from(1u32) << SCALAR_BITS)).neg();
+        let offset_correction = self.constant_point(&offset_correction);
+        self.add_points(&acc, &offset_correction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generator() {
+        assert!(GENERATOR.is_on_curve());
+        assert!(OFFSET.is_on_curve());
+        assert!(GENERATOR.mul(&ecgfp5_order()).is_inf);
+        assert!(OFFSET.mul(&ecgfp5_order()).is_inf);
+
+        let p = GENERATOR.mul(&BigUint::from(42u32));
+        let q = GENERATOR.mul(&BigUint::from(40u32));
+        assert_eq!(p, q.add(&GENERATOR.double()));
+        assert_eq!(p.add(&p.neg()), ECPoint::INFINITY);
+    }
+}
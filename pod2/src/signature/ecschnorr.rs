@@ -0,0 +1,243 @@
+//! Schnorr signatures over the prime order subgroup of ecgfp5, with Poseidon as the hash
+//! function: sign computes R = k*G, e = H(R, msg) and s = k - e*sk mod n, verify recomputes
+//! R = s*G + e*PK and checks that H(R, msg) == e.
+use num::BigUint;
+use plonky2::field::extension::quintic::QuinticExtension;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::PrimeField64;
+use plonky2::hash::{hash_types::HashOut, poseidon::PoseidonHash};
+use plonky2::plonk::config::Hasher;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::ecgfp5::{ecgfp5_order, ECPoint, GENERATOR};
+
+/// Size of a serialized secret key, enough to hold any scalar (n is a 319-bit number).
+pub const SECRET_KEY_BYTES: usize = 40;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EcSchnorrSecretKey {
+    pub sk: BigUint,
+}
+
+impl EcSchnorrSecretKey {
+    pub fn new(sk: BigUint) -> Self {
+        Self {
+            sk: sk % ecgfp5_order(),
+        }
+    }
+
+    pub fn random<R: Rng>(rng: &mut R) -> Self {
+        Self::new(random_scalar(rng))
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.sk.to_bytes_le();
+        bytes.resize(SECRET_KEY_BYTES, 0);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self::new(BigUint::from_bytes_le(bytes))
+    }
+}
+
+impl From<u64> for EcSchnorrSecretKey {
+    fn from(sk: u64) -> Self {
+        Self::new(BigUint::from(sk))
+    }
+}
+
+/// Affine coordinates of the public key point.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EcSchnorrPublicKey {
+    pub x: [GoldilocksField; 5],
+    pub y: [GoldilocksField; 5],
+}
+
+impl EcSchnorrPublicKey {
+    pub fn point(&self) -> ECPoint {
+        ECPoint::new(QuinticExtension(self.x), QuinticExtension(self.y))
+    }
+
+    pub fn to_fields(&self) -> Vec<GoldilocksField> {
+        [self.x, self.y].concat()
+    }
+
+    /// Hash committing to the key, which PODs store in full as their signer (see
+    /// `ScalarOrVec::from_hash`).
+    pub fn hash(&self) -> HashOut<GoldilocksField> {
+        PoseidonHash::hash_no_pad(&self.to_fields())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EcSchnorrSignature {
+    /// s, as little-endian u64 limbs.
+    pub s: [u64; 5],
+    /// e = H(R, msg), read as a 256-bit little-endian integer.
+    pub e: [GoldilocksField; 4],
+}
+
+impl EcSchnorrSignature {
+    pub fn s_biguint(&self) -> BigUint {
+        BigUint::from_slice(
+            &self
+                .s
+                .iter()
+                .flat_map(|&l| [l as u32, (l >> 32) as u32])
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn e_biguint(&self) -> BigUint {
+        hash_to_biguint(&self.e)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct EcSchnorrSigner;
+
+impl EcSchnorrSigner {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn keygen(&self, sk: &EcSchnorrSecretKey) -> EcSchnorrPublicKey {
+        let pk = GENERATOR.mul(&sk.sk);
+        EcSchnorrPublicKey {
+            x: pk.x.0,
+            y: pk.y.0,
+        }
+    }
+
+    pub fn hash(&self, r: &ECPoint, msg: &[GoldilocksField]) -> [GoldilocksField; 4] {
+        let poseidon_input: Vec<GoldilocksField> = [&r.x.0[..], &r.y.0[..], msg].concat();
+        PoseidonHash::hash_no_pad(&poseidon_input).elements
+    }
+
+    pub fn sign<R: Rng>(
+        &self,
+        msg: &[GoldilocksField],
+        sk: &EcSchnorrSecretKey,
+        rng: &mut R,
+    ) -> EcSchnorrSignature {
+        let n = ecgfp5_order();
+        let k = random_scalar(rng);
+        let r = GENERATOR.mul(&k);
+        let e = self.hash(&r, msg);
+        let e_times_sk = (hash_to_biguint(&e) * &sk.sk) % &n;
+        let s = (k + &n - e_times_sk) % &n;
+        let mut s_limbs = s.to_u64_digits();
+        s_limbs.resize(5, 0);
+        EcSchnorrSignature {
+            s: std::array::from_fn(|i| s_limbs[i]),
+            e,
+        }
+    }
+
+    pub fn verify(
+        &self,
+        sig: &EcSchnorrSignature,
+        msg: &[GoldilocksField],
+        pk: &EcSchnorrPublicKey,
+    ) -> bool {
+        let pk = pk.point();
+        if !pk.is_on_curve() {
+            return false;
+        }
+        let r = GENERATOR
+            .mul(&sig.s_biguint())
+            .add(&pk.mul(&sig.e_biguint()));
+        !r.is_inf && self.hash(&r, msg) == sig.e
+    }
+}
+
+fn hash_to_biguint(e: &[GoldilocksField; 4]) -> BigUint {
+    BigUint::from_slice(
+        &e.iter()
+            .flat_map(|l| {
+                let l = l.to_canonical_u64();
+                [l as u32, (l >> 32) as u32]
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Uniformly random non-zero scalar, reducing 512 random bits so that the bias is negligible.
+fn random_scalar<R: Rng>(rng: &mut R) -> BigUint {
+    let n = ecgfp5_order();
+    loop {
+        let mut bytes = [0u8; 64];
+        rng.fill(&mut bytes[..]);
+        let k = BigUint::from_bytes_le(&bytes) % &n;
+        if k != BigUint::from(0u32) {
+            return k;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::field::types::Field;
+
+    use super::*;
+
+    #[test]
+    fn test_ecschnorr_native() {
+        let mut rng = rand::thread_rng();
+        let signer = EcSchnorrSigner::new();
+        let sk = EcSchnorrSecretKey::random(&mut rng);
+        let pk = signer.keygen(&sk);
+        let msg: Vec<GoldilocksField> = [1500, 1600, 0, 0, 0]
+            .into_iter()
+            .map(GoldilocksField::from_canonical_u64)
+            .collect();
+
+        let sig = signer.sign(&msg, &sk, &mut rng);
+        assert!(signer.verify(&sig, &msg, &pk));
+
+        // wrong message
+        let msg1: Vec<GoldilocksField> = [1510, 1600, 0, 0, 0]
+            .into_iter()
+            .map(GoldilocksField::from_canonical_u64)
+            .collect();
+        assert!(!signer.verify(&sig, &msg1, &pk));
+
+        // wrong key
+        let other_pk = signer.keygen(&EcSchnorrSecretKey::random(&mut rng));
+        assert!(!signer.verify(&sig, &msg, &other_pk));
+
+        // key serialization round trip
+        assert_eq!(EcSchnorrSecretKey::from_bytes(&sk.to_bytes()), sk);
+    }
+
+    #[test]
+    fn test_ecschnorr_keygen() {
+        // 42 * G, computed independently
+        let pk = EcSchnorrSigner::new().keygen(&EcSchnorrSecretKey::from(42));
+        assert_eq!(
+            pk.x,
+            [
+                9124284575316560287,
+                8639640122296184292,
+                5572080831175165531,
+                5542193516508277368,
+                9929769158507466805
+            ]
+            .map(GoldilocksField)
+        );
+        assert_eq!(
+            pk.y,
+            [
+                3707478194470383341,
+                7733584375436762651,
+                11468107865776999185,
+                1758077056340111451,
+                16797863614791246606
+            ]
+            .map(GoldilocksField)
+        );
+    }
+}
@@ -1,98 +1,169 @@
-use anyhow::Result;
+use babyjubjub_ark::{Point, Signature};
 use num::BigUint;
+use parcnet_pod::pod::Fq;
+use std::str::FromStr;
 
-use plonky2::field::{goldilocks_field::GoldilocksField, types::Field};
-use plonky2::hash::poseidon::PoseidonHash;
-use plonky2::iop::{
-    target::{BoolTarget, Target},
-    witness::{PartialWitness, WitnessWrite},
-};
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::iop::{target::BoolTarget, witness::PartialWitness};
 use plonky2::plonk::{circuit_builder::CircuitBuilder, config::GenericConfig};
 
+use crate::plonky2_u32::gadgets::range_check::range_check_u32_circuit;
+use crate::signature::biguint::{BigUintTarget, CircuitBuilderBiguint, WitnessBigUint};
 use crate::signature::jubjubcurve::{CircuitBuilderJubjubCurve, JubjubCurveTarget};
-use crate::signature::{
-    biguint::{BigUintTarget, CircuitBuilderBiguint},
-    mod65537::Mod65537Builder,
-    schnorr::{SchnorrPublicKey, SchnorrSignature},
+use crate::signature::jubjubfield::{
+    ark_to_biguint, CircuitBuilderJubjubField, JubjubFieldTarget, WitnessJubjubField,
 };
+use crate::signature::poseidon_bn254::CircuitBuilderPoseidonBn254;
+use crate::Result;
 
 type GoldF = GoldilocksField;
 
+// Functions to verify a babyjubjub EdDSA-Poseidon signature (as produced by
+// babyjubjub-ark, i.e. by `parcnet_pod::pod::Pod::sign`) in a plonky2 circuit.
+// Verification checks
+//     B8 * s == R + 8 * h * A,  with h = Poseidon(R.x, R.y, A.x, A.y, msg)
+// where all the babyjubjub arithmetic is done non-natively.
+
+/// Order of the subgroup generated by B8.
+const BABYJUBJUB_SUBORDER: &str =
+    "2736030358979909402780800718157159386076813972158567259200215660948447373041";
+
+/// Virtual jubjub field element whose limbs are range-checked to 32 bits.
+fn add_virtual_jubjubfield_checked(builder: &mut CircuitBuilder<GoldF, 2>) -> JubjubFieldTarget {
+    let x = builder.add_virtual_jubjubfield_target();
+    range_check_u32_circuit(builder, x.0.limbs.clone());
+    x
+}
+
+/// The signed message, for a POD this is its content ID.
 #[derive(Clone, Debug)]
 pub struct MessageHashTarget {
-    m: BigUintTarget,
+    pub m: BigUintTarget,
 }
 
-#[derive(Clone, Debug)]
-pub struct PoseidonOutputTarget {
-    h: BigUintTarget,
+impl MessageHashTarget {
+    pub fn new_virtual(builder: &mut CircuitBuilder<GoldF, 2>) -> Self {
+        Self {
+            m: add_virtual_jubjubfield_checked(builder).0,
+        }
+    }
+
+    pub fn set_witness(&self, pw: &mut PartialWitness<GoldF>, msg: &Fq) -> Result<()> {
+        pw.set_biguint_target(&self.m, &ark_to_biguint(msg));
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct EddsaSignatureTarget {
-    r: JubjubCurveTarget,
-    s: BigUintTarget,
+    pub r: JubjubCurveTarget,
+    pub s: BigUintTarget,
+}
+
+impl EddsaSignatureTarget {
+    pub fn new_virtual(builder: &mut CircuitBuilder<GoldF, 2>) -> Self {
+        let r = JubjubCurveTarget {
+            x: add_virtual_jubjubfield_checked(builder),
+            y: add_virtual_jubjubfield_checked(builder),
+        };
+        let s = add_virtual_jubjubfield_checked(builder).0;
+        Self { r, s }
+    }
+
+    pub fn set_witness(&self, pw: &mut PartialWitness<GoldF>, sig: &Signature) -> Result<()> {
+        pw.set_jubjubfield_target(&self.r.x, &ark_to_biguint(&sig.r_b8.x));
+        pw.set_jubjubfield_target(&self.r.y, &ark_to_biguint(&sig.r_b8.y));
+        pw.set_biguint_target(&self.s, &ark_to_biguint(&sig.s));
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct EddsaPublicKeyTarget {
-    a: JubjubCurveTarget,
+    pub a: JubjubCurveTarget,
+}
+
+impl EddsaPublicKeyTarget {
+    pub fn new_virtual(builder: &mut CircuitBuilder<GoldF, 2>) -> Self {
+        let a = JubjubCurveTarget {
+            x: add_virtual_jubjubfield_checked(builder),
+            y: add_virtual_jubjubfield_checked(builder),
+        };
+        Self { a }
+    }
+
+    pub fn set_witness(&self, pw: &mut PartialWitness<GoldF>, pk: &Point) -> Result<()> {
+        pw.set_jubjubfield_target(&self.a.x, &ark_to_biguint(&pk.x));
+        pw.set_jubjubfield_target(&self.a.y, &ark_to_biguint(&pk.y));
+        Ok(())
+    }
 }
 
 pub trait EddsaBuilder {
-    fn verify_eddsa<C: GenericConfig<2, F = GoldF>> (
+    fn verify_eddsa<C: GenericConfig<2, F = GoldF>>(
         &mut self,
         sig: &EddsaSignatureTarget,
         msg: &MessageHashTarget,
         pk: &EddsaPublicKeyTarget,
-        hash: &PoseidonOutputTarget, // will not be needed in final version
     ) -> BoolTarget;
 
-    fn constrain_eddsa<C: GenericConfig<2, F = GoldF>> (
+    fn constrain_eddsa<C: GenericConfig<2, F = GoldF>>(
         &mut self,
         sig: &EddsaSignatureTarget,
         msg: &MessageHashTarget,
         pk: &EddsaPublicKeyTarget,
-        hash: &PoseidonOutputTarget, // will not be needed in final version
     );
 }
 
 impl EddsaBuilder for CircuitBuilder<GoldF, 2> {
-    fn verify_eddsa<C: GenericConfig<2, F = GoldF>> (
+    fn verify_eddsa<C: GenericConfig<2, F = GoldF>>(
         &mut self,
         sig: &EddsaSignatureTarget,
         msg: &MessageHashTarget,
         pk: &EddsaPublicKeyTarget,
-        hash: &PoseidonOutputTarget, // will not be needed in final version
-    ) -> BoolTarget {      
+    ) -> BoolTarget {
         self.verify_jubjub_point(&sig.r);
         self.verify_jubjub_point(&pk.a);
-//        HASH TO BE IMPLEMENTED: h should be Poseidon hash of sig.r, pk, msg
-//        let h: BigUintTarget = p_hash_to_implement(sig.r.x, sig.r.y, pk.x, pk.y, msg);
+
+        // s must be reduced mod the order of B8
+        let max_s =
+            self.constant_biguint(&(BigUint::from_str(BABYJUBJUB_SUBORDER).unwrap() - 1u32));
+        let s_in_range = self.cmp_biguint(&sig.s, &max_s);
+
+        let h = self.poseidon_bn254(&[
+            sig.r.x.clone(),
+            sig.r.y.clone(),
+            pk.a.x.clone(),
+            pk.a.y.clone(),
+            JubjubFieldTarget(msg.m.clone()),
+        ]);
+
         let b8 = self.B8_jubjub_curve();
         let lhs: JubjubCurveTarget = self.mul_scalar(&b8, &sig.s);
         let eight = self.constant_biguint(&BigUint::new(vec![8]));
         let eight_times_pk = self.mul_scalar(&pk.a, &eight);
-        let hash_times_pk = self.mul_scalar(&eight_times_pk, &hash.h);
+        let hash_times_pk = self.mul_scalar(&eight_times_pk, &h.0);
         let rhs = self.add_jubjub_curve(&sig.r, &hash_times_pk);
-        self.is_equal_jubjub_curve(&lhs, &rhs)
+        let sig_matches = self.is_equal_jubjub_curve(&lhs, &rhs);
+
+        self.and(s_in_range, sig_matches)
     }
-    
-    fn constrain_eddsa<C: GenericConfig<2, F = GoldF>> (
+
+    fn constrain_eddsa<C: GenericConfig<2, F = GoldF>>(
         &mut self,
         sig: &EddsaSignatureTarget,
         msg: &MessageHashTarget,
         pk: &EddsaPublicKeyTarget,
-        hash: &PoseidonOutputTarget, // will not be needed in final version
     ) {
-        let verification_output = self.verify_eddsa::<C>(sig, msg, pk, hash);
+        let verification_output = self.verify_eddsa::<C>(sig, msg, pk);
         let true_target = self._true();
         self.connect(verification_output.target, true_target.target);
     }
-} 
+}
 
 #[cfg(test)]
 mod tests {
+    use anyhow::Result;
     use chrono::Local;
     use num::BigUint;
     use plonky2::field::{goldilocks_field::GoldilocksField, types::Field};
@@ -100,6 +171,7 @@ mod tests {
     use plonky2::plonk::circuit_builder::CircuitBuilder;
     use plonky2::plonk::circuit_data::CircuitConfig;
     use plonky2::plonk::config::{GenericConfig, PoseidonGoldilocksConfig};
+    use parcnet_pod::{pod::create_pod, pod_entries};
     use std::str::FromStr;
 
     use crate::signature::biguint::{BigUintTarget, CircuitBuilderBiguint};
@@ -108,7 +180,6 @@ mod tests {
         EddsaPublicKeyTarget, 
         EddsaSignatureTarget,
         MessageHashTarget,
-        PoseidonOutputTarget,
     };
     use crate::signature::jubjubcurve::{CircuitBuilderJubjubCurve, JubjubCurveTarget};
     use crate::signature::jubjubfield::{CircuitBuilderJubjubField, JubjubFieldTarget};
@@ -161,12 +232,6 @@ mod tests {
             14992547060379445581,
             133925459310743680,
         ]));
-        let hash_val = BigUint::new(u64_to_u32(vec![ // should be computed automatically later
-            2709608945152055826,
-            8217237346447623338,
-            9578917324956230137,
-            552733937179642407,
-        ]));
 
         let px = JubjubFieldTarget(builder.constant_biguint(&px_val));
         let py = JubjubFieldTarget(builder.constant_biguint(&py_val));
@@ -174,17 +239,15 @@ mod tests {
         let sig_r_x = JubjubFieldTarget(builder.constant_biguint(&sig_r_x_val));
         let sig_r_y = JubjubFieldTarget(builder.constant_biguint(&sig_r_y_val));
         let sig_s = JubjubFieldTarget(builder.constant_biguint(&sig_s_val));
-        let hash = JubjubFieldTarget(builder.constant_biguint(&hash_val));
 
         let pk = JubjubCurveTarget{ x: px, y: py };
         let sig_r = JubjubCurveTarget{ x: sig_r_x, y: sig_r_y };
 
         let msg = MessageHashTarget{ m: msg.0 };
-        let hash = PoseidonOutputTarget{ h: hash.0 };
         let sig = EddsaSignatureTarget{ r: sig_r, s: sig_s.0 };
         let pk = EddsaPublicKeyTarget{ a: pk };
 
-        builder.constrain_eddsa::<C>(&sig, &msg, &pk, &hash);
+        builder.constrain_eddsa::<C>(&sig, &msg, &pk);
 
         let data = builder.build::<C>();
         let proof = data.prove(pw).unwrap();
@@ -236,12 +299,6 @@ mod tests {
             14992547060379445581,
             133925459310743681, // this value was changed from the correct val
         ]));
-        let hash_val = BigUint::new(u64_to_u32(vec![ // should be computed automatically later
-            2709608945152055826,
-            8217237346447623338,
-            9578917324956230137,
-            552733937179642407,
-        ]));
 
         let px = JubjubFieldTarget(builder.constant_biguint(&px_val));
         let py = JubjubFieldTarget(builder.constant_biguint(&py_val));
@@ -249,17 +306,15 @@ mod tests {
         let sig_r_x = JubjubFieldTarget(builder.constant_biguint(&sig_r_x_val));
         let sig_r_y = JubjubFieldTarget(builder.constant_biguint(&sig_r_y_val));
         let sig_s = JubjubFieldTarget(builder.constant_biguint(&sig_s_val));
-        let hash = JubjubFieldTarget(builder.constant_biguint(&hash_val));
 
         let pk = JubjubCurveTarget{ x: px, y: py };
         let sig_r = JubjubCurveTarget{ x: sig_r_x, y: sig_r_y };
 
         let msg = MessageHashTarget{ m: msg.0 };
-        let hash = PoseidonOutputTarget{ h: hash.0 };
         let sig = EddsaSignatureTarget{ r: sig_r, s: sig_s.0 };
         let pk = EddsaPublicKeyTarget{ a: pk };
 
-        let sig_result = builder.verify_eddsa::<C>(&sig, &msg, &pk, &hash);
+        let sig_result = builder.verify_eddsa::<C>(&sig, &msg, &pk);
         let false_target = builder._false();
         builder.connect(sig_result.target, false_target.target);
 
@@ -267,4 +322,39 @@ mod tests {
         let proof = data.prove(pw).unwrap();
         data.verify(proof);
     }
+
+    #[test]
+    fn test_verify_pod_signature() -> Result<()> {
+        type C = PoseidonGoldilocksConfig;
+
+        let private_key = [
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 1, 2, 3, 4, 5, 6, 7, 8,
+            9, 0, 1,
+        ];
+        let pod = create_pod(
+            &private_key,
+            pod_entries![
+                "attendee" => 42,
+                "eventID" => "devcon",
+            ],
+        )?;
+        assert!(pod.verify().unwrap());
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut pw: PartialWitness<GoldilocksField> = PartialWitness::new();
+        let mut builder = CircuitBuilder::<GoldilocksField, 2>::new(config);
+
+        let sig = EddsaSignatureTarget::new_virtual(&mut builder);
+        let msg = MessageHashTarget::new_virtual(&mut builder);
+        let pk = EddsaPublicKeyTarget::new_virtual(&mut builder);
+        builder.constrain_eddsa::<C>(&sig, &msg, &pk);
+
+        sig.set_witness(&mut pw, &pod.signature())?;
+        msg.set_witness(&mut pw, &pod.content_id()?)?;
+        pk.set_witness(&mut pw, &pod.signer_public_key())?;
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
 }
@@ -0,0 +1,226 @@
+//! Arithmetic over GF(p^5) = GF(p)[z] / (z^5 - 3), p being the Goldilocks prime. This is the base
+//! field of the ecgfp5 curve (see `ecgfp5.rs`).
+use anyhow::{anyhow, Result};
+use plonky2::field::extension::quintic::QuinticExtension;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::Field;
+use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::iop::witness::{PartialWitness, PartitionWitness, Witness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::CommonCircuitData;
+use plonky2::util::serialization::{Buffer, IoResult, Read, Write};
+
+pub type GFp5 = QuinticExtension<GoldilocksField>;
+
+/// z^5 = W
+const W: u64 = 3;
+
+#[derive(Copy, Clone, Debug)]
+pub struct GFp5Target(pub [Target; 5]);
+
+pub trait CircuitBuilderGFp5 {
+    fn add_virtual_gfp5_target(&mut self) -> GFp5Target;
+
+    fn constant_gfp5(&mut self, c: GFp5) -> GFp5Target;
+
+    fn connect_gfp5(&mut self, a: &GFp5Target, b: &GFp5Target);
+
+    fn is_equal_gfp5(&mut self, a: &GFp5Target, b: &GFp5Target) -> BoolTarget;
+
+    fn select_gfp5(&mut self, b: BoolTarget, x: &GFp5Target, y: &GFp5Target) -> GFp5Target;
+
+    fn add_gfp5(&mut self, a: &GFp5Target, b: &GFp5Target) -> GFp5Target;
+
+    fn sub_gfp5(&mut self, a: &GFp5Target, b: &GFp5Target) -> GFp5Target;
+
+    /// Multiplication by a base field constant.
+    fn scalar_mul_gfp5(&mut self, c: GoldilocksField, a: &GFp5Target) -> GFp5Target;
+
+    fn mul_gfp5(&mut self, a: &GFp5Target, b: &GFp5Target) -> GFp5Target;
+
+    fn square_gfp5(&mut self, a: &GFp5Target) -> GFp5Target;
+
+    /// Inverse of `a`. The circuit is only satisfiable if `a` is non-zero.
+    fn inverse_gfp5(&mut self, a: &GFp5Target) -> GFp5Target;
+}
+
+impl CircuitBuilderGFp5 for CircuitBuilder<GoldilocksField, 2> {
+    fn add_virtual_gfp5_target(&mut self) -> GFp5Target {
+        GFp5Target(self.add_virtual_target_arr())
+    }
+
+    fn constant_gfp5(&mut self, c: GFp5) -> GFp5Target {
+        GFp5Target(c.0.map(|l| self.constant(l)))
+    }
+
+    fn connect_gfp5(&mut self, a: &GFp5Target, b: &GFp5Target) {
+        for (&x, &y) in a.0.iter().zip(b.0.iter()) {
+            self.connect(x, y);
+        }
+    }
+
+    fn is_equal_gfp5(&mut self, a: &GFp5Target, b: &GFp5Target) -> BoolTarget {
+        let limbs_eq: Vec<BoolTarget> =
+            a.0.iter()
+                .zip(b.0.iter())
+                .map(|(&x, &y)| self.is_equal(x, y))
+                .collect();
+        limbs_eq[1..]
+            .iter()
+            .fold(limbs_eq[0], |acc, &e| self.and(acc, e))
+    }
+
+    fn select_gfp5(&mut self, b: BoolTarget, x: &GFp5Target, y: &GFp5Target) -> GFp5Target {
+        GFp5Target(std::array::from_fn(|i| self.select(b, x.0[i], y.0[i])))
+    }
+
+    fn add_gfp5(&mut self, a: &GFp5Target, b: &GFp5Target) -> GFp5Target {
+        GFp5Target(std::array::from_fn(|i| self.add(a.0[i], b.0[i])))
+    }
+
+    fn sub_gfp5(&mut self, a: &GFp5Target, b: &GFp5Target) -> GFp5Target {
+        GFp5Target(std::array::from_fn(|i| self.sub(a.0[i], b.0[i])))
+    }
+
+    fn scalar_mul_gfp5(&mut self, c: GoldilocksField, a: &GFp5Target) -> GFp5Target {
+        GFp5Target(a.0.map(|l| self.mul_const(c, l)))
+    }
+
+    fn mul_gfp5(&mut self, a: &GFp5Target, b: &GFp5Target) -> GFp5Target {
+        // c_k = sum_{i+j=k} a_i*b_j + W * sum_{i+j=k+5} a_i*b_j, each term being a single
+        // arithmetic operation accumulated on the previous one.
+        let w = GoldilocksField::from_canonical_u64(W);
+        GFp5Target(std::array::from_fn(|k| {
+            let mut acc = self.zero();
+            for i in 0..5 {
+                let j = (k + 5 - i) % 5;
+                let coeff = if i <= k { GoldilocksField::ONE } else { w };
+                acc = self.arithmetic(coeff, GoldilocksField::ONE, a.0[i], b.0[j], acc);
+            }
+            acc
+        }))
+    }
+
+    fn square_gfp5(&mut self, a: &GFp5Target) -> GFp5Target {
+        self.mul_gfp5(a, a)
+    }
+
+    fn inverse_gfp5(&mut self, a: &GFp5Target) -> GFp5Target {
+        let a_inv = self.add_virtual_gfp5_target();
+        self.add_simple_generator(GFp5InverseGenerator { a: *a, a_inv });
+        let prod = self.mul_gfp5(a, &a_inv);
+        let one = self.constant_gfp5(GFp5::ONE);
+        self.connect_gfp5(&prod, &one);
+        a_inv
+    }
+}
+
+pub trait WitnessGFp5 {
+    fn set_gfp5_target(&mut self, target: &GFp5Target, value: GFp5) -> Result<()>;
+}
+
+impl WitnessGFp5 for PartialWitness<GoldilocksField> {
+    fn set_gfp5_target(&mut self, target: &GFp5Target, value: GFp5) -> Result<()> {
+        for (&t, &v) in target.0.iter().zip(value.0.iter()) {
+            self.set_target(t, v)?;
+        }
+        Ok(())
+    }
+}
+
+fn get_gfp5_target(witness: &PartitionWitness<GoldilocksField>, target: &GFp5Target) -> GFp5 {
+    QuinticExtension(target.0.map(|t| witness.get_target(t)))
+}
+
+#[derive(Debug)]
+pub(crate) struct GFp5InverseGenerator {
+    a: GFp5Target,
+    a_inv: GFp5Target,
+}
+
+impl SimpleGenerator<GoldilocksField, 2> for GFp5InverseGenerator {
+    fn id(&self) -> String {
+        "GFp5InverseGenerator".to_string()
+    }
+
+    fn dependencies(&self) -> Vec<Target> {
+        self.a.0.to_vec()
+    }
+
+    fn run_once(
+        &self,
+        witness: &PartitionWitness<GoldilocksField>,
+        out_buffer: &mut GeneratedValues<GoldilocksField>,
+    ) -> Result<()> {
+        let a = get_gfp5_target(witness, &self.a);
+        let a_inv = a
+            .try_inverse()
+            .ok_or(anyhow!("Attempted division by zero in GF(p^5)."))?;
+        for (&t, &v) in self.a_inv.0.iter().zip(a_inv.0.iter()) {
+            out_buffer.set_target(t, v)?;
+        }
+        Ok(())
+    }
+
+    fn serialize(
+        &self,
+        dst: &mut Vec<u8>,
+        _common_data: &CommonCircuitData<GoldilocksField, 2>,
+    ) -> IoResult<()> {
+        dst.write_target_vec(&self.a.0)?;
+        dst.write_target_vec(&self.a_inv.0)?;
+        Ok(())
+    }
+
+    fn deserialize(
+        src: &mut Buffer,
+        _common_data: &CommonCircuitData<GoldilocksField, 2>,
+    ) -> IoResult<Self>
+    where
+        Self: Sized,
+    {
+        let a = src.read_target_vec()?;
+        let a_inv = src.read_target_vec()?;
+        Ok(Self {
+            a: GFp5Target(std::array::from_fn(|i| a[i])),
+            a_inv: GFp5Target(std::array::from_fn(|i| a_inv[i])),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::types::Sample;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::{circuit_builder::CircuitBuilder, circuit_data::CircuitConfig};
+
+    use super::*;
+    use crate::{C, D, F};
+
+    #[test]
+    fn test_gfp5_mul_inverse() -> Result<()> {
+        let a = GFp5::rand();
+        let b = GFp5::rand();
+
+        let config = CircuitConfig::standard_recursion_config();
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let a_targ = builder.add_virtual_gfp5_target();
+        let b_targ = builder.add_virtual_gfp5_target();
+        let prod = builder.mul_gfp5(&a_targ, &b_targ);
+        let expected_prod = builder.constant_gfp5(a * b);
+        builder.connect_gfp5(&prod, &expected_prod);
+        let a_inv = builder.inverse_gfp5(&a_targ);
+        let expected_inv = builder.constant_gfp5(a.inverse());
+        builder.connect_gfp5(&a_inv, &expected_inv);
+
+        let mut pw = PartialWitness::<F>::new();
+        pw.set_gfp5_target(&a_targ, a)?;
+        pw.set_gfp5_target(&b_targ, b)?;
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+}
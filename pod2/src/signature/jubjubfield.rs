@@ -1,4 +1,5 @@
 use anyhow::Error;
+use ark_ff::{BigInteger, PrimeField};
 use num::BigUint;
 use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2::iop::generator::{GeneratedValues, SimpleGenerator};
@@ -32,6 +33,12 @@ impl JubjubP for BigUint {
     }
 }
 
+/// Converts an arkworks field element (e.g. a BN254 scalar as used by
+/// parcnet_pod, or a babyjubjub scalar) into its canonical integer representative.
+pub fn ark_to_biguint<P: PrimeField>(x: &P) -> BigUint {
+    BigUint::from_bytes_le(&x.into_bigint().to_bytes_le())
+}
+
 #[derive(Clone, Debug)]
 pub struct JubjubFieldTarget(pub BigUintTarget);
 
@@ -237,7 +244,7 @@ impl Display for DivisionByZeroError {
 impl std::error::Error for DivisionByZeroError {}
 
 #[derive(Debug)]
-struct JubjubFieldReciprocalGenerator {
+pub(crate) struct JubjubFieldReciprocalGenerator {
     a: JubjubFieldTarget,
     a_inv: JubjubFieldTarget,
 }
@@ -1,9 +1,13 @@
 /// code forked from https://github.com/tideofwords/schnorr
 pub mod biguint;
+pub mod ecgfp5;
+pub mod ecschnorr;
 pub mod eddsa;
+pub mod gfp5;
 pub mod jubjubcurve;
 pub mod jubjubfield;
-pub mod mod65537;
+pub mod poseidon_bn254;
 pub mod schnorr;
 pub mod schnorr_prover;
 pub mod serialization;
+pub mod sha256;
@@ -0,0 +1,117 @@
+use num::BigUint;
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use poseidon_ark::load_constants;
+
+use crate::signature::biguint::{BigUintTarget, CircuitBuilderBiguint};
+use crate::signature::jubjubfield::{ark_to_biguint, CircuitBuilderJubjubField, JubjubFieldTarget};
+
+// Poseidon over the BN254 scalar field (which is the jubjub base field),
+// computed non-natively with `JubjubFieldTarget`s. Uses the circomlib
+// parameters from poseidon-ark, so the output matches the hashes used by
+// parcnet_pod (content IDs) and babyjubjub-ark (EdDSA challenge).
+
+pub trait CircuitBuilderPoseidonBn254 {
+    fn poseidon_bn254(&mut self, inputs: &[JubjubFieldTarget]) -> JubjubFieldTarget;
+}
+
+impl CircuitBuilderPoseidonBn254 for CircuitBuilder<GoldilocksField, 2> {
+    fn poseidon_bn254(&mut self, inputs: &[JubjubFieldTarget]) -> JubjubFieldTarget {
+        let constants = load_constants();
+        let t = inputs.len() + 1;
+        assert!(
+            !inputs.is_empty() && inputs.len() <= constants.n_rounds_p.len(),
+            "poseidon_bn254 supports between 1 and {} inputs",
+            constants.n_rounds_p.len()
+        );
+        let n_rounds_f = constants.n_rounds_f;
+        let n_rounds_p = constants.n_rounds_p[t - 2];
+        let c: Vec<BigUint> = constants.c[t - 2].iter().map(ark_to_biguint).collect();
+        let m: Vec<Vec<BigUint>> = constants.m[t - 2]
+            .iter()
+            .map(|row| row.iter().map(ark_to_biguint).collect())
+            .collect();
+
+        let mut state: Vec<JubjubFieldTarget> =
+            [vec![self.zero_jubjubfield()], inputs.to_vec()].concat();
+        for i in 0..(n_rounds_f + n_rounds_p) {
+            // add round constants
+            for (j, s) in state.iter_mut().enumerate() {
+                let c_ij = JubjubFieldTarget(self.constant_biguint(&c[i * t + j]));
+                *s = self.add_jubjubfield(s, &c_ij);
+            }
+
+            // s-box x^5, on the whole state in full rounds and on the first
+            // element only in partial rounds
+            let is_full_round = i < n_rounds_f / 2 || i >= n_rounds_f / 2 + n_rounds_p;
+            let sbox_width = if is_full_round { t } else { 1 };
+            for s in state.iter_mut().take(sbox_width) {
+                let s2 = self.mul_jubjubfield(s, s);
+                let s4 = self.mul_jubjubfield(&s2, &s2);
+                *s = self.mul_jubjubfield(&s4, s);
+            }
+
+            // mix; the products are summed as plain integers and reduced once per row
+            state = m
+                .iter()
+                .map(|row| {
+                    let sum = row.iter().zip(state.iter()).fold(
+                        self.zero_biguint(),
+                        |acc: BigUintTarget, (m_ij, s)| {
+                            let m_ij = self.constant_biguint(m_ij);
+                            let prod = self.mul_biguint(&m_ij, &s.0);
+                            self.add_biguint(&acc, &prod)
+                        },
+                    );
+                    self.from_biguint_jubjubfield(&sum)
+                })
+                .collect();
+        }
+
+        state[0].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use parcnet_pod::pod::Fq;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::PoseidonGoldilocksConfig;
+    use poseidon_ark::Poseidon;
+
+    use crate::signature::biguint::CircuitBuilderBiguint;
+    use crate::signature::jubjubfield::{
+        ark_to_biguint, CircuitBuilderJubjubField, JubjubFieldTarget,
+    };
+    use crate::signature::poseidon_bn254::CircuitBuilderPoseidonBn254;
+
+    #[test]
+    fn test_poseidon_bn254() -> Result<()> {
+        type C = PoseidonGoldilocksConfig;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let pw: PartialWitness<GoldilocksField> = PartialWitness::new();
+        let mut builder = CircuitBuilder::<GoldilocksField, 2>::new(config);
+
+        let inputs = vec![Fq::from(1), Fq::from(2)];
+        let expected = Poseidon::new()
+            .hash(inputs.clone())
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        let input_targets: Vec<JubjubFieldTarget> = inputs
+            .iter()
+            .map(|x| JubjubFieldTarget(builder.constant_biguint(&ark_to_biguint(x))))
+            .collect();
+        let hash = builder.poseidon_bn254(&input_targets);
+        let expected = JubjubFieldTarget(builder.constant_biguint(&ark_to_biguint(&expected)));
+        builder.connect_jubjubfield(&hash, &expected);
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+}
@@ -17,6 +17,7 @@ const BIG_GROUP_GEN: GoldilocksField = GoldilocksField(14293326489335486720);
 
 // 8-bit security (i.e. totally insecure, DO NOT USE if you want any security at all)
 // because it uses the multiplicative group of the Goldilocks field
+// Only kept to check legacy SCHNORR16 PODs, new PODs are signed with `ecschnorr`.
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct SchnorrSigner {
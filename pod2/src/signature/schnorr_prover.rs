@@ -1,8 +1,11 @@
 /// code forked from https://github.com/tideofwords/schnorr
-use anyhow::Result;
-
-use plonky2::field::{goldilocks_field::GoldilocksField, types::Field};
-use plonky2::hash::poseidon::PoseidonHash;
+use plonky2::field::{
+    extension::quintic::QuinticExtension, goldilocks_field::GoldilocksField, types::Field,
+};
+use plonky2::hash::{
+    hash_types::{HashOut, HashOutTarget},
+    poseidon::PoseidonHash,
+};
 use plonky2::iop::{
     target::{BoolTarget, Target},
     witness::{PartialWitness, WitnessWrite},
@@ -10,17 +13,19 @@ use plonky2::iop::{
 use plonky2::plonk::{circuit_builder::CircuitBuilder, config::GenericConfig};
 
 use super::{
-    mod65537::Mod65537Builder,
-    schnorr::{SchnorrPublicKey, SchnorrSignature},
+    ecgfp5::{CircuitBuilderECGFp5, ECPointTarget},
+    ecschnorr::{EcSchnorrPublicKey, EcSchnorrSignature},
+    gfp5::WitnessGFp5,
 };
+use crate::{Error, Result};
 
 type GoldF = GoldilocksField;
 
 // Functions to verify a Schnorr signature in a plonky2 circuit.
 
-// 8-bit security (i.e. totally insecure, DO NOT USE if you want any security at all)
-// because it uses the multiplicative group of the Goldilocks field
-// This is intended solely as a proof of concept.
+// Signatures are over the ecgfp5 curve (see `ecschnorr.rs`), replacing the former toy scheme
+// over a subgroup of order 65537 of the Goldilocks multiplicative group, which is still
+// available natively in `schnorr.rs` to check legacy PODs.
 
 pub struct MessageTarget {
     pub msg: Vec<Target>,
@@ -34,7 +39,13 @@ impl MessageTarget {
     }
 
     pub fn set_witness(&self, pw: &mut PartialWitness<GoldF>, msg: &[GoldF]) -> Result<()> {
-        assert!(msg.len() == self.msg.len());
+        if msg.len() != self.msg.len() {
+            return Err(Error::InvalidInput(format!(
+                "Message of {} elements, expected {}",
+                msg.len(),
+                self.msg.len()
+            )));
+        }
         for (&t, &x) in self.msg.iter().zip(msg.iter()) {
             pw.set_target(t, x)?;
         }
@@ -44,66 +55,87 @@ impl MessageTarget {
 }
 
 pub struct SchnorrSignatureTarget {
-    pub s: Target,
-    pub e: Target,
+    /// s, as little-endian u32 limbs.
+    pub s: [Target; 10],
+    pub e: HashOutTarget,
 }
 
 impl SchnorrSignatureTarget {
     pub fn new_virtual(builder: &mut CircuitBuilder<GoldF, 2>) -> Self {
-        let s = builder.add_virtual_target();
-        let e = builder.add_virtual_target();
+        let s = builder.add_virtual_target_arr();
+        let e = builder.add_virtual_hash();
         Self { s, e }
     }
 
     pub fn set_witness(
         &self,
         pw: &mut PartialWitness<GoldF>,
-        sig: &SchnorrSignature,
+        sig: &EcSchnorrSignature,
     ) -> Result<()> {
-        pw.set_target(self.s, GoldilocksField::from_canonical_u64(sig.s))?;
-        pw.set_target(self.e, GoldilocksField::from_canonical_u64(sig.e))?;
+        for (i, &limb) in sig.s.iter().enumerate() {
+            pw.set_target(self.s[2 * i], GoldF::from_canonical_u64(limb & 0xffffffff))?;
+            pw.set_target(self.s[2 * i + 1], GoldF::from_canonical_u64(limb >> 32))?;
+        }
+        pw.set_hash_target(self.e, HashOut { elements: sig.e })?;
         Ok(())
     }
 }
 
 pub struct SchnorrPublicKeyTarget {
-    pub pk: Target,
+    pub pk: ECPointTarget,
 }
 
 impl SchnorrPublicKeyTarget {
     pub fn new_virtual(builder: &mut CircuitBuilder<GoldF, 2>) -> Self {
         Self {
-            pk: builder.add_virtual_target(),
+            pk: builder.add_virtual_point_target(),
         }
     }
 
-    pub fn set_witness(&self, pw: &mut PartialWitness<GoldF>, pk: &SchnorrPublicKey) -> Result<()> {
-        pw.set_target(self.pk, pk.pk)?;
+    pub fn set_witness(
+        &self,
+        pw: &mut PartialWitness<GoldF>,
+        pk: &EcSchnorrPublicKey,
+    ) -> Result<()> {
+        pw.set_gfp5_target(&self.pk.x, QuinticExtension(pk.x))?;
+        pw.set_gfp5_target(&self.pk.y, QuinticExtension(pk.y))?;
         Ok(())
     }
+
+    /// In-circuit counterpart of `EcSchnorrPublicKey::hash`.
+    pub fn hash(&self, builder: &mut CircuitBuilder<GoldF, 2>) -> HashOutTarget {
+        builder.hash_n_to_hash_no_pad::<PoseidonHash>([self.pk.x.0, self.pk.y.0].concat())
+    }
 }
 
+/// Verifier of Schnorr signatures over ecgfp5, see `ecschnorr.rs`.
 pub struct SchnorrBuilder;
 
 pub trait SignatureVerifierBuilder {
+    type SignatureTarget;
+    type PublicKeyTarget;
+
     fn constrain_sig<C: GenericConfig<2, F = GoldF>>(
         &self,
         builder: &mut CircuitBuilder<GoldF, 2>,
-        sig: &SchnorrSignatureTarget,
+        sig: &Self::SignatureTarget,
         msg: &MessageTarget,
-        pk: &SchnorrPublicKeyTarget,
+        pk: &Self::PublicKeyTarget,
     );
 
     fn verify_sig<C: GenericConfig<2, F = GoldF>>(
         &self,
         builder: &mut CircuitBuilder<GoldF, 2>,
-        sig: &SchnorrSignatureTarget,
+        sig: &Self::SignatureTarget,
         msg: &MessageTarget,
-        pk: &SchnorrPublicKeyTarget,
+        pk: &Self::PublicKeyTarget,
     ) -> BoolTarget;
 }
 
 impl SignatureVerifierBuilder for SchnorrBuilder {
+    type SignatureTarget = SchnorrSignatureTarget;
+    type PublicKeyTarget = SchnorrPublicKeyTarget;
+
     fn constrain_sig<C: GenericConfig<2, F = GoldF>>(
         &self,
         builder: &mut CircuitBuilder<GoldF, 2>,
@@ -123,32 +155,54 @@ impl SignatureVerifierBuilder for SchnorrBuilder {
         msg: &MessageTarget,
         pk: &SchnorrPublicKeyTarget,
     ) -> BoolTarget {
-        let prime_group_gen: Target =
-            builder.constant(GoldF::from_canonical_u64(6612579038192137166));
-        const NUM_BITS_EXP: usize = 32;
-
         /* here's the direct verification calculation,
         which we verify in-circuit
-        let r: GoldF = Self::pow(self.PRIME_GROUP_GEN, sig.s)
-            * Self::pow(pk.pk, sig.e);
-        let e_v: u64 = self.hash_insecure(&r, msg);
+        let r = s * G + e * pk;
+        let e_v = hash(r, msg);
         e_v == sig.e   */
 
-        let gs: Target = builder.exp(prime_group_gen, sig.s, NUM_BITS_EXP);
-        let pe: Target = builder.exp(pk.pk, sig.e, NUM_BITS_EXP);
-        let r: Target = builder.mul(gs, pe);
+        // s limbs are range checked by their decomposition
+        let s_bits: Vec<BoolTarget> = sig
+            .s
+            .iter()
+            .flat_map(|&limb| builder.split_le(limb, 32))
+            .collect();
+
+        // e is read as an integer, so its decomposition must be canonical, i.e. for each element
+        // hi == 2^32 - 1 implies lo == 0
+        let max_hi = builder.constant(GoldF::from_canonical_u64(u32::MAX as u64));
+        let e_bits: Vec<BoolTarget> = sig
+            .e
+            .elements
+            .iter()
+            .flat_map(|&x| {
+                let bits = builder.split_le(x, 64);
+                let lo = builder.le_sum(bits[..32].iter());
+                let hi = builder.le_sum(bits[32..].iter());
+                let hi_is_max = builder.is_equal(hi, max_hi);
+                let check = builder.mul(hi_is_max.target, lo);
+                builder.assert_zero(check);
+                bits
+            })
+            .collect();
+
+        let r = builder.mul_add_generator(&s_bits, &e_bits, &pk.pk);
 
         // compute hash
         // note that it's safe to clone Targets since they just contain indices
-        let hash_input: Vec<Target> = std::iter::once(r).chain(msg.msg.iter().cloned()).collect();
-        let hash_output: Target = builder
-            .hash_n_to_hash_no_pad::<PoseidonHash>(hash_input)
-            .elements[0]; // whoops have to take mod group order;
-
-        let e: Target = Mod65537Builder::mod_65537(builder, hash_output);
-
-        // test equality
-        builder.is_equal(e, sig.e)
+        let hash_input: Vec<Target> = [&r.x.0[..], &r.y.0[..], &msg.msg[..]].concat();
+        let hash_output = builder.hash_n_to_hash_no_pad::<PoseidonHash>(hash_input);
+
+        // test equality, and that the key is a curve point
+        let pk_on_curve = builder.is_on_curve(&pk.pk);
+        hash_output
+            .elements
+            .iter()
+            .zip(sig.e.elements.iter())
+            .fold(pk_on_curve, |acc, (&h, &e)| {
+                let eq = builder.is_equal(h, e);
+                builder.and(acc, eq)
+            })
     }
 }
 
@@ -158,10 +212,10 @@ mod tests {
         MessageTarget, SchnorrBuilder, SchnorrPublicKeyTarget, SchnorrSignatureTarget,
         SignatureVerifierBuilder,
     };
-    use crate::signature::schnorr::{
-        SchnorrPublicKey, SchnorrSecretKey, SchnorrSignature, SchnorrSigner,
+    use crate::signature::ecschnorr::{
+        EcSchnorrPublicKey, EcSchnorrSecretKey, EcSchnorrSignature, EcSchnorrSigner,
     };
-    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::field::{goldilocks_field::GoldilocksField, types::Field};
     use plonky2::iop::witness::PartialWitness;
     use plonky2::plonk::{
         circuit_builder::CircuitBuilder,
@@ -183,12 +237,14 @@ mod tests {
         let sb: SchnorrBuilder = SchnorrBuilder;
 
         // create keypair, message, signature
-        let sk: SchnorrSecretKey = SchnorrSecretKey { sk: 133 };
-        let ss = SchnorrSigner::new();
-        let pk: SchnorrPublicKey = ss.keygen(&sk);
-        let msg: Vec<GoldilocksField> = ss.u64_into_goldilocks_vec(vec![1500, 1600, 0, 0, 0]);
+        let sk: EcSchnorrSecretKey = EcSchnorrSecretKey::random(&mut rng);
+        let ss = EcSchnorrSigner::new();
+        let pk: EcSchnorrPublicKey = ss.keygen(&sk);
+        let msg: Vec<GoldilocksField> = [1500, 1600, 0, 0, 0]
+            .map(GoldilocksField::from_canonical_u64)
+            .to_vec();
         let msg_size: usize = msg.len();
-        let sig: SchnorrSignature = ss.sign(&msg, &sk, &mut rng);
+        let sig: EcSchnorrSignature = ss.sign(&msg, &sk, &mut rng);
 
         let pk_targ = SchnorrPublicKeyTarget::new_virtual(&mut builder);
         let sig_targ = SchnorrSignatureTarget::new_virtual(&mut builder);
@@ -220,14 +276,18 @@ mod tests {
         let sb: SchnorrBuilder = SchnorrBuilder;
 
         // create keypair, message, signature
-        let sk: SchnorrSecretKey = SchnorrSecretKey { sk: 133 };
-        let ss = SchnorrSigner::new();
-        let pk: SchnorrPublicKey = ss.keygen(&sk);
-        let msg0: Vec<GoldilocksField> = ss.u64_into_goldilocks_vec(vec![1500, 1600, 0, 0, 0]);
+        let sk: EcSchnorrSecretKey = EcSchnorrSecretKey::random(&mut rng);
+        let ss = EcSchnorrSigner::new();
+        let pk: EcSchnorrPublicKey = ss.keygen(&sk);
+        let msg0: Vec<GoldilocksField> = [1500, 1600, 0, 0, 0]
+            .map(GoldilocksField::from_canonical_u64)
+            .to_vec();
         let msg_size: usize = msg0.len();
-        let sig: SchnorrSignature = ss.sign(&msg0, &sk, &mut rng);
+        let sig: EcSchnorrSignature = ss.sign(&msg0, &sk, &mut rng);
 
-        let msg1: Vec<GoldilocksField> = ss.u64_into_goldilocks_vec(vec![1510, 1600, 0, 0, 0]);
+        let msg1: Vec<GoldilocksField> = [1510, 1600, 0, 0, 0]
+            .map(GoldilocksField::from_canonical_u64)
+            .to_vec();
 
         let pk_targ = SchnorrPublicKeyTarget::new_virtual(&mut builder);
         let sig_targ = SchnorrSignatureTarget::new_virtual(&mut builder);
@@ -265,14 +325,18 @@ mod tests {
         let sb: SchnorrBuilder = SchnorrBuilder;
 
         // create keypair, message, signature
-        let sk: SchnorrSecretKey = SchnorrSecretKey { sk: 133 };
-        let ss = SchnorrSigner::new();
-        let pk: SchnorrPublicKey = ss.keygen(&sk);
-        let msg0: Vec<GoldilocksField> = ss.u64_into_goldilocks_vec(vec![1500, 1600, 0, 0, 0]);
+        let sk: EcSchnorrSecretKey = EcSchnorrSecretKey::random(&mut rng);
+        let ss = EcSchnorrSigner::new();
+        let pk: EcSchnorrPublicKey = ss.keygen(&sk);
+        let msg0: Vec<GoldilocksField> = [1500, 1600, 0, 0, 0]
+            .map(GoldilocksField::from_canonical_u64)
+            .to_vec();
         let msg_size: usize = msg0.len();
-        let sig: SchnorrSignature = ss.sign(&msg0, &sk, &mut rng);
+        let sig: EcSchnorrSignature = ss.sign(&msg0, &sk, &mut rng);
 
-        let msg1: Vec<GoldilocksField> = ss.u64_into_goldilocks_vec(vec![1510, 1600, 0, 0, 0]);
+        let msg1: Vec<GoldilocksField> = [1510, 1600, 0, 0, 0]
+            .map(GoldilocksField::from_canonical_u64)
+            .to_vec();
 
         let pk_targ = SchnorrPublicKeyTarget::new_virtual(&mut builder);
         let sig_targ = SchnorrSignatureTarget::new_virtual(&mut builder);
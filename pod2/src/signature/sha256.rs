@@ -0,0 +1,225 @@
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::field::types::Field;
+use plonky2::iop::target::{BoolTarget, Target};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+
+// SHA-256 compression of single padded blocks, as used by parcnet_pod to hash
+// the keys of POD1 entries. Words are handled as 32 little-endian bits, so that
+// rotations and shifts are free and boolean functions are a few arithmetic ops.
+
+/// Word of 32 bits, least significant first.
+pub type WordTarget = [BoolTarget; 32];
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const H: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+pub trait CircuitBuilderSha256 {
+    /// Digest of a message fitting in one block, given as the 16 big-endian words of the block
+    /// padded as in FIPS 180-4. The words are range checked here.
+    fn sha256_block(&mut self, block: &[Target; 16]) -> [WordTarget; 8];
+}
+
+impl CircuitBuilderSha256 for CircuitBuilder<GoldilocksField, 2> {
+    fn sha256_block(&mut self, block: &[Target; 16]) -> [WordTarget; 8] {
+        let mut w: Vec<WordTarget> = block.iter().map(|x| word_bits(self, *x, 32)).collect();
+        for t in 16..64 {
+            let s0 = sigma(self, &w[t - 15], [7, 18], 3);
+            let s1 = sigma(self, &w[t - 2], [17, 19], 10);
+            let wt = add_words(self, &[&w[t - 16], &s0, &w[t - 7], &s1], &[]);
+            w.push(wt);
+        }
+
+        let initial = H.map(|h| constant_word(self, h));
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = initial;
+        for t in 0..64 {
+            let s1 = big_sigma(self, &e, [6, 11, 25]);
+            let choice = ch(self, &e, &f, &g);
+            let t1 = add_words(self, &[&h, &s1, &choice, &w[t]], &[K[t]]);
+            let s0 = big_sigma(self, &a, [2, 13, 22]);
+            let majority = maj(self, &a, &b, &c);
+            h = g;
+            g = f;
+            f = e;
+            e = add_words(self, &[&d, &t1], &[]);
+            d = c;
+            c = b;
+            b = a;
+            a = add_words(self, &[&t1, &s0, &majority], &[]);
+        }
+
+        let state = [a, b, c, d, e, f, g, h];
+        std::array::from_fn(|i| add_words(self, &[&state[i]], &[H[i]]))
+    }
+}
+
+/// Bits of a value of at most `num_bits` bits, the first 32 of which are returned.
+fn word_bits(
+    builder: &mut CircuitBuilder<GoldilocksField, 2>,
+    x: Target,
+    num_bits: usize,
+) -> WordTarget {
+    let bits = builder.split_le(x, num_bits);
+    std::array::from_fn(|i| bits[i])
+}
+
+fn constant_word(builder: &mut CircuitBuilder<GoldilocksField, 2>, x: u32) -> WordTarget {
+    std::array::from_fn(|i| builder.constant_bool((x >> i) & 1 == 1))
+}
+
+/// Sum of words and constants modulo 2^32. At most 8 terms are summed, so that
+/// the sum fits in 35 bits.
+fn add_words(
+    builder: &mut CircuitBuilder<GoldilocksField, 2>,
+    words: &[&WordTarget],
+    constants: &[u32],
+) -> WordTarget {
+    let constant = constants.iter().fold(GoldilocksField::ZERO, |acc, c| {
+        acc + GoldilocksField::from_canonical_u32(*c)
+    });
+    let terms = words
+        .iter()
+        .map(|word| builder.le_sum(word.iter()))
+        .collect::<Vec<_>>();
+    let sum = builder.add_many(terms);
+    let sum = builder.add_const(sum, constant);
+    word_bits(builder, sum, 35)
+}
+
+fn xor(
+    builder: &mut CircuitBuilder<GoldilocksField, 2>,
+    x: BoolTarget,
+    y: BoolTarget,
+) -> BoolTarget {
+    // x + y - 2xy
+    let sum = builder.add(x.target, y.target);
+    let xor = builder.arithmetic(
+        -GoldilocksField::TWO,
+        GoldilocksField::ONE,
+        x.target,
+        y.target,
+        sum,
+    );
+    BoolTarget::new_unsafe(xor)
+}
+
+fn rotr(x: &WordTarget, n: usize) -> WordTarget {
+    std::array::from_fn(|i| x[(i + n) % 32])
+}
+
+fn shr(builder: &mut CircuitBuilder<GoldilocksField, 2>, x: &WordTarget, n: usize) -> WordTarget {
+    let zero = builder._false();
+    std::array::from_fn(|i| if i + n < 32 { x[i + n] } else { zero })
+}
+
+fn xor3(
+    builder: &mut CircuitBuilder<GoldilocksField, 2>,
+    x: &WordTarget,
+    y: &WordTarget,
+    z: &WordTarget,
+) -> WordTarget {
+    std::array::from_fn(|i| {
+        let xy = xor(builder, x[i], y[i]);
+        xor(builder, xy, z[i])
+    })
+}
+
+/// Σ functions of the compression, ie. the XOR of three rotations.
+fn big_sigma(
+    builder: &mut CircuitBuilder<GoldilocksField, 2>,
+    x: &WordTarget,
+    [r1, r2, r3]: [usize; 3],
+) -> WordTarget {
+    xor3(builder, &rotr(x, r1), &rotr(x, r2), &rotr(x, r3))
+}
+
+/// σ functions of the message schedule, ie. the XOR of two rotations and a shift.
+fn sigma(
+    builder: &mut CircuitBuilder<GoldilocksField, 2>,
+    x: &WordTarget,
+    [r1, r2]: [usize; 2],
+    s: usize,
+) -> WordTarget {
+    let shifted = shr(builder, x, s);
+    xor3(builder, &rotr(x, r1), &rotr(x, r2), &shifted)
+}
+
+/// (x AND y) XOR (NOT x AND z), ie. z + x(y - z)
+fn ch(
+    builder: &mut CircuitBuilder<GoldilocksField, 2>,
+    x: &WordTarget,
+    y: &WordTarget,
+    z: &WordTarget,
+) -> WordTarget {
+    std::array::from_fn(|i| {
+        let diff = builder.sub(y[i].target, z[i].target);
+        BoolTarget::new_unsafe(builder.mul_add(x[i].target, diff, z[i].target))
+    })
+}
+
+/// Majority of x, y and z, ie. xy + z(x XOR y)
+fn maj(
+    builder: &mut CircuitBuilder<GoldilocksField, 2>,
+    x: &WordTarget,
+    y: &WordTarget,
+    z: &WordTarget,
+) -> WordTarget {
+    std::array::from_fn(|i| {
+        let xy = builder.mul(x[i].target, y[i].target);
+        let x_xor_y = xor(builder, x[i], y[i]);
+        BoolTarget::new_unsafe(builder.mul_add(z[i].target, x_xor_y.target, xy))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use plonky2::field::goldilocks_field::GoldilocksField;
+    use plonky2::iop::witness::PartialWitness;
+    use plonky2::plonk::circuit_builder::CircuitBuilder;
+    use plonky2::plonk::circuit_data::CircuitConfig;
+    use plonky2::plonk::config::PoseidonGoldilocksConfig;
+
+    use super::CircuitBuilderSha256;
+
+    #[test]
+    fn test_sha256_block() -> Result<()> {
+        type C = PoseidonGoldilocksConfig;
+
+        let config = CircuitConfig::standard_recursion_config();
+        let pw: PartialWitness<GoldilocksField> = PartialWitness::new();
+        let mut builder = CircuitBuilder::<GoldilocksField, 2>::new(config);
+
+        // "abc", padded
+        let mut words = [0u32; 16];
+        words[0] = 0x61626380;
+        words[15] = 24;
+        let block = words.map(|w| builder.constant(GoldilocksField(w as u64)));
+        let digest = builder.sha256_block(&block);
+
+        let expected: [u32; 8] = [
+            0xba7816bf, 0x8f01cfea, 0x414140de, 0x5dae2223, 0xb00361a3, 0x96177a9c, 0xb410ff61,
+            0xf20015ad,
+        ];
+        for (word, expected) in digest.iter().zip(expected) {
+            let word = builder.le_sum(word.iter());
+            let expected = builder.constant(GoldilocksField(expected as u64));
+            builder.connect(word, expected);
+        }
+
+        let data = builder.build::<C>();
+        let proof = data.prove(pw)?;
+        data.verify(proof)
+    }
+}